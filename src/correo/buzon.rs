@@ -0,0 +1,180 @@
+//! Dónde se guardan los correos: un archivo `.eml` por mensaje, un buzón
+//! mbox con todos los mensajes seguidos o una carpeta Maildir.
+
+use super::{mime, ErrorCorreo, Mensaje};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Mensajes de un archivo `.eml`, un buzón mbox, una carpeta Maildir o una
+/// carpeta con archivos `.eml`
+pub fn leer_ruta(ruta: &Path) -> Result<Vec<Mensaje>, ErrorCorreo> {
+    let mensajes = if ruta.is_dir() {
+        archivos_de_carpeta(ruta)?
+            .iter()
+            .map(|archivo| leer_bytes(archivo).map(|bytes| mime::leer(&bytes)))
+            .collect::<Result<Vec<_>, _>>()?
+    } else {
+        let bytes = leer_bytes(ruta)?;
+        if bytes.starts_with(b"From ") {
+            separar_mbox(&bytes).iter().map(|m| mime::leer(m)).collect()
+        } else if es_eml(ruta) {
+            vec![mime::leer(&bytes)]
+        } else {
+            return Err(ErrorCorreo::FormatoDesconocido(ruta.to_path_buf()));
+        }
+    };
+
+    if mensajes.is_empty() {
+        Err(ErrorCorreo::SinMensajes(ruta.to_path_buf()))
+    } else {
+        Ok(mensajes)
+    }
+}
+
+fn leer_bytes(ruta: &Path) -> Result<Vec<u8>, ErrorCorreo> {
+    fs::read(ruta).map_err(|e| ErrorCorreo::Lectura(ruta.to_path_buf(), e))
+}
+
+fn es_eml(ruta: &Path) -> bool {
+    ruta.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("eml"))
+}
+
+/// Archivos de los mensajes de la carpeta, ordenados por nombre. En una
+/// Maildir están en `new` y `cur` (los de `tmp` todavía se están
+/// escribiendo); en otra carpeta se toman los `.eml`.
+fn archivos_de_carpeta(carpeta: &Path) -> Result<Vec<PathBuf>, ErrorCorreo> {
+    let listar = |carpeta: &Path| -> Result<Vec<PathBuf>, ErrorCorreo> {
+        let entradas =
+            fs::read_dir(carpeta).map_err(|e| ErrorCorreo::Lectura(carpeta.to_path_buf(), e))?;
+        Ok(entradas
+            .filter_map(|entrada| entrada.ok().map(|e| e.path()))
+            .filter(|ruta| ruta.is_file())
+            .collect())
+    };
+
+    let maildir = ["new", "cur"].map(|sub| carpeta.join(sub));
+    let mut archivos = if maildir.iter().any(|sub| sub.is_dir()) {
+        let mut archivos = Vec::new();
+        for sub in maildir.iter().filter(|sub| sub.is_dir()) {
+            archivos.extend(listar(sub)?);
+        }
+        // Los nombres de Maildir empiezan con la hora de llegada
+        archivos.retain(|ruta| {
+            !ruta
+                .file_name()
+                .is_some_and(|n| n.to_string_lossy().starts_with('.'))
+        });
+        archivos
+    } else {
+        let mut archivos = listar(carpeta)?;
+        archivos.retain(|ruta| es_eml(ruta));
+        archivos
+    };
+    archivos.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+    Ok(archivos)
+}
+
+/// Mensajes de un buzón mbox. Cada uno empieza con una línea "From " que no
+/// es parte del mensaje; las líneas del texto que empezaban así se
+/// guardaron con un `>` adelante, que se quita.
+fn separar_mbox(bytes: &[u8]) -> Vec<Vec<u8>> {
+    let mut mensajes: Vec<Vec<u8>> = Vec::new();
+    let mut anterior_en_blanco = true;
+    for linea in bytes.split_inclusive(|&b| b == b'\n') {
+        if linea.starts_with(b"From ") && anterior_en_blanco {
+            mensajes.push(Vec::new());
+            anterior_en_blanco = false;
+            continue;
+        }
+        anterior_en_blanco = linea == b"\n" || linea == b"\r\n";
+        let Some(mensaje) = mensajes.last_mut() else {
+            continue;
+        };
+        let sin_marcas = linea.iter().take_while(|&&b| b == b'>').count();
+        if sin_marcas > 0 && linea[sin_marcas..].starts_with(b"From ") {
+            mensaje.extend_from_slice(&linea[1..]);
+        } else {
+            mensaje.extend_from_slice(linea);
+        }
+    }
+    mensajes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(nombre: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("src/correo/fixtures")
+            .join(nombre)
+    }
+
+    #[test]
+    fn buzon_mbox() {
+        let mensajes = leer_ruta(&fixture("buzon.mbox")).unwrap();
+        assert_eq!(mensajes.len(), 2);
+        assert_eq!(mensajes[0].asunto, "Notificacion - Causa LP 98765/2021");
+        assert_eq!(
+            mensajes[0].cuerpo,
+            "Se notifica el traslado de la demanda.\nFrom the desk of the clerk."
+        );
+        assert_eq!(mensajes[1].remitente, "Juan Perez <juan.perez@correo.com>");
+        assert_eq!(mensajes[1].cuerpo, "Quería saber cómo sigue la causa.");
+    }
+
+    #[test]
+    fn carpeta_maildir() {
+        let mensajes = leer_ruta(&fixture("maildir")).unwrap();
+        let asuntos: Vec<&str> = mensajes.iter().map(|m| m.asunto.as_str()).collect();
+        assert_eq!(
+            asuntos,
+            [
+                "Expte. CNT 12345/2023 - Cedula",
+                "Expte. CIV 45678/2022 - Audiencia"
+            ]
+        );
+    }
+
+    #[test]
+    fn mbox_con_lineas_from_en_el_texto() {
+        let mbox = b"From juzgado Tue Mar 12 10:15:00 2024\n\
+                     Subject: uno\n\
+                     \n\
+                     >From the desk\n\
+                     >>From citado\n\
+                     From sin linea en blanco antes\n\
+                     \n\
+                     From cliente Wed Mar 13 09:00:00 2024\r\n\
+                     Subject: dos\r\n\
+                     \r\n\
+                     sin salto final";
+        let mensajes = separar_mbox(mbox);
+        assert_eq!(mensajes.len(), 2);
+        assert_eq!(
+            mensajes[0],
+            b"Subject: uno\n\nFrom the desk\n>From citado\nFrom sin linea en blanco antes\n\n"
+        );
+        assert_eq!(mensajes[1], b"Subject: dos\r\n\r\nsin salto final");
+        // Lo anterior a la primera línea "From " no es de ningún mensaje
+        assert_eq!(
+            separar_mbox(b"basura\n\nFrom x\nSubject: tres\n"),
+            [b"Subject: tres\n".to_vec()]
+        );
+    }
+
+    #[test]
+    fn otros_archivos_no_son_correos() {
+        let cargo = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+        assert!(matches!(
+            leer_ruta(&cargo),
+            Err(ErrorCorreo::FormatoDesconocido(_))
+        ));
+        assert!(matches!(
+            leer_ruta(&fixture("no_existe.eml")),
+            Err(ErrorCorreo::Lectura(..))
+        ));
+    }
+}
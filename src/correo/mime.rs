@@ -0,0 +1,586 @@
+//! Lectura de un mensaje de correo (RFC 5322 y MIME): encabezados con
+//! palabras codificadas, partes anidadas, base64 y quoted-printable. Solo
+//! lo necesario para convertir el mensaje en hoja.
+
+use super::{ArchivoAdjunto, Mensaje};
+use crate::importacion::{self, html};
+use base64::alphabet;
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use base64::Engine;
+use chrono::DateTime;
+
+/// Los programas de correo no siempre completan el relleno final
+const BASE64: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new()
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent)
+        .with_decode_allow_trailing_bits(true),
+);
+
+/// Caracteres de windows-1252 en 0x80..0x9F, donde ISO-8859-1 tiene
+/// controles; Outlook declara uno y usa el otro
+const WINDOWS_1252: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', // 0x80
+    'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8D}', 'Ž', '\u{8F}', // 0x88
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', // 0x90
+    '˜', '™', 'š', '›', 'œ', '\u{9D}', 'ž', 'Ÿ', // 0x98
+];
+
+/// Partes anidadas que se recorren como máximo, por si el mensaje está mal
+/// formado
+const PROFUNDIDAD_MAXIMA: usize = 10;
+
+pub fn leer(bytes: &[u8]) -> Mensaje {
+    let (encabezados, cuerpo) = separar(bytes);
+    let fecha = encabezados
+        .valor("date")
+        .and_then(|fecha| DateTime::parse_from_rfc2822(&limpiar_fecha(fecha)).ok());
+
+    let mut contenido = Contenido::default();
+    contenido.recorrer(&encabezados, cuerpo, 0);
+
+    Mensaje {
+        asunto: encabezados
+            .valor("subject")
+            .map(decodificar_palabras)
+            .unwrap_or_default(),
+        remitente: encabezados
+            .valor("from")
+            .map(decodificar_palabras)
+            .unwrap_or_default(),
+        fecha,
+        cuerpo: contenido
+            .texto
+            .or_else(|| contenido.html.map(|h| html::texto(&h)))
+            .unwrap_or_default(),
+        adjuntos: contenido.adjuntos,
+    }
+}
+
+/// Encabezados de un mensaje o de una parte, con los nombres en minúsculas
+#[derive(Debug, Default)]
+struct Encabezados(Vec<(String, String)>);
+
+impl Encabezados {
+    fn leer(texto: &str) -> Self {
+        let mut encabezados: Vec<(String, String)> = Vec::new();
+        for linea in texto.lines() {
+            // Las líneas que empiezan con espacio continúan la anterior
+            if linea.starts_with([' ', '\t']) {
+                if let Some((_, valor)) = encabezados.last_mut() {
+                    valor.push(' ');
+                    valor.push_str(linea.trim());
+                }
+                continue;
+            }
+            if let Some((nombre, valor)) = linea.split_once(':') {
+                encabezados.push((nombre.trim().to_lowercase(), valor.trim().to_string()));
+            }
+        }
+        Self(encabezados)
+    }
+
+    fn valor(&self, nombre: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(n, _)| n == nombre)
+            .map(|(_, valor)| valor.as_str())
+    }
+
+    /// Valor principal en minúsculas y parámetros de un encabezado como
+    /// `Content-Type: text/plain; charset=utf-8`
+    fn con_parametros(&self, nombre: &str) -> (String, Parametros) {
+        let valor = self.valor(nombre).unwrap_or_default();
+        let mut partes = dividir_parametros(valor).into_iter();
+        let principal = partes.next().unwrap_or_default().trim().to_lowercase();
+        let parametros = partes
+            .filter_map(|parte| {
+                let (clave, valor) = parte.split_once('=')?;
+                let valor = valor.trim();
+                let valor = valor
+                    .strip_prefix('"')
+                    .and_then(|v| v.strip_suffix('"'))
+                    .unwrap_or(valor);
+                Some((clave.trim().to_lowercase(), valor.replace("\\\"", "\"")))
+            })
+            .collect();
+        (principal, Parametros(parametros))
+    }
+}
+
+#[derive(Debug, Default)]
+struct Parametros(Vec<(String, String)>);
+
+impl Parametros {
+    /// Valor del parámetro, también si viene en partes o codificado según
+    /// RFC 2231 (`filename*=utf-8''informe%20final.pdf`)
+    fn valor(&self, nombre: &str) -> Option<String> {
+        if let Some((_, valor)) = self.0.iter().find(|(clave, _)| clave == nombre) {
+            return Some(decodificar_palabras(valor));
+        }
+
+        let prefijo = format!("{}*", nombre);
+        let mut partes: Vec<(usize, bool, &str)> = self
+            .0
+            .iter()
+            .filter_map(|(clave, valor)| {
+                let resto = clave.strip_prefix(&prefijo)?;
+                let (indice, codificada) = match resto.strip_suffix('*') {
+                    Some(indice) => (indice, true),
+                    None => (resto, resto.is_empty()),
+                };
+                let indice = if indice.is_empty() {
+                    0
+                } else {
+                    indice.parse().ok()?
+                };
+                Some((indice, codificada, valor.as_str()))
+            })
+            .collect();
+        if partes.is_empty() {
+            return None;
+        }
+        partes.sort_by_key(|(indice, _, _)| *indice);
+
+        let mut conjunto = "utf-8".to_string();
+        let mut bytes = Vec::new();
+        for (indice, codificada, valor) in partes {
+            if !codificada {
+                bytes.extend_from_slice(valor.as_bytes());
+                continue;
+            }
+            let mut valor = valor;
+            if indice == 0 {
+                // charset'idioma'texto
+                let mut campos = valor.splitn(3, '\'');
+                if let (Some(charset), Some(_), Some(texto)) =
+                    (campos.next(), campos.next(), campos.next())
+                {
+                    conjunto = charset.to_string();
+                    valor = texto;
+                }
+            }
+            bytes.extend(decodificar_porcentajes(valor));
+        }
+        Some(texto_en(bytes, &conjunto))
+    }
+}
+
+/// Lo que se junta al recorrer las partes del mensaje
+#[derive(Debug, Default)]
+struct Contenido {
+    texto: Option<String>,
+    html: Option<String>,
+    adjuntos: Vec<ArchivoAdjunto>,
+}
+
+impl Contenido {
+    fn recorrer(&mut self, encabezados: &Encabezados, cuerpo: &[u8], profundidad: usize) {
+        let (tipo, parametros) = encabezados.con_parametros("content-type");
+        let tipo = if tipo.is_empty() {
+            "text/plain".to_string()
+        } else {
+            tipo
+        };
+        let (disposicion, parametros_disposicion) =
+            encabezados.con_parametros("content-disposition");
+        let nombre = parametros_disposicion
+            .valor("filename")
+            .or_else(|| parametros.valor("name"));
+
+        if tipo.starts_with("multipart/") && profundidad < PROFUNDIDAD_MAXIMA {
+            if let Some(limite) = parametros.valor("boundary") {
+                for parte in partes_multipart(cuerpo, &limite) {
+                    let (encabezados, cuerpo) = separar(parte);
+                    self.recorrer(&encabezados, cuerpo, profundidad + 1);
+                }
+                return;
+            }
+        }
+
+        let codificacion = encabezados
+            .valor("content-transfer-encoding")
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase();
+        let datos = match codificacion.as_str() {
+            "base64" => {
+                let limpio: Vec<u8> = cuerpo
+                    .iter()
+                    .copied()
+                    .filter(|b| !b.is_ascii_whitespace())
+                    .collect();
+                BASE64.decode(limpio).unwrap_or_default()
+            }
+            "quoted-printable" => decodificar_quoted_printable(cuerpo),
+            _ => cuerpo.to_vec(),
+        };
+
+        let adjunto = disposicion == "attachment" || nombre.is_some();
+        let charset = parametros.valor("charset").unwrap_or_default();
+        match tipo.as_str() {
+            // Del cuerpo vale la primera versión de cada tipo; si hay otras
+            // partes de texto, se guardan como adjuntos
+            "text/plain" if !adjunto && self.texto.is_none() => {
+                self.texto = Some(normalizar_saltos(&texto_en(datos, &charset)));
+            }
+            "text/html" if !adjunto && self.html.is_none() => {
+                self.html = Some(texto_en(datos, &charset));
+            }
+            _ => {
+                let nombre = nombre.unwrap_or_else(|| {
+                    let extension = match tipo.as_str() {
+                        "message/rfc822" => "eml",
+                        "text/html" => "html",
+                        "text/plain" => "txt",
+                        _ => "bin",
+                    };
+                    format!("adjunto_{}.{}", self.adjuntos.len() + 1, extension)
+                });
+                self.adjuntos.push(ArchivoAdjunto {
+                    nombre,
+                    contenido: datos,
+                });
+            }
+        }
+    }
+}
+
+/// Encabezados y cuerpo, separados por la primera línea en blanco
+fn separar(bytes: &[u8]) -> (Encabezados, &[u8]) {
+    let mut inicio = 0;
+    let mut fin = bytes.len();
+    let mut cuerpo = bytes.len();
+    for (i, _) in bytes.iter().enumerate().filter(|(_, b)| **b == b'\n') {
+        let linea = &bytes[inicio..i];
+        if linea.is_empty() || linea == b"\r" {
+            fin = inicio;
+            cuerpo = i + 1;
+            break;
+        }
+        inicio = i + 1;
+    }
+    let texto = importacion::decodificar(bytes[..fin].to_vec());
+    (Encabezados::leer(&texto), &bytes[cuerpo..])
+}
+
+/// Partes de un cuerpo multipart, sin el preámbulo ni el epílogo
+fn partes_multipart<'a>(cuerpo: &'a [u8], limite: &str) -> Vec<&'a [u8]> {
+    let separador = format!("--{}", limite).into_bytes();
+    let mut partes = Vec::new();
+    let mut inicio: Option<usize> = None;
+    let mut posicion = 0;
+
+    while posicion <= cuerpo.len() {
+        let fin_linea = cuerpo[posicion..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(cuerpo.len(), |i| posicion + i);
+        let linea = &cuerpo[posicion..fin_linea];
+
+        if linea.starts_with(&separador) {
+            if let Some(desde) = inicio {
+                // El salto previo al separador pertenece al separador
+                let hasta = posicion.saturating_sub(1).max(desde);
+                let hasta = if hasta > desde && cuerpo[hasta - 1] == b'\r' {
+                    hasta - 1
+                } else {
+                    hasta
+                };
+                partes.push(&cuerpo[desde..hasta]);
+            }
+            if linea[separador.len()..].starts_with(b"--") {
+                return partes;
+            }
+            inicio = Some(fin_linea + 1);
+        }
+        posicion = fin_linea + 1;
+    }
+
+    // Mensaje cortado antes del separador final
+    if let Some(desde) = inicio.filter(|&desde| desde < cuerpo.len()) {
+        partes.push(&cuerpo[desde..]);
+    }
+    partes
+}
+
+/// Separa por `;` los parámetros de un encabezado, sin cortar los valores
+/// entre comillas
+fn dividir_parametros(valor: &str) -> Vec<&str> {
+    let mut partes = Vec::new();
+    let mut entre_comillas = false;
+    let mut inicio = 0;
+    for (i, c) in valor.char_indices() {
+        match c {
+            '"' => entre_comillas = !entre_comillas,
+            ';' if !entre_comillas => {
+                partes.push(&valor[inicio..i]);
+                inicio = i + 1;
+            }
+            _ => {}
+        }
+    }
+    partes.push(&valor[inicio..]);
+    partes
+}
+
+/// Reemplaza las palabras codificadas de RFC 2047 (`=?utf-8?B?...?=`). Los
+/// espacios entre dos palabras codificadas seguidas no se conservan.
+pub(super) fn decodificar_palabras(texto: &str) -> String {
+    let mut resultado = String::new();
+    let mut resto = texto;
+    let mut despues_de_palabra = false;
+
+    while let Some(inicio) = resto.find("=?") {
+        let Some((palabra, largo)) = palabra_codificada(&resto[inicio..]) else {
+            resultado.push_str(&resto[..inicio + 2]);
+            resto = &resto[inicio + 2..];
+            despues_de_palabra = false;
+            continue;
+        };
+        let previo = &resto[..inicio];
+        if !(despues_de_palabra && previo.trim().is_empty()) {
+            resultado.push_str(previo);
+        }
+        resultado.push_str(&palabra);
+        resto = &resto[inicio + largo..];
+        despues_de_palabra = true;
+    }
+    resultado.push_str(resto);
+    resultado
+}
+
+/// Texto de la palabra codificada al comienzo de `texto` y su largo
+fn palabra_codificada(texto: &str) -> Option<(String, usize)> {
+    let interior = texto.strip_prefix("=?")?;
+    let fin_charset = interior.find('?')?;
+    let (charset, resto) = (&interior[..fin_charset], &interior[fin_charset + 1..]);
+    let (codificacion, resto) = resto.split_once('?')?;
+    let fin = resto.find("?=")?;
+    let codificado = &resto[..fin];
+    if codificado.contains(char::is_whitespace) {
+        return None;
+    }
+
+    let bytes = match codificacion {
+        "B" | "b" => BASE64.decode(codificado).ok()?,
+        "Q" | "q" => decodificar_quoted_printable(codificado.replace('_', " ").as_bytes()),
+        _ => return None,
+    };
+    // charset*idioma, según RFC 2231
+    let charset = charset.split('*').next().unwrap_or_default();
+    let largo = 2 + fin_charset + 1 + codificacion.len() + 1 + fin + 2;
+    Some((texto_en(bytes, charset), largo))
+}
+
+/// Saltos de línea suaves (`=` al final) y bytes escritos como `=XX`
+fn decodificar_quoted_printable(datos: &[u8]) -> Vec<u8> {
+    let mut resultado = Vec::with_capacity(datos.len());
+    let mut i = 0;
+    while i < datos.len() {
+        if datos[i] != b'=' {
+            resultado.push(datos[i]);
+            i += 1;
+            continue;
+        }
+        let siguiente = &datos[i + 1..];
+        if siguiente.starts_with(b"\r\n") {
+            i += 3;
+        } else if siguiente.starts_with(b"\n") {
+            i += 2;
+        } else if let Some(byte) = siguiente.get(..2).and_then(byte_hexadecimal) {
+            resultado.push(byte);
+            i += 3;
+        } else {
+            resultado.push(b'=');
+            i += 1;
+        }
+    }
+    resultado
+}
+
+fn decodificar_porcentajes(texto: &str) -> Vec<u8> {
+    let bytes = texto.as_bytes();
+    let mut resultado = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = (bytes[i] == b'%')
+            .then(|| bytes.get(i + 1..i + 3))
+            .flatten()
+            .and_then(byte_hexadecimal);
+        match byte {
+            Some(byte) => {
+                resultado.push(byte);
+                i += 3;
+            }
+            None => {
+                resultado.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    resultado
+}
+
+/// Byte escrito con dos cifras hexadecimales; `from_str_radix` solo no
+/// alcanza porque acepta un signo adelante
+fn byte_hexadecimal(hex: &[u8]) -> Option<u8> {
+    if !hex.iter().all(u8::is_ascii_hexdigit) {
+        return None;
+    }
+    u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()
+}
+
+/// Convierte a texto los bytes escritos en `charset`. Los desconocidos se
+/// leen como UTF-8 o, si no lo son, como ISO-8859-1.
+fn texto_en(bytes: Vec<u8>, charset: &str) -> String {
+    match charset.trim().to_lowercase().as_str() {
+        "iso-8859-1" | "iso8859-1" | "latin1" | "latin-1" | "iso-8859-15" => {
+            bytes.iter().map(|&b| char::from(b)).collect()
+        }
+        "windows-1252" | "cp1252" => bytes
+            .iter()
+            .map(|&b| match b {
+                0x80..=0x9F => WINDOWS_1252[usize::from(b - 0x80)],
+                _ => char::from(b),
+            })
+            .collect(),
+        _ => importacion::decodificar(bytes),
+    }
+}
+
+fn normalizar_saltos(texto: &str) -> String {
+    texto.replace("\r\n", "\n").trim_end().to_string()
+}
+
+/// Quita el comentario final que agregan algunos servidores, como en
+/// "Tue, 12 Mar 2024 10:15:00 -0300 (ART)"
+fn limpiar_fecha(fecha: &str) -> String {
+    let fecha = match fecha.find('(') {
+        Some(i) => &fecha[..i],
+        None => fecha,
+    };
+    fecha.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn notificacion_con_adjunto() {
+        let mensaje = leer(include_bytes!("fixtures/notificacion_pjn.eml"));
+        assert_eq!(
+            mensaje.asunto,
+            "Notificación electrónica - Expte. Nro. CNT 012345/2023 - PÉREZ"
+        );
+        assert_eq!(
+            mensaje.remitente,
+            "Poder Judicial de la Nación <notificaciones@pjn.gov.ar>"
+        );
+        assert_eq!(
+            mensaje.fecha.map(|f| f.date_naive()),
+            NaiveDate::from_ymd_opt(2024, 3, 12)
+        );
+        // Se prefiere el texto a la versión HTML
+        assert!(mensaje
+            .cuerpo
+            .starts_with("Se le notifica la providencia dictada en autos \"PÉREZ, JUAN"));
+        assert!(mensaje
+            .cuerpo
+            .ends_with("Téngase presente lo manifestado. Notifíquese."));
+
+        assert_eq!(mensaje.adjuntos.len(), 1);
+        assert_eq!(mensaje.adjuntos[0].nombre, "cédula 12345-2023.pdf");
+        assert!(mensaje.adjuntos[0].contenido.starts_with(b"%PDF-1.4"));
+        assert!(mensaje.adjuntos[0].contenido.ends_with(b"%%EOF\n"));
+    }
+
+    #[test]
+    fn mensaje_solo_html_en_latin1() {
+        let mensaje = leer(include_bytes!("fixtures/cliente_html.eml"));
+        assert_eq!(
+            mensaje.asunto,
+            "Consulta audiencia del 15/03/2024 - LP 98765/2021 Gómez"
+        );
+        assert_eq!(
+            mensaje.cuerpo,
+            "Buenas tardes, doctora:\n\nLe consulto si la audiencia sigue en pie.\nSaludos,\n\
+             María Gómez"
+        );
+        assert!(mensaje.adjuntos.is_empty());
+    }
+
+    #[test]
+    fn palabras_codificadas() {
+        assert_eq!(
+            decodificar_palabras("=?utf-8?B?Q8OpZHVsYQ==?= =?utf-8?Q?_electr=C3=B3nica?= ok"),
+            "Cédula electrónica ok"
+        );
+        assert_eq!(
+            decodificar_palabras("Re: sin codificar =?"),
+            "Re: sin codificar =?"
+        );
+        assert_eq!(
+            decodificar_palabras("=?windows-1252?Q?=93Expte=94?="),
+            "“Expte”"
+        );
+        // Sin terminar, con otra codificación o con base64 inválido quedan
+        // como están
+        for texto in [
+            "Cédula =?utf-8?B?Q8OpZHVsYQ==",
+            "=?utf-8?X?Q8OpZHVsYQ==?=",
+            "=?utf-8?B?no es base64?=",
+            "=?utf-8?B?***?=",
+        ] {
+            assert_eq!(decodificar_palabras(texto), texto);
+        }
+    }
+
+    #[test]
+    fn quoted_printable_mal_formado() {
+        assert_eq!(
+            decodificar_quoted_printable(b"l=C3=ADnea lar=\r\nga = 3"),
+            "línea larga = 3".as_bytes()
+        );
+        // Solo dos cifras hexadecimales forman un byte
+        assert_eq!(
+            decodificar_quoted_printable(b"=3D =ZZ =+1 =-0 =4 fin="),
+            b"= =ZZ =+1 =-0 =4 fin="
+        );
+        assert_eq!(decodificar_porcentajes("a%20b%+1%2"), b"a b%+1%2");
+    }
+
+    #[test]
+    fn mensaje_cortado() {
+        // Sin el separador final y con el adjunto a medio llegar
+        let mensaje = leer(
+            b"From: Juzgado <juzgado@pjn.gov.ar>\r\n\
+              Date: ayer a la tarde\r\n\
+              Subject: =?utf-8?Q?C=C3=A9dula?=\r\n\
+              Content-Type: multipart/mixed; boundary=\"XX\"\r\n\
+              \r\n\
+              --XX\r\n\
+              Content-Type: text/plain\r\n\
+              \r\n\
+              Se adjunta c\xe9dula.\r\n\
+              --XX\r\n\
+              Content-Type: application/pdf; name=\"cedula.pdf\"\r\n\
+              Content-Transfer-Encoding: base64\r\n\
+              \r\n\
+              JVBERi0",
+        );
+        assert_eq!(mensaje.asunto, "Cédula");
+        assert_eq!(mensaje.fecha, None);
+        assert_eq!(mensaje.cuerpo, "Se adjunta cédula.");
+        assert_eq!(mensaje.adjuntos.len(), 1);
+        assert_eq!(mensaje.adjuntos[0].nombre, "cedula.pdf");
+        assert_eq!(mensaje.adjuntos[0].contenido, b"%PDF-");
+
+        let solo_encabezados = leer(b"Subject: Sin cuerpo\r\nFrom: alguien");
+        assert_eq!(solo_encabezados.asunto, "Sin cuerpo");
+        assert_eq!(solo_encabezados.remitente, "alguien");
+        assert_eq!(solo_encabezados.cuerpo, "");
+    }
+}
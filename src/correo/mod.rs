@@ -0,0 +1,404 @@
+//! Importación de correos como hojas: notificaciones electrónicas de los
+//! juzgados y mensajes de clientes guardados como `.eml`, mbox o Maildir.
+//! Cada mensaje va a la ficha indicada o, si no se indica, a la que
+//! corresponde según el expediente mencionado en el asunto.
+
+pub mod buzon;
+pub mod mime;
+
+pub use buzon::leer_ruta;
+
+use crate::importacion::ClaveExpediente;
+use crate::models::{Adjunto, Ficha, ReglaCorreo};
+use crate::ui::fechas::FormatoFecha;
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Mensaje leído, ya decodificado
+#[derive(Debug, Clone)]
+pub struct Mensaje {
+    pub asunto: String,
+    pub remitente: String,
+    /// Fecha de envío, si el mensaje la trae y es válida
+    pub fecha: Option<DateTime<FixedOffset>>,
+    /// Texto del mensaje; si solo venía en HTML, convertido a texto
+    pub cuerpo: String,
+    pub adjuntos: Vec<ArchivoAdjunto>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchivoAdjunto {
+    pub nombre: String,
+    pub contenido: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub enum ErrorCorreo {
+    Lectura(PathBuf, io::Error),
+    /// No es un `.eml`, un buzón mbox ni una carpeta
+    FormatoDesconocido(PathBuf),
+    SinMensajes(PathBuf),
+}
+
+impl fmt::Display for ErrorCorreo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorCorreo::Lectura(ruta, e) => {
+                write!(f, "no se pudo leer {}: {}", ruta.display(), e)
+            }
+            ErrorCorreo::FormatoDesconocido(ruta) => write!(
+                f,
+                "{} no es un correo .eml, un buzón mbox ni una carpeta Maildir",
+                ruta.display()
+            ),
+            ErrorCorreo::SinMensajes(ruta) => {
+                write!(f, "no se encontraron correos en {}", ruta.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ErrorCorreo {}
+
+impl Mensaje {
+    /// Día en que se envió, en la zona horaria configurada; hoy si el
+    /// mensaje no tiene fecha
+    pub fn fecha_evento(&self, fechas: &FormatoFecha) -> NaiveDate {
+        self.fecha
+            .map(|fecha| fechas.dia(fecha.with_timezone(&Utc)))
+            .unwrap_or_else(|| fechas.hoy())
+    }
+
+    /// Contenido de la hoja: el asunto como título, el remitente y el texto
+    pub fn contenido(&self) -> String {
+        let asunto = self.asunto.trim();
+        let mut contenido = format!(
+            "**{}**",
+            if asunto.is_empty() {
+                "(sin asunto)"
+            } else {
+                asunto
+            }
+        );
+        if !self.remitente.trim().is_empty() {
+            contenido.push_str(&format!("\n\nDe: {}", self.remitente.trim()));
+        }
+        if !self.cuerpo.trim().is_empty() {
+            contenido.push_str("\n\n");
+            contenido.push_str(self.cuerpo.trim());
+        }
+        contenido
+    }
+}
+
+/// Ficha a la que va un mensaje y categoría de la hoja
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Destino {
+    pub ficha_id: Uuid,
+    pub categoria: Option<String>,
+}
+
+/// Ficha que corresponde al expediente mencionado en el asunto. Las reglas
+/// tienen prioridad sobre el expediente de cada ficha. Si el asunto tiene
+/// varios números con forma de expediente (una fecha, por ejemplo), vale el
+/// primero que corresponda a alguna.
+pub fn destino(asunto: &str, reglas: &[ReglaCorreo], fichas: &[Ficha]) -> Option<Destino> {
+    let existe = |id: Uuid| fichas.iter().any(|f| f.id == id);
+    let coincide = |texto: &str, clave: &ClaveExpediente| {
+        ClaveExpediente::extraer(texto).is_some_and(|c| c.coincide(clave))
+    };
+
+    ClaveExpediente::todas(asunto).iter().find_map(|clave| {
+        reglas
+            .iter()
+            .find(|r| existe(r.ficha_id) && coincide(&r.expediente, clave))
+            .map(|r| Destino {
+                ficha_id: r.ficha_id,
+                categoria: r.categoria.clone(),
+            })
+            .or_else(|| {
+                fichas
+                    .iter()
+                    .find(|f| coincide(&f.expediente, clave))
+                    .map(|f| Destino {
+                        ficha_id: f.id,
+                        categoria: None,
+                    })
+            })
+    })
+}
+
+/// Si la ficha ya tiene una hoja del mensaje, con la misma fecha y contenido
+pub fn ya_cargado(ficha: &Ficha, mensaje: &Mensaje, fechas: &FormatoFecha) -> bool {
+    let (fecha, contenido) = (mensaje.fecha_evento(fechas), mensaje.contenido());
+    ficha
+        .obtener_hojas()
+        .iter()
+        .any(|h| h.fecha == fecha && h.contenido == contenido)
+}
+
+/// Resultado de importar correos
+#[derive(Debug, Default)]
+pub struct Resumen {
+    pub mensajes: usize,
+    pub agregadas: usize,
+    /// Mensajes que ya estaban como hoja en su ficha
+    pub repetidos: usize,
+    /// Asuntos de los mensajes que no corresponden a ninguna ficha
+    pub sin_ficha: Vec<String>,
+}
+
+/// Mensaje por agregar como hoja a una ficha
+#[derive(Debug)]
+pub struct Entrega<'a> {
+    pub mensaje: &'a Mensaje,
+    pub destino: Destino,
+}
+
+/// Decide a qué ficha va cada mensaje: a `fija` si se indica, o según el
+/// asunto. Se omiten los que ya están cargados, también si el mismo
+/// mensaje aparece dos veces.
+pub fn repartir<'a>(
+    mensajes: &'a [Mensaje],
+    fichas: &[Ficha],
+    reglas: &[ReglaCorreo],
+    fija: Option<Uuid>,
+    fechas: &FormatoFecha,
+    resumen: &mut Resumen,
+) -> Vec<Entrega<'a>> {
+    let mut entregas = Vec::new();
+    let mut vistos = BTreeSet::new();
+    for mensaje in mensajes {
+        resumen.mensajes += 1;
+        let destino = match fija {
+            Some(ficha_id) => Some(Destino {
+                ficha_id,
+                categoria: None,
+            }),
+            None => destino(&mensaje.asunto, reglas, fichas),
+        };
+        let Some(destino) = destino else {
+            resumen.sin_ficha.push(mensaje.asunto.clone());
+            continue;
+        };
+        let Some(ficha) = fichas.iter().find(|f| f.id == destino.ficha_id) else {
+            resumen.sin_ficha.push(mensaje.asunto.clone());
+            continue;
+        };
+
+        let clave = (ficha.id, mensaje.fecha_evento(fechas), mensaje.contenido());
+        if ya_cargado(ficha, mensaje, fechas) || !vistos.insert(clave) {
+            resumen.repetidos += 1;
+            continue;
+        }
+        entregas.push(Entrega { mensaje, destino });
+    }
+    entregas
+}
+
+/// Guarda los adjuntos del mensaje en `destino`. Si ya hay un archivo con
+/// el mismo nombre y otro contenido, se agrega un número al nombre.
+pub fn guardar_adjuntos(mensaje: &Mensaje, destino: &Path) -> io::Result<Vec<Adjunto>> {
+    if mensaje.adjuntos.is_empty() {
+        return Ok(Vec::new());
+    }
+    fs::create_dir_all(destino)?;
+
+    let mut guardados = Vec::with_capacity(mensaje.adjuntos.len());
+    for archivo in &mensaje.adjuntos {
+        let nombre = nombre_seguro(&archivo.nombre);
+        let (base, extension) = match nombre.rsplit_once('.') {
+            Some((base, extension)) if !base.is_empty() => (base, format!(".{}", extension)),
+            _ => (nombre.as_str(), String::new()),
+        };
+
+        let mut candidato = nombre.clone();
+        let mut numero = 1;
+        let ruta = loop {
+            let ruta = destino.join(&candidato);
+            match fs::read(&ruta) {
+                Ok(existente) if existente == archivo.contenido => break ruta,
+                Ok(_) => {
+                    numero += 1;
+                    candidato = format!("{} ({}){}", base, numero, extension);
+                }
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    fs::write(&ruta, &archivo.contenido)?;
+                    break ruta;
+                }
+                Err(e) => return Err(e),
+            }
+        };
+        guardados.push(Adjunto {
+            nombre: candidato,
+            ruta,
+        });
+    }
+    Ok(guardados)
+}
+
+/// Nombre de archivo sin directorios ni caracteres que los sistemas de
+/// archivos no admiten
+fn nombre_seguro(nombre: &str) -> String {
+    let nombre = nombre.rsplit(['/', '\\']).next().unwrap_or_default();
+    let limpio: String = nombre
+        .chars()
+        .map(|c| match c {
+            ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    let limpio = limpio.trim().trim_start_matches('.');
+    if limpio.is_empty() {
+        "adjunto".to_string()
+    } else {
+        limpio.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::fechas::FORMATO_PREDETERMINADO;
+
+    fn fechas() -> FormatoFecha {
+        FormatoFecha::new(FORMATO_PREDETERMINADO, "America/Argentina/Buenos_Aires")
+    }
+
+    fn mensaje(asunto: &str) -> Mensaje {
+        Mensaje {
+            asunto: asunto.into(),
+            remitente: "notificaciones@pjn.gov.ar".into(),
+            fecha: DateTime::parse_from_rfc2822("Tue, 12 Mar 2024 10:15:00 -0300").ok(),
+            cuerpo: "Téngase presente.".into(),
+            adjuntos: vec![ArchivoAdjunto {
+                nombre: "../cédula.pdf".into(),
+                contenido: b"%PDF".to_vec(),
+            }],
+        }
+    }
+
+    #[test]
+    fn fecha_en_la_zona_configurada() {
+        let mut tarde = mensaje("Cédula");
+        tarde.fecha = DateTime::parse_from_rfc2822("Tue, 12 Mar 2024 22:30:00 -0300").ok();
+        let dia = |zona| tarde.fecha_evento(&FormatoFecha::new(FORMATO_PREDETERMINADO, zona));
+        assert_eq!(
+            dia("America/Argentina/Buenos_Aires"),
+            NaiveDate::from_ymd_opt(2024, 3, 12).unwrap()
+        );
+        assert_eq!(
+            dia("Europe/Madrid"),
+            NaiveDate::from_ymd_opt(2024, 3, 13).unwrap()
+        );
+    }
+
+    #[test]
+    fn contenido_de_la_hoja() {
+        assert_eq!(
+            mensaje("Cédula").contenido(),
+            "**Cédula**\n\nDe: notificaciones@pjn.gov.ar\n\nTéngase presente."
+        );
+        // Un correo sin asunto, remitente ni texto igual deja una hoja
+        let vacio = Mensaje {
+            asunto: "  ".into(),
+            remitente: "\t".into(),
+            cuerpo: "\r\n".into(),
+            ..mensaje("")
+        };
+        assert_eq!(vacio.contenido(), "**(sin asunto)**");
+    }
+
+    #[test]
+    fn asigna_por_regla_o_por_expediente() {
+        let fichas = [
+            Ficha::de_prueba("CNT 12345/2023"),
+            Ficha::de_prueba("LP 98765/2021"),
+        ];
+        let reglas = [ReglaCorreo::new(
+            "CNT 555/2020".into(),
+            fichas[1].id,
+            Some("notificacion".into()),
+        )];
+
+        let por_ficha = destino("Expte. Nro. 012345/2023", &reglas, &fichas);
+        assert_eq!(por_ficha.map(|d| d.ficha_id), Some(fichas[0].id));
+
+        let por_regla = destino("Notificación CNT 555/2020", &reglas, &fichas).unwrap();
+        assert_eq!(por_regla.ficha_id, fichas[1].id);
+        assert_eq!(por_regla.categoria.as_deref(), Some("notificacion"));
+
+        // La fecha del asunto también parece un expediente, pero no es de
+        // ninguna ficha
+        let con_fecha = destino("Audiencia 15/03/2024 - LP 98765/2021", &reglas, &fichas);
+        assert_eq!(con_fecha.map(|d| d.ficha_id), Some(fichas[1].id));
+
+        assert_eq!(destino("Consulta", &reglas, &fichas), None);
+        assert_eq!(destino("CIV 1/2000", &reglas, &fichas), None);
+    }
+
+    #[test]
+    fn no_repite_los_mensajes_cargados() {
+        let mut fichas = [Ficha::de_prueba("CNT 12345/2023")];
+        let cargado = mensaje("Expte. CNT 12345/2023 - cédula");
+        fichas[0].agregar_hoja_fechada(
+            cargado.fecha_evento(&fechas()),
+            cargado.contenido(),
+            None,
+            Vec::new(),
+            None,
+        );
+        let mensajes = [
+            cargado,
+            mensaje("Expte. CNT 12345/2023 - audiencia"),
+            mensaje("Expte. CNT 12345/2023 - audiencia"),
+            mensaje("Sin expediente"),
+        ];
+
+        let mut resumen = Resumen::default();
+        let entregas = repartir(&mensajes, &fichas, &[], None, &fechas(), &mut resumen);
+        assert_eq!(entregas.len(), 1);
+        assert_eq!(
+            entregas[0].mensaje.asunto,
+            "Expte. CNT 12345/2023 - audiencia"
+        );
+        assert_eq!(resumen.repetidos, 2);
+        assert_eq!(resumen.sin_ficha, ["Sin expediente"]);
+
+        // Con la ficha indicada no importa el asunto
+        let mut resumen = Resumen::default();
+        let entregas = repartir(
+            &mensajes[3..],
+            &fichas,
+            &[],
+            Some(fichas[0].id),
+            &fechas(),
+            &mut resumen,
+        );
+        assert_eq!(entregas.len(), 1);
+    }
+
+    #[test]
+    fn adjuntos_con_el_mismo_nombre() {
+        let destino = std::env::temp_dir().join(format!("correo-{}", Uuid::new_v4()));
+        let mut mensaje = mensaje("Cédula");
+
+        let primero = guardar_adjuntos(&mensaje, &destino).unwrap();
+        assert_eq!(primero[0].nombre, "cédula.pdf");
+        assert_eq!(primero[0].ruta, destino.join("cédula.pdf"));
+        // El mismo archivo se reutiliza; uno distinto no lo pisa
+        assert_eq!(guardar_adjuntos(&mensaje, &destino).unwrap(), primero);
+        mensaje.adjuntos[0].contenido = b"otro".to_vec();
+        let segundo = guardar_adjuntos(&mensaje, &destino).unwrap();
+        assert_eq!(segundo[0].nombre, "cédula (2).pdf");
+        assert_eq!(fs::read(&segundo[0].ruta).unwrap(), b"otro");
+
+        fs::remove_dir_all(&destino).unwrap();
+    }
+}
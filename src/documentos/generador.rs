@@ -0,0 +1,280 @@
+use crate::documentos::motor::{self, Contexto, ErrorPlantilla};
+use crate::documentos::pdf;
+use crate::models::{Adjunto, Ficha, Liquidacion};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+/// Partes de un ODT que pueden contener variables; en los ODT los
+/// encabezados y pies de página están en `styles.xml`
+const PARTES_ODT: [&str; 2] = ["content.xml", "styles.xml"];
+
+/// Prefijos de las partes de un DOCX que pueden contener variables: el
+/// documento, los estilos y cada encabezado y pie de página
+const PREFIJOS_DOCX: [&str; 4] = ["word/document", "word/styles", "word/header", "word/footer"];
+
+/// Si la parte del paquete puede contener variables
+fn parte_con_texto(nombre: &str) -> bool {
+    PARTES_ODT.contains(&nombre)
+        || (nombre.ends_with(".xml") && PREFIJOS_DOCX.iter().any(|p| nombre.starts_with(p)))
+}
+
+/// Plantilla de escrito encontrada en el directorio de plantillas
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlantillaEscrito {
+    pub nombre: String,
+    pub ruta: PathBuf,
+}
+
+impl fmt::Display for PlantillaEscrito {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.nombre)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Formato {
+    Texto,
+    Office,
+}
+
+impl Formato {
+    fn desde_ruta(ruta: &Path) -> Option<Self> {
+        let extension = ruta.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "md" | "txt" => Some(Formato::Texto),
+            "odt" | "docx" => Some(Formato::Office),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ErrorDocumento {
+    Plantilla(ErrorPlantilla),
+    Io(io::Error),
+    FormatoNoSoportado(PathBuf),
+}
+
+impl fmt::Display for ErrorDocumento {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorDocumento::Plantilla(e) => write!(f, "{}", e),
+            ErrorDocumento::Io(e) => write!(f, "{}", e),
+            ErrorDocumento::FormatoNoSoportado(ruta) => {
+                write!(f, "formato de plantilla no soportado: {}", ruta.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ErrorDocumento {}
+
+impl From<ErrorPlantilla> for ErrorDocumento {
+    fn from(e: ErrorPlantilla) -> Self {
+        ErrorDocumento::Plantilla(e)
+    }
+}
+
+impl From<io::Error> for ErrorDocumento {
+    fn from(e: io::Error) -> Self {
+        ErrorDocumento::Io(e)
+    }
+}
+
+impl From<zip::result::ZipError> for ErrorDocumento {
+    fn from(e: zip::result::ZipError) -> Self {
+        ErrorDocumento::Io(e.into())
+    }
+}
+
+/// Lista las plantillas de escritos (.md, .txt, .odt, .docx) de un directorio
+pub fn listar_plantillas(directorio: &Path) -> Vec<PlantillaEscrito> {
+    let Ok(entradas) = fs::read_dir(directorio) else {
+        return Vec::new();
+    };
+
+    let mut plantillas: Vec<PlantillaEscrito> = entradas
+        .filter_map(|entrada| entrada.ok().map(|e| e.path()))
+        .filter(|ruta| Formato::desde_ruta(ruta).is_some())
+        .filter_map(|ruta| {
+            let nombre = ruta.file_stem()?.to_string_lossy().into_owned();
+            Some(PlantillaEscrito { nombre, ruta })
+        })
+        .collect();
+
+    plantillas.sort_by(|a, b| a.nombre.cmp(&b.nombre));
+    plantillas
+}
+
+/// Genera un escrito a partir de la plantilla y los datos de la ficha.
+///
+/// El archivo se guarda en `destino` con `sello`, la fecha y hora de
+/// generación, en el nombre, y se devuelve como adjunto listo para agregarse
+/// a una hoja. Si ya hay un archivo con ese nombre se agrega un número.
+/// `fecha` es el día que completa la variable `{fecha}`.
+pub fn generar(
+    plantilla: &PlantillaEscrito,
+    ficha: &Ficha,
+    destino: &Path,
+    fecha: &str,
+    sello: &str,
+) -> Result<Adjunto, ErrorDocumento> {
+    let formato = Formato::desde_ruta(&plantilla.ruta)
+        .ok_or_else(|| ErrorDocumento::FormatoNoSoportado(plantilla.ruta.clone()))?;
+    let contexto = motor::contexto_de_ficha(ficha, fecha);
+
+    let contenido = match formato {
+        Formato::Texto => {
+            let texto = fs::read_to_string(&plantilla.ruta)?;
+            motor::completar(&texto, &contexto)?.into_bytes()
+        }
+        Formato::Office => completar_office(&plantilla.ruta, &contexto)?,
+    };
+
+    let extension = plantilla
+        .ruta
+        .extension()
+        .map(|e| e.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let base = format!("{}_{}", plantilla.nombre, sello);
+
+    fs::create_dir_all(destino)?;
+    let mut nombre = format!("{}.{}", base, extension);
+    let mut numero = 1;
+    // `create_new` no pisa un escrito generado en el mismo segundo
+    let ruta = loop {
+        let ruta = destino.join(&nombre);
+        match OpenOptions::new().write(true).create_new(true).open(&ruta) {
+            Ok(mut archivo) => {
+                archivo.write_all(&contenido)?;
+                break ruta;
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                numero += 1;
+                nombre = format!("{} ({}).{}", base, numero, extension);
+            }
+            Err(e) => return Err(e.into()),
+        }
+    };
+
+    Ok(Adjunto { nombre, ruta })
+}
+
+/// Guarda la liquidación en PDF y CSV dentro de `destino`. Ambos indican
+/// `emitida`, la fecha y hora de emisión con su zona horaria.
+pub fn generar_liquidacion(
+    liquidacion: &Liquidacion,
+    destino: &Path,
+    emitida: &str,
+) -> Result<Vec<Adjunto>, ErrorDocumento> {
+    fs::create_dir_all(destino)?;
+    let base = format!("liquidacion_{}", liquidacion.numero_formateado());
+
+    let archivos = [
+        (format!("{}.pdf", base), pdf::texto_a_pdf(&liquidacion.a_lineas(emitida))),
+        (format!("{}.csv", base), liquidacion.a_csv(emitida).into_bytes()),
+    ];
+
+    archivos
+        .into_iter()
+        .map(|(nombre, contenido)| {
+            let ruta = destino.join(&nombre);
+            fs::write(&ruta, contenido)?;
+            Ok(Adjunto { nombre, ruta })
+        })
+        .collect()
+}
+
+/// Copia el paquete ODT/DOCX completando las variables de las partes con texto.
+///
+/// Las variables deben estar escritas de corrido en el procesador de textos;
+/// si el formato parte una variable en varios fragmentos no se reconoce.
+fn completar_office(ruta: &Path, contexto: &Contexto) -> Result<Vec<u8>, ErrorDocumento> {
+    let contexto_xml: Contexto = contexto
+        .iter()
+        .map(|(clave, valor)| (clave.clone(), escapar_xml(valor)))
+        .collect();
+
+    let mut origen = ZipArchive::new(fs::File::open(ruta)?)?;
+    let mut salida = ZipWriter::new(io::Cursor::new(Vec::new()));
+
+    for i in 0..origen.len() {
+        let mut entrada = origen.by_index(i)?;
+        if !parte_con_texto(entrada.name()) {
+            salida.raw_copy_file(entrada)?;
+            continue;
+        }
+
+        let nombre = entrada.name().to_string();
+        let mut xml = String::new();
+        entrada.read_to_string(&mut xml)?;
+        let xml = motor::completar(&xml, &contexto_xml)?;
+
+        salida.start_file(nombre, SimpleFileOptions::default())?;
+        salida.write_all(xml.as_bytes())?;
+    }
+
+    Ok(salida.finish()?.into_inner())
+}
+
+fn escapar_xml(valor: &str) -> String {
+    valor
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn partes_con_variables() {
+        for parte in [
+            "content.xml",
+            "styles.xml",
+            "word/document.xml",
+            "word/styles.xml",
+            "word/header1.xml",
+            "word/footer2.xml",
+        ] {
+            assert!(parte_con_texto(parte), "{}", parte);
+        }
+        for parte in [
+            "meta.xml",
+            "word/_rels/document.xml.rels",
+            "word/media/header.png",
+            "word/footnotes.xml",
+        ] {
+            assert!(!parte_con_texto(parte), "{}", parte);
+        }
+    }
+
+    #[test]
+    fn no_pisa_un_escrito_con_el_mismo_nombre() {
+        let directorio = std::env::temp_dir().join(format!("escritos-{}", Uuid::new_v4()));
+        fs::create_dir_all(&directorio).unwrap();
+        let plantilla = PlantillaEscrito {
+            nombre: "demanda".into(),
+            ruta: directorio.join("demanda.md"),
+        };
+        fs::write(&plantilla.ruta, "Autos {caratula}").unwrap();
+        let ficha = Ficha::de_prueba("CNT 12345/2023");
+        let destino = directorio.join("adjuntos");
+
+        let sello = "20240312_101500";
+        let primero = generar(&plantilla, &ficha, &destino, "12/03/2024", sello).unwrap();
+        let segundo = generar(&plantilla, &ficha, &destino, "12/03/2024", sello).unwrap();
+        assert_eq!(primero.nombre, "demanda_20240312_101500.md");
+        assert_eq!(segundo.nombre, "demanda_20240312_101500 (2).md");
+        assert_eq!(fs::read_dir(&destino).unwrap().count(), 2);
+
+        fs::remove_dir_all(&directorio).unwrap();
+    }
+}
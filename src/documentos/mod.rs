@@ -0,0 +1,5 @@
+pub mod generador;
+pub mod motor;
+pub mod pdf;
+
+pub use generador::{generar, generar_liquidacion, listar_plantillas, PlantillaEscrito};
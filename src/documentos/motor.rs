@@ -0,0 +1,217 @@
+use crate::models::Ficha;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Roles de parte que siempre están disponibles como variables,
+/// aunque la ficha no tenga cargada ninguna parte con ese rol.
+const ROLES_BASICOS: [&str; 2] = ["actor", "demandado"];
+
+/// Valores disponibles para completar una plantilla, por nombre de variable
+pub type Contexto = BTreeMap<String, String>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorPlantilla {
+    /// La variable no existe en el contexto
+    VariableDesconocida(String),
+    /// La variable existe pero la ficha no tiene ese dato cargado
+    CampoFaltante(String),
+    /// Se abrió una llave en la posición indicada y nunca se cerró
+    LlaveSinCerrar(usize),
+}
+
+impl fmt::Display for ErrorPlantilla {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorPlantilla::VariableDesconocida(nombre) => {
+                write!(f, "la variable {{{}}} no existe", nombre)
+            }
+            ErrorPlantilla::CampoFaltante(nombre) => {
+                write!(f, "la ficha no tiene cargado el dato {{{}}}", nombre)
+            }
+            ErrorPlantilla::LlaveSinCerrar(posicion) => {
+                write!(f, "llave sin cerrar en la posición {}", posicion)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ErrorPlantilla {}
+
+/// Arma el contexto de variables a partir de los datos y partes de una ficha.
+///
+/// Cada rol de parte se expone en minúsculas (`{actor}`, `{demandado}`, ...);
+/// si hay varias partes con el mismo rol, sus nombres se unen con comas.
+/// `{fecha}` toma `fecha`, el día de hoy ya formateado en la zona configurada.
+pub fn contexto_de_ficha(ficha: &Ficha, fecha: &str) -> Contexto {
+    let mut contexto = Contexto::new();
+    contexto.insert("caratula".into(), ficha.titulo.clone());
+    contexto.insert("descripcion".into(), ficha.descripcion.clone());
+    contexto.insert("expediente".into(), ficha.expediente.clone());
+    contexto.insert("cliente".into(), ficha.cliente.clone());
+    contexto.insert("fecha".into(), fecha.to_string());
+
+    for rol in ROLES_BASICOS {
+        contexto.insert(rol.into(), String::new());
+    }
+    for parte in &ficha.partes {
+        let valor = contexto.entry(parte.rol.to_lowercase()).or_default();
+        if !valor.is_empty() {
+            valor.push_str(", ");
+        }
+        valor.push_str(&parte.nombre);
+    }
+
+    contexto.insert(
+        "partes".into(),
+        ficha
+            .partes
+            .iter()
+            .map(|p| p.nombre.as_str())
+            .collect::<Vec<_>>()
+            .join(", "),
+    );
+
+    contexto
+}
+
+/// Reemplaza cada `{variable}` de la plantilla por su valor en el contexto.
+///
+/// Las llaves dobles `{{` y `}}` se escriben como llaves literales.
+pub fn completar(plantilla: &str, contexto: &Contexto) -> Result<String, ErrorPlantilla> {
+    let mut resultado = String::with_capacity(plantilla.len());
+    let mut resto = plantilla;
+    let mut desplazamiento = 0;
+
+    while let Some(inicio) = resto.find(['{', '}']) {
+        resultado.push_str(&resto[..inicio]);
+        let despues = &resto[inicio + 1..];
+
+        if resto[inicio..].starts_with("{{") || resto[inicio..].starts_with("}}") {
+            resultado.push_str(&resto[inicio..inicio + 1]);
+            desplazamiento += inicio + 2;
+            resto = &resto[inicio + 2..];
+            continue;
+        }
+
+        if resto[inicio..].starts_with('}') {
+            resultado.push('}');
+            desplazamiento += inicio + 1;
+            resto = despues;
+            continue;
+        }
+
+        let fin = despues
+            .find('}')
+            .ok_or(ErrorPlantilla::LlaveSinCerrar(desplazamiento + inicio))?;
+        let nombre = despues[..fin].trim();
+
+        match contexto.get(nombre) {
+            Some(valor) if valor.trim().is_empty() => {
+                return Err(ErrorPlantilla::CampoFaltante(nombre.to_string()));
+            }
+            Some(valor) => resultado.push_str(valor),
+            None => return Err(ErrorPlantilla::VariableDesconocida(nombre.to_string())),
+        }
+
+        desplazamiento += inicio + fin + 2;
+        resto = &despues[fin + 1..];
+    }
+
+    resultado.push_str(resto);
+    Ok(resultado)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Parte;
+    use chrono::NaiveDate;
+
+    fn ficha_de_prueba() -> Ficha {
+        let mut ficha = Ficha::new(
+            "Pérez c/ ACME s/ despido".into(),
+            "Reclamo laboral".into(),
+            "Inicio".into(),
+            NaiveDate::default(),
+            None,
+        );
+        ficha.expediente = "CNT 1234/2024".into();
+        ficha.cliente = "Juan Pérez".into();
+        ficha.partes = vec![
+            Parte {
+                rol: "Actor".into(),
+                nombre: "Juan Pérez".into(),
+            },
+            Parte {
+                rol: "Demandado".into(),
+                nombre: "ACME S.A.".into(),
+            },
+            Parte {
+                rol: "Demandado".into(),
+                nombre: "Seguros XYZ".into(),
+            },
+        ];
+        ficha
+    }
+
+    #[test]
+    fn resuelve_variables_de_la_ficha() {
+        let contexto = contexto_de_ficha(&ficha_de_prueba(), "19/10/2026");
+        let texto = completar("Autos {caratula}, Expte. {expediente}", &contexto).unwrap();
+        assert_eq!(texto, "Autos Pérez c/ ACME s/ despido, Expte. CNT 1234/2024");
+    }
+
+    #[test]
+    fn une_partes_con_el_mismo_rol() {
+        let contexto = contexto_de_ficha(&ficha_de_prueba(), "19/10/2026");
+        assert_eq!(
+            completar("contra {demandado}", &contexto).unwrap(),
+            "contra ACME S.A., Seguros XYZ"
+        );
+        assert_eq!(completar("{ actor }", &contexto).unwrap(), "Juan Pérez");
+    }
+
+    #[test]
+    fn campo_vacio_es_error_de_campo_faltante() {
+        let mut ficha = ficha_de_prueba();
+        ficha.expediente.clear();
+        ficha.partes.clear();
+        let contexto = contexto_de_ficha(&ficha, "19/10/2026");
+
+        assert_eq!(
+            completar("Expte. {expediente}", &contexto),
+            Err(ErrorPlantilla::CampoFaltante("expediente".into()))
+        );
+        assert_eq!(
+            completar("contra {demandado}", &contexto),
+            Err(ErrorPlantilla::CampoFaltante("demandado".into()))
+        );
+    }
+
+    #[test]
+    fn variable_inexistente_es_error() {
+        let contexto = contexto_de_ficha(&ficha_de_prueba(), "19/10/2026");
+        assert_eq!(
+            completar("{juzgado}", &contexto),
+            Err(ErrorPlantilla::VariableDesconocida("juzgado".into()))
+        );
+    }
+
+    #[test]
+    fn llave_sin_cerrar_indica_la_posicion() {
+        let contexto = contexto_de_ficha(&ficha_de_prueba(), "19/10/2026");
+        assert_eq!(
+            completar("Sr. {{Juez}}: {cliente", &contexto),
+            Err(ErrorPlantilla::LlaveSinCerrar(14))
+        );
+    }
+
+    #[test]
+    fn llaves_dobles_son_literales() {
+        let contexto = contexto_de_ficha(&ficha_de_prueba(), "19/10/2026");
+        assert_eq!(
+            completar("{{cliente}} = {cliente}", &contexto).unwrap(),
+            "{cliente} = Juan Pérez"
+        );
+    }
+}
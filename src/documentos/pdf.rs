@@ -0,0 +1,119 @@
+//! Generación de PDFs de texto simple (una fuente, sin imágenes).
+
+const ANCHO_PAGINA: f32 = 595.0;
+const ALTO_PAGINA: f32 = 842.0;
+const MARGEN: f32 = 50.0;
+const TAMANIO_FUENTE: f32 = 10.0;
+const INTERLINEADO: f32 = 14.0;
+
+/// Arma un PDF A4 con las líneas indicadas en fuente Courier, paginando solo.
+///
+/// Los caracteres fuera de Latin-1 se reemplazan por `?`.
+pub fn texto_a_pdf(lineas: &[String]) -> Vec<u8> {
+    let lineas_por_pagina = ((ALTO_PAGINA - 2.0 * MARGEN) / INTERLINEADO) as usize;
+    let paginas: Vec<&[String]> = if lineas.is_empty() {
+        vec![&[]]
+    } else {
+        lineas.chunks(lineas_por_pagina).collect()
+    };
+
+    // Objetos: 1 catálogo, 2 árbol de páginas, 3 fuente, luego página y contenido
+    let mut objetos: Vec<Vec<u8>> = Vec::new();
+    objetos.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+
+    let hijos: Vec<String> = (0..paginas.len())
+        .map(|i| format!("{} 0 R", 4 + i * 2))
+        .collect();
+    objetos.push(
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            hijos.join(" "),
+            paginas.len()
+        )
+        .into_bytes(),
+    );
+    objetos.push(
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Courier /Encoding /WinAnsiEncoding >>".to_vec(),
+    );
+
+    for (i, pagina) in paginas.iter().enumerate() {
+        let contenido = contenido_pagina(pagina);
+        objetos.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                 /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+                ANCHO_PAGINA,
+                ALTO_PAGINA,
+                5 + i * 2
+            )
+            .into_bytes(),
+        );
+
+        let mut flujo = format!("<< /Length {} >>\nstream\n", contenido.len()).into_bytes();
+        flujo.extend_from_slice(&contenido);
+        flujo.extend_from_slice(b"\nendstream");
+        objetos.push(flujo);
+    }
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut desplazamientos = Vec::with_capacity(objetos.len());
+    for (i, objeto) in objetos.iter().enumerate() {
+        desplazamientos.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+        pdf.extend_from_slice(objeto);
+        pdf.extend_from_slice(b"\nendobj\n");
+    }
+
+    let inicio_xref = pdf.len();
+    pdf.extend_from_slice(
+        format!("xref\n0 {}\n0000000000 65535 f \n", objetos.len() + 1).as_bytes(),
+    );
+    for desplazamiento in desplazamientos {
+        pdf.extend_from_slice(format!("{:010} 00000 n \n", desplazamiento).as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objetos.len() + 1,
+            inicio_xref
+        )
+        .as_bytes(),
+    );
+    pdf
+}
+
+fn contenido_pagina(lineas: &[String]) -> Vec<u8> {
+    let mut contenido = format!(
+        "BT\n/F1 {} Tf\n{} TL\n{} {} Td\n",
+        TAMANIO_FUENTE,
+        INTERLINEADO,
+        MARGEN,
+        ALTO_PAGINA - MARGEN
+    )
+    .into_bytes();
+
+    for linea in lineas {
+        contenido.push(b'(');
+        contenido.extend(codificar(linea));
+        contenido.extend_from_slice(b") Tj T*\n");
+    }
+    contenido.extend_from_slice(b"ET");
+    contenido
+}
+
+/// Convierte a Latin-1 escapando los caracteres especiales de las cadenas PDF
+fn codificar(linea: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(linea.len());
+    for c in linea.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                bytes.push(b'\\');
+                bytes.push(c as u8);
+            }
+            c if (c as u32) < 0x20 => bytes.push(b' '),
+            c if (c as u32) <= 0xFF => bytes.push(c as u32 as u8),
+            _ => bytes.push(b'?'),
+        }
+    }
+    bytes
+}
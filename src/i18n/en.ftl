@@ -0,0 +1,339 @@
+app-titulo = Case Files - Management System
+
+## Dates (chrono format)
+
+fecha-larga = %B %-d, %Y
+fecha-ayuda = dd/mm/yyyy
+
+## Case file list
+
+fichas-titulo = Case files
+fichas-cantidad =
+    { $cantidad ->
+        [one] { $cantidad } file
+       *[other] { $cantidad } files
+    }
+fichas-vacia = There are no case files yet. Create a new one.
+fichas-buscar = Search (Ctrl+F)
+fichas-ver-tabla = Table
+fichas-ver-tarjetas = Cards
+fichas-nueva = + New case file
+fichas-filtrar-etiqueta = Filter by tag
+fichas-filtrar-estado = Status
+fichas-filtrar-responsable = Assignee
+ir-inicio = Home
+ir-etiquetas = Tags
+ir-categorias = Categories
+ir-honorarios = Fees
+ir-plantillas = Templates
+ir-mis-tareas = My tasks
+ir-horas = Hours
+ir-usuarios = Users
+ir-preferencias = Settings
+ir-importar = Import
+ir-correo = Email
+ir-auditoria = Audit log
+columna-titulo = Title
+columna-expediente = Docket
+columna-ultima-hoja = Latest entry
+columna-ultimo-movimiento = Latest activity
+columna-estado = Status
+tarjeta-sin-registrar = None recorded
+tarjeta-ultimo-escrito = Latest filing
+tarjeta-ultima-notificacion = Latest notice
+tarjeta-saldo = Balance: { $saldo }
+tarjeta-creada = Created: { $fecha }
+tarjeta-ver-hojas = View entries
+tarjeta-editar = Edit
+tarjeta-eliminar = Delete
+cronometro-iniciar = ▶ Time
+
+## Case file form
+
+formulario-editar = Edit case file
+formulario-nueva = New case file
+formulario-titulo = Title:
+formulario-titulo-ayuda = Case file title
+formulario-descripcion = Description:
+formulario-descripcion-ayuda = Case file description
+formulario-expediente = Docket:
+formulario-expediente-ayuda = Docket number
+formulario-cliente = Client:
+formulario-cliente-ayuda = Client name
+formulario-partes = Parties:
+formulario-partes-ayuda = Plaintiff: John Doe; Defendant: ACME Inc.
+formulario-etiquetas = Tags (click a tag to remove it):
+formulario-etiqueta-nueva = New tag and Enter
+formulario-estado = Status:
+formulario-responsable = Assignee:
+formulario-sin-responsable = Unassigned
+formulario-hoja-inicial = First entry:
+formulario-hoja-inicial-ayuda = Content of the first entry (Markdown supported)
+formulario-guardar = Save
+formulario-cancelar = Cancel
+
+## Entry history
+
+historial-titulo = Case file entries - { $titulo }
+historial-volver = ← Back
+historial-datos = Assignee: { $responsable } · Created by { $creador } · Modified by { $modificador } on { $fecha }
+historial-hojas =
+    { $cantidad ->
+        [one] One entry
+       *[other] { $cantidad } entries
+    } (newest first):
+historial-todas = All
+historial-hoja-datos = { $fecha } · Recorded on { $registrada } by { $autor }
+historial-no-encontrada = Case file not found
+sincronizacion-nunca = Not checked on the court portal yet
+sincronizacion-correcta = Checked on the court portal on { $fecha }: { $nuevas ->
+        [0] no new events
+        [one] one new event
+       *[other] { $nuevas } new events
+    }
+sincronizacion-fallida = ⚠ Court portal check failed on { $fecha }: { $error }
+sincronizacion-ahora = Check the portal
+sincronizacion-en-curso = Checking…
+pestania-hojas = Entries
+pestania-cuenta = Fees and expenses
+pestania-tiempo = Time
+nueva-hoja-titulo = Add a new entry:
+nueva-hoja-plantilla = Insert template...
+nueva-hoja-contenido = Type the content of the new entry (Markdown supported)...
+nueva-hoja-fecha = Activity date:
+nueva-hoja-categoria = Category (optional)
+nueva-hoja-agregar = Add
+nueva-hoja-fecha-invalida = Enter the activity date as dd/mm/yyyy
+nueva-hoja-fecha-futura = The activity date cannot be in the future
+escritos-titulo = Filings:
+correo-soltar = Drop an email (.eml), an mbox mailbox or a Maildir folder here to add it as an entry.
+escritos-generar = Generate filing...
+escritos-sin-plantillas = There are no filing templates in the plantillas_escritos folder
+
+## Descriptions of symbol-only buttons
+
+quitar-filtro = Clear filter
+nueva-hoja-quitar-categoria = Clear category
+selector-fecha-abrir = Pick from calendar
+selector-fecha-mes-anterior = Previous month
+selector-fecha-mes-siguiente = Next month
+
+## Comunes
+
+volver = ← Back
+guardar = Save
+cancelar = Cancel
+eliminar = Delete
+editar = Edit
+registrar = Record
+campo-nombre = Name:
+campo-color = Color:
+guardar-error = Could not save: { $error }
+hojas-nuevas-en-ficha = { $titulo }: { $cantidad ->
+        [one] one new entry
+       *[other] { $cantidad } new entries
+    }
+
+## Usuarios y roles
+
+administracion-titulo = Users and roles
+administracion-nuevo = New user
+administracion-crear = Create
+
+## Auditoría
+
+auditoria-verificar = Verify integrity
+auditoria-titulo = Audit log
+auditoria-vacia = No operations recorded
+
+## Honorarios y gastos
+
+balances-liquidar = New statement
+balances-titulo = Fees and expenses
+balances-moneda = Currency
+balances-cargos = Charges
+balances-pagos = Payments
+balances-saldo = Balance
+balances-por-cliente = By client
+balances-cliente = Client
+balances-por-mes = By month
+balances-mes = Month
+
+## Categorías
+
+categorias-titulo = Entry categories
+categorias-nueva = + New category
+categorias-ayuda = Select a category to change it or create a new one.
+categorias-icono = Icon:
+
+## Cuenta de la ficha
+
+cuenta-registrar = Record a movement:
+cuenta-saldo = Balance:
+cuenta-movimientos = Movements:
+cuenta-sin-movimientos = No movements
+
+## Etiquetas
+
+etiquetas-titulo = Tags
+etiquetas-vacia = No case file has tags yet.
+etiquetas-ayuda = Select a tag to rename it or change its color.
+etiquetas-sin-color = No color
+etiquetas-nuevo-nombre = New name:
+
+## Editor de hojas
+
+editor-negrita = Bold
+editor-lista = • List
+editor-titulo = Heading
+
+## Liquidaciones
+
+liquidacion-titulo = New statement
+liquidacion-cliente = Client:
+liquidacion-seleccionar-todo = Select all
+liquidacion-generar = Generate statement
+
+## Mis tareas
+
+mis-tareas-titulo = My tasks
+mis-tareas-vacia = No pending tasks
+mis-tareas-abrir = Open
+
+## Paleta de comandos
+
+paleta-sin-resultados = No results
+paleta-ayuda = ↑ ↓ to choose · Enter to open · Esc to close
+
+## Plantillas
+
+plantillas-nueva = + New Template
+plantillas-titulo = Entry templates
+plantillas-editar-titulo = Edit Template
+plantillas-nueva-titulo = New Template
+plantillas-contenido = Content:
+plantillas-marcadores =
+    Available placeholders: { "{caratula}" }, { "{expediente}" }, { "{fecha}" }, { "{cliente}" }
+plantillas-vacia = No templates. Create a new one.
+
+## Preferencias
+
+preferencias-titulo = Settings
+preferencias-apariencia = Appearance
+preferencias-accesibilidad = Accessibility
+preferencias-teclado =
+    Tab and Shift+Tab move between fields; Ctrl+K opens the command palette
+preferencias-avisos = Home screen alerts
+preferencias-datos = Data and backups
+preferencias-sincronizacion = Court portal sync
+preferencias-respaldar = Back up now
+preferencias-formato-invalido = invalid format
+preferencias-idioma = Language
+preferencias-tema = Theme
+preferencias-formato-fecha = Date and time format
+preferencias-zona-horaria = Time zone
+preferencias-zona-del-sistema = System default
+preferencias-tarjetas = Cards per row
+preferencias-tarjetas-segun-ancho = Based on width
+preferencias-escala = Interface scale
+preferencias-tamanio-minimo = Minimum font size
+preferencias-dias-sin-movimiento = Days without activity
+preferencias-dias-vencimientos = Days of deadline notice
+preferencias-archivo-datos = Data file
+preferencias-respaldo-al-iniciar = Back up the data on startup
+preferencias-directorio-respaldos = Backup folder
+preferencias-respaldos-conservar = Backups to keep
+preferencias-sincronizacion-activa =
+    Periodically check the movements of case files with a docket number
+preferencias-sincronizacion-url = Query address
+preferencias-sincronizacion-intervalo = Interval in minutes
+preferencias-sincronizacion-ayuda =
+    { "{expediente}" } is replaced by the full number (CNT 12345/2023);
+    { "{numero}" } and { "{anio}" }, by each part
+
+## Horas trabajadas
+
+reporte-tiempo-titulo = Hours worked
+reporte-tiempo-desde = From:
+reporte-tiempo-hasta = To:
+reporte-tiempo-columna = Time
+reporte-tiempo-por-ficha = By case file
+reporte-tiempo-ficha = Case file
+reporte-tiempo-por-cliente = By client
+reporte-tiempo-cliente = Client
+
+## Inicio
+
+tablero-titulo = Home
+tablero-ver-todas = View all case files
+tablero-sin-etiquetas = No tagged case files
+tablero-sin-movimiento = No activity for
+tablero-dias-o-mas = days or more
+tablero-sin-vencimientos = No upcoming deadlines
+tablero-grafico-ayuda = Click a bar to see that week's case files
+
+## Tareas de la ficha
+
+tareas-agregar = Add
+tareas-pendientes = Pending:
+tareas-hecha = Done
+tareas-hecha-hoja = Done + entry
+
+## Tiempo de la ficha
+
+tiempo-registrar = Record time:
+
+## Elección de usuario
+
+usuarios-vacia = There are no users yet. Create the first one.
+usuarios-titulo = Who is using the system?
+usuarios-primero = First user (administrator)
+usuarios-crear = Create and sign in
+
+## Fichas vinculadas
+
+vinculos-vincular = Link
+vinculos-vacia = No linked case files
+vinculos-titulo = Linked case files:
+
+## Correos
+
+correo-titulo = Import emails
+correo-ayuda =
+    Choose a saved email (.eml), an mbox mailbox or a Maildir folder. Each email is added as an entry to the case file whose docket number appears in the subject, with its sending date and attachments.
+correo-ayuda-arrastrar = To add emails to a specific case file, drag them onto its entries.
+correo-ruta = Email or mailbox:
+correo-importar = Import
+correo-sin-ficha = No case file: { $asunto }
+correo-resumen =
+    { $mensajes } emails read: { $agregadas } new entries, { $repetidos } already loaded
+correo-resumen-sin-ficha = { $resumen }, { $cantidad } without a case file
+correo-error-importar = Could not import: { $error }
+correo-error-adjuntos = Could not save the attachments: { $error }
+correo-reglas = Rules
+correo-reglas-ayuda =
+    They assign to a case file the emails that mention another docket number, such as that of a related case.
+correo-sin-reglas = No rules: each email goes to the case file with the same docket number.
+correo-ficha-eliminada = (deleted case file)
+correo-regla-expediente = Docket number in the subject (CNT 12345/2023)
+correo-regla-ficha = Case file
+correo-regla-categoria = Entry category (optional)
+correo-regla-agregar = Add rule
+correo-regla-sin-expediente =
+    No docket number recognized in “{ $expediente }” (example: CNT 12345/2023)
+correo-regla-sin-ficha = Choose the case file the emails go to
+
+## Importación de movimientos
+
+importacion-titulo = Import docket entries
+importacion-ayuda =
+    Save the case lookup from the PJN or MEV portal in your browser (HTML page, CSV export or text copied from the PDF) and choose the file here.
+importacion-ayuda-hojas =
+    Each docket entry is added to the case file with the same docket number. Those already loaded with the same date and text are skipped.
+importacion-ruta = File (.html, .csv or .txt):
+importacion-importar = Import
+importacion-resumen =
+    { $movimientos } docket entries read: { $agregadas } new entries in { $fichas } case files, { $repetidas } already loaded
+importacion-resumen-sin-ficha = { $resumen }. No case file: { $expedientes }
+importacion-resumen-ambiguos = { $resumen }. In more than one case file: { $expedientes }
+importacion-error = Could not import: { $error }
@@ -0,0 +1,340 @@
+app-titulo = Fichas Procuración - Sistema de Gestión
+
+## Fechas (formato de chrono)
+
+fecha-larga = %-d de %B de %Y
+fecha-ayuda = dd/mm/aaaa
+
+## Lista de fichas
+
+fichas-titulo = Fichas
+fichas-cantidad =
+    { $cantidad ->
+        [one] { $cantidad } ficha
+       *[other] { $cantidad } fichas
+    }
+fichas-vacia = No hay fichas. Crea una nueva.
+fichas-buscar = Buscar (Ctrl+F)
+fichas-ver-tabla = Tabla
+fichas-ver-tarjetas = Tarjetas
+fichas-nueva = + Nueva Ficha
+fichas-filtrar-etiqueta = Filtrar por etiqueta
+fichas-filtrar-estado = Estado
+fichas-filtrar-responsable = Responsable
+ir-inicio = Inicio
+ir-etiquetas = Etiquetas
+ir-categorias = Categorías
+ir-honorarios = Honorarios
+ir-plantillas = Plantillas
+ir-mis-tareas = Mis tareas
+ir-horas = Horas
+ir-usuarios = Usuarios
+ir-preferencias = Preferencias
+ir-importar = Importar
+ir-correo = Correos
+ir-auditoria = Auditoría
+columna-titulo = Título
+columna-expediente = Expediente
+columna-ultima-hoja = Última hoja
+columna-ultimo-movimiento = Último movimiento
+columna-estado = Estado
+tarjeta-sin-registrar = Sin registrar
+tarjeta-ultimo-escrito = Último escrito
+tarjeta-ultima-notificacion = Última notificación
+tarjeta-saldo = Saldo: { $saldo }
+tarjeta-creada = Creada: { $fecha }
+tarjeta-ver-hojas = Ver Hojas
+tarjeta-editar = Editar
+tarjeta-eliminar = Eliminar
+cronometro-iniciar = ▶ Tiempo
+
+## Formulario de ficha
+
+formulario-editar = Editar Ficha
+formulario-nueva = Nueva Ficha
+formulario-titulo = Título:
+formulario-titulo-ayuda = Título de la ficha
+formulario-descripcion = Descripción:
+formulario-descripcion-ayuda = Descripción de la ficha
+formulario-expediente = Expediente:
+formulario-expediente-ayuda = Número de expediente
+formulario-cliente = Cliente:
+formulario-cliente-ayuda = Nombre del cliente
+formulario-partes = Partes:
+formulario-partes-ayuda = Actor: Juan Pérez; Demandado: ACME S.A.
+formulario-etiquetas = Etiquetas (clic en una etiqueta para quitarla):
+formulario-etiqueta-nueva = Nueva etiqueta y Enter
+formulario-estado = Estado:
+formulario-responsable = Responsable:
+formulario-sin-responsable = Sin responsable
+formulario-hoja-inicial = Hoja inicial:
+formulario-hoja-inicial-ayuda = Contenido de la hoja inicial (admite Markdown)
+formulario-guardar = Guardar
+formulario-cancelar = Cancelar
+
+## Historial de hojas
+
+historial-titulo = Hojas de la Ficha - { $titulo }
+historial-volver = ← Volver
+historial-datos = Responsable: { $responsable } · Creada por { $creador } · Modificada por { $modificador } el { $fecha }
+historial-hojas =
+    { $cantidad ->
+        [one] Una hoja
+       *[other] { $cantidad } hojas
+    } (más reciente primero):
+historial-todas = Todas
+historial-hoja-datos = { $fecha } · Cargada el { $registrada } por { $autor }
+historial-no-encontrada = Ficha no encontrada
+sincronizacion-nunca = Todavía no se consultó en el portal
+sincronizacion-correcta = Consultada en el portal el { $fecha }: { $nuevas ->
+        [0] sin movimientos nuevos
+        [one] un movimiento nuevo
+       *[other] { $nuevas } movimientos nuevos
+    }
+sincronizacion-fallida = ⚠ Falló la consulta en el portal el { $fecha }: { $error }
+sincronizacion-ahora = Consultar el portal
+sincronizacion-en-curso = Consultando…
+pestania-hojas = Hojas
+pestania-cuenta = Honorarios y gastos
+pestania-tiempo = Tiempo
+nueva-hoja-titulo = Agregar nueva hoja:
+nueva-hoja-plantilla = Insertar plantilla...
+nueva-hoja-contenido = Ingrese el contenido de la nueva hoja (admite Markdown)...
+nueva-hoja-fecha = Fecha del movimiento:
+nueva-hoja-categoria = Categoría (opcional)
+nueva-hoja-agregar = Agregar
+nueva-hoja-fecha-invalida = Ingrese la fecha del movimiento como dd/mm/aaaa
+nueva-hoja-fecha-futura = La fecha del movimiento no puede ser futura
+escritos-titulo = Escritos:
+correo-soltar = Arrastre aquí un correo (.eml), un buzón mbox o una carpeta Maildir para agregarlo como hoja.
+escritos-generar = Generar escrito...
+escritos-sin-plantillas = No hay plantillas de escritos en la carpeta plantillas_escritos
+
+## Descripciones de los botones con símbolos
+
+quitar-filtro = Quitar filtro
+nueva-hoja-quitar-categoria = Quitar categoría
+selector-fecha-abrir = Elegir en el calendario
+selector-fecha-mes-anterior = Mes anterior
+selector-fecha-mes-siguiente = Mes siguiente
+
+## Comunes
+
+volver = ← Volver
+guardar = Guardar
+cancelar = Cancelar
+eliminar = Eliminar
+editar = Editar
+registrar = Registrar
+campo-nombre = Nombre:
+campo-color = Color:
+guardar-error = No se pudo guardar: { $error }
+hojas-nuevas-en-ficha = { $titulo }: { $cantidad ->
+        [one] una hoja nueva
+       *[other] { $cantidad } hojas nuevas
+    }
+
+## Usuarios y roles
+
+administracion-titulo = Usuarios y roles
+administracion-nuevo = Nuevo usuario
+administracion-crear = Crear
+
+## Auditoría
+
+auditoria-verificar = Verificar integridad
+auditoria-titulo = Auditoría
+auditoria-vacia = No hay operaciones registradas
+
+## Honorarios y gastos
+
+balances-liquidar = Nueva liquidación
+balances-titulo = Honorarios y gastos
+balances-moneda = Moneda
+balances-cargos = Cargos
+balances-pagos = Pagos
+balances-saldo = Saldo
+balances-por-cliente = Por cliente
+balances-cliente = Cliente
+balances-por-mes = Por mes
+balances-mes = Mes
+
+## Categorías
+
+categorias-titulo = Categorías de hojas
+categorias-nueva = + Nueva categoría
+categorias-ayuda = Seleccioná una categoría para modificarla o creá una nueva.
+categorias-icono = Ícono:
+
+## Cuenta de la ficha
+
+cuenta-registrar = Registrar movimiento:
+cuenta-saldo = Saldo:
+cuenta-movimientos = Movimientos:
+cuenta-sin-movimientos = Sin movimientos
+
+## Etiquetas
+
+etiquetas-titulo = Etiquetas
+etiquetas-vacia = Ninguna ficha tiene etiquetas todavía.
+etiquetas-ayuda = Seleccioná una etiqueta para renombrarla o cambiar su color.
+etiquetas-sin-color = Sin color
+etiquetas-nuevo-nombre = Nuevo nombre:
+
+## Editor de hojas
+
+editor-negrita = Negrita
+editor-lista = • Lista
+editor-titulo = Título
+
+## Liquidaciones
+
+liquidacion-titulo = Nueva liquidación
+liquidacion-cliente = Cliente:
+liquidacion-seleccionar-todo = Seleccionar todo
+liquidacion-generar = Generar liquidación
+
+## Mis tareas
+
+mis-tareas-titulo = Mis tareas
+mis-tareas-vacia = No hay tareas pendientes
+mis-tareas-abrir = Abrir
+
+## Paleta de comandos
+
+paleta-sin-resultados = Sin resultados
+paleta-ayuda = ↑ ↓ para elegir · Enter para abrir · Esc para cerrar
+
+## Plantillas
+
+plantillas-nueva = + Nueva Plantilla
+plantillas-titulo = Plantillas de hojas
+plantillas-editar-titulo = Editar Plantilla
+plantillas-nueva-titulo = Nueva Plantilla
+plantillas-contenido = Contenido:
+plantillas-marcadores =
+    Marcadores disponibles: { "{caratula}" }, { "{expediente}" }, { "{fecha}" }, { "{cliente}" }
+plantillas-vacia = No hay plantillas. Crea una nueva.
+
+## Preferencias
+
+preferencias-titulo = Preferencias
+preferencias-apariencia = Apariencia
+preferencias-accesibilidad = Accesibilidad
+preferencias-teclado =
+    Tab y Shift+Tab recorren los campos; Ctrl+K abre la paleta de comandos
+preferencias-avisos = Avisos del inicio
+preferencias-datos = Datos y respaldos
+preferencias-sincronizacion = Sincronización con el portal
+preferencias-respaldar = Respaldar ahora
+preferencias-formato-invalido = formato inválido
+preferencias-idioma = Idioma
+preferencias-tema = Tema
+preferencias-formato-fecha = Formato de fecha y hora
+preferencias-zona-horaria = Zona horaria
+preferencias-zona-del-sistema = Del sistema
+preferencias-tarjetas = Tarjetas por fila
+preferencias-tarjetas-segun-ancho = Según el ancho
+preferencias-escala = Escala de la interfaz
+preferencias-tamanio-minimo = Tamaño mínimo de letra
+preferencias-dias-sin-movimiento = Días sin movimiento
+preferencias-dias-vencimientos = Días de aviso de vencimientos
+preferencias-archivo-datos = Archivo de datos
+preferencias-respaldo-al-iniciar = Respaldar los datos al iniciar
+preferencias-directorio-respaldos = Directorio de respaldos
+preferencias-respaldos-conservar = Respaldos a conservar
+preferencias-sincronizacion-activa =
+    Consultar periódicamente los movimientos de las fichas con expediente
+preferencias-sincronizacion-url = Dirección de la consulta
+preferencias-sincronizacion-intervalo = Intervalo en minutos
+preferencias-sincronizacion-ayuda =
+    { "{expediente}" } se reemplaza por el número completo (CNT 12345/2023);
+    { "{numero}" } y { "{anio}" }, por cada parte
+
+## Horas trabajadas
+
+reporte-tiempo-titulo = Horas trabajadas
+reporte-tiempo-desde = Desde:
+reporte-tiempo-hasta = Hasta:
+reporte-tiempo-columna = Tiempo
+reporte-tiempo-por-ficha = Por ficha
+reporte-tiempo-ficha = Ficha
+reporte-tiempo-por-cliente = Por cliente
+reporte-tiempo-cliente = Cliente
+
+## Inicio
+
+tablero-titulo = Inicio
+tablero-ver-todas = Ver todas las fichas
+tablero-sin-etiquetas = No hay fichas etiquetadas
+tablero-sin-movimiento = Sin movimiento hace
+tablero-dias-o-mas = días o más
+tablero-sin-vencimientos = No hay vencimientos próximos
+tablero-grafico-ayuda = Clic en una barra para ver las fichas de esa semana
+
+## Tareas de la ficha
+
+tareas-agregar = Agregar
+tareas-pendientes = Pendientes:
+tareas-hecha = Hecha
+tareas-hecha-hoja = Hecha + hoja
+
+## Tiempo de la ficha
+
+tiempo-registrar = Registrar tiempo:
+
+## Elección de usuario
+
+usuarios-vacia = Todavía no hay usuarios. Cree el primero.
+usuarios-titulo = ¿Quién está usando el sistema?
+usuarios-primero = Primer usuario (administrador)
+usuarios-crear = Crear y entrar
+
+## Fichas vinculadas
+
+vinculos-vincular = Vincular
+vinculos-vacia = Sin fichas vinculadas
+vinculos-titulo = Fichas vinculadas:
+
+## Correos
+
+correo-titulo = Importar correos
+correo-ayuda =
+    Indique un correo guardado (.eml), un buzón mbox o una carpeta Maildir. Cada correo se agrega como hoja a la ficha del expediente mencionado en el asunto, con la fecha de envío y sus adjuntos.
+correo-ayuda-arrastrar =
+    Para agregar correos a una ficha en particular, arrástrelos sobre sus hojas.
+correo-ruta = Correo o buzón:
+correo-importar = Importar
+correo-sin-ficha = Sin ficha: { $asunto }
+correo-resumen =
+    { $mensajes } correos leídos: { $agregadas } hojas nuevas, { $repetidos } ya cargados
+correo-resumen-sin-ficha = { $resumen }, { $cantidad } sin ficha
+correo-error-importar = No se pudo importar: { $error }
+correo-error-adjuntos = No se pudieron guardar los adjuntos: { $error }
+correo-reglas = Reglas
+correo-reglas-ayuda =
+    Asignan a una ficha los correos que mencionan otro número de expediente, como el de una causa conexa.
+correo-sin-reglas = Sin reglas: cada correo va a la ficha con su mismo número de expediente.
+correo-ficha-eliminada = (ficha eliminada)
+correo-regla-expediente = Expediente del asunto (CNT 12345/2023)
+correo-regla-ficha = Ficha
+correo-regla-categoria = Categoría de la hoja (opcional)
+correo-regla-agregar = Agregar regla
+correo-regla-sin-expediente =
+    No se reconoce un número de expediente en «{ $expediente }» (ejemplo: CNT 12345/2023)
+correo-regla-sin-ficha = Elija la ficha a la que van los correos
+
+## Importación de movimientos
+
+importacion-titulo = Importar movimientos
+importacion-ayuda =
+    Guarde desde el navegador la consulta de la causa en el PJN o en la MEV (página HTML, exportación CSV o texto copiado del PDF) e indique aquí el archivo.
+importacion-ayuda-hojas =
+    Cada movimiento se agrega como hoja a la ficha con el mismo número de expediente. Los que ya estén cargados con la misma fecha y texto se omiten.
+importacion-ruta = Archivo (.html, .csv o .txt):
+importacion-importar = Importar
+importacion-resumen =
+    { $movimientos } movimientos leídos: { $agregadas } hojas nuevas en { $fichas } fichas, { $repetidas } ya cargadas
+importacion-resumen-sin-ficha = { $resumen }. Sin ficha: { $expedientes }
+importacion-resumen-ambiguos = { $resumen }. En más de una ficha: { $expedientes }
+importacion-error = No se pudo importar: { $error }
@@ -0,0 +1,186 @@
+//! Textos de la interfaz en cada idioma, con catálogos de Fluent
+//! (https://projectfluent.org). Los catálogos se incluyen en el ejecutable;
+//! para agregar un texto hay que sumar la clave en todos ellos.
+
+use crate::models::Idioma;
+use chrono::Locale;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use std::sync::OnceLock;
+use unic_langid::LanguageIdentifier;
+
+const CATALOGO_ES: &str = include_str!("es.ftl");
+const CATALOGO_EN: &str = include_str!("en.ftl");
+const CATALOGO_PT: &str = include_str!("pt.ftl");
+
+type Catalogo = FluentBundle<FluentResource>;
+
+fn fuente(idioma: Idioma) -> &'static str {
+    match idioma {
+        Idioma::Es => CATALOGO_ES,
+        Idioma::En => CATALOGO_EN,
+        Idioma::Pt => CATALOGO_PT,
+    }
+}
+
+/// Identificador de idioma y región, que define las reglas de plural
+fn identificador(idioma: Idioma) -> LanguageIdentifier {
+    let codigo = match idioma {
+        Idioma::Es => "es-AR",
+        Idioma::En => "en-US",
+        Idioma::Pt => "pt-BR",
+    };
+    codigo.parse().expect("identificador de idioma válido")
+}
+
+/// Convenciones de `chrono` para nombres de meses y días
+pub fn locale(idioma: Idioma) -> Locale {
+    match idioma {
+        Idioma::Es => Locale::es_AR,
+        Idioma::En => Locale::en_US,
+        Idioma::Pt => Locale::pt_BR,
+    }
+}
+
+fn crear_catalogo(idioma: Idioma) -> Catalogo {
+    let recurso = FluentResource::try_new(fuente(idioma).to_string())
+        .unwrap_or_else(|(_, errores)| panic!("catálogo {:?} inválido: {:?}", idioma, errores));
+    let mut catalogo = FluentBundle::new_concurrent(vec![identificador(idioma)]);
+    // Sin las marcas de aislamiento de Unicode, que la interfaz mostraría
+    catalogo.set_use_isolating(false);
+    catalogo.add_resource(recurso).unwrap_or_else(|errores| {
+        panic!("catálogo {:?} con claves repetidas: {:?}", idioma, errores)
+    });
+    catalogo
+}
+
+/// Catálogo del idioma, armado la primera vez que se usa
+fn catalogo(idioma: Idioma) -> &'static Catalogo {
+    static CATALOGOS: OnceLock<Vec<Catalogo>> = OnceLock::new();
+    let catalogos =
+        CATALOGOS.get_or_init(|| Idioma::TODOS.iter().map(|i| crear_catalogo(*i)).collect());
+    let posicion = Idioma::TODOS
+        .iter()
+        .position(|i| *i == idioma)
+        .unwrap_or_default();
+    &catalogos[posicion]
+}
+
+/// Texto de la clave en el idioma; si faltara, se usa el español y, en
+/// último caso, la clave misma para que se note en la interfaz
+pub fn texto(idioma: Idioma, clave: &str) -> String {
+    texto_con(idioma, clave, &[])
+}
+
+/// Como `texto`, con argumentos para las variables del mensaje. Los números
+/// eligen la forma plural según las reglas del idioma.
+pub fn texto_con(idioma: Idioma, clave: &str, argumentos: &[(&str, FluentValue<'_>)]) -> String {
+    let mut args = FluentArgs::new();
+    for (nombre, valor) in argumentos {
+        args.set(*nombre, valor.clone());
+    }
+    [idioma, Idioma::default()]
+        .into_iter()
+        .find_map(|idioma| formatear(catalogo(idioma), clave, &args))
+        .unwrap_or_else(|| clave.to_string())
+}
+
+fn formatear(catalogo: &Catalogo, clave: &str, args: &FluentArgs) -> Option<String> {
+    let patron = catalogo.get_message(clave)?.value()?;
+    let mut errores = Vec::new();
+    let texto = catalogo.format_pattern(patron, Some(args), &mut errores);
+    Some(texto.into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fluent_syntax::ast::Entry;
+    use std::collections::BTreeSet;
+
+    fn claves(idioma: Idioma) -> BTreeSet<String> {
+        let recurso = fluent_syntax::parser::parse(fuente(idioma))
+            .unwrap_or_else(|(_, errores)| panic!("{:?}: {:?}", idioma, errores));
+        recurso
+            .body
+            .iter()
+            .filter_map(|entrada| match entrada {
+                Entry::Message(mensaje) => Some(mensaje.id.name.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn todas_las_claves_estan_en_todos_los_catalogos() {
+        let todas: BTreeSet<String> = Idioma::TODOS.iter().flat_map(|i| claves(*i)).collect();
+        for idioma in Idioma::TODOS {
+            let propias = claves(idioma);
+            let faltantes: Vec<_> = todas.difference(&propias).collect();
+            assert!(
+                faltantes.is_empty(),
+                "al catálogo {:?} le faltan: {:?}",
+                idioma,
+                faltantes
+            );
+        }
+    }
+
+    #[test]
+    fn los_catalogos_se_cargan_sin_errores() {
+        for idioma in Idioma::TODOS {
+            let catalogo = crear_catalogo(idioma);
+            for clave in claves(idioma) {
+                let patron = catalogo.get_message(&clave).and_then(|m| m.value());
+                assert!(patron.is_some(), "{:?}: «{}» no tiene texto", idioma, clave);
+            }
+        }
+    }
+
+    #[test]
+    fn plurales_segun_el_idioma() {
+        let cantidad =
+            |idioma, n: usize| texto_con(idioma, "fichas-cantidad", &[("cantidad", n.into())]);
+        assert_eq!(cantidad(Idioma::Es, 1), "1 ficha");
+        assert_eq!(cantidad(Idioma::Es, 3), "3 fichas");
+        assert_eq!(cantidad(Idioma::En, 1), "1 file");
+        assert_eq!(cantidad(Idioma::En, 0), "0 files");
+        assert_eq!(cantidad(Idioma::Pt, 0), "0 ficha");
+        assert_eq!(cantidad(Idioma::Pt, 2), "2 fichas");
+    }
+
+    #[test]
+    fn argumentos_faltantes_o_de_otro_tipo() {
+        // Sin la cantidad queda la variante por defecto con el nombre de la variable
+        assert_eq!(texto(Idioma::Es, "fichas-cantidad"), "{$cantidad} fichas");
+        // Un texto no elige la forma singular aunque diga "1"
+        let texto_uno = texto_con(Idioma::En, "fichas-cantidad", &[("cantidad", "1".into())]);
+        assert_eq!(texto_uno, "1 files");
+        // Los argumentos de más se ignoran
+        let de_mas = [("cantidad", 1.into()), ("otro", "x".into())];
+        assert_eq!(texto_con(Idioma::Pt, "fichas-cantidad", &de_mas), "1 ficha");
+        assert_eq!(texto(Idioma::En, "no-existe"), "no-existe");
+        assert_eq!(texto(Idioma::En, ""), "");
+    }
+
+    #[test]
+    fn las_fechas_largas_son_formatos_validos() {
+        use chrono::format::{Item, StrftimeItems};
+        for idioma in Idioma::TODOS {
+            let formato = texto(idioma, "fecha-larga");
+            let invalido = StrftimeItems::new_with_locale(&formato, locale(idioma))
+                .any(|item| item == Item::Error);
+            assert!(!invalido, "{:?}: «{}» no es un formato de fecha", idioma, formato);
+        }
+    }
+
+    #[test]
+    fn los_marcadores_de_las_plantillas_se_muestran_tal_cual() {
+        for idioma in Idioma::TODOS {
+            let marcadores = texto(idioma, "plantillas-marcadores");
+            assert!(marcadores.ends_with("{caratula}, {expediente}, {fecha}, {cliente}"));
+            let ayuda = texto(idioma, "preferencias-sincronizacion-ayuda");
+            assert!(ayuda.starts_with("{expediente} "), "{:?}: «{}»", idioma, ayuda);
+        }
+    }
+}
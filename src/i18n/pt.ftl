@@ -0,0 +1,340 @@
+app-titulo = Fichas Procuração - Sistema de Gestão
+
+## Datas (formato do chrono)
+
+fecha-larga = %-d de %B de %Y
+fecha-ayuda = dd/mm/aaaa
+
+## Lista de fichas
+
+fichas-titulo = Fichas
+fichas-cantidad =
+    { $cantidad ->
+        [one] { $cantidad } ficha
+       *[other] { $cantidad } fichas
+    }
+fichas-vacia = Não há fichas. Crie uma nova.
+fichas-buscar = Buscar (Ctrl+F)
+fichas-ver-tabla = Tabela
+fichas-ver-tarjetas = Cartões
+fichas-nueva = + Nova Ficha
+fichas-filtrar-etiqueta = Filtrar por etiqueta
+fichas-filtrar-estado = Situação
+fichas-filtrar-responsable = Responsável
+ir-inicio = Início
+ir-etiquetas = Etiquetas
+ir-categorias = Categorias
+ir-honorarios = Honorários
+ir-plantillas = Modelos
+ir-mis-tareas = Minhas tarefas
+ir-horas = Horas
+ir-usuarios = Usuários
+ir-preferencias = Preferências
+ir-importar = Importar
+ir-correo = E-mails
+ir-auditoria = Auditoria
+columna-titulo = Título
+columna-expediente = Processo
+columna-ultima-hoja = Última folha
+columna-ultimo-movimiento = Última movimentação
+columna-estado = Situação
+tarjeta-sin-registrar = Sem registro
+tarjeta-ultimo-escrito = Última petição
+tarjeta-ultima-notificacion = Última intimação
+tarjeta-saldo = Saldo: { $saldo }
+tarjeta-creada = Criada: { $fecha }
+tarjeta-ver-hojas = Ver Folhas
+tarjeta-editar = Editar
+tarjeta-eliminar = Excluir
+cronometro-iniciar = ▶ Tempo
+
+## Formulário de ficha
+
+formulario-editar = Editar Ficha
+formulario-nueva = Nova Ficha
+formulario-titulo = Título:
+formulario-titulo-ayuda = Título da ficha
+formulario-descripcion = Descrição:
+formulario-descripcion-ayuda = Descrição da ficha
+formulario-expediente = Processo:
+formulario-expediente-ayuda = Número do processo
+formulario-cliente = Cliente:
+formulario-cliente-ayuda = Nome do cliente
+formulario-partes = Partes:
+formulario-partes-ayuda = Autor: João Silva; Réu: ACME Ltda.
+formulario-etiquetas = Etiquetas (clique em uma etiqueta para removê-la):
+formulario-etiqueta-nueva = Nova etiqueta e Enter
+formulario-estado = Situação:
+formulario-responsable = Responsável:
+formulario-sin-responsable = Sem responsável
+formulario-hoja-inicial = Folha inicial:
+formulario-hoja-inicial-ayuda = Conteúdo da folha inicial (aceita Markdown)
+formulario-guardar = Salvar
+formulario-cancelar = Cancelar
+
+## Histórico de folhas
+
+historial-titulo = Folhas da Ficha - { $titulo }
+historial-volver = ← Voltar
+historial-datos = Responsável: { $responsable } · Criada por { $creador } · Modificada por { $modificador } em { $fecha }
+historial-hojas =
+    { $cantidad ->
+        [one] { $cantidad } folha
+       *[other] { $cantidad } folhas
+    } (mais recente primeiro):
+historial-todas = Todas
+historial-hoja-datos = { $fecha } · Registrada em { $registrada } por { $autor }
+historial-no-encontrada = Ficha não encontrada
+sincronizacion-nunca = Ainda não consultada no portal
+sincronizacion-correcta = Consultada no portal em { $fecha }: { $nuevas ->
+        [0] sem movimentos novos
+        [one] um movimento novo
+       *[other] { $nuevas } movimentos novos
+    }
+sincronizacion-fallida = ⚠ A consulta no portal falhou em { $fecha }: { $error }
+sincronizacion-ahora = Consultar o portal
+sincronizacion-en-curso = Consultando…
+pestania-hojas = Folhas
+pestania-cuenta = Honorários e despesas
+pestania-tiempo = Tempo
+nueva-hoja-titulo = Adicionar nova folha:
+nueva-hoja-plantilla = Inserir modelo...
+nueva-hoja-contenido = Digite o conteúdo da nova folha (aceita Markdown)...
+nueva-hoja-fecha = Data da movimentação:
+nueva-hoja-categoria = Categoria (opcional)
+nueva-hoja-agregar = Adicionar
+nueva-hoja-fecha-invalida = Informe a data da movimentação como dd/mm/aaaa
+nueva-hoja-fecha-futura = A data da movimentação não pode ser futura
+escritos-titulo = Petições:
+correo-soltar = Arraste aqui um e-mail (.eml), uma caixa mbox ou uma pasta Maildir para adicioná-lo como folha.
+escritos-generar = Gerar petição...
+escritos-sin-plantillas = Não há modelos de petições na pasta plantillas_escritos
+
+## Descrições dos botões com símbolos
+
+quitar-filtro = Remover filtro
+nueva-hoja-quitar-categoria = Remover categoria
+selector-fecha-abrir = Escolher no calendário
+selector-fecha-mes-anterior = Mês anterior
+selector-fecha-mes-siguiente = Próximo mês
+
+## Comunes
+
+volver = ← Voltar
+guardar = Salvar
+cancelar = Cancelar
+eliminar = Excluir
+editar = Editar
+registrar = Registrar
+campo-nombre = Nome:
+campo-color = Cor:
+guardar-error = Não foi possível salvar: { $error }
+hojas-nuevas-en-ficha = { $titulo }: { $cantidad ->
+        [one] uma folha nova
+       *[other] { $cantidad } folhas novas
+    }
+
+## Usuarios y roles
+
+administracion-titulo = Usuários e funções
+administracion-nuevo = Novo usuário
+administracion-crear = Criar
+
+## Auditoría
+
+auditoria-verificar = Verificar integridade
+auditoria-titulo = Auditoria
+auditoria-vacia = Não há operações registradas
+
+## Honorarios y gastos
+
+balances-liquidar = Nova liquidação
+balances-titulo = Honorários e despesas
+balances-moneda = Moeda
+balances-cargos = Cobranças
+balances-pagos = Pagamentos
+balances-saldo = Saldo
+balances-por-cliente = Por cliente
+balances-cliente = Cliente
+balances-por-mes = Por mês
+balances-mes = Mês
+
+## Categorías
+
+categorias-titulo = Categorias de folhas
+categorias-nueva = + Nova categoria
+categorias-ayuda = Selecione uma categoria para alterá-la ou crie uma nova.
+categorias-icono = Ícone:
+
+## Cuenta de la ficha
+
+cuenta-registrar = Registrar movimento:
+cuenta-saldo = Saldo:
+cuenta-movimientos = Movimentos:
+cuenta-sin-movimientos = Sem movimentos
+
+## Etiquetas
+
+etiquetas-titulo = Etiquetas
+etiquetas-vacia = Nenhuma ficha tem etiquetas ainda.
+etiquetas-ayuda = Selecione uma etiqueta para renomeá-la ou mudar sua cor.
+etiquetas-sin-color = Sem cor
+etiquetas-nuevo-nombre = Novo nome:
+
+## Editor de hojas
+
+editor-negrita = Negrito
+editor-lista = • Lista
+editor-titulo = Título
+
+## Liquidaciones
+
+liquidacion-titulo = Nova liquidação
+liquidacion-cliente = Cliente:
+liquidacion-seleccionar-todo = Selecionar tudo
+liquidacion-generar = Gerar liquidação
+
+## Mis tareas
+
+mis-tareas-titulo = Minhas tarefas
+mis-tareas-vacia = Não há tarefas pendentes
+mis-tareas-abrir = Abrir
+
+## Paleta de comandos
+
+paleta-sin-resultados = Sem resultados
+paleta-ayuda = ↑ ↓ para escolher · Enter para abrir · Esc para fechar
+
+## Plantillas
+
+plantillas-nueva = + Novo Modelo
+plantillas-titulo = Modelos de folhas
+plantillas-editar-titulo = Editar Modelo
+plantillas-nueva-titulo = Novo Modelo
+plantillas-contenido = Conteúdo:
+plantillas-marcadores =
+    Marcadores disponíveis: { "{caratula}" }, { "{expediente}" }, { "{fecha}" }, { "{cliente}" }
+plantillas-vacia = Não há modelos. Crie um novo.
+
+## Preferencias
+
+preferencias-titulo = Preferências
+preferencias-apariencia = Aparência
+preferencias-accesibilidad = Acessibilidade
+preferencias-teclado =
+    Tab e Shift+Tab percorrem os campos; Ctrl+K abre a paleta de comandos
+preferencias-avisos = Avisos do início
+preferencias-datos = Dados e backups
+preferencias-sincronizacion = Sincronização com o portal
+preferencias-respaldar = Fazer backup agora
+preferencias-formato-invalido = formato inválido
+preferencias-idioma = Idioma
+preferencias-tema = Tema
+preferencias-formato-fecha = Formato de data e hora
+preferencias-zona-horaria = Fuso horário
+preferencias-zona-del-sistema = Do sistema
+preferencias-tarjetas = Cartões por linha
+preferencias-tarjetas-segun-ancho = Conforme a largura
+preferencias-escala = Escala da interface
+preferencias-tamanio-minimo = Tamanho mínimo da letra
+preferencias-dias-sin-movimiento = Dias sem movimento
+preferencias-dias-vencimientos = Dias de aviso de prazos
+preferencias-archivo-datos = Arquivo de dados
+preferencias-respaldo-al-iniciar = Fazer backup dos dados ao iniciar
+preferencias-directorio-respaldos = Pasta de backups
+preferencias-respaldos-conservar = Backups a manter
+preferencias-sincronizacion-activa =
+    Consultar periodicamente os movimentos das fichas com processo
+preferencias-sincronizacion-url = Endereço da consulta
+preferencias-sincronizacion-intervalo = Intervalo em minutos
+preferencias-sincronizacion-ayuda =
+    { "{expediente}" } é substituído pelo número completo (CNT 12345/2023);
+    { "{numero}" } e { "{anio}" }, por cada parte
+
+## Horas trabajadas
+
+reporte-tiempo-titulo = Horas trabalhadas
+reporte-tiempo-desde = De:
+reporte-tiempo-hasta = Até:
+reporte-tiempo-columna = Tempo
+reporte-tiempo-por-ficha = Por ficha
+reporte-tiempo-ficha = Ficha
+reporte-tiempo-por-cliente = Por cliente
+reporte-tiempo-cliente = Cliente
+
+## Inicio
+
+tablero-titulo = Início
+tablero-ver-todas = Ver todas as fichas
+tablero-sin-etiquetas = Não há fichas com etiquetas
+tablero-sin-movimiento = Sem movimento há
+tablero-dias-o-mas = dias ou mais
+tablero-sin-vencimientos = Não há prazos próximos
+tablero-grafico-ayuda = Clique em uma barra para ver as fichas dessa semana
+
+## Tareas de la ficha
+
+tareas-agregar = Adicionar
+tareas-pendientes = Pendentes:
+tareas-hecha = Feita
+tareas-hecha-hoja = Feita + folha
+
+## Tiempo de la ficha
+
+tiempo-registrar = Registrar tempo:
+
+## Elección de usuario
+
+usuarios-vacia = Ainda não há usuários. Crie o primeiro.
+usuarios-titulo = Quem está usando o sistema?
+usuarios-primero = Primeiro usuário (administrador)
+usuarios-crear = Criar e entrar
+
+## Fichas vinculadas
+
+vinculos-vincular = Vincular
+vinculos-vacia = Sem fichas vinculadas
+vinculos-titulo = Fichas vinculadas:
+
+## Correos
+
+correo-titulo = Importar e-mails
+correo-ayuda =
+    Indique um e-mail salvo (.eml), uma caixa mbox ou uma pasta Maildir. Cada e-mail é adicionado como folha à ficha do processo mencionado no assunto, com a data de envio e seus anexos.
+correo-ayuda-arrastrar =
+    Para adicionar e-mails a uma ficha específica, arraste-os sobre suas folhas.
+correo-ruta = E-mail ou caixa:
+correo-importar = Importar
+correo-sin-ficha = Sem ficha: { $asunto }
+correo-resumen =
+    { $mensajes } e-mails lidos: { $agregadas } folhas novas, { $repetidos } já carregados
+correo-resumen-sin-ficha = { $resumen }, { $cantidad } sem ficha
+correo-error-importar = Não foi possível importar: { $error }
+correo-error-adjuntos = Não foi possível salvar os anexos: { $error }
+correo-reglas = Regras
+correo-reglas-ayuda =
+    Atribuem a uma ficha os e-mails que mencionam outro número de processo, como o de uma causa conexa.
+correo-sin-reglas = Sem regras: cada e-mail vai para a ficha com o mesmo número de processo.
+correo-ficha-eliminada = (ficha excluída)
+correo-regla-expediente = Processo do assunto (CNT 12345/2023)
+correo-regla-ficha = Ficha
+correo-regla-categoria = Categoria da folha (opcional)
+correo-regla-agregar = Adicionar regra
+correo-regla-sin-expediente =
+    Nenhum número de processo reconhecido em «{ $expediente }» (exemplo: CNT 12345/2023)
+correo-regla-sin-ficha = Escolha a ficha para a qual vão os e-mails
+
+## Importación de movimientos
+
+importacion-titulo = Importar movimentações
+importacion-ayuda =
+    Salve no navegador a consulta da causa no PJN ou na MEV (página HTML, exportação CSV ou texto copiado do PDF) e indique aqui o arquivo.
+importacion-ayuda-hojas =
+    Cada movimentação é adicionada como folha à ficha com o mesmo número de processo. As que já estiverem carregadas com a mesma data e texto são omitidas.
+importacion-ruta = Arquivo (.html, .csv ou .txt):
+importacion-importar = Importar
+importacion-resumen =
+    { $movimientos } movimentações lidas: { $agregadas } folhas novas em { $fichas } fichas, { $repetidas } já carregadas
+importacion-resumen-sin-ficha = { $resumen }. Sem ficha: { $expedientes }
+importacion-resumen-ambiguos = { $resumen }. Em mais de uma ficha: { $expedientes }
+importacion-error = Não foi possível importar: { $error }
@@ -0,0 +1,150 @@
+//! Listados exportados a CSV desde los portales o desde una planilla. Los
+//! portales separan con punto y coma; las planillas, a veces con coma.
+//! Antes de la fila de títulos puede haber líneas con los datos de la causa.
+
+use super::{
+    buscar_expediente, movimientos_de_tabla, ClaveExpediente, ErrorImportacion, Movimiento,
+};
+
+pub fn parsear(
+    contenido: &str,
+    conocido: Option<&ClaveExpediente>,
+) -> Result<Vec<Movimiento>, ErrorImportacion> {
+    let contenido = contenido.trim_start_matches('\u{feff}');
+    let separador = detectar_separador(contenido);
+    let filas = leer_filas(contenido, separador);
+
+    let lineas: Vec<String> = filas.iter().map(|fila| fila.join(" ")).collect();
+    let expediente =
+        buscar_expediente(lineas.iter().map(String::as_str)).or_else(|| conocido.cloned());
+    movimientos_de_tabla(&filas, expediente.as_ref())
+}
+
+/// El separador que más aparece fuera de comillas en las primeras líneas
+fn detectar_separador(contenido: &str) -> char {
+    let mut puntos_y_coma = 0;
+    let mut comas = 0;
+    let mut entre_comillas = false;
+    for c in contenido.lines().take(20).flat_map(str::chars) {
+        match c {
+            '"' => entre_comillas = !entre_comillas,
+            ';' if !entre_comillas => puntos_y_coma += 1,
+            ',' if !entre_comillas => comas += 1,
+            _ => {}
+        }
+    }
+    if comas > puntos_y_coma {
+        ','
+    } else {
+        ';'
+    }
+}
+
+/// Filas con sus campos; los campos entre comillas pueden tener el
+/// separador, saltos de línea y comillas dobladas
+fn leer_filas(contenido: &str, separador: char) -> Vec<Vec<String>> {
+    let mut filas = Vec::new();
+    let mut fila = Vec::new();
+    let mut campo = String::new();
+    let mut entre_comillas = false;
+    let mut caracteres = contenido.chars().peekable();
+
+    while let Some(c) = caracteres.next() {
+        match c {
+            '"' if entre_comillas && caracteres.peek() == Some(&'"') => {
+                campo.push('"');
+                caracteres.next();
+            }
+            '"' => entre_comillas = !entre_comillas,
+            c if c == separador && !entre_comillas => fila.push(std::mem::take(&mut campo)),
+            '\r' if !entre_comillas => {}
+            '\n' if !entre_comillas => {
+                fila.push(std::mem::take(&mut campo));
+                filas.push(std::mem::take(&mut fila));
+            }
+            c => campo.push(c),
+        }
+    }
+    if !campo.is_empty() || !fila.is_empty() {
+        fila.push(campo);
+        filas.push(fila);
+    }
+    filas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    const PJN: &str = include_str!("fixtures/pjn_actuaciones.csv");
+    const PLANILLA: &str = include_str!("fixtures/planilla_movimientos.csv");
+
+    #[test]
+    fn actuaciones_del_pjn_con_punto_y_coma() {
+        let movimientos = parsear(PJN, None).unwrap();
+        assert_eq!(movimientos.len(), 3);
+
+        let expediente = ClaveExpediente::extraer("CNT 12345/2023").unwrap();
+        assert!(movimientos.iter().all(|m| m.expediente == expediente));
+        assert_eq!(
+            movimientos[0].fecha,
+            NaiveDate::from_ymd_opt(2024, 3, 12).unwrap()
+        );
+        assert_eq!(
+            movimientos[0].texto,
+            "FIRMA DESPACHO: SE PROVEE; téngase presente \"lo manifestado\""
+        );
+        assert_eq!(movimientos[2].texto, "ESCRITO INCORPORADO: INICIA DEMANDA");
+    }
+
+    #[test]
+    fn planilla_con_expediente_por_fila() {
+        let movimientos = parsear(PLANILLA, None).unwrap();
+        assert_eq!(movimientos.len(), 3);
+
+        let civil = ClaveExpediente::extraer("CIV 45678/2022").unwrap();
+        let laboral = ClaveExpediente::extraer("CNT 12345/2023").unwrap();
+        assert_eq!(movimientos[0].expediente, civil);
+        assert_eq!(movimientos[1].expediente, laboral);
+        assert_eq!(
+            movimientos[1].fecha,
+            NaiveDate::from_ymd_opt(2024, 4, 2).unwrap()
+        );
+        assert_eq!(movimientos[2].texto, "Audiencia de conciliación fijada");
+    }
+
+    #[test]
+    fn filas_incompletas_y_fechas_imposibles() {
+        // Filas cortadas antes de la descripción, fechas que no existen y
+        // una comilla sin cerrar que se lleva el resto del archivo
+        let csv = "Expediente;Fecha;Detalle\n\
+                   CNT 1/2020;01/02/2024\n\
+                   CNT 1/2020;31/02/2024;Fecha imposible\n\
+                   CNT 1/2020;2024-13-01;Mes imposible\n\
+                   CNT 1/2020\n\
+                   CNT 1/2020;05/02/2024;Se provee\n\
+                   CNT 1/2020;06/02/2024;\"Sin cerrar\n\
+                   CNT 1/2020;07/02/2024;Queda dentro del campo\n";
+        let movimientos = parsear(csv, None).unwrap();
+        assert_eq!(movimientos.len(), 2);
+        assert_eq!(movimientos[0].texto, "Se provee");
+        assert_eq!(
+            movimientos[1].texto,
+            "Sin cerrar CNT 1/2020;07/02/2024;Queda dentro del campo"
+        );
+
+        let sin_expediente = "Fecha;Descripción\n01/02/2024;Se provee\n";
+        assert!(matches!(
+            parsear(sin_expediente, None),
+            Err(ErrorImportacion::SinExpediente)
+        ));
+    }
+
+    #[test]
+    fn campos_entre_comillas() {
+        let filas = leer_filas("a;\"b;c\"\r\n\"d\ne\";\"f \"\"g\"\"\"\n", ';');
+        assert_eq!(filas, vec![vec!["a", "b;c"], vec!["d\ne", "f \"g\""]]);
+        assert_eq!(detectar_separador("Fecha,Detalle\n\"1;2\",x"), ',');
+    }
+}
@@ -0,0 +1,309 @@
+//! Páginas guardadas con "Guardar como" desde el navegador: la consulta de
+//! causas del PJN y el detalle de la causa en la MEV. Los movimientos están
+//! en una tabla; el expediente, en el encabezado de la página.
+
+use super::{
+    buscar_expediente, movimientos_de_tabla, ClaveExpediente, ErrorImportacion, Movimiento,
+};
+
+/// Elementos que cortan el texto en líneas al aplanar la página
+const BLOQUES: [&str; 12] = [
+    "br", "p", "div", "tr", "li", "h1", "h2", "h3", "h4", "table", "section", "header",
+];
+
+pub fn parsear(
+    contenido: &str,
+    conocido: Option<&ClaveExpediente>,
+) -> Result<Vec<Movimiento>, ErrorImportacion> {
+    let pagina = Pagina::leer(contenido);
+    let expediente = buscar_expediente(pagina.texto.lines()).or_else(|| conocido.cloned());
+
+    // La página puede tener otras tablas (menús, datos de la causa); vale
+    // la primera que tenga columnas de movimientos
+    let mut error = ErrorImportacion::SinMovimientos;
+    for tabla in &pagina.tablas {
+        match movimientos_de_tabla(tabla, expediente.as_ref()) {
+            Ok(movimientos) => return Ok(movimientos),
+            Err(ErrorImportacion::SinMovimientos) => {}
+            Err(e) => error = e,
+        }
+    }
+    Err(error)
+}
+
+/// Texto de un documento HTML, una línea por bloque y sin líneas en blanco
+/// repetidas; para mostrar como texto plano un correo que solo trae HTML
+pub fn texto(html: &str) -> String {
+    let pagina = Pagina::leer(html);
+    let mut texto = String::new();
+    let mut en_blanco = true;
+    for linea in pagina.texto.lines().map(super::unir_espacios) {
+        if linea.is_empty() {
+            if !en_blanco {
+                texto.push('\n');
+            }
+            en_blanco = true;
+            continue;
+        }
+        texto.push_str(&linea);
+        texto.push('\n');
+        en_blanco = false;
+    }
+    texto.trim_end().to_string()
+}
+
+/// Lo que interesa de la página: las tablas con el texto de cada celda y el
+/// texto completo separado en líneas
+#[derive(Debug, Default)]
+struct Pagina {
+    tablas: Vec<Vec<Vec<String>>>,
+    texto: String,
+}
+
+impl Pagina {
+    fn leer(html: &str) -> Self {
+        let mut pagina = Pagina::default();
+        // Tablas abiertas; las anidadas se apilan y al cerrarse se guardan
+        let mut abiertas: Vec<Vec<Vec<String>>> = Vec::new();
+        let mut celda: Option<String> = None;
+        let mut resto = html;
+
+        while let Some(inicio) = resto.find(['<', '&']) {
+            pagina.agregar_texto(&resto[..inicio], &mut celda);
+            resto = &resto[inicio..];
+
+            if resto.starts_with('&') {
+                let (caracter, largo) = entidad(resto);
+                pagina.agregar_texto(&caracter, &mut celda);
+                resto = &resto[largo..];
+                continue;
+            }
+            if let Some(comentario) = resto.strip_prefix("<!--") {
+                resto = comentario
+                    .find("-->")
+                    .map_or("", |fin| &comentario[fin + 3..]);
+                continue;
+            }
+
+            let fin = resto.find('>').map_or(resto.len(), |i| i + 1);
+            let etiqueta = nombre_etiqueta(&resto[..fin]);
+            resto = &resto[fin..];
+
+            let (cierre, nombre) = match etiqueta.strip_prefix('/') {
+                Some(nombre) => (true, nombre),
+                None => (false, etiqueta.as_str()),
+            };
+            if !cierre && (nombre == "script" || nombre == "style") {
+                let cierre = format!("</{}", nombre);
+                resto = buscar_sin_mayusculas(resto, &cierre).map_or("", |i| &resto[i..]);
+                continue;
+            }
+            if BLOQUES.contains(&nombre) {
+                pagina.texto.push('\n');
+            }
+
+            match (nombre, cierre) {
+                ("table", false) => abiertas.push(Vec::new()),
+                ("table", true) => {
+                    cerrar_celda(&mut abiertas, &mut celda);
+                    if let Some(tabla) = abiertas.pop() {
+                        pagina.tablas.push(tabla);
+                    }
+                }
+                ("tr", false) => {
+                    cerrar_celda(&mut abiertas, &mut celda);
+                    if let Some(tabla) = abiertas.last_mut() {
+                        tabla.push(Vec::new());
+                    }
+                }
+                ("td" | "th", false) => {
+                    cerrar_celda(&mut abiertas, &mut celda);
+                    celda = Some(String::new());
+                    pagina.texto.push(' ');
+                }
+                ("td" | "th" | "tr", true) => cerrar_celda(&mut abiertas, &mut celda),
+                ("br" | "p" | "div", _) => {
+                    if let Some(celda) = celda.as_mut() {
+                        celda.push(' ');
+                    }
+                }
+                _ => {}
+            }
+        }
+        pagina.agregar_texto(resto, &mut celda);
+        // Tablas sin cerrar en páginas guardadas a medias, con su última celda
+        cerrar_celda(&mut abiertas, &mut celda);
+        pagina.tablas.extend(abiertas);
+        pagina
+    }
+
+    fn agregar_texto(&mut self, texto: &str, celda: &mut Option<String>) {
+        self.texto.push_str(texto);
+        if let Some(celda) = celda.as_mut() {
+            celda.push_str(texto);
+        }
+    }
+}
+
+fn cerrar_celda(abiertas: &mut [Vec<Vec<String>>], celda: &mut Option<String>) {
+    let Some(texto) = celda.take() else {
+        return;
+    };
+    if let Some(tabla) = abiertas.last_mut() {
+        if tabla.is_empty() {
+            tabla.push(Vec::new());
+        }
+        if let Some(fila) = tabla.last_mut() {
+            fila.push(texto.trim().to_string());
+        }
+    }
+}
+
+/// Nombre de la etiqueta en minúsculas, con la barra si es de cierre
+fn nombre_etiqueta(etiqueta: &str) -> String {
+    etiqueta
+        .trim_start_matches('<')
+        .trim_end_matches('>')
+        .split(|c: char| c.is_whitespace() || c == '>')
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches('/')
+        .to_lowercase()
+}
+
+fn buscar_sin_mayusculas(texto: &str, buscado: &str) -> Option<usize> {
+    texto.char_indices().map(|(i, _)| i).find(|&i| {
+        texto
+            .get(i..i + buscado.len())
+            .is_some_and(|s| s.eq_ignore_ascii_case(buscado))
+    })
+}
+
+/// Carácter de la entidad al comienzo de `texto` y cuántos bytes ocupa;
+/// un `&` que no empieza una entidad conocida queda como está
+fn entidad(texto: &str) -> (String, usize) {
+    let Some(fin) = texto[1..].find(';').map(|i| i + 1).filter(|&i| i <= 10) else {
+        return ("&".to_string(), 1);
+    };
+    let nombre = &texto[1..fin];
+    let caracter = match nombre {
+        "nbsp" => Some(' '),
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "aacute" => Some('á'),
+        "eacute" => Some('é'),
+        "iacute" => Some('í'),
+        "oacute" => Some('ó'),
+        "uacute" => Some('ú'),
+        "Aacute" => Some('Á'),
+        "Eacute" => Some('É'),
+        "Iacute" => Some('Í'),
+        "Oacute" => Some('Ó'),
+        "Uacute" => Some('Ú'),
+        "ntilde" => Some('ñ'),
+        "Ntilde" => Some('Ñ'),
+        "uuml" => Some('ü'),
+        "ordm" => Some('º'),
+        "deg" => Some('°'),
+        _ => nombre
+            .strip_prefix("#x")
+            .or_else(|| nombre.strip_prefix("#X"))
+            .map(|hex| u32::from_str_radix(hex, 16))
+            .or_else(|| nombre.strip_prefix('#').map(str::parse))
+            .and_then(Result::ok)
+            .and_then(char::from_u32),
+    };
+    match caracter {
+        Some(caracter) => (caracter.to_string(), fin + 1),
+        None => ("&".to_string(), 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    const PJN: &str = include_str!("fixtures/pjn_actuaciones.html");
+    const MEV: &[u8] = include_bytes!("fixtures/mev_pasos.html");
+
+    fn fecha(dia: u32, mes: u32, anio: i32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(anio, mes, dia).unwrap()
+    }
+
+    #[test]
+    fn actuaciones_del_pjn() {
+        let movimientos = parsear(PJN, None).unwrap();
+        assert_eq!(movimientos.len(), 4);
+
+        let expediente = ClaveExpediente::extraer("CNT 12345/2023").unwrap();
+        assert!(movimientos.iter().all(|m| m.expediente == expediente));
+
+        assert_eq!(movimientos[0].fecha, fecha(12, 3, 2024));
+        assert_eq!(
+            movimientos[0].texto,
+            "FIRMA DESPACHO: SE PROVEE - Téngase presente lo manifestado & agréguese"
+        );
+        assert_eq!(movimientos[3].fecha, fecha(1, 11, 2023));
+        assert_eq!(movimientos[3].texto, "ESCRITO INCORPORADO: INICIA DEMANDA");
+    }
+
+    #[test]
+    fn pasos_de_la_mev_en_latin1() {
+        let movimientos = parsear(&super::super::decodificar(MEV.to_vec()), None).unwrap();
+        assert_eq!(movimientos.len(), 3);
+
+        let expediente = ClaveExpediente::extraer("LP 98765/2021").unwrap();
+        assert!(movimientos.iter().all(|m| m.expediente == expediente));
+        assert_eq!(movimientos[0].fecha, fecha(5, 2, 2024));
+        assert_eq!(
+            movimientos[0].texto,
+            "AUTOS PARA SENTENCIA - Pasen los autos a resolución"
+        );
+        assert_eq!(movimientos[2].texto, "TRASLADO DE LA DEMANDA");
+    }
+
+    #[test]
+    fn pagina_sin_tabla_de_movimientos() {
+        let html = "<html><body><p>Expediente: CNT 1/2020</p><table><tr><td>Menú</td></tr>\
+                    </table></body></html>";
+        assert!(matches!(
+            parsear(html, None),
+            Err(ErrorImportacion::SinMovimientos)
+        ));
+    }
+
+    #[test]
+    fn entidades_sin_terminar_o_invalidas() {
+        assert_eq!(entidad("&aacute;rbol"), ("á".to_string(), 8));
+        assert_eq!(entidad("&#xF3;"), ("ó".to_string(), 6));
+        // Sin punto y coma, el `;` demasiado lejos o al final del texto
+        assert_eq!(entidad("&aacute"), ("&".to_string(), 1));
+        assert_eq!(entidad("&amp y otras cosas;"), ("&".to_string(), 1));
+        assert_eq!(entidad("&"), ("&".to_string(), 1));
+        // Números que no son un carácter
+        assert_eq!(entidad("&#xD800;"), ("&".to_string(), 1));
+        assert_eq!(entidad("&#1114112;"), ("&".to_string(), 1));
+        assert_eq!(entidad("&#x;"), ("&".to_string(), 1));
+        assert_eq!(entidad("&ñ;"), ("&".to_string(), 1));
+        assert_eq!(texto("Tasa &amp costas &#xZZ; &lt;b&gt;"), "Tasa &amp costas &#xZZ; <b>");
+    }
+
+    #[test]
+    fn pagina_guardada_a_medias() {
+        // Se cortó dentro de una etiqueta, con la tabla y la celda abiertas
+        let html = "<p>Expediente: CNT 1/2020</p><table><tr><th>Fecha<th>Detalle\
+                    <tr><td>12/03/2024<td>Se provee <!-- sin cerrar <tr><td>13/03/2024";
+        let movimientos = parsear(html, None).unwrap();
+        assert_eq!(movimientos.len(), 1);
+        assert_eq!(movimientos[0].texto, "Se provee");
+
+        let cortada = "<table><tr><th>Fecha<th>Detalle<tr><td>12/03/2024<td>Traslado<td";
+        let movimientos = parsear(cortada, ClaveExpediente::extraer("CNT 1/2020").as_ref());
+        assert_eq!(movimientos.unwrap()[0].texto, "Traslado");
+        assert_eq!(texto("<script>alert(1)"), "");
+    }
+}
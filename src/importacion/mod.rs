@@ -0,0 +1,602 @@
+//! Importación de movimientos publicados por los portales judiciales
+//! (consulta de causas del PJN, MEV de la SCBA) a partir de las páginas o
+//! listados que el usuario guarda en su equipo.
+
+pub mod csv;
+pub mod html;
+pub mod texto;
+
+use crate::models::Ficha;
+use chrono::{Datelike, NaiveDate};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Nombres con que los portales titulan cada columna de los movimientos,
+/// ya normalizados con `normalizar`
+const COLUMNAS_FECHA: [&str; 4] = [
+    "fecha",
+    "fecha del paso",
+    "fecha de firma",
+    "fecha despacho",
+];
+const COLUMNAS_DESCRIPCION: [&str; 8] = [
+    "descripcion",
+    "descripcion/detalle",
+    "detalle",
+    "tramite",
+    "titulo",
+    "paso procesal",
+    "despacho",
+    "actuacion",
+];
+const COLUMNAS_TIPO: [&str; 2] = ["tipo", "tipo de actuacion"];
+const COLUMNAS_EXPEDIENTE: [&str; 4] = ["expediente", "causa", "numero de causa", "receptoria"];
+
+/// Rótulos que preceden al número de expediente en el encabezado de las
+/// páginas y de los PDF
+const ROTULOS_EXPEDIENTE: [&str; 5] = [
+    "expediente",
+    "numero de causa",
+    "nro. de causa",
+    "causa nro",
+    "receptoria",
+];
+
+/// Abreviaturas de "número" que pueden preceder al expediente y no son una
+/// sigla ("Expte. Nro. 12345/2023")
+const ABREVIATURAS_NUMERO: [&str; 4] = ["no", "nro", "num", "nros"];
+
+/// Movimiento leído de un portal, antes de convertirlo en hoja
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Movimiento {
+    pub expediente: ClaveExpediente,
+    pub fecha: NaiveDate,
+    pub texto: String,
+}
+
+#[derive(Debug)]
+pub enum ErrorImportacion {
+    Lectura(PathBuf, io::Error),
+    /// La extensión no corresponde a ningún formato conocido
+    FormatoDesconocido(PathBuf),
+    /// No se encontró la tabla o las columnas de fecha y descripción
+    SinMovimientos,
+    /// Hay movimientos pero no se pudo saber de qué expediente son
+    SinExpediente,
+}
+
+impl fmt::Display for ErrorImportacion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorImportacion::Lectura(ruta, e) => {
+                write!(f, "no se pudo leer {}: {}", ruta.display(), e)
+            }
+            ErrorImportacion::FormatoDesconocido(ruta) => write!(
+                f,
+                "{} no es un archivo HTML, CSV o de texto (.html, .csv, .txt)",
+                ruta.display()
+            ),
+            ErrorImportacion::SinMovimientos => {
+                write!(f, "el archivo no tiene una lista de movimientos con fecha")
+            }
+            ErrorImportacion::SinExpediente => {
+                write!(f, "no se encontró el número de expediente en el archivo")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ErrorImportacion {}
+
+/// Número de expediente reducido a sus partes, para comparar los que cada
+/// portal escribe distinto: "CNT 012345/2023", "CNT 12345/2023" y
+/// "CNT-12345-2023" son el mismo.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ClaveExpediente {
+    /// Sigla de la cámara o departamento judicial, si figura
+    pub prefijo: Option<String>,
+    pub numero: u64,
+    pub anio: u32,
+    /// Incidentes o recursos ("/1", "/2"), que son expedientes distintos
+    pub incidentes: Vec<u32>,
+}
+
+impl ClaveExpediente {
+    /// Busca un número de expediente en `texto`: un número seguido del año,
+    /// con la sigla previa y los incidentes posteriores si los hay
+    pub fn extraer(texto: &str) -> Option<Self> {
+        Self::todas(texto).into_iter().next()
+    }
+
+    /// Todos los números con forma de expediente de `texto`, en orden. Una
+    /// fecha como 15/03/2024 también la tiene; quien busca entre varios
+    /// candidatos decide cuál corresponde.
+    pub fn todas(texto: &str) -> Vec<Self> {
+        let partes = fragmentos(texto);
+        let es_numero =
+            |s: &str| !s.is_empty() && s.len() <= 9 && s.bytes().all(|b| b.is_ascii_digit());
+        let es_anio =
+            |s: &str| s.len() == 4 && s.parse::<u32>().is_ok_and(|a| (1900..2100).contains(&a));
+
+        let mut claves = Vec::new();
+        for posicion in 0..partes.len().saturating_sub(1) {
+            if !(es_numero(partes[posicion].0)
+                && matches!(partes[posicion + 1].1, '/' | '-')
+                && es_anio(partes[posicion + 1].0))
+            {
+                continue;
+            }
+
+            let prefijo = posicion
+                .checked_sub(1)
+                .map(|i| partes[i].0)
+                .filter(|p| {
+                    (2..=5).contains(&p.len())
+                        && p.chars().all(|c| c.is_ascii_alphabetic())
+                        && !ABREVIATURAS_NUMERO.contains(&p.to_lowercase().as_str())
+                })
+                .map(|p| p.to_uppercase());
+            let incidentes = partes[posicion + 2..]
+                .iter()
+                .take_while(|(parte, separador)| *separador == '/' && es_numero(parte))
+                .filter_map(|(parte, _)| parte.parse().ok())
+                .collect();
+
+            if let (Ok(numero), Ok(anio)) =
+                (partes[posicion].0.parse(), partes[posicion + 1].0.parse())
+            {
+                claves.push(Self {
+                    prefijo,
+                    numero,
+                    anio,
+                    incidentes,
+                });
+            }
+        }
+        claves
+    }
+
+    /// Si es el mismo expediente; la sigla solo se compara si ambos la tienen
+    pub fn coincide(&self, otra: &ClaveExpediente) -> bool {
+        self.numero == otra.numero
+            && self.anio == otra.anio
+            && self.incidentes == otra.incidentes
+            && match (&self.prefijo, &otra.prefijo) {
+                (Some(a), Some(b)) => a == b,
+                _ => true,
+            }
+    }
+}
+
+impl fmt::Display for ClaveExpediente {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(prefijo) = &self.prefijo {
+            write!(f, "{} ", prefijo)?;
+        }
+        write!(f, "{}/{}", self.numero, self.anio)?;
+        for incidente in &self.incidentes {
+            write!(f, "/{}", incidente)?;
+        }
+        Ok(())
+    }
+}
+
+/// Partes alfanuméricas del texto, cada una con el separador que la precede
+fn fragmentos(texto: &str) -> Vec<(&str, char)> {
+    let mut partes = Vec::new();
+    let mut separador = ' ';
+    let mut inicio = None;
+    for (i, c) in texto.char_indices() {
+        if c.is_alphanumeric() {
+            inicio.get_or_insert(i);
+            continue;
+        }
+        if let Some(desde) = inicio.take() {
+            partes.push((&texto[desde..i], separador));
+            separador = c;
+        } else if !c.is_whitespace() || separador == ' ' {
+            // Entre dos partes manda el separador visible, no los espacios
+            separador = if c.is_whitespace() { separador } else { c };
+        }
+    }
+    if let Some(desde) = inicio {
+        partes.push((&texto[desde..], separador));
+    }
+    partes
+}
+
+/// Formatos en que los portales publican los movimientos
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Formato {
+    Html,
+    Csv,
+    /// Texto extraído de un PDF
+    Texto,
+}
+
+impl Formato {
+    pub fn de_extension(ruta: &Path) -> Option<Self> {
+        let extension = ruta.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "html" | "htm" => Some(Formato::Html),
+            "csv" => Some(Formato::Csv),
+            "txt" => Some(Formato::Texto),
+            _ => None,
+        }
+    }
+
+    /// Formato de una respuesta según su `Content-Type`; los portales que no
+    /// lo indican devuelven páginas
+    pub fn de_tipo_mime(tipo: &str) -> Self {
+        let tipo = tipo.split(';').next().unwrap_or_default().trim();
+        match tipo.to_lowercase().as_str() {
+            "text/csv" | "application/csv" => Formato::Csv,
+            "text/plain" => Formato::Texto,
+            _ => Formato::Html,
+        }
+    }
+}
+
+/// Movimientos de un listado en `formato`. `conocido` es el expediente que
+/// se atribuye a los movimientos si el listado no lo indica.
+pub fn parsear(
+    contenido: &str,
+    formato: Formato,
+    conocido: Option<&ClaveExpediente>,
+) -> Result<Vec<Movimiento>, ErrorImportacion> {
+    match formato {
+        Formato::Html => html::parsear(contenido, conocido),
+        Formato::Csv => csv::parsear(contenido, conocido),
+        Formato::Texto => texto::parsear(contenido, conocido),
+    }
+}
+
+/// Lee los movimientos de un archivo guardado desde un portal; el formato
+/// se elige por la extensión
+pub fn leer_archivo(ruta: &Path) -> Result<Vec<Movimiento>, ErrorImportacion> {
+    let formato = Formato::de_extension(ruta)
+        .ok_or_else(|| ErrorImportacion::FormatoDesconocido(ruta.to_path_buf()))?;
+    let bytes = fs::read(ruta).map_err(|e| ErrorImportacion::Lectura(ruta.to_path_buf(), e))?;
+    parsear(&decodificar(bytes), formato, None)
+}
+
+/// Texto del archivo; las páginas viejas de la MEV se guardan en
+/// ISO-8859-1, que se convierte carácter por carácter si no es UTF-8
+pub fn decodificar(bytes: Vec<u8>) -> String {
+    String::from_utf8(bytes)
+        .unwrap_or_else(|e| e.into_bytes().iter().map(|&b| char::from(b)).collect())
+}
+
+/// Minúsculas, sin acentos ni signos de puntuación finales, con los
+/// espacios unificados; para comparar títulos de columnas y rótulos
+fn normalizar(texto: &str) -> String {
+    let sin_acentos: String = texto
+        .chars()
+        .map(|c| match c {
+            'á' | 'Á' => 'a',
+            'é' | 'É' => 'e',
+            'í' | 'Í' => 'i',
+            'ó' | 'Ó' => 'o',
+            'ú' | 'Ú' | 'ü' | 'Ü' => 'u',
+            'º' | '°' => 'o',
+            c => c,
+        })
+        .collect();
+    unir_espacios(&sin_acentos.to_lowercase())
+        .trim_end_matches([':', '.'])
+        .to_string()
+}
+
+fn unir_espacios(texto: &str) -> String {
+    texto.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Fecha al comienzo del texto: dd/mm/aaaa, dd/mm/aa, dd-mm-aaaa o
+/// aaaa-mm-dd, con o sin hora a continuación
+fn leer_fecha(texto: &str) -> Option<NaiveDate> {
+    let primera = texto.split_whitespace().next()?;
+    // `%Y` también acepta "24" como año 24, por eso se descartan los años
+    // imposibles antes de probar con dos cifras
+    ["%d/%m/%Y", "%d-%m-%Y", "%Y-%m-%d", "%d.%m.%Y", "%d/%m/%y"]
+        .iter()
+        .filter_map(|formato| NaiveDate::parse_from_str(primera, formato).ok())
+        .find(|fecha| fecha.year() >= 1900)
+}
+
+/// Número de expediente que sigue a alguno de los rótulos conocidos
+fn buscar_expediente<'a>(lineas: impl IntoIterator<Item = &'a str>) -> Option<ClaveExpediente> {
+    lineas.into_iter().find_map(|linea| {
+        let normalizada = normalizar(linea);
+        ROTULOS_EXPEDIENTE
+            .iter()
+            .find_map(|rotulo| {
+                normalizada
+                    .find(rotulo)
+                    .map(|i| &normalizada[i + rotulo.len()..])
+            })
+            .and_then(ClaveExpediente::extraer)
+    })
+}
+
+/// Posición de la columna cuyo título es uno de `nombres`
+fn columna(titulos: &[String], nombres: &[&str]) -> Option<usize> {
+    titulos
+        .iter()
+        .position(|t| nombres.contains(&normalizar(t).as_str()))
+}
+
+/// Columnas de una tabla de movimientos, reconocidas por sus títulos
+struct Columnas {
+    fecha: usize,
+    descripcion: usize,
+    tipo: Option<usize>,
+    expediente: Option<usize>,
+}
+
+impl Columnas {
+    fn reconocer(titulos: &[String]) -> Option<Self> {
+        Some(Self {
+            fecha: columna(titulos, &COLUMNAS_FECHA)?,
+            descripcion: columna(titulos, &COLUMNAS_DESCRIPCION)?,
+            tipo: columna(titulos, &COLUMNAS_TIPO),
+            expediente: columna(titulos, &COLUMNAS_EXPEDIENTE),
+        })
+    }
+
+    /// Movimiento de una fila de la tabla; `None` si no tiene fecha o texto.
+    /// El expediente de la fila, si hay columna, tiene prioridad sobre el
+    /// del encabezado.
+    fn movimiento(
+        &self,
+        celdas: &[String],
+        expediente: Option<&ClaveExpediente>,
+    ) -> Option<Result<Movimiento, ErrorImportacion>> {
+        let celda = |i: usize| celdas.get(i).map(|c| unir_espacios(c)).unwrap_or_default();
+        let fecha = leer_fecha(&celda(self.fecha))?;
+        let descripcion = celda(self.descripcion);
+        if descripcion.is_empty() {
+            return None;
+        }
+        let texto = match self.tipo.map(celda).filter(|t| !t.is_empty()) {
+            Some(tipo) => format!("{}: {}", tipo, descripcion),
+            None => descripcion,
+        };
+        let expediente = self
+            .expediente
+            .and_then(|i| ClaveExpediente::extraer(&celda(i)))
+            .or_else(|| expediente.cloned());
+
+        Some(match expediente {
+            Some(expediente) => Ok(Movimiento {
+                expediente,
+                fecha,
+                texto,
+            }),
+            None => Err(ErrorImportacion::SinExpediente),
+        })
+    }
+}
+
+/// Filas de una tabla (la primera con los títulos) convertidas en
+/// movimientos. Se usa para las tablas HTML y los CSV.
+fn movimientos_de_tabla(
+    filas: &[Vec<String>],
+    expediente: Option<&ClaveExpediente>,
+) -> Result<Vec<Movimiento>, ErrorImportacion> {
+    // Los títulos pueden no estar en la primera fila si la página tiene
+    // otras filas de encabezado antes
+    let (inicio, columnas) = filas
+        .iter()
+        .enumerate()
+        .find_map(|(i, fila)| Columnas::reconocer(fila).map(|c| (i, c)))
+        .ok_or(ErrorImportacion::SinMovimientos)?;
+
+    let movimientos = filas[inicio + 1..]
+        .iter()
+        .filter_map(|fila| columnas.movimiento(fila, expediente))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if movimientos.is_empty() {
+        Err(ErrorImportacion::SinMovimientos)
+    } else {
+        Ok(movimientos)
+    }
+}
+
+/// Resultado de importar un archivo
+#[derive(Debug, Default)]
+pub struct Resumen {
+    pub movimientos: usize,
+    pub agregadas: usize,
+    /// Movimientos que ya estaban como hoja con la misma fecha y texto
+    pub repetidas: usize,
+    /// Fichas que recibieron hojas nuevas
+    pub fichas: usize,
+    /// Expedientes del archivo que no corresponden a ninguna ficha
+    pub sin_ficha: BTreeSet<String>,
+    /// Expedientes del archivo que corresponden a más de una ficha, por
+    /// ejemplo porque uno de los dos números no tiene sigla
+    pub ambiguos: BTreeSet<String>,
+}
+
+/// Reparte los movimientos entre las fichas según el expediente. Los que no
+/// corresponden a ninguna, o a más de una, se anotan en el resumen.
+pub fn agrupar<'a>(
+    fichas: &[Ficha],
+    movimientos: &'a [Movimiento],
+    resumen: &mut Resumen,
+) -> Vec<(Uuid, Vec<&'a Movimiento>)> {
+    let claves: Vec<(Uuid, ClaveExpediente)> = fichas
+        .iter()
+        .filter_map(|f| ClaveExpediente::extraer(&f.expediente).map(|c| (f.id, c)))
+        .collect();
+
+    let mut grupos: Vec<(Uuid, Vec<&Movimiento>)> = Vec::new();
+    for movimiento in movimientos {
+        resumen.movimientos += 1;
+        let mut candidatas = claves
+            .iter()
+            .filter(|(_, c)| c.coincide(&movimiento.expediente));
+        let Some((ficha_id, _)) = candidatas.next() else {
+            resumen.sin_ficha.insert(movimiento.expediente.to_string());
+            continue;
+        };
+        if candidatas.next().is_some() {
+            resumen.ambiguos.insert(movimiento.expediente.to_string());
+            continue;
+        }
+        match grupos.iter_mut().find(|(id, _)| id == ficha_id) {
+            Some((_, grupo)) => grupo.push(movimiento),
+            None => grupos.push((*ficha_id, vec![movimiento])),
+        }
+    }
+    grupos
+}
+
+/// Si la ficha ya tiene una hoja con la fecha y el texto del movimiento
+pub fn ya_cargado(ficha: &Ficha, movimiento: &Movimiento) -> bool {
+    let texto = unir_espacios(&movimiento.texto);
+    ficha
+        .obtener_hojas()
+        .iter()
+        .any(|h| h.fecha == movimiento.fecha && unir_espacios(&h.contenido) == texto)
+}
+
+/// Agrega a la ficha los movimientos que no tenga ya como hoja de la misma
+/// fecha y texto. Devuelve cuántas hojas agregó.
+pub fn aplicar(ficha: &mut Ficha, movimientos: &[&Movimiento], autor: Option<Uuid>) -> usize {
+    let mut agregadas = 0;
+    for movimiento in movimientos {
+        if !ya_cargado(ficha, movimiento) {
+            let texto = unir_espacios(&movimiento.texto);
+            ficha.agregar_hoja_fechada(movimiento.fecha, texto, None, Vec::new(), autor);
+            agregadas += 1;
+        }
+    }
+    agregadas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clave(texto: &str) -> ClaveExpediente {
+        ClaveExpediente::extraer(texto).expect(texto)
+    }
+
+    #[test]
+    fn expedientes_escritos_distinto_coinciden() {
+        let pjn = clave("CNT 012345/2023");
+        assert!(pjn.coincide(&clave("CNT 12345/2023")));
+        assert!(pjn.coincide(&clave("12345/2023")));
+        assert!(clave("LP-12345-2023").coincide(&clave("LP 12345/2023")));
+        assert!(clave("Expte. Nro. 12345/2023").coincide(&clave("CNT 12345/2023")));
+        assert!(!pjn.coincide(&clave("CIV 12345/2023")));
+        assert!(!pjn.coincide(&clave("CNT 12345/2023/1")));
+    }
+
+    #[test]
+    fn expediente_dentro_de_un_texto() {
+        let encontrada = clave("Expediente: CIV 045678/2022/1 - PEREZ c/ ACME s/ daños");
+        assert_eq!(encontrada.prefijo.as_deref(), Some("CIV"));
+        assert_eq!((encontrada.numero, encontrada.anio), (45678, 2022));
+        assert_eq!(encontrada.incidentes, vec![1]);
+        assert_eq!(ClaveExpediente::extraer("Sin número"), None);
+    }
+
+    #[test]
+    fn fechas_imposibles_o_ambiguas() {
+        let dia = |d, m, a| NaiveDate::from_ymd_opt(a, m, d);
+        assert_eq!(leer_fecha("12/03/24 10:15"), dia(12, 3, 2024));
+        assert_eq!(leer_fecha("2024-03-12T10:15"), None);
+        assert_eq!(leer_fecha("29/02/2024"), dia(29, 2, 2024));
+        assert_eq!(leer_fecha("29/02/2023"), None);
+        assert_eq!(leer_fecha("12/13/2024"), None);
+        assert_eq!(leer_fecha("00/03/2024"), None);
+        // Un año de tres cifras no es de este siglo ni del anterior
+        assert_eq!(leer_fecha("12/03/024"), None);
+        assert_eq!(leer_fecha(""), None);
+    }
+
+    #[test]
+    fn no_agrega_movimientos_repetidos() {
+        let mut ficha = Ficha::de_prueba("CNT 12345/2023");
+        let fecha = NaiveDate::from_ymd_opt(2024, 3, 5).unwrap();
+        let movimiento = Movimiento {
+            expediente: clave("CNT 012345/2023"),
+            fecha,
+            texto: "DESPACHO:  Téngase presente".into(),
+        };
+        let otro = Movimiento {
+            texto: "DESPACHO: Téngase presente".into(),
+            ..movimiento.clone()
+        };
+
+        assert_eq!(aplicar(&mut ficha, &[&movimiento, &otro], None), 1);
+        assert_eq!(aplicar(&mut ficha, &[&movimiento], None), 0);
+        assert_eq!(ficha.obtener_hojas().len(), 2);
+    }
+
+    #[test]
+    fn agrupa_por_ficha_y_anota_los_desconocidos() {
+        let fichas = [Ficha::de_prueba("CNT 12345/2023")];
+        let fecha = NaiveDate::from_ymd_opt(2024, 3, 5).unwrap();
+        let movimientos = vec![
+            Movimiento {
+                expediente: clave("CNT 012345/2023"),
+                fecha,
+                texto: "Uno".into(),
+            },
+            Movimiento {
+                expediente: clave("CNT 999/2020"),
+                fecha,
+                texto: "Dos".into(),
+            },
+        ];
+
+        let mut resumen = Resumen::default();
+        let grupos = agrupar(&fichas, &movimientos, &mut resumen);
+        assert_eq!(grupos.len(), 1);
+        assert_eq!(grupos[0].0, fichas[0].id);
+        assert_eq!(
+            resumen.sin_ficha.iter().collect::<Vec<_>>(),
+            ["CNT 999/2020"]
+        );
+    }
+
+    #[test]
+    fn no_elige_entre_fichas_con_el_mismo_numero() {
+        let fichas = [
+            Ficha::de_prueba("CNT 12345/2023"),
+            Ficha::de_prueba("12345/2023"),
+            Ficha::de_prueba("CIV 777/2022"),
+        ];
+        let fecha = NaiveDate::from_ymd_opt(2024, 3, 5).unwrap();
+        let movimientos = vec![
+            Movimiento {
+                expediente: clave("CNT 12345/2023"),
+                fecha,
+                texto: "Uno".into(),
+            },
+            Movimiento {
+                expediente: clave("CIV 777/2022"),
+                fecha,
+                texto: "Dos".into(),
+            },
+        ];
+
+        let mut resumen = Resumen::default();
+        let grupos = agrupar(&fichas, &movimientos, &mut resumen);
+        assert_eq!(grupos.len(), 1);
+        assert_eq!(grupos[0].0, fichas[2].id);
+        assert_eq!(
+            resumen.ambiguos.iter().collect::<Vec<_>>(),
+            ["CNT 12345/2023"]
+        );
+        assert!(resumen.sin_ficha.is_empty());
+    }
+}
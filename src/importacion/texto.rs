@@ -0,0 +1,188 @@
+//! Texto de los PDF de movimientos, copiado del visor o extraído con
+//! `pdftotext -layout`. Cada movimiento empieza en una línea con la fecha y
+//! sigue en las líneas siguientes hasta una línea en blanco, otra fecha o
+//! el pie de página. Un mismo PDF puede listar varias causas, cada una
+//! precedida por su número de expediente.
+
+use super::{
+    buscar_expediente, leer_fecha, normalizar, unir_espacios, ClaveExpediente, ErrorImportacion,
+    Movimiento, ROTULOS_EXPEDIENTE,
+};
+use chrono::NaiveDate;
+
+/// Lo que los visores anteponen al rótulo del expediente
+const ANTES_DEL_ROTULO: [&str; 4] = ["no de ", "nro de ", "nro. de ", "numero de "];
+
+pub fn parsear(
+    contenido: &str,
+    conocido: Option<&ClaveExpediente>,
+) -> Result<Vec<Movimiento>, ErrorImportacion> {
+    let mut movimientos = Vec::new();
+    let mut expediente = conocido.cloned();
+    let mut abierto: Option<(NaiveDate, String)> = None;
+
+    let mut cerrar = |abierto: &mut Option<(NaiveDate, String)>,
+                      expediente: &Option<ClaveExpediente>|
+     -> Result<(), ErrorImportacion> {
+        if let Some((fecha, texto)) = abierto.take() {
+            let expediente = expediente.clone().ok_or(ErrorImportacion::SinExpediente)?;
+            let texto = unir_espacios(&texto);
+            if !texto.is_empty() {
+                movimientos.push(Movimiento {
+                    expediente,
+                    fecha,
+                    texto,
+                });
+            }
+        }
+        Ok(())
+    };
+
+    for linea in contenido.lines() {
+        let linea = linea.trim();
+        if let Some(nuevo) = rotulo_de_expediente(linea) {
+            cerrar(&mut abierto, &expediente)?;
+            expediente = Some(nuevo);
+        } else if let Some(fecha) = leer_fecha(linea) {
+            cerrar(&mut abierto, &expediente)?;
+            abierto = Some((fecha, sin_fecha(linea).to_string()));
+        } else if linea.is_empty() || es_pie_de_pagina(linea) {
+            cerrar(&mut abierto, &expediente)?;
+        } else if let Some((_, texto)) = abierto.as_mut() {
+            texto.push(' ');
+            texto.push_str(linea);
+        }
+    }
+    cerrar(&mut abierto, &expediente)?;
+
+    if movimientos.is_empty() {
+        Err(ErrorImportacion::SinMovimientos)
+    } else {
+        Ok(movimientos)
+    }
+}
+
+/// Expediente de una línea que empieza con su rótulo. Las menciones a otro
+/// expediente dentro del texto de un movimiento no cuentan.
+fn rotulo_de_expediente(linea: &str) -> Option<ClaveExpediente> {
+    let normalizada = normalizar(linea);
+    let sin_prefijo = ANTES_DEL_ROTULO
+        .iter()
+        .find_map(|antes| normalizada.strip_prefix(antes))
+        .unwrap_or(&normalizada);
+    if ROTULOS_EXPEDIENTE
+        .iter()
+        .any(|r| sin_prefijo.starts_with(r))
+    {
+        buscar_expediente([sin_prefijo])
+    } else {
+        None
+    }
+}
+
+/// El resto de la línea después de la fecha y de la hora, si la hay
+fn sin_fecha(linea: &str) -> &str {
+    let resto = linea
+        .split_once(char::is_whitespace)
+        .map_or("", |(_, resto)| resto.trim_start());
+    match resto.split_once(char::is_whitespace) {
+        Some((hora, despues)) if es_hora(hora) => despues.trim_start(),
+        None if es_hora(resto) => "",
+        _ => resto,
+    }
+}
+
+fn es_hora(texto: &str) -> bool {
+    let mut partes = texto.split(':');
+    let cifras =
+        |p: Option<&str>| p.is_some_and(|p| p.len() == 2 && p.bytes().all(|b| b.is_ascii_digit()));
+    (1..=2).contains(&partes.next().map_or(0, str::len))
+        && cifras(partes.next())
+        && partes.all(|p| cifras(Some(p)))
+}
+
+/// "Página 3 de 7", "- 3 -" o el número solo que agregan los PDF al pie
+fn es_pie_de_pagina(linea: &str) -> bool {
+    let normalizada = normalizar(linea);
+    let numero = normalizada.trim_matches(|c: char| c == '-' || c.is_whitespace());
+    normalizada.starts_with("pagina ") || numero.bytes().all(|b| b.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEV: &str = include_str!("fixtures/mev_pasos.txt");
+    const PJN: &str = include_str!("fixtures/pjn_actuaciones.txt");
+
+    fn fecha(dia: u32, mes: u32, anio: i32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(anio, mes, dia).unwrap()
+    }
+
+    #[test]
+    fn pasos_de_la_mev_en_varias_lineas() {
+        let movimientos = parsear(MEV, None).unwrap();
+        assert_eq!(movimientos.len(), 4);
+
+        let expediente = ClaveExpediente::extraer("LP 98765/2021").unwrap();
+        assert!(movimientos.iter().all(|m| m.expediente == expediente));
+        assert_eq!(movimientos[0].fecha, fecha(5, 2, 2024));
+        assert_eq!(
+            movimientos[0].texto,
+            "AUTOS PARA SENTENCIA Pasen los autos a resolución. Notifíquese."
+        );
+        // El movimiento cortado por el salto de página termina en el pie
+        assert_eq!(movimientos[1].texto, "SE PROVEE ESCRITO ELECTRÓNICO");
+        assert_eq!(movimientos[3].fecha, fecha(10, 8, 2021));
+    }
+
+    #[test]
+    fn listado_del_pjn_con_varias_causas() {
+        let movimientos = parsear(PJN, None).unwrap();
+        assert_eq!(movimientos.len(), 3);
+
+        let laboral = ClaveExpediente::extraer("CNT 12345/2023").unwrap();
+        let civil = ClaveExpediente::extraer("CIV 45678/2022/1").unwrap();
+        assert_eq!(movimientos[0].expediente, laboral);
+        assert_eq!(
+            movimientos[0].texto,
+            "FIRMA DESPACHO - SE PROVEE - Se acumula al expediente CIV 1111/2020"
+        );
+        assert_eq!(movimientos[1].expediente, laboral);
+        assert_eq!(movimientos[1].fecha, fecha(4, 3, 2024));
+        assert_eq!(movimientos[2].expediente, civil);
+        assert_eq!(movimientos[2].texto, "CEDULA - NOTIFICACION ELECTRONICA");
+    }
+
+    #[test]
+    fn fechas_imposibles_no_empiezan_movimientos() {
+        let texto = "Expediente: CNT 1/2020\n\
+                     12/03/2024 Se provee\n\
+                     31/02/2024 sigue el mismo movimiento\n\
+                     \n\
+                     29/02/2023 sin movimiento abierto\n\
+                     29/02/2024\n";
+        let movimientos = parsear(texto, None).unwrap();
+        // La fecha sola no deja texto y no cuenta como movimiento
+        assert_eq!(movimientos.len(), 1);
+        assert_eq!(
+            movimientos[0].texto,
+            "Se provee 31/02/2024 sigue el mismo movimiento"
+        );
+
+        assert!(matches!(
+            parsear("12/03/2024 Se provee\n", None),
+            Err(ErrorImportacion::SinExpediente)
+        ));
+    }
+
+    #[test]
+    fn fecha_con_hora() {
+        assert_eq!(
+            sin_fecha("12/03/2024 10:15 FIRMA DESPACHO"),
+            "FIRMA DESPACHO"
+        );
+        assert_eq!(sin_fecha("12/03/2024   FIRMA DESPACHO"), "FIRMA DESPACHO");
+        assert_eq!(sin_fecha("12/03/2024 10:15:30"), "");
+    }
+}
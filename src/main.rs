@@ -1,14 +1,52 @@
+mod correo;
+mod documentos;
+mod i18n;
+mod importacion;
 mod models;
+mod sincronizacion;
 mod storage;
 mod ui;
 
-use iced::widget::container;
-use iced::{Element, Length, Task, Theme};
-use std::path::PathBuf;
+use iced::widget::{container, stack, text_input};
+use iced::{Element, Length, Subscription, Task, Theme};
+use chrono::Utc;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
-use storage::JsonStorage;
+use documentos::PlantillaEscrito;
+use importacion::Resumen;
+use storage::{cargar_preferencias, guardar_preferencias, JsonStorage, Verificacion};
+use uuid::Uuid;
+use models::{cuenta, Accion, Preferencias, ReglaCorreo};
+use ui::administracion::AdministracionMessage;
+use ui::accesibilidad::Letra;
+use ui::atajos::Atajo;
+use ui::auditoria::AuditoriaMessage;
+use ui::balances::BalanceMessage;
+use ui::categorias::CategoriaMessage;
+use ui::cuenta::CuentaMessage;
+use ui::etiquetas::EtiquetaMessage;
+use ui::fechas::FormatoFecha;
 use ui::fichas::FichaMessage;
-use ui::FichasView;
+use ui::liquidaciones::LiquidacionMessage;
+use ui::mis_tareas::MisTareasMessage;
+use ui::paleta::{Comando, Destino, PaletaMessage};
+use ui::hoja_editor;
+use ui::correo::CorreoMessage;
+use ui::importacion::ImportacionMessage;
+use ui::plantillas::PlantillaMessage;
+use ui::preferencias::PreferenciasMessage;
+use ui::reporte_tiempo::ReporteTiempoMessage;
+use ui::tablero::TableroMessage;
+use ui::tareas::TareaMessage;
+use ui::tiempo::TiempoMessage;
+use ui::usuarios::UsuarioMessage;
+use ui::vinculos::VinculoMessage;
+use ui::{
+    AdministracionView, AuditoriaView, BalancesView, CategoriasView, CorreoView, EtiquetasView,
+    FichasView, ImportacionView, LiquidacionesView, MisTareasView, PaletaView, PlantillasView,
+    PreferenciasView, ReporteTiempoView, TableroView, UsuariosView,
+};
 
 fn main() -> iced::Result {
     iced::application(
@@ -17,68 +55,447 @@ fn main() -> iced::Result {
         FichasProcuracionApp::view,
     )
     .theme(FichasProcuracionApp::theme)
+    .scale_factor(FichasProcuracionApp::escala)
+    .subscription(FichasProcuracionApp::subscription)
     .run_with(FichasProcuracionApp::new)
 }
 
+/// Archivo de preferencias, independiente del de datos
+const ARCHIVO_PREFERENCIAS: &str = "preferencias.json";
+/// Cada cuánto se comprueba si toca consultar el portal
+const REVISION_SINCRONIZACION: Duration = Duration::from_secs(60);
+/// Espera entre la consulta de un expediente y la del siguiente
+const PAUSA_ENTRE_CONSULTAS: Duration = Duration::from_secs(2);
+
+fn intervalo_sincronizacion(preferencias: &Preferencias) -> Duration {
+    Duration::from_secs(u64::from(preferencias.sincronizacion.intervalo_minutos) * 60)
+}
+
 #[derive(Debug, Clone)]
 enum Message {
+    Tablero(TableroMessage),
     Ficha(FichaMessage),
+    Plantilla(PlantillaMessage),
+    Etiqueta(EtiquetaMessage),
+    Categoria(CategoriaMessage),
+    Balance(BalanceMessage),
+    Liquidacion(LiquidacionMessage),
+    ReporteTiempo(ReporteTiempoMessage),
+    MisTareas(MisTareasMessage),
+    Usuario(UsuarioMessage),
+    Administracion(AdministracionMessage),
+    Auditoria(AuditoriaMessage),
+    Preferencias(PreferenciasMessage),
+    Importacion(ImportacionMessage),
+    Correo(CorreoMessage),
+    Atajo(Atajo),
+    Paleta(PaletaMessage),
+    /// Refresca los cronómetros en marcha
+    Tick,
+    /// Comprueba si toca consultar el portal
+    RevisarSincronizacion,
+    SincronizacionTerminada(Vec<sincronizacion::Respuesta>),
+    /// Archivo o carpeta soltado sobre la ventana
+    ArchivoSoltado(PathBuf),
+}
+
+/// Pantalla visible de la aplicación
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pantalla {
+    /// Selección del usuario actual, al iniciar
+    Usuarios,
+    /// Resumen de las fichas, al que se llega tras elegir usuario
+    Tablero,
+    Fichas,
+    Plantillas,
+    Etiquetas,
+    Categorias,
+    Balances,
+    Liquidaciones,
+    ReporteTiempo,
+    MisTareas,
+    Administracion,
+    Auditoria,
+    Preferencias,
+    Importacion,
+    Correo,
 }
 
 struct FichasProcuracionApp {
     storage: JsonStorage,
+    pantalla: Pantalla,
+    /// Usuario que figura como autor de lo que se registra
+    usuario_actual: Option<Uuid>,
+    tablero_view: TableroView,
     fichas_view: FichasView,
+    plantillas_view: PlantillasView,
+    etiquetas_view: EtiquetasView,
+    categorias_view: CategoriasView,
+    balances_view: BalancesView,
+    liquidaciones_view: LiquidacionesView,
+    reporte_tiempo_view: ReporteTiempoView,
+    mis_tareas_view: MisTareasView,
+    usuarios_view: UsuariosView,
+    administracion_view: AdministracionView,
+    auditoria_view: AuditoriaView,
+    paleta_view: PaletaView,
+    preferencias_view: PreferenciasView,
+    importacion_view: ImportacionView,
+    correo_view: CorreoView,
+    preferencias: Preferencias,
+    planificador: sincronizacion::Planificador,
+    /// Tema resuelto a partir de las preferencias
+    tema: Theme,
 }
 
 impl FichasProcuracionApp {
     fn new() -> (Self, Task<Message>) {
-        let mut storage = JsonStorage::new(PathBuf::from("datos_procuracion.json"));
+        // Si el archivo de preferencias es inválido se usan las predeterminadas
+        // sin sobrescribirlo, y se avisa al elegir usuario
+        let (preferencias, aviso) = match cargar_preferencias(Path::new(ARCHIVO_PREFERENCIAS)) {
+            Ok(preferencias) => (preferencias, None),
+            Err(e) => (
+                Preferencias::default(),
+                Some(format!("{}. Se usan las preferencias predeterminadas.", e)),
+            ),
+        };
 
-        // Intentar cargar datos
-        let _ = storage.cargar();
+        let mut storage = JsonStorage::new(preferencias.ruta_datos.clone());
 
-        // Inicializar la vista de fichas con los datos cargados
-        let mut fichas_view = FichasView::new();
-        fichas_view.actualizar_fichas(storage.obtener_fichas().clone());
+        // Si el archivo de datos no se puede leer, el storage no guarda nada
+        // hasta que se corrija, y se avisa al elegir usuario
+        let aviso_datos = storage.cargar().err().map(|e| {
+            format!(
+                "No se pudo abrir el archivo de datos {}: {}. No se guardarán cambios hasta \
+                 corregirlo o elegir otro archivo en Preferencias.",
+                preferencias.ruta_datos.display(),
+                e
+            )
+        });
+        let fechas = FormatoFecha::new(&preferencias.formato_fecha, &preferencias.zona_horaria);
+        storage.migrar_fechas(|momento| fechas.dia(momento));
 
-        (
-            Self {
-                storage,
-                fichas_view,
-            },
-            Task::none(),
+        let mut app = Self {
+            storage,
+            pantalla: Pantalla::Usuarios,
+            usuario_actual: None,
+            tablero_view: TableroView::new(),
+            fichas_view: FichasView::new(),
+            plantillas_view: PlantillasView::new(),
+            etiquetas_view: EtiquetasView::new(),
+            categorias_view: CategoriasView::new(),
+            balances_view: BalancesView::new(),
+            liquidaciones_view: LiquidacionesView::new(),
+            reporte_tiempo_view: ReporteTiempoView::new(),
+            mis_tareas_view: MisTareasView::new(),
+            usuarios_view: UsuariosView::new(),
+            administracion_view: AdministracionView::new(),
+            auditoria_view: AuditoriaView::new(),
+            paleta_view: PaletaView::new(),
+            preferencias_view: PreferenciasView::new(),
+            importacion_view: ImportacionView::new(),
+            correo_view: CorreoView::new(),
+            planificador: sincronizacion::Planificador::new(intervalo_sincronizacion(
+                &preferencias,
+            )),
+            preferencias,
+            tema: Theme::Light,
+        };
+        app.cargar_vistas();
+        app.aplicar_preferencias();
+        app.usuarios_view.error = match (aviso, aviso_datos) {
+            (Some(aviso), Some(aviso_datos)) => Some(format!("{}\n{}", aviso, aviso_datos)),
+            (aviso, aviso_datos) => aviso.or(aviso_datos),
+        };
+
+        if app.preferencias.respaldo.al_iniciar {
+            let respaldo = &app.preferencias.respaldo;
+            if let Err(e) = app.storage.respaldar(&respaldo.directorio, respaldo.conservar) {
+                app.usuarios_view.error = Some(format!("No se pudo respaldar los datos: {}", e));
+            }
+        }
+
+        (app, Task::none())
+    }
+
+    /// Inicializa las vistas con los datos cargados
+    fn cargar_vistas(&mut self) {
+        self.fichas_view
+            .actualizar_fichas(self.storage.obtener_fichas().clone());
+        self.fichas_view
+            .actualizar_categorias(self.storage.obtener_categorias().clone());
+        self.fichas_view
+            .actualizar_plantillas(self.storage.obtener_plantillas().clone());
+        self.fichas_view
+            .actualizar_colores_etiquetas(self.storage.obtener_colores_etiquetas().clone());
+        self.plantillas_view
+            .actualizar_plantillas(self.storage.obtener_plantillas().clone());
+        self.actualizar_usuarios();
+    }
+
+    /// Formato de fechas según las preferencias vigentes
+    fn fechas(&self) -> FormatoFecha {
+        FormatoFecha::new(
+            &self.preferencias.formato_fecha,
+            &self.preferencias.zona_horaria,
         )
+        .con_idioma(self.preferencias.idioma)
+    }
+
+    /// Propaga las preferencias vigentes a las vistas
+    fn aplicar_preferencias(&mut self) {
+        let fechas = self.fechas();
+        let preferencias = &self.preferencias;
+        self.tema = ui::styles::tema(preferencias.tema);
+        self.fichas_view.tema = self.tema.clone();
+        self.fichas_view.fechas = fechas.clone();
+        self.fichas_view.idioma = preferencias.idioma;
+        self.fichas_view.tarjetas_por_fila = preferencias.tarjetas_por_fila;
+        self.fichas_view.sincronizacion_activa = preferencias.sincronizacion.activa;
+        self.planificador
+            .cambiar_intervalo(intervalo_sincronizacion(preferencias));
+        self.auditoria_view.fechas = fechas.clone();
+        self.tablero_view.fechas = fechas;
+        self.tablero_view.dias_input = preferencias.dias_sin_movimiento.to_string();
+        self.tablero_view.dias_aviso = preferencias.dias_aviso_vencimientos;
+        let idioma = preferencias.idioma;
+        self.tablero_view.idioma = idioma;
+        self.plantillas_view.idioma = idioma;
+        self.etiquetas_view.idioma = idioma;
+        self.categorias_view.idioma = idioma;
+        self.balances_view.idioma = idioma;
+        self.liquidaciones_view.idioma = idioma;
+        self.reporte_tiempo_view.idioma = idioma;
+        self.mis_tareas_view.idioma = idioma;
+        self.usuarios_view.idioma = idioma;
+        self.administracion_view.idioma = idioma;
+        self.auditoria_view.idioma = idioma;
+        self.paleta_view.idioma = idioma;
+        self.importacion_view.idioma = idioma;
+        self.correo_view.idioma = idioma;
+        self.preferencias_view.idioma_interfaz = idioma;
+        let letra = Letra::new(preferencias.tamanio_minimo_texto);
+        self.tablero_view.letra = letra;
+        self.fichas_view.letra = letra;
+        self.plantillas_view.letra = letra;
+        self.etiquetas_view.letra = letra;
+        self.categorias_view.letra = letra;
+        self.balances_view.letra = letra;
+        self.liquidaciones_view.letra = letra;
+        self.reporte_tiempo_view.letra = letra;
+        self.mis_tareas_view.letra = letra;
+        self.usuarios_view.letra = letra;
+        self.administracion_view.letra = letra;
+        self.auditoria_view.letra = letra;
+        self.paleta_view.letra = letra;
+        self.preferencias_view.letra = letra;
+        self.importacion_view.letra = letra;
+        self.correo_view.letra = letra;
+        self.preferencias_view.cargar(preferencias);
+    }
+
+    /// Factor de escala de toda la interfaz
+    fn escala(&self) -> f64 {
+        self.preferencias.escala
     }
 
     fn title(&self) -> String {
-        String::from("Fichas Procuración - Sistema de Gestión")
+        i18n::texto(self.preferencias.idioma, "app-titulo")
     }
 
     fn update(&mut self, message: Message) -> Task<Message> {
         match message {
+            Message::Tablero(tablero_msg) => {
+                self.manejar_mensaje_tablero(tablero_msg);
+            }
+            Message::Ficha(FichaMessage::Sincronizar(id)) => {
+                if self.puede(Accion::EditarFichas) {
+                    return self.sincronizar(Some(id));
+                }
+            }
             Message::Ficha(ficha_msg) => {
                 self.manejar_mensaje_ficha(ficha_msg);
             }
+            Message::Plantilla(plantilla_msg) => {
+                if self.puede(Accion::Configurar) {
+                    self.manejar_mensaje_plantilla(plantilla_msg);
+                }
+            }
+            Message::Etiqueta(etiqueta_msg) => {
+                if self.puede(Accion::Configurar) {
+                    self.manejar_mensaje_etiqueta(etiqueta_msg);
+                }
+            }
+            Message::Categoria(categoria_msg) => {
+                if self.puede(Accion::Configurar) {
+                    self.manejar_mensaje_categoria(categoria_msg);
+                }
+            }
+            Message::Balance(BalanceMessage::Volver) => {
+                self.pantalla = Pantalla::Fichas;
+            }
+            Message::Balance(BalanceMessage::NuevaLiquidacion)
+                if self.puede(Accion::EditarCuenta) =>
+            {
+                self.liquidaciones_view.mensaje = None;
+                self.liquidaciones_view
+                    .actualizar(self.storage.obtener_fichas());
+                self.pantalla = Pantalla::Liquidaciones;
+            }
+            Message::Balance(BalanceMessage::NuevaLiquidacion) => {}
+            Message::Liquidacion(liquidacion_msg) => {
+                if self.puede(Accion::EditarCuenta) {
+                    self.manejar_mensaje_liquidacion(liquidacion_msg);
+                }
+            }
+            Message::ReporteTiempo(ReporteTiempoMessage::Volver) => {
+                self.pantalla = Pantalla::Fichas;
+            }
+            Message::ReporteTiempo(ReporteTiempoMessage::DesdeChanged(valor)) => {
+                self.reporte_tiempo_view.desde_input = valor;
+                self.reporte_tiempo_view
+                    .actualizar(self.storage.obtener_fichas());
+            }
+            Message::ReporteTiempo(ReporteTiempoMessage::HastaChanged(valor)) => {
+                self.reporte_tiempo_view.hasta_input = valor;
+                self.reporte_tiempo_view
+                    .actualizar(self.storage.obtener_fichas());
+            }
+            Message::MisTareas(mis_tareas_msg) => {
+                self.manejar_mensaje_mis_tareas(mis_tareas_msg);
+            }
+            Message::Usuario(usuario_msg) => {
+                self.manejar_mensaje_usuario(usuario_msg);
+            }
+            Message::Administracion(administracion_msg) => {
+                if self.puede(Accion::GestionarUsuarios) {
+                    self.manejar_mensaje_administracion(administracion_msg);
+                }
+            }
+            Message::Auditoria(auditoria_msg) => {
+                if self.puede(Accion::VerAuditoria) {
+                    self.manejar_mensaje_auditoria(auditoria_msg);
+                }
+            }
+            Message::Preferencias(preferencias_msg) => {
+                self.manejar_mensaje_preferencias(preferencias_msg);
+            }
+            Message::Importacion(importacion_msg) => {
+                if self.puede(Accion::EditarFichas) {
+                    self.manejar_mensaje_importacion(importacion_msg);
+                }
+            }
+            Message::Correo(correo_msg) => {
+                if self.puede(Accion::EditarFichas) {
+                    self.manejar_mensaje_correo(correo_msg);
+                }
+            }
+            Message::Atajo(atajo) => {
+                return self.manejar_atajo(atajo);
+            }
+            Message::Paleta(paleta_msg) => {
+                self.manejar_mensaje_paleta(paleta_msg);
+            }
+            Message::Tick => {
+                // Solo fuerza un redibujado; el tiempo transcurrido se calcula al mostrarlo
+            }
+            Message::RevisarSincronizacion => {
+                // No se consulta hasta que alguien elige usuario
+                if self.usuario_actual.is_some() && self.planificador.toca(Instant::now()) {
+                    return self.sincronizar(None);
+                }
+            }
+            Message::SincronizacionTerminada(respuestas) => {
+                self.registrar_sincronizacion(respuestas);
+            }
+            Message::ArchivoSoltado(ruta) => {
+                if self.puede(Accion::EditarFichas) {
+                    self.soltar_archivo(ruta);
+                }
+            }
         }
         Task::none()
     }
 
-    fn view(&self) -> Element<Message> {
-        let contenido = self.fichas_view.view().map(Message::Ficha);
+    fn view(&self) -> Element<'_, Message> {
+        let contenido = match self.pantalla {
+            Pantalla::Usuarios => self.usuarios_view.view().map(Message::Usuario),
+            Pantalla::Tablero => self.tablero_view.view().map(Message::Tablero),
+            Pantalla::Fichas => self.fichas_view.view().map(Message::Ficha),
+            Pantalla::Plantillas => self.plantillas_view.view().map(Message::Plantilla),
+            Pantalla::Etiquetas => self.etiquetas_view.view().map(Message::Etiqueta),
+            Pantalla::Categorias => self.categorias_view.view().map(Message::Categoria),
+            Pantalla::Balances => self.balances_view.view().map(Message::Balance),
+            Pantalla::Liquidaciones => self.liquidaciones_view.view().map(Message::Liquidacion),
+            Pantalla::ReporteTiempo => self.reporte_tiempo_view.view().map(Message::ReporteTiempo),
+            Pantalla::MisTareas => self.mis_tareas_view.view().map(Message::MisTareas),
+            Pantalla::Administracion => {
+                self.administracion_view.view().map(Message::Administracion)
+            }
+            Pantalla::Auditoria => self.auditoria_view.view().map(Message::Auditoria),
+            Pantalla::Preferencias => self.preferencias_view.view().map(Message::Preferencias),
+            Pantalla::Importacion => self.importacion_view.view().map(Message::Importacion),
+            Pantalla::Correo => self.correo_view.view().map(Message::Correo),
+        };
 
-        container(contenido)
+        let contenido = container(contenido)
             .width(Length::Fill)
-            .height(Length::Fill)
-            .into()
+            .height(Length::Fill);
+
+        if self.paleta_view.abierta {
+            stack![contenido, self.paleta_view.view().map(Message::Paleta)].into()
+        } else {
+            contenido.into()
+        }
     }
 
     fn theme(&self) -> Theme {
-        Theme::Light
+        self.tema.clone()
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        let atajos = iced::event::listen_with(|evento, estado, ventana| {
+            ui::atajos::atajo(evento, estado, ventana).map(Message::Atajo)
+        });
+
+        let archivos = iced::event::listen_with(|evento, _estado, _ventana| match evento {
+            iced::Event::Window(iced::window::Event::FileDropped(ruta)) => {
+                Some(Message::ArchivoSoltado(ruta))
+            }
+            _ => None,
+        });
+
+        let mut suscripciones = vec![atajos, archivos];
+        if self.storage.hay_cronometro_activo() {
+            suscripciones.push(iced::time::every(Duration::from_secs(1)).map(|_| Message::Tick));
+        }
+        if self.preferencias.sincronizacion.activa {
+            suscripciones.push(
+                iced::time::every(REVISION_SINCRONIZACION)
+                    .map(|_| Message::RevisarSincronizacion),
+            );
+        }
+        Subscription::batch(suscripciones)
     }
 }
 
 impl FichasProcuracionApp {
+    /// Indica si el rol del usuario actual permite la acción
+    fn puede(&self, accion: Accion) -> bool {
+        self.storage
+            .obtener_usuarios()
+            .iter()
+            .find(|u| Some(u.id) == self.usuario_actual)
+            .is_some_and(|u| u.rol.puede(accion))
+    }
+
     fn manejar_mensaje_ficha(&mut self, mensaje: FichaMessage) {
+        if let Some(accion) = mensaje.accion_requerida() {
+            if !self.puede(accion) {
+                return;
+            }
+        }
+
         match mensaje {
             FichaMessage::NuevaFicha => {
                 self.fichas_view.iniciar_nueva_ficha();
@@ -87,7 +504,7 @@ impl FichasProcuracionApp {
                 self.fichas_view.iniciar_edicion(id);
             }
             FichaMessage::EliminarFicha(id) => {
-                let _ = self.storage.eliminar_ficha(id);
+                let _ = self.storage.eliminar_ficha(id, self.usuario_actual);
                 self.fichas_view
                     .actualizar_fichas(self.storage.obtener_fichas().clone());
             }
@@ -97,24 +514,326 @@ impl FichasProcuracionApp {
             FichaMessage::DescripcionChanged(valor) => {
                 self.fichas_view.descripcion_input = valor;
             }
-            FichaMessage::HojaChanged(hoja) => {
-                self.fichas_view.hoja_input = hoja.clone();
-                self.fichas_view.nueva_hoja_input = hoja;
+            FichaMessage::ExpedienteChanged(valor) => {
+                self.fichas_view.expediente_input = valor;
+            }
+            FichaMessage::ClienteChanged(valor) => {
+                self.fichas_view.cliente_input = valor;
+            }
+            FichaMessage::PartesChanged(valor) => {
+                self.fichas_view.partes_input = valor;
+            }
+            FichaMessage::NuevaEtiquetaChanged(valor) => {
+                self.fichas_view.nueva_etiqueta_input = valor;
+            }
+            FichaMessage::AgregarEtiqueta(etiqueta) => {
+                self.fichas_view.agregar_etiqueta(etiqueta);
+            }
+            FichaMessage::QuitarEtiqueta(etiqueta) => {
+                self.fichas_view.etiquetas_input.retain(|e| *e != etiqueta);
+            }
+            FichaMessage::FiltrarEtiqueta(etiqueta) => {
+                self.fichas_view.filtro_etiqueta = Some(etiqueta);
+            }
+            FichaMessage::QuitarFiltroEtiqueta => {
+                self.fichas_view.filtro_etiqueta = None;
+            }
+            FichaMessage::EstadoSeleccionado(estado) => {
+                self.fichas_view.estado_input = estado;
+            }
+            FichaMessage::FiltrarEstado(estado) => {
+                self.fichas_view.filtro_estado = Some(estado);
+            }
+            FichaMessage::QuitarFiltroEstado => {
+                self.fichas_view.filtro_estado = None;
+            }
+            FichaMessage::QuitarFiltroSeleccion => {
+                self.fichas_view.filtro_seleccion = None;
+            }
+            FichaMessage::BusquedaChanged(valor) => {
+                self.fichas_view.busqueda_input = valor;
+            }
+            FichaMessage::CambiarModoLista(modo) => {
+                self.fichas_view.modo_lista = modo;
+            }
+            FichaMessage::OrdenarPor(columna) => {
+                self.fichas_view.ordenar_por(columna);
+            }
+            FichaMessage::VerTablero => {
+                self.abrir_tablero();
+            }
+            FichaMessage::GestionarEtiquetas => {
+                self.etiquetas_view.seleccionar(None);
+                self.etiquetas_view.mensaje = None;
+                self.actualizar_etiquetas();
+                self.pantalla = Pantalla::Etiquetas;
+            }
+            FichaMessage::GestionarCategorias => {
+                self.categorias_view.cancelar_edicion();
+                self.categorias_view.mensaje = None;
+                self.actualizar_categorias();
+                self.pantalla = Pantalla::Categorias;
+            }
+            FichaMessage::HojaEditada(accion) => {
+                self.fichas_view.hoja_input.perform(accion);
+            }
+            FichaMessage::FormatoHoja(formato) => {
+                hoja_editor::aplicar_formato(&mut self.fichas_view.hoja_input, formato);
+            }
+            FichaMessage::NuevaHojaEditada(accion) => {
+                self.fichas_view.nueva_hoja_input.perform(accion);
+            }
+            FichaMessage::FormatoNuevaHoja(formato) => {
+                hoja_editor::aplicar_formato(&mut self.fichas_view.nueva_hoja_input, formato);
+            }
+            FichaMessage::EnlaceHoja => {
+                // Los enlaces de las hojas se muestran pero no se abren desde la aplicación
+            }
+            FichaMessage::CategoriaNuevaHoja(categoria) => {
+                self.fichas_view.categoria_nueva_hoja = Some(categoria);
+            }
+            FichaMessage::QuitarCategoriaNuevaHoja => {
+                self.fichas_view.categoria_nueva_hoja = None;
+            }
+            FichaMessage::FechaNuevaHojaChanged(valor) => {
+                self.fichas_view.fecha_nueva_hoja_input = valor;
+                self.fichas_view.error_nueva_hoja = None;
+            }
+            FichaMessage::SelectorFechaNuevaHoja(mensaje) => {
+                self.fichas_view.actualizar_selector_fecha(mensaje);
+            }
+            FichaMessage::FiltrarCategoria(categoria) => {
+                self.fichas_view.filtro_categoria = categoria;
+            }
+            FichaMessage::InsertarPlantilla(plantilla) => {
+                if let Some(ficha) = self
+                    .fichas_view
+                    .ficha_historial_id
+                    .and_then(|id| self.storage.obtener_ficha(id))
+                {
+                    let fechas = self.fechas();
+                    let texto = plantilla.completar(ficha, &fechas.dia_corto(fechas.hoy()));
+                    self.fichas_view.insertar_en_nueva_hoja(texto);
+                }
+            }
+            FichaMessage::VerPreferencias => {
+                self.preferencias_view.puede_configurar = self.puede(Accion::Configurar);
+                self.preferencias_view.cargar(&self.preferencias);
+                self.preferencias_view.mensaje = None;
+                self.pantalla = Pantalla::Preferencias;
+            }
+            FichaMessage::VerImportacion => {
+                self.importacion_view.limpiar_resultado();
+                self.pantalla = Pantalla::Importacion;
+            }
+            FichaMessage::VerCorreo => {
+                self.correo_view.limpiar_resultado();
+                self.correo_view.limpiar_regla();
+                self.actualizar_correo();
+                self.pantalla = Pantalla::Correo;
+            }
+            FichaMessage::Sincronizar(_) => {
+                // Se atiende en `update`, porque devuelve la tarea de la consulta
+            }
+            FichaMessage::GestionarPlantillas => {
+                self.plantillas_view.cancelar_edicion();
+                self.pantalla = Pantalla::Plantillas;
+            }
+            FichaMessage::GenerarEscrito(plantilla) => {
+                if let Some(ficha_id) = self.fichas_view.ficha_historial_id {
+                    self.generar_escrito(ficha_id, &plantilla);
+                }
+            }
+            FichaMessage::CambiarPestania(pestania) => {
+                self.fichas_view.pestania = pestania;
+            }
+            FichaMessage::Cuenta(CuentaMessage::AgregarAsiento(ficha_id)) => {
+                match self.fichas_view.cuenta.obtener_asiento() {
+                    Ok(asiento) => {
+                        if let Some(ficha) = self.storage.obtener_ficha_mut(ficha_id) {
+                            ficha.agregar_asiento(asiento);
+                            let cliente = ficha.cliente.clone();
+                            let _ = self.storage.guardar();
+                            let hoy = self.fichas_view.fechas.hoy();
+                            self.fichas_view.cuenta.reiniciar(&cliente, hoy);
+                        }
+                        self.fichas_view
+                            .actualizar_fichas(self.storage.obtener_fichas().clone());
+                    }
+                    Err(e) => self.fichas_view.cuenta.error = Some(e),
+                }
+            }
+            FichaMessage::Cuenta(CuentaMessage::EliminarAsiento(ficha_id, asiento_id)) => {
+                if let Some(ficha) = self.storage.obtener_ficha_mut(ficha_id) {
+                    if ficha.eliminar_asiento(asiento_id) {
+                        let _ = self.storage.guardar();
+                    }
+                }
+                self.fichas_view
+                    .actualizar_fichas(self.storage.obtener_fichas().clone());
+            }
+            FichaMessage::Cuenta(cuenta_msg) => {
+                self.fichas_view.cuenta.actualizar(cuenta_msg);
+            }
+            FichaMessage::IniciarCronometro(ficha_id) => {
+                let _ = self.storage.iniciar_cronometro(ficha_id);
+                self.fichas_view
+                    .actualizar_fichas(self.storage.obtener_fichas().clone());
+            }
+            FichaMessage::DetenerCronometro(ficha_id) => {
+                let fechas = self.fechas();
+                let _ = self.storage.detener_cronometro(
+                    ficha_id,
+                    String::from("Cronómetro"),
+                    |momento| fechas.dia(momento),
+                );
+                self.fichas_view
+                    .actualizar_fichas(self.storage.obtener_fichas().clone());
+            }
+            FichaMessage::Tiempo(TiempoMessage::AgregarRegistro(ficha_id)) => {
+                match self.fichas_view.tiempo.obtener_registro() {
+                    Ok(registro) => {
+                        if let Some(ficha) = self.storage.obtener_ficha_mut(ficha_id) {
+                            ficha.agregar_tiempo(registro);
+                            let _ = self.storage.guardar();
+                            let hoy = self.fichas_view.fechas.hoy();
+                            self.fichas_view.tiempo.reiniciar(hoy);
+                        }
+                        self.fichas_view
+                            .actualizar_fichas(self.storage.obtener_fichas().clone());
+                    }
+                    Err(e) => self.fichas_view.tiempo.error = Some(e),
+                }
+            }
+            FichaMessage::Tiempo(TiempoMessage::EliminarRegistro(ficha_id, registro_id)) => {
+                if let Some(ficha) = self.storage.obtener_ficha_mut(ficha_id) {
+                    if ficha.eliminar_tiempo(registro_id) {
+                        let _ = self.storage.guardar();
+                    }
+                }
+                self.fichas_view
+                    .actualizar_fichas(self.storage.obtener_fichas().clone());
+            }
+            FichaMessage::Tiempo(tiempo_msg) => {
+                self.fichas_view.tiempo.actualizar(tiempo_msg);
+            }
+            FichaMessage::VerReporteTiempo => {
+                self.reporte_tiempo_view
+                    .actualizar(self.storage.obtener_fichas());
+                self.pantalla = Pantalla::ReporteTiempo;
+            }
+            FichaMessage::Tarea(TareaMessage::AgregarTarea(ficha_id)) => {
+                match self.fichas_view.tarea.obtener_tarea() {
+                    Ok(tarea) => {
+                        if let Some(ficha) = self.storage.obtener_ficha_mut(ficha_id) {
+                            ficha.agregar_tarea(tarea);
+                            let _ = self.storage.guardar();
+                            self.fichas_view.tarea.reiniciar();
+                        }
+                        self.fichas_view
+                            .actualizar_fichas(self.storage.obtener_fichas().clone());
+                    }
+                    Err(e) => self.fichas_view.tarea.error = Some(e),
+                }
+            }
+            FichaMessage::Tarea(tarea_msg) => {
+                if !self.manejar_accion_tarea(&tarea_msg) {
+                    self.fichas_view.tarea.actualizar(tarea_msg);
+                }
+            }
+            FichaMessage::VerMisTareas => {
+                // Se muestran primero las tareas del usuario actual, si tiene alguna
+                self.mis_tareas_view.filtro_responsable = self
+                    .storage
+                    .obtener_usuarios()
+                    .iter()
+                    .find(|u| Some(u.id) == self.usuario_actual)
+                    .map(|u| u.nombre.clone());
+                self.mis_tareas_view
+                    .actualizar(self.storage.obtener_fichas());
+                self.pantalla = Pantalla::MisTareas;
+            }
+            FichaMessage::Vinculo(VinculoMessage::Vincular(ficha_id)) => {
+                if let Some(destino) = self.fichas_view.vinculo.ficha.take() {
+                    let tipo = self.fichas_view.vinculo.tipo;
+                    let _ = self.storage.vincular_fichas(ficha_id, destino.id, tipo);
+                    self.fichas_view
+                        .actualizar_fichas(self.storage.obtener_fichas().clone());
+                }
+            }
+            FichaMessage::Vinculo(VinculoMessage::Desvincular(ficha_id, otra_id)) => {
+                let _ = self.storage.desvincular_fichas(ficha_id, otra_id);
+                self.fichas_view
+                    .actualizar_fichas(self.storage.obtener_fichas().clone());
+            }
+            FichaMessage::Vinculo(VinculoMessage::Abrir(ficha_id)) => {
+                self.manejar_mensaje_ficha(FichaMessage::VerHistorial(ficha_id));
+            }
+            FichaMessage::Vinculo(vinculo_msg) => {
+                self.fichas_view.vinculo.actualizar(vinculo_msg);
+            }
+            FichaMessage::ResponsableSeleccionado(usuario) => {
+                self.fichas_view.responsable_input = Some(usuario.id);
+            }
+            FichaMessage::FiltrarResponsable(usuario) => {
+                self.fichas_view.filtro_responsable = Some(usuario.id);
+            }
+            FichaMessage::QuitarFiltroResponsable => {
+                self.fichas_view.filtro_responsable = None;
+            }
+            FichaMessage::GestionarUsuarios => {
+                self.administracion_view.mensaje = None;
+                self.administracion_view.limpiar_formulario();
+                self.pantalla = Pantalla::Administracion;
+            }
+            FichaMessage::VerAuditoria => {
+                self.auditoria_view.quitar_filtros();
+                self.auditoria_view.mensaje = None;
+                self.actualizar_auditoria();
+                self.pantalla = Pantalla::Auditoria;
+            }
+            FichaMessage::CambiarUsuario => {
+                self.fichas_view.cancelar_edicion();
+                self.fichas_view.cerrar_historial();
+                self.usuarios_view.limpiar_formulario();
+                self.pantalla = Pantalla::Usuarios;
+            }
+            FichaMessage::VerBalances => {
+                self.actualizar_balances();
+                self.pantalla = Pantalla::Balances;
             }
             FichaMessage::VerHistorial(ficha_id) => {
-                self.fichas_view.ficha_historial_id = Some(ficha_id);
-                self.fichas_view.nueva_hoja_input.clear();
+                if self.storage.obtener_ficha(ficha_id).is_some() {
+                    self.fichas_view.plantillas_escritos = documentos::listar_plantillas(
+                        &self.storage.directorio_plantillas_escritos(),
+                    );
+                    self.fichas_view.abrir_historial(ficha_id);
+                }
             }
             FichaMessage::CerrarHistorial => {
-                self.fichas_view.ficha_historial_id = None;
-                self.fichas_view.nueva_hoja_input.clear();
+                self.fichas_view.cerrar_historial();
             }
             FichaMessage::AgregarHoja(ficha_id) => {
-                if !self.fichas_view.nueva_hoja_input.is_empty() {
-                    if let Some(ficha) = self.storage.obtener_ficha_mut(ficha_id) {
-                        ficha.agregar_hoja(self.fichas_view.nueva_hoja_input.clone());
-                        let _ = self.storage.guardar();
-                        self.fichas_view.nueva_hoja_input.clear();
+                let contenido = self.fichas_view.texto_nueva_hoja();
+                let categoria = self
+                    .fichas_view
+                    .categoria_nueva_hoja
+                    .as_ref()
+                    .map(|c| c.id.clone());
+                let fecha = match self.fichas_view.fecha_nueva_hoja() {
+                    Ok(fecha) => fecha,
+                    Err(error) => {
+                        self.fichas_view.error_nueva_hoja = Some(error);
+                        return;
+                    }
+                };
+                if !contenido.is_empty() {
+                    let autor = self.usuario_actual;
+                    let agregada = self.storage.modificar_ficha(ficha_id, autor, |ficha| {
+                        ficha.agregar_hoja_fechada(fecha, contenido, categoria, Vec::new(), autor)
+                    });
+                    if matches!(agregada, Ok(Some(_))) {
+                        self.fichas_view.limpiar_nueva_hoja();
                     }
                     self.fichas_view
                         .actualizar_fichas(self.storage.obtener_fichas().clone());
@@ -141,4 +860,1110 @@ impl FichasProcuracionApp {
             }
         }
     }
+
+    /// Completa o elimina una tarea; devuelve `false` si el mensaje es solo
+    /// de edición del formulario
+    fn manejar_accion_tarea(&mut self, mensaje: &TareaMessage) -> bool {
+        let autor = self.usuario_actual;
+        let _ = match *mensaje {
+            TareaMessage::CompletarTarea(ficha_id, tarea_id, registrar_hoja) => {
+                let hoy = self.fechas().hoy();
+                self.storage.modificar_ficha(ficha_id, autor, |f| {
+                    f.completar_tarea(tarea_id, registrar_hoja, hoy, autor)
+                })
+            }
+            TareaMessage::EliminarTarea(ficha_id, tarea_id) => self
+                .storage
+                .modificar_ficha(ficha_id, autor, |f| f.eliminar_tarea(tarea_id)),
+            _ => return false,
+        };
+
+        self.fichas_view
+            .actualizar_fichas(self.storage.obtener_fichas().clone());
+        self.mis_tareas_view
+            .actualizar(self.storage.obtener_fichas());
+        true
+    }
+
+    fn manejar_mensaje_usuario(&mut self, mensaje: UsuarioMessage) {
+        match mensaje {
+            UsuarioMessage::Seleccionar(usuario_id) => {
+                self.seleccionar_usuario(usuario_id);
+            }
+            UsuarioMessage::NombreChanged(valor) => {
+                self.usuarios_view.nombre_input = valor;
+            }
+            UsuarioMessage::InicialesChanged(valor) => {
+                self.usuarios_view.iniciales_input = valor;
+            }
+            UsuarioMessage::ColorSeleccionado(color) => {
+                self.usuarios_view.color = color;
+            }
+            UsuarioMessage::Crear => match self.usuarios_view.obtener_usuario() {
+                Ok(usuario) => {
+                    let usuario_id = usuario.id;
+                    match self.storage.agregar_usuario(usuario) {
+                        Ok(()) => {
+                            self.actualizar_usuarios();
+                            self.seleccionar_usuario(usuario_id);
+                        }
+                        Err(e) => {
+                            self.usuarios_view.error = Some(format!("No se pudo guardar: {}", e))
+                        }
+                    }
+                }
+                Err(e) => self.usuarios_view.error = Some(e),
+            },
+        }
+    }
+
+    fn seleccionar_usuario(&mut self, usuario_id: Uuid) {
+        self.usuario_actual = Some(usuario_id);
+        self.fichas_view.usuario_actual = Some(usuario_id);
+        self.administracion_view.usuario_actual = Some(usuario_id);
+        self.usuarios_view.limpiar_formulario();
+        self.actualizar_permisos();
+        self.abrir_tablero();
+    }
+
+    /// Resuelve un atajo de teclado según la pantalla visible
+    fn manejar_atajo(&mut self, atajo: Atajo) -> Task<Message> {
+        // El foco recorre los campos de cualquier pantalla, incluso antes de
+        // elegir usuario
+        match atajo {
+            Atajo::CampoSiguiente => return iced::widget::focus_next(),
+            Atajo::CampoAnterior => return iced::widget::focus_previous(),
+            _ => {}
+        }
+
+        if self.usuario_actual.is_none() {
+            return Task::none();
+        }
+
+        if self.paleta_view.abierta {
+            match atajo {
+                Atajo::Siguiente => self.paleta_view.mover(1),
+                Atajo::Anterior => self.paleta_view.mover(-1),
+                Atajo::Cancelar | Atajo::Paleta => self.paleta_view.cerrar(),
+                _ => {}
+            }
+            return Task::none();
+        }
+
+        let en_fichas = self.pantalla == Pantalla::Fichas;
+        let editando = en_fichas && self.fichas_view.editando;
+        let historial = self
+            .fichas_view
+            .ficha_historial_id
+            .filter(|_| en_fichas && !editando);
+        let en_lista = en_fichas && !editando && historial.is_none();
+
+        match atajo {
+            Atajo::Paleta => {
+                let comandos = Comando::TODOS
+                    .into_iter()
+                    .filter(|c| c.accion_requerida().is_none_or(|a| self.puede(a)))
+                    .collect();
+                self.paleta_view.abrir(comandos, self.storage.obtener_fichas());
+                return text_input::focus(PaletaView::id_consulta());
+            }
+            Atajo::NuevaFicha => self.ejecutar_comando(Comando::NuevaFicha),
+            Atajo::Buscar if !editando => {
+                self.fichas_view.cerrar_historial();
+                self.pantalla = Pantalla::Fichas;
+                return text_input::focus(FichasView::id_busqueda());
+            }
+            Atajo::Siguiente if en_lista => self.fichas_view.mover_seleccion(1),
+            Atajo::Anterior if en_lista => self.fichas_view.mover_seleccion(-1),
+            Atajo::AbrirHistorial if en_lista => {
+                if let Some(ficha_id) = self.fichas_view.seleccion {
+                    self.manejar_mensaje_ficha(FichaMessage::VerHistorial(ficha_id));
+                }
+            }
+            Atajo::AgregarHoja => {
+                if let Some(ficha_id) = historial {
+                    self.manejar_mensaje_ficha(FichaMessage::AgregarHoja(ficha_id));
+                }
+            }
+            Atajo::Guardar if editando => {
+                self.manejar_mensaje_ficha(FichaMessage::GuardarFicha);
+            }
+            Atajo::Guardar
+                if self.pantalla == Pantalla::Plantillas && self.plantillas_view.editando =>
+            {
+                return self.update(Message::Plantilla(PlantillaMessage::GuardarPlantilla));
+            }
+            Atajo::Cancelar
+                if self.pantalla == Pantalla::Plantillas && self.plantillas_view.editando =>
+            {
+                return self.update(Message::Plantilla(PlantillaMessage::CancelarEdicion));
+            }
+            Atajo::Cancelar | Atajo::Volver => return self.volver(),
+            _ => {}
+        }
+        Task::none()
+    }
+
+    /// Sale de la edición o del historial, o vuelve desde otra pantalla
+    fn volver(&mut self) -> Task<Message> {
+        let mensaje = match self.pantalla {
+            Pantalla::Usuarios | Pantalla::Tablero => return Task::none(),
+            Pantalla::Fichas => {
+                if self.fichas_view.editando {
+                    self.manejar_mensaje_ficha(FichaMessage::CancelarEdicion);
+                } else if self.fichas_view.ficha_historial_id.is_some() {
+                    self.manejar_mensaje_ficha(FichaMessage::CerrarHistorial);
+                } else {
+                    self.abrir_tablero();
+                }
+                return Task::none();
+            }
+            Pantalla::Plantillas => Message::Plantilla(PlantillaMessage::Volver),
+            Pantalla::Etiquetas => Message::Etiqueta(EtiquetaMessage::Volver),
+            Pantalla::Categorias => Message::Categoria(CategoriaMessage::Volver),
+            Pantalla::Balances => Message::Balance(BalanceMessage::Volver),
+            Pantalla::Liquidaciones => Message::Liquidacion(LiquidacionMessage::Volver),
+            Pantalla::ReporteTiempo => Message::ReporteTiempo(ReporteTiempoMessage::Volver),
+            Pantalla::MisTareas => Message::MisTareas(MisTareasMessage::Volver),
+            Pantalla::Administracion => Message::Administracion(AdministracionMessage::Volver),
+            Pantalla::Auditoria => Message::Auditoria(AuditoriaMessage::Volver),
+            Pantalla::Preferencias => Message::Preferencias(PreferenciasMessage::Volver),
+            Pantalla::Importacion => Message::Importacion(ImportacionMessage::Volver),
+            Pantalla::Correo => Message::Correo(CorreoMessage::Volver),
+        };
+        self.update(mensaje)
+    }
+
+    fn manejar_mensaje_paleta(&mut self, mensaje: PaletaMessage) {
+        match mensaje {
+            PaletaMessage::ConsultaChanged(valor) => {
+                self.paleta_view.consulta = valor;
+                self.paleta_view.buscar(self.storage.obtener_fichas());
+            }
+            PaletaMessage::Confirmar => {
+                if let Some(destino) = self.paleta_view.seleccionado() {
+                    self.ir_a(destino);
+                }
+            }
+            PaletaMessage::Ejecutar(destino) => self.ir_a(destino),
+            PaletaMessage::Cerrar => self.paleta_view.cerrar(),
+        }
+    }
+
+    /// Abre el resultado elegido en la paleta
+    fn ir_a(&mut self, destino: Destino) {
+        self.paleta_view.cerrar();
+        match destino {
+            Destino::Comando(comando) => self.ejecutar_comando(comando),
+            Destino::Ficha(ficha_id) => {
+                self.fichas_view.cancelar_edicion();
+                self.manejar_mensaje_ficha(FichaMessage::VerHistorial(ficha_id));
+                self.pantalla = Pantalla::Fichas;
+            }
+        }
+    }
+
+    /// Ejecuta una acción de navegación desde cualquier pantalla
+    fn ejecutar_comando(&mut self, comando: Comando) {
+        let mensaje = match comando {
+            Comando::Inicio => return self.abrir_tablero(),
+            Comando::Fichas => {
+                self.fichas_view.cancelar_edicion();
+                self.fichas_view.cerrar_historial();
+                self.fichas_view.quitar_filtros();
+                self.pantalla = Pantalla::Fichas;
+                return;
+            }
+            Comando::NuevaFicha => {
+                if !self.puede(Accion::EditarFichas) {
+                    return;
+                }
+                self.fichas_view.cerrar_historial();
+                self.pantalla = Pantalla::Fichas;
+                FichaMessage::NuevaFicha
+            }
+            Comando::MisTareas => FichaMessage::VerMisTareas,
+            Comando::Horas => FichaMessage::VerReporteTiempo,
+            Comando::Honorarios => FichaMessage::VerBalances,
+            Comando::Etiquetas => FichaMessage::GestionarEtiquetas,
+            Comando::Categorias => FichaMessage::GestionarCategorias,
+            Comando::Plantillas => FichaMessage::GestionarPlantillas,
+            Comando::Usuarios => FichaMessage::GestionarUsuarios,
+            Comando::Auditoria => FichaMessage::VerAuditoria,
+            Comando::Preferencias => FichaMessage::VerPreferencias,
+            Comando::ImportarMovimientos => FichaMessage::VerImportacion,
+            Comando::ImportarCorreos => FichaMessage::VerCorreo,
+            Comando::CambiarUsuario => FichaMessage::CambiarUsuario,
+        };
+        self.manejar_mensaje_ficha(mensaje);
+    }
+
+    fn abrir_tablero(&mut self) {
+        self.fichas_view.cancelar_edicion();
+        self.fichas_view.cerrar_historial();
+        self.tablero_view
+            .actualizar(self.storage.obtener_fichas());
+        self.pantalla = Pantalla::Tablero;
+    }
+
+    /// Los widgets del tablero llevan a la lista con el filtro correspondiente
+    fn manejar_mensaje_tablero(&mut self, mensaje: TableroMessage) {
+        match mensaje {
+            TableroMessage::DiasChanged(valor) => {
+                self.tablero_view.dias_input = valor;
+                self.tablero_view
+                    .actualizar(self.storage.obtener_fichas());
+                return;
+            }
+            TableroMessage::VerFicha(ficha_id) => {
+                self.manejar_mensaje_ficha(FichaMessage::VerHistorial(ficha_id));
+            }
+            TableroMessage::VerTodas => {
+                self.fichas_view.quitar_filtros();
+            }
+            TableroMessage::FiltrarEstado(estado) => {
+                self.fichas_view.quitar_filtros();
+                self.fichas_view.filtro_estado = Some(estado);
+            }
+            TableroMessage::FiltrarEtiqueta(etiqueta) => {
+                self.fichas_view.quitar_filtros();
+                self.fichas_view.filtro_etiqueta = Some(etiqueta);
+            }
+            TableroMessage::VerSeleccion(filtro) => {
+                self.fichas_view.quitar_filtros();
+                self.fichas_view.filtro_seleccion = Some(filtro);
+            }
+        }
+        self.pantalla = Pantalla::Fichas;
+    }
+
+    /// Habilita en las vistas las acciones que permite el rol del usuario actual
+    fn actualizar_permisos(&mut self) {
+        self.balances_view.puede_liquidar = self.puede(Accion::EditarCuenta);
+        self.mis_tareas_view.editable = self.puede(Accion::EditarFichas);
+    }
+
+    fn manejar_mensaje_administracion(&mut self, mensaje: AdministracionMessage) {
+        match mensaje {
+            AdministracionMessage::Volver => {
+                self.pantalla = Pantalla::Fichas;
+            }
+            AdministracionMessage::CambiarRol(usuario_id, rol) => {
+                self.administracion_view.mensaje =
+                    match self.storage.cambiar_rol_usuario(usuario_id, rol) {
+                        Ok(true) => None,
+                        Ok(false) => Some("Debe quedar al menos un administrador".into()),
+                        Err(e) => Some(format!("No se pudo guardar: {}", e)),
+                    };
+                self.actualizar_usuarios();
+                self.actualizar_permisos();
+                if !self.puede(Accion::GestionarUsuarios) {
+                    self.pantalla = Pantalla::Fichas;
+                }
+            }
+            AdministracionMessage::EliminarUsuario(usuario_id) => {
+                self.administracion_view.mensaje = match self.storage.eliminar_usuario(usuario_id)
+                {
+                    Ok(true) => None,
+                    Ok(false) => Some("No se puede eliminar al último administrador".into()),
+                    Err(e) => Some(format!("No se pudo guardar: {}", e)),
+                };
+                self.actualizar_usuarios();
+            }
+            AdministracionMessage::NombreChanged(valor) => {
+                self.administracion_view.nombre_input = valor;
+            }
+            AdministracionMessage::InicialesChanged(valor) => {
+                self.administracion_view.iniciales_input = valor;
+            }
+            AdministracionMessage::ColorSeleccionado(color) => {
+                self.administracion_view.color = color;
+            }
+            AdministracionMessage::RolSeleccionado(rol) => {
+                self.administracion_view.rol = rol;
+            }
+            AdministracionMessage::CrearUsuario => {
+                self.administracion_view.mensaje = match self.administracion_view.obtener_usuario()
+                {
+                    Ok(usuario) => {
+                        let nombre = usuario.nombre.clone();
+                        match self.storage.agregar_usuario(usuario) {
+                            Ok(()) => {
+                                self.administracion_view.limpiar_formulario();
+                                Some(format!("Usuario \"{}\" creado", nombre))
+                            }
+                            Err(e) => Some(format!("No se pudo guardar: {}", e)),
+                        }
+                    }
+                    Err(e) => Some(e),
+                };
+                self.actualizar_usuarios();
+            }
+        }
+    }
+
+    /// Propaga los perfiles de usuario a las vistas que los usan
+    fn actualizar_usuarios(&mut self) {
+        let usuarios = self.storage.obtener_usuarios().clone();
+        self.usuarios_view.actualizar_usuarios(usuarios.clone());
+        self.administracion_view
+            .actualizar_usuarios(usuarios.clone());
+        self.fichas_view.actualizar_usuarios(usuarios);
+    }
+
+    fn manejar_mensaje_auditoria(&mut self, mensaje: AuditoriaMessage) {
+        match mensaje {
+            AuditoriaMessage::Volver => {
+                self.pantalla = Pantalla::Fichas;
+            }
+            AuditoriaMessage::TextoChanged(valor) => {
+                self.auditoria_view.filtro_texto = valor;
+            }
+            AuditoriaMessage::FiltrarOperacion(operacion) => {
+                self.auditoria_view.filtro_operacion = Some(operacion);
+            }
+            AuditoriaMessage::FiltrarUsuario(usuario) => {
+                self.auditoria_view.filtro_usuario = Some(usuario.id);
+            }
+            AuditoriaMessage::QuitarFiltros => {
+                self.auditoria_view.quitar_filtros();
+            }
+            AuditoriaMessage::Verificar => {
+                self.actualizar_auditoria();
+                self.auditoria_view.mensaje = Some(match self.storage.verificar_auditoria() {
+                    Ok(Verificacion::Integra(cantidad)) => {
+                        format!("Registro íntegro: {} operaciones verificadas", cantidad)
+                    }
+                    Ok(Verificacion::Alterada { linea, motivo }) => {
+                        format!("⚠ El registro fue alterado en la línea {}: {}", linea, motivo)
+                    }
+                    Err(e) => format!("No se pudo leer el registro: {}", e),
+                });
+            }
+        }
+    }
+
+    fn manejar_mensaje_importacion(&mut self, mensaje: ImportacionMessage) {
+        match mensaje {
+            ImportacionMessage::Volver => {
+                self.pantalla = Pantalla::Fichas;
+            }
+            ImportacionMessage::RutaChanged(valor) => {
+                self.importacion_view.ruta_input = valor;
+            }
+            ImportacionMessage::Importar => self.importar_movimientos(),
+        }
+    }
+
+    /// Agrega como hojas los movimientos del archivo indicado, cada uno en
+    /// la ficha de su expediente
+    fn importar_movimientos(&mut self) {
+        self.importacion_view.limpiar_resultado();
+        let ruta = PathBuf::from(self.importacion_view.ruta_input.trim());
+        let movimientos = match importacion::leer_archivo(&ruta) {
+            Ok(movimientos) => movimientos,
+            Err(e) => {
+                self.importacion_view.mensaje = Some(i18n::texto_con(
+                    self.preferencias.idioma,
+                    "importacion-error",
+                    &[("error", e.to_string().into())],
+                ));
+                return;
+            }
+        };
+
+        let mut resumen = Resumen::default();
+        let grupos =
+            importacion::agrupar(self.storage.obtener_fichas(), &movimientos, &mut resumen);
+        let autor = self.usuario_actual;
+        for (ficha_id, grupo) in grupos {
+            // Las fichas sin movimientos nuevos no se tocan, para no guardar en vano
+            let Some(ficha) = self.storage.obtener_ficha(ficha_id) else {
+                continue;
+            };
+            if grupo.iter().all(|m| importacion::ya_cargado(ficha, m)) {
+                resumen.repetidas += grupo.len();
+                continue;
+            }
+            let titulo = ficha.titulo.clone();
+            match self.storage.modificar_ficha(ficha_id, autor, |ficha| {
+                importacion::aplicar(ficha, &grupo, autor)
+            }) {
+                Ok(Some(agregadas)) => {
+                    resumen.agregadas += agregadas;
+                    resumen.repetidas += grupo.len() - agregadas;
+                    resumen.fichas += usize::from(agregadas > 0);
+                    self.importacion_view
+                        .fichas_actualizadas
+                        .push((titulo, agregadas));
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    self.importacion_view.mensaje = Some(i18n::texto_con(
+                        self.preferencias.idioma,
+                        "guardar-error",
+                        &[("error", e.to_string().into())],
+                    ));
+                    break;
+                }
+            }
+        }
+
+        if self.importacion_view.mensaje.is_none() {
+            let idioma = self.preferencias.idioma;
+            self.importacion_view.mensaje = Some(ui::importacion::describir(&resumen, idioma));
+        }
+        self.fichas_view
+            .actualizar_fichas(self.storage.obtener_fichas().clone());
+    }
+
+    fn manejar_mensaje_correo(&mut self, mensaje: CorreoMessage) {
+        match mensaje {
+            CorreoMessage::Volver => {
+                self.pantalla = Pantalla::Fichas;
+            }
+            CorreoMessage::RutaChanged(valor) => {
+                self.correo_view.ruta_input = valor;
+            }
+            CorreoMessage::Importar => {
+                let ruta = PathBuf::from(self.correo_view.ruta_input.trim());
+                self.correo_view.limpiar_resultado();
+                match self.importar_correos(&ruta, None) {
+                    Ok((resumen, fichas)) => {
+                        let idioma = self.preferencias.idioma;
+                        self.correo_view.mensaje = Some(ui::correo::describir(&resumen, idioma));
+                        self.correo_view.fichas_actualizadas = fichas;
+                        self.correo_view.sin_ficha = resumen.sin_ficha;
+                    }
+                    Err(e) => self.correo_view.mensaje = Some(e),
+                }
+            }
+            // Las reglas son parte de la configuración
+            _ if !self.puede(Accion::Configurar) => {}
+            CorreoMessage::ExpedienteReglaChanged(valor) => {
+                self.correo_view.expediente_input = valor;
+                self.correo_view.error_regla = None;
+            }
+            CorreoMessage::FichaRegla(ficha) => {
+                self.correo_view.ficha_regla = Some(ficha);
+                self.correo_view.error_regla = None;
+            }
+            CorreoMessage::CategoriaRegla(categoria) => {
+                self.correo_view.categoria_regla = Some(categoria);
+            }
+            CorreoMessage::QuitarCategoriaRegla => {
+                self.correo_view.categoria_regla = None;
+            }
+            CorreoMessage::AgregarRegla => self.agregar_regla_correo(),
+            CorreoMessage::EliminarRegla(id) => {
+                let _ = self.storage.eliminar_regla_correo(id);
+                self.actualizar_correo();
+            }
+        }
+    }
+
+    fn agregar_regla_correo(&mut self) {
+        let vista = &mut self.correo_view;
+        let expediente = vista.expediente_input.trim().to_string();
+        if importacion::ClaveExpediente::extraer(&expediente).is_none() {
+            vista.error_regla = Some(i18n::texto_con(
+                self.preferencias.idioma,
+                "correo-regla-sin-expediente",
+                &[("expediente", expediente.into())],
+            ));
+            return;
+        }
+        let Some(ficha) = &vista.ficha_regla else {
+            vista.error_regla =
+                Some(i18n::texto(self.preferencias.idioma, "correo-regla-sin-ficha"));
+            return;
+        };
+
+        let regla = ReglaCorreo::new(
+            expediente,
+            ficha.id,
+            vista.categoria_regla.as_ref().map(|c| c.id.clone()),
+        );
+        match self.storage.agregar_regla_correo(regla) {
+            Ok(()) => {
+                self.correo_view.limpiar_regla();
+                self.actualizar_correo();
+            }
+            Err(e) => {
+                self.correo_view.error_regla = Some(i18n::texto_con(
+                    self.preferencias.idioma,
+                    "guardar-error",
+                    &[("error", e.to_string().into())],
+                ));
+            }
+        }
+    }
+
+    /// Correos soltados sobre la ventana: van a la ficha abierta o, en la
+    /// pantalla de correos, a la ficha de cada uno
+    fn soltar_archivo(&mut self, ruta: PathBuf) {
+        match self.pantalla {
+            Pantalla::Fichas => {
+                let Some(ficha_id) = self.fichas_view.ficha_historial_id else {
+                    return;
+                };
+                if self.fichas_view.editando {
+                    return;
+                }
+                self.fichas_view.mensaje_correo = Some(
+                    match self.importar_correos(&ruta, Some(ficha_id)) {
+                        Ok((resumen, _)) => {
+                            ui::correo::describir(&resumen, self.preferencias.idioma)
+                        }
+                        Err(e) => e,
+                    },
+                );
+            }
+            Pantalla::Correo => {
+                self.correo_view.ruta_input = ruta.display().to_string();
+                self.manejar_mensaje_correo(CorreoMessage::Importar);
+            }
+            _ => {}
+        }
+    }
+
+    /// Agrega como hojas los correos de `ruta`, todos a la ficha `fija` o
+    /// cada uno a la de su expediente. Devuelve el resumen y las fichas que
+    /// recibieron hojas, con cuántas.
+    fn importar_correos(
+        &mut self,
+        ruta: &Path,
+        fija: Option<Uuid>,
+    ) -> Result<(correo::Resumen, Vec<(String, usize)>), String> {
+        let idioma = self.preferencias.idioma;
+        let fechas = self.fechas();
+        let error = |clave: &str, e: String| i18n::texto_con(idioma, clave, &[("error", e.into())]);
+        let mensajes =
+            correo::leer_ruta(ruta).map_err(|e| error("correo-error-importar", e.to_string()))?;
+        let mut resumen = correo::Resumen::default();
+        let entregas = correo::repartir(
+            &mensajes,
+            self.storage.obtener_fichas(),
+            self.storage.obtener_reglas_correo(),
+            fija,
+            &fechas,
+            &mut resumen,
+        );
+
+        // Los adjuntos se copian antes de agregar las hojas que los citan
+        let mut hojas = Vec::with_capacity(entregas.len());
+        for entrega in entregas {
+            let destino = self.storage.directorio_adjuntos(entrega.destino.ficha_id);
+            let adjuntos = correo::guardar_adjuntos(entrega.mensaje, &destino)
+                .map_err(|e| error("correo-error-adjuntos", e.to_string()))?;
+            hojas.push((entrega, adjuntos));
+        }
+
+        let mut ids: Vec<Uuid> = hojas.iter().map(|(e, _)| e.destino.ficha_id).collect();
+        ids.sort();
+        ids.dedup();
+        let autor = self.usuario_actual;
+        let actualizadas = self
+            .storage
+            .modificar_fichas(&ids, autor, |ficha| {
+                let ficha_id = ficha.id;
+                let mut agregadas = 0;
+                let propias = hojas.iter().filter(|(e, _)| e.destino.ficha_id == ficha_id);
+                for (entrega, adjuntos) in propias {
+                    ficha.agregar_hoja_fechada(
+                        entrega.mensaje.fecha_evento(&fechas),
+                        entrega.mensaje.contenido(),
+                        entrega.destino.categoria.clone(),
+                        adjuntos.clone(),
+                        autor,
+                    );
+                    agregadas += 1;
+                }
+                (ficha.titulo.clone(), agregadas)
+            })
+            .map_err(|e| error("guardar-error", e.to_string()))?;
+
+        self.fichas_view
+            .actualizar_fichas(self.storage.obtener_fichas().clone());
+        let fichas: Vec<(String, usize)> = actualizadas.into_iter().map(|(_, f)| f).collect();
+        resumen.agregadas = fichas.iter().map(|(_, agregadas)| agregadas).sum();
+        Ok((resumen, fichas))
+    }
+
+    /// Propaga las reglas, fichas y categorías a la pantalla de correos
+    fn actualizar_correo(&mut self) {
+        self.correo_view.puede_configurar = self.puede(Accion::Configurar);
+        self.correo_view.actualizar(
+            self.storage.obtener_reglas_correo().clone(),
+            self.storage.obtener_fichas(),
+            self.storage.obtener_categorias().clone(),
+        );
+    }
+
+    /// Consulta en el portal los movimientos de todas las fichas con número
+    /// de expediente, o solo los de `solo`. La consulta corre en otro hilo y
+    /// termina con `Message::SincronizacionTerminada`.
+    fn sincronizar(&mut self, solo: Option<Uuid>) -> Task<Message> {
+        let configuracion = &self.preferencias.sincronizacion;
+        if !configuracion.activa {
+            return Task::none();
+        }
+        let comenzada = match solo {
+            Some(_) => self.planificador.comenzar_manual(),
+            None => self.planificador.comenzar(Instant::now()),
+        };
+        if !comenzada {
+            return Task::none();
+        }
+
+        let consultas = sincronizacion::consultas(
+            self.storage
+                .obtener_fichas()
+                .iter()
+                .filter(|f| solo.is_none_or(|id| f.id == id)),
+        );
+        let fuente = sincronizacion::FuenteHttp::new(&configuracion.url);
+        self.fichas_view.sincronizando = true;
+
+        let (enviar, recibir) = iced::futures::channel::oneshot::channel();
+        std::thread::spawn(move || {
+            let respuestas =
+                sincronizacion::consultar(&fuente, &consultas, PAUSA_ENTRE_CONSULTAS);
+            let _ = enviar.send(respuestas);
+        });
+        Task::perform(recibir, |respuestas| {
+            Message::SincronizacionTerminada(respuestas.unwrap_or_default())
+        })
+    }
+
+    /// Vuelca en las fichas lo obtenido en la última consulta al portal
+    fn registrar_sincronizacion(&mut self, respuestas: Vec<sincronizacion::Respuesta>) {
+        self.planificador.terminar();
+        self.fichas_view.sincronizando = false;
+
+        let ids: Vec<Uuid> = respuestas.iter().map(|r| r.ficha_id).collect();
+        // Las hojas traídas del portal no tienen autor
+        let guardado = self.storage.modificar_fichas(&ids, None, |ficha| {
+            respuestas
+                .iter()
+                .find(|r| r.ficha_id == ficha.id)
+                .map(|respuesta| sincronizacion::registrar(ficha, respuesta, None))
+        });
+        self.fichas_view.error_sincronizacion = guardado.err().map(|e| {
+            format!("No se pudo guardar lo consultado en el portal: {}", e)
+        });
+        self.fichas_view
+            .actualizar_fichas(self.storage.obtener_fichas().clone());
+    }
+
+    fn manejar_mensaje_preferencias(&mut self, mensaje: PreferenciasMessage) {
+        match mensaje {
+            PreferenciasMessage::Volver => {
+                self.pantalla = Pantalla::Fichas;
+            }
+            PreferenciasMessage::Guardar => self.guardar_preferencias(),
+            PreferenciasMessage::RespaldarAhora if self.puede(Accion::Configurar) => {
+                let respaldo = &self.preferencias.respaldo;
+                self.preferencias_view.mensaje = Some(
+                    match self.storage.respaldar(&respaldo.directorio, respaldo.conservar) {
+                        Ok(ruta) => format!("Respaldo guardado en {}", ruta.display()),
+                        Err(e) => format!("No se pudo respaldar los datos: {}", e),
+                    },
+                );
+            }
+            PreferenciasMessage::RespaldarAhora => {}
+            otro => self.preferencias_view.actualizar(otro),
+        }
+    }
+
+    /// Valida y guarda el formulario de preferencias. Si cambió el archivo de
+    /// datos se abre el nuevo y se vuelve a elegir usuario. Sin permiso para
+    /// configurar solo se guardan las preferencias de visualización.
+    fn guardar_preferencias(&mut self) {
+        self.preferencias_view.mensaje = None;
+        let nuevas = match self.preferencias_view.obtener_preferencias() {
+            Ok(nuevas) if !self.puede(Accion::Configurar) => {
+                self.preferencias.con_visualizacion_de(&nuevas)
+            }
+            Ok(nuevas) => nuevas,
+            Err(errores) => {
+                self.preferencias_view.errores = errores;
+                return;
+            }
+        };
+
+        // Si el archivo actual no se pudo leer, se vuelve a intentar aunque
+        // sea el mismo, por si ya se corrigió
+        let otro_archivo = nuevas.ruta_datos != self.preferencias.ruta_datos
+            || self.storage.error_carga().is_some();
+        let mut storage = JsonStorage::new(nuevas.ruta_datos.clone());
+        if otro_archivo {
+            if let Err(e) = storage.cargar() {
+                self.preferencias_view.errores = vec![format!(
+                    "No se pudo abrir el archivo de datos {}: {}",
+                    nuevas.ruta_datos.display(),
+                    e
+                )];
+                return;
+            }
+        }
+
+        if let Err(e) = guardar_preferencias(Path::new(ARCHIVO_PREFERENCIAS), &nuevas) {
+            self.preferencias_view.errores =
+                vec![format!("No se pudieron guardar las preferencias: {}", e)];
+            return;
+        }
+        self.preferencias = nuevas;
+        self.aplicar_preferencias();
+        self.preferencias_view.mensaje = Some(String::from("Preferencias guardadas"));
+
+        if otro_archivo {
+            let fechas = self.fechas();
+            self.storage = storage;
+            self.storage.migrar_fechas(|momento| fechas.dia(momento));
+            self.cargar_vistas();
+            self.usuario_actual = None;
+            self.fichas_view.usuario_actual = None;
+            self.usuarios_view.limpiar_formulario();
+            self.pantalla = Pantalla::Usuarios;
+        }
+    }
+
+    /// Vuelve a leer el registro de auditoría para mostrarlo
+    fn actualizar_auditoria(&mut self) {
+        let usuarios = self.storage.obtener_usuarios().clone();
+        match self.storage.obtener_auditoria() {
+            Ok(registros) => self.auditoria_view.actualizar(registros, usuarios),
+            Err(e) => {
+                self.auditoria_view.actualizar(Vec::new(), usuarios);
+                self.auditoria_view.mensaje = Some(format!("No se pudo leer el registro: {}", e));
+            }
+        }
+    }
+
+    fn manejar_mensaje_mis_tareas(&mut self, mensaje: MisTareasMessage) {
+        match mensaje {
+            MisTareasMessage::Volver => {
+                self.pantalla = Pantalla::Fichas;
+            }
+            MisTareasMessage::FiltrarResponsable(responsable) => {
+                self.mis_tareas_view.filtro_responsable = Some(responsable);
+            }
+            MisTareasMessage::QuitarFiltro => {
+                self.mis_tareas_view.filtro_responsable = None;
+            }
+            MisTareasMessage::VerFicha(ficha_id) => {
+                self.manejar_mensaje_ficha(FichaMessage::VerHistorial(ficha_id));
+                self.pantalla = Pantalla::Fichas;
+            }
+            MisTareasMessage::Tarea(tarea_msg) => {
+                if self.puede(Accion::EditarFichas) {
+                    self.manejar_accion_tarea(&tarea_msg);
+                }
+            }
+        }
+    }
+
+    /// Genera un escrito para la ficha y registra una hoja con el archivo adjunto
+    fn generar_escrito(&mut self, ficha_id: Uuid, plantilla: &PlantillaEscrito) {
+        let destino = self.storage.directorio_adjuntos(ficha_id);
+        let Some(ficha) = self.storage.obtener_ficha(ficha_id) else {
+            return;
+        };
+
+        let fechas = self.fechas();
+        let hoy = fechas.dia_corto(fechas.hoy());
+        let sello = FormatoFecha::new("%Y%m%d_%H%M%S", &self.preferencias.zona_horaria)
+            .fecha_hora(Utc::now());
+        match documentos::generar(plantilla, ficha, &destino, &hoy, &sello) {
+            Ok(adjunto) => {
+                let autor = self.usuario_actual;
+                let _ = self.storage.modificar_ficha(ficha_id, autor, |ficha| {
+                    ficha.agregar_hoja_fechada(
+                        fechas.hoy(),
+                        format!("Se generó escrito {}", plantilla.nombre),
+                        None,
+                        vec![adjunto],
+                        autor,
+                    )
+                });
+                self.fichas_view.mensaje_escrito =
+                    Some(format!("Escrito \"{}\" generado", plantilla.nombre));
+                self.fichas_view
+                    .actualizar_fichas(self.storage.obtener_fichas().clone());
+            }
+            Err(e) => {
+                self.fichas_view.mensaje_escrito =
+                    Some(format!("No se pudo generar el escrito: {}", e));
+            }
+        }
+    }
+
+    fn manejar_mensaje_liquidacion(&mut self, mensaje: LiquidacionMessage) {
+        match mensaje {
+            LiquidacionMessage::Volver => {
+                self.actualizar_balances();
+                self.pantalla = Pantalla::Balances;
+            }
+            LiquidacionMessage::ClienteSeleccionado(cliente) => {
+                self.liquidaciones_view.cliente = Some(cliente);
+                self.liquidaciones_view.seleccion.clear();
+                self.liquidaciones_view
+                    .actualizar(self.storage.obtener_fichas());
+            }
+            LiquidacionMessage::AlternarAsiento(ficha_id, asiento_id, marcado) => {
+                if marcado {
+                    self.liquidaciones_view
+                        .seleccion
+                        .insert((ficha_id, asiento_id));
+                } else {
+                    self.liquidaciones_view
+                        .seleccion
+                        .remove(&(ficha_id, asiento_id));
+                }
+            }
+            LiquidacionMessage::SeleccionarTodo => {
+                self.liquidaciones_view.seleccionar_todo();
+            }
+            LiquidacionMessage::Generar => {
+                self.liquidaciones_view.mensaje = Some(self.generar_liquidacion());
+                self.liquidaciones_view.seleccion.clear();
+                self.liquidaciones_view
+                    .actualizar(self.storage.obtener_fichas());
+                self.fichas_view
+                    .actualizar_fichas(self.storage.obtener_fichas().clone());
+            }
+        }
+    }
+
+    /// Emite la liquidación de los asientos seleccionados y devuelve el resultado a mostrar
+    fn generar_liquidacion(&mut self) -> String {
+        let Some(cliente) = self.liquidaciones_view.cliente.clone() else {
+            return String::from("Seleccione un cliente");
+        };
+        let seleccion: Vec<_> = self.liquidaciones_view.seleccion.iter().copied().collect();
+
+        let hoy = self.fechas().hoy();
+        let liquidacion = match self.storage.reservar_liquidacion(&cliente, &seleccion, hoy) {
+            Ok(Some(liquidacion)) => liquidacion,
+            Ok(None) => return String::from("No hay movimientos sin liquidar seleccionados"),
+            Err(e) => return format!("No se pudo reservar el número de liquidación: {}", e),
+        };
+
+        let destino = self.storage.directorio_liquidaciones();
+        let emitida = self.fechas().fecha_hora_con_zona(Utc::now());
+        let adjuntos = match documentos::generar_liquidacion(&liquidacion, &destino, &emitida) {
+            Ok(adjuntos) => adjuntos,
+            Err(e) => {
+                return format!(
+                    "No se pudo generar la liquidación N° {}: {}",
+                    liquidacion.numero_formateado(),
+                    e
+                )
+            }
+        };
+
+        match self
+            .storage
+            .registrar_liquidacion(&liquidacion, adjuntos, self.usuario_actual)
+        {
+            Ok(()) => format!(
+                "Liquidación N° {} generada en {}",
+                liquidacion.numero_formateado(),
+                destino.display()
+            ),
+            Err(e) => format!("No se pudo guardar la liquidación: {}", e),
+        }
+    }
+
+    fn actualizar_balances(&mut self) {
+        let fichas = self.storage.obtener_fichas();
+        self.balances_view.actualizar_totales(
+            cuenta::totales_por_cliente(fichas),
+            cuenta::totales_por_mes(fichas),
+        );
+    }
+
+    fn manejar_mensaje_plantilla(&mut self, mensaje: PlantillaMessage) {
+        match mensaje {
+            PlantillaMessage::Volver => {
+                self.plantillas_view.cancelar_edicion();
+                self.pantalla = Pantalla::Fichas;
+            }
+            PlantillaMessage::NuevaPlantilla => {
+                self.plantillas_view.iniciar_nueva_plantilla();
+            }
+            PlantillaMessage::EditarPlantilla(id) => {
+                self.plantillas_view.iniciar_edicion(id);
+            }
+            PlantillaMessage::EliminarPlantilla(id) => {
+                let _ = self.storage.eliminar_plantilla(id);
+                self.actualizar_plantillas();
+            }
+            PlantillaMessage::NombreChanged(valor) => {
+                self.plantillas_view.nombre_input = valor;
+            }
+            PlantillaMessage::ContenidoEditado(accion) => {
+                self.plantillas_view.contenido_input.perform(accion);
+            }
+            PlantillaMessage::FormatoContenido(formato) => {
+                hoja_editor::aplicar_formato(&mut self.plantillas_view.contenido_input, formato);
+            }
+            PlantillaMessage::GuardarPlantilla => {
+                if let Some(plantilla) = self.plantillas_view.obtener_plantilla() {
+                    if self.plantillas_view.plantilla_editando_id.is_some() {
+                        let _ = self.storage.actualizar_plantilla(plantilla);
+                    } else {
+                        let _ = self.storage.agregar_plantilla(plantilla);
+                    }
+                }
+                self.plantillas_view.cancelar_edicion();
+                self.actualizar_plantillas();
+            }
+            PlantillaMessage::CancelarEdicion => {
+                self.plantillas_view.cancelar_edicion();
+            }
+        }
+    }
+
+    fn manejar_mensaje_etiqueta(&mut self, mensaje: EtiquetaMessage) {
+        match mensaje {
+            EtiquetaMessage::Volver => {
+                self.pantalla = Pantalla::Fichas;
+            }
+            EtiquetaMessage::Seleccionar(etiqueta) => {
+                self.etiquetas_view.seleccionar(Some(etiqueta));
+                self.etiquetas_view.mensaje = None;
+            }
+            EtiquetaMessage::NuevoNombreChanged(valor) => {
+                self.etiquetas_view.nuevo_nombre_input = valor;
+            }
+            EtiquetaMessage::Renombrar => {
+                if let Some(anterior) = self.etiquetas_view.seleccionada.clone() {
+                    let autor = self.usuario_actual;
+                    let nueva = self.etiquetas_view.nuevo_nombre_input.trim().to_string();
+                    self.etiquetas_view.mensaje =
+                        match self.storage.renombrar_etiqueta(&anterior, &nueva, autor) {
+                            Ok(0) => None,
+                            Ok(cantidad) => {
+                                self.etiquetas_view.seleccionar(Some(nueva));
+                                Some(format!("{} fichas actualizadas", cantidad))
+                            }
+                            Err(e) => Some(format!("No se pudo guardar: {}", e)),
+                        };
+                    self.fichas_view.filtro_etiqueta = None;
+                    self.fichas_view
+                        .actualizar_fichas(self.storage.obtener_fichas().clone());
+                    self.actualizar_etiquetas();
+                }
+            }
+            EtiquetaMessage::AsignarColor(color) => {
+                if let Some(etiqueta) = self.etiquetas_view.seleccionada.clone() {
+                    let _ = self.storage.asignar_color_etiqueta(&etiqueta, color);
+                    self.actualizar_etiquetas();
+                }
+            }
+        }
+    }
+
+    /// Propaga las etiquetas y sus colores a las vistas que las usan
+    fn actualizar_etiquetas(&mut self) {
+        let etiquetas = self
+            .storage
+            .obtener_etiquetas()
+            .into_iter()
+            .map(|etiqueta| {
+                let cantidad = self
+                    .storage
+                    .obtener_fichas()
+                    .iter()
+                    .filter(|f| f.tiene_etiqueta(&etiqueta))
+                    .count();
+                (etiqueta, cantidad)
+            })
+            .collect();
+        let colores = self.storage.obtener_colores_etiquetas().clone();
+
+        self.etiquetas_view
+            .actualizar_etiquetas(etiquetas, colores.clone());
+        self.fichas_view.actualizar_colores_etiquetas(colores);
+    }
+
+    fn manejar_mensaje_categoria(&mut self, mensaje: CategoriaMessage) {
+        match mensaje {
+            CategoriaMessage::Volver => {
+                self.categorias_view.cancelar_edicion();
+                self.pantalla = Pantalla::Fichas;
+            }
+            CategoriaMessage::Seleccionar(id) => {
+                self.categorias_view.iniciar_edicion(&id);
+            }
+            CategoriaMessage::NuevaCategoria => {
+                self.categorias_view.iniciar_nueva_categoria();
+            }
+            CategoriaMessage::NombreChanged(valor) => {
+                self.categorias_view.nombre_input = valor;
+            }
+            CategoriaMessage::IconoChanged(valor) => {
+                self.categorias_view.icono_input = valor;
+            }
+            CategoriaMessage::ColorSeleccionado(color) => {
+                self.categorias_view.color = color;
+            }
+            CategoriaMessage::Guardar => {
+                let categoria = match self.categorias_view.obtener_categoria() {
+                    Ok(categoria) => categoria,
+                    Err(e) => {
+                        self.categorias_view.mensaje = Some(e);
+                        return;
+                    }
+                };
+                let nombre = categoria.nombre.clone();
+                let guardada = if self.categorias_view.categoria_editando_id.is_some() {
+                    self.storage.actualizar_categoria(categoria).map(|_| ())
+                } else {
+                    self.storage.agregar_categoria(categoria)
+                };
+                self.categorias_view.mensaje = Some(match guardada {
+                    Ok(()) => {
+                        self.categorias_view.cancelar_edicion();
+                        format!("Categoría \"{}\" guardada", nombre)
+                    }
+                    Err(e) => format!("No se pudo guardar: {}", e),
+                });
+                self.actualizar_categorias();
+            }
+            CategoriaMessage::Eliminar => {
+                let Some(id) = self.categorias_view.categoria_editando_id.clone() else {
+                    return;
+                };
+                self.categorias_view.mensaje = Some(match self.storage.eliminar_categoria(&id) {
+                    Ok(true) => {
+                        self.categorias_view.cancelar_edicion();
+                        String::from("Categoría eliminada")
+                    }
+                    Ok(false) => String::from(
+                        "La categoría tiene hojas: cámbieles la categoría antes de eliminarla",
+                    ),
+                    Err(e) => format!("No se pudo guardar: {}", e),
+                });
+                self.actualizar_categorias();
+            }
+        }
+    }
+
+    /// Propaga las categorías de hojas a las vistas que las usan
+    fn actualizar_categorias(&mut self) {
+        let categorias = self.storage.obtener_categorias().clone();
+        self.categorias_view
+            .actualizar_categorias(categorias.clone(), self.storage.usos_categorias());
+        self.fichas_view.actualizar_categorias(categorias);
+    }
+
+    /// Propaga las plantillas guardadas a las vistas que las usan
+    fn actualizar_plantillas(&mut self) {
+        let plantillas = self.storage.obtener_plantillas().clone();
+        self.plantillas_view.actualizar_plantillas(plantillas.clone());
+        self.fichas_view.actualizar_plantillas(plantillas);
+    }
 }
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Archivo asociado a una hoja, guardado junto al archivo de datos
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Adjunto {
+    pub nombre: String,
+    pub ruta: PathBuf,
+}
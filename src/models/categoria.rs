@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Categoría de una hoja (escrito, notificación, audiencia, etc.)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CategoriaHoja {
+    pub id: String,
+    pub nombre: String,
+    pub icono: String,
+    /// Color RGB de la etiqueta
+    pub color: [u8; 3],
+}
+
+impl CategoriaHoja {
+    pub const ESCRITO: &'static str = "escrito";
+    pub const NOTIFICACION: &'static str = "notificacion";
+    pub const PAGO: &'static str = "pago";
+
+    pub fn new(id: &str, nombre: &str, icono: &str, color: [u8; 3]) -> Self {
+        Self {
+            id: id.to_string(),
+            nombre: nombre.to_string(),
+            icono: icono.to_string(),
+            color,
+        }
+    }
+
+    /// Categorías con las que se inicializa un archivo de datos nuevo
+    pub fn predeterminadas() -> Vec<Self> {
+        vec![
+            Self::new(Self::ESCRITO, "Escrito presentado", "✎", [37, 99, 235]),
+            Self::new(Self::NOTIFICACION, "Notificación recibida", "✉", [217, 119, 6]),
+            Self::new("resolucion", "Resolución", "⚖", [124, 58, 237]),
+            Self::new("audiencia", "Audiencia", "☷", [220, 38, 38]),
+            Self::new("llamada", "Llamada al cliente", "☎", [13, 148, 136]),
+            Self::new(Self::PAGO, "Pago", "$", [22, 163, 74]),
+            Self::new("nota", "Nota interna", "✱", [100, 116, 139]),
+        ]
+    }
+}
+
+impl fmt::Display for CategoriaHoja {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.icono, self.nombre)
+    }
+}
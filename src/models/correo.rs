@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Regla para asignar a una ficha los correos que mencionan un expediente
+/// en el asunto. Sirve para los números que no coinciden con el de la
+/// ficha, como el de una causa conexa o el que usa otro tribunal; los
+/// demás correos se asignan por el expediente de cada ficha.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReglaCorreo {
+    pub id: Uuid,
+    /// Número de expediente tal como se escribió; se compara con
+    /// `ClaveExpediente::coincide`
+    pub expediente: String,
+    pub ficha_id: Uuid,
+    /// Id de la `CategoriaHoja` de las hojas creadas, si se eligió una
+    #[serde(default)]
+    pub categoria: Option<String>,
+}
+
+impl ReglaCorreo {
+    pub fn new(expediente: String, ficha_id: Uuid, categoria: Option<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            expediente,
+            ficha_id,
+            categoria,
+        }
+    }
+}
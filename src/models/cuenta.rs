@@ -0,0 +1,292 @@
+use crate::models::Ficha;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::{Add, AddAssign, Sub};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Moneda {
+    #[serde(rename = "ARS")]
+    Pesos,
+    #[serde(rename = "USD")]
+    Dolares,
+}
+
+impl Moneda {
+    pub const TODAS: [Moneda; 2] = [Moneda::Pesos, Moneda::Dolares];
+}
+
+impl fmt::Display for Moneda {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Moneda::Pesos => write!(f, "ARS"),
+            Moneda::Dolares => write!(f, "USD"),
+        }
+    }
+}
+
+/// Importe en centavos, para no acumular errores de redondeo
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Importe(pub i64);
+
+impl Importe {
+    /// Mayor importe que se puede cargar: un billón. Hacen falta más de
+    /// noventa mil asientos de este importe para desbordar una suma.
+    pub const MAXIMO: Importe = Importe(100_000_000_000_000);
+
+    /// Interpreta importes escritos como `1.234,56`, `1234,56` o `1234.56`,
+    /// hasta `Importe::MAXIMO`
+    pub fn parsear(texto: &str) -> Option<Importe> {
+        let texto = texto.trim().trim_start_matches('$').trim();
+        let puntos_de_miles = texto.matches('.').count() > 1
+            || texto.rsplit_once('.').is_some_and(|(_, d)| d.len() == 3);
+        let normalizado = if texto.contains(',') {
+            texto.replace('.', "").replace(',', ".")
+        } else if puntos_de_miles {
+            texto.replace('.', "")
+        } else {
+            texto.to_string()
+        };
+
+        let (enteros, decimales) = normalizado
+            .split_once('.')
+            .unwrap_or((normalizado.as_str(), ""));
+        // Solo dígitos: sin signo, que `parse` aceptaría en cualquiera de las partes
+        let solo_digitos = |parte: &str| parte.bytes().all(|b| b.is_ascii_digit());
+        if enteros.is_empty()
+            || decimales.len() > 2
+            || !solo_digitos(enteros)
+            || !solo_digitos(decimales)
+        {
+            return None;
+        }
+
+        let enteros: i64 = enteros.parse().ok()?;
+        let decimales: i64 = format!("{:0<2}", decimales).parse().ok()?;
+        enteros
+            .checked_mul(100)
+            .and_then(|centavos| centavos.checked_add(decimales))
+            .map(Importe)
+            .filter(|importe| *importe <= Importe::MAXIMO)
+    }
+
+    pub fn es_cero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl fmt::Display for Importe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let signo = if self.0 < 0 { "-" } else { "" };
+        let absoluto = self.0.unsigned_abs();
+        let enteros = (absoluto / 100).to_string();
+
+        // Separador de miles con punto y decimales con coma
+        let mut agrupado = String::new();
+        for (i, c) in enteros.chars().enumerate() {
+            if i > 0 && (enteros.len() - i).is_multiple_of(3) {
+                agrupado.push('.');
+            }
+            agrupado.push(c);
+        }
+
+        write!(f, "{}{},{:02}", signo, agrupado, absoluto % 100)
+    }
+}
+
+impl Add for Importe {
+    type Output = Importe;
+
+    fn add(self, otro: Importe) -> Importe {
+        Importe(self.0.saturating_add(otro.0))
+    }
+}
+
+impl AddAssign for Importe {
+    fn add_assign(&mut self, otro: Importe) {
+        *self = *self + otro;
+    }
+}
+
+impl Sub for Importe {
+    type Output = Importe;
+
+    fn sub(self, otro: Importe) -> Importe {
+        Importe(self.0.saturating_sub(otro.0))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TipoAsiento {
+    /// Gasto adelantado por el estudio (tasas, sellados, copias, cédulas)
+    Gasto,
+    Honorario,
+    /// Pago recibido del cliente
+    Pago,
+}
+
+impl TipoAsiento {
+    pub const TODOS: [TipoAsiento; 3] = [
+        TipoAsiento::Gasto,
+        TipoAsiento::Honorario,
+        TipoAsiento::Pago,
+    ];
+
+    /// Indica si el asiento se le cobra al cliente
+    pub fn es_cargo(&self) -> bool {
+        !matches!(self, TipoAsiento::Pago)
+    }
+}
+
+impl fmt::Display for TipoAsiento {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TipoAsiento::Gasto => write!(f, "Gasto"),
+            TipoAsiento::Honorario => write!(f, "Honorario"),
+            TipoAsiento::Pago => write!(f, "Pago"),
+        }
+    }
+}
+
+/// Movimiento de la cuenta de una ficha: cargo al cliente o pago recibido
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Asiento {
+    pub id: Uuid,
+    pub tipo: TipoAsiento,
+    pub concepto: String,
+    pub importe: Importe,
+    pub moneda: Moneda,
+    pub fecha: NaiveDate,
+    pub cliente: String,
+    /// Número de la liquidación en la que se facturó, si ya se facturó
+    #[serde(default)]
+    pub liquidacion: Option<u32>,
+}
+
+impl Asiento {
+    pub fn new(
+        tipo: TipoAsiento,
+        concepto: String,
+        importe: Importe,
+        moneda: Moneda,
+        fecha: NaiveDate,
+        cliente: String,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            tipo,
+            concepto,
+            importe,
+            moneda,
+            fecha,
+            cliente,
+            liquidacion: None,
+        }
+    }
+
+    pub fn esta_liquidado(&self) -> bool {
+        self.liquidacion.is_some()
+    }
+}
+
+/// Cargos y pagos acumulados en una moneda
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Totales {
+    pub cargos: Importe,
+    pub pagos: Importe,
+}
+
+impl Totales {
+    pub fn sumar(&mut self, asiento: &Asiento) {
+        if asiento.tipo.es_cargo() {
+            self.cargos += asiento.importe;
+        } else {
+            self.pagos += asiento.importe;
+        }
+    }
+
+    /// Lo que el cliente adeuda (negativo si pagó de más)
+    pub fn saldo(&self) -> Importe {
+        self.cargos - self.pagos
+    }
+}
+
+/// Totales por moneda de un conjunto de asientos
+pub fn totales<'a>(asientos: impl IntoIterator<Item = &'a Asiento>) -> BTreeMap<Moneda, Totales> {
+    let mut totales = BTreeMap::new();
+    for asiento in asientos {
+        totales
+            .entry(asiento.moneda)
+            .or_insert_with(Totales::default)
+            .sumar(asiento);
+    }
+    totales
+}
+
+/// Totales de todas las fichas agrupados por cliente y moneda
+pub fn totales_por_cliente(fichas: &[Ficha]) -> BTreeMap<(String, Moneda), Totales> {
+    let mut totales = BTreeMap::new();
+    for asiento in fichas.iter().flat_map(|f| &f.cuenta) {
+        totales
+            .entry((asiento.cliente.clone(), asiento.moneda))
+            .or_insert_with(Totales::default)
+            .sumar(asiento);
+    }
+    totales
+}
+
+/// Totales de todas las fichas agrupados por mes (`AAAA-MM`) y moneda
+pub fn totales_por_mes(fichas: &[Ficha]) -> BTreeMap<(String, Moneda), Totales> {
+    let mut totales = BTreeMap::new();
+    for asiento in fichas.iter().flat_map(|f| &f.cuenta) {
+        totales
+            .entry((asiento.fecha.format("%Y-%m").to_string(), asiento.moneda))
+            .or_insert_with(Totales::default)
+            .sumar(asiento);
+    }
+    totales
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsea_importes_con_separadores_de_miles_y_decimales() {
+        assert_eq!(Importe::parsear("$ 1.234,56"), Some(Importe(123456)));
+        assert_eq!(Importe::parsear("1234.5"), Some(Importe(123450)));
+        assert_eq!(Importe::parsear("1.234.567"), Some(Importe(123456700)));
+        assert_eq!(Importe::parsear("0,05"), Some(Importe(5)));
+    }
+
+    #[test]
+    fn rechaza_signos_en_cualquiera_de_las_partes() {
+        assert_eq!(Importe::parsear("1,-5"), None);
+        assert_eq!(Importe::parsear("1,+5"), None);
+        assert_eq!(Importe::parsear("-10"), None);
+        assert_eq!(Importe::parsear("+10"), None);
+        assert_eq!(Importe::parsear(",50"), None);
+        assert_eq!(Importe::parsear("12,345"), None);
+    }
+
+    #[test]
+    fn rechaza_importes_de_mas_de_un_billon() {
+        assert_eq!(
+            Importe::parsear("1.000.000.000.000"),
+            Some(Importe::MAXIMO)
+        );
+        assert_eq!(Importe::parsear("1.000.000.000.000,01"), None);
+        assert_eq!(Importe::parsear(&(i64::MAX / 100).to_string()), None);
+        assert_eq!(Importe::parsear("99999999999999999999"), None);
+    }
+
+    #[test]
+    fn las_sumas_no_desbordan() {
+        let mut total = Importe(i64::MAX - 1);
+        total += Importe::MAXIMO;
+        assert_eq!(total, Importe(i64::MAX));
+        assert_eq!(Importe(i64::MIN) - Importe::MAXIMO, Importe(i64::MIN));
+    }
+}
@@ -1,11 +1,155 @@
-use chrono::{DateTime, Utc};
+use crate::models::cuenta::{self, Totales};
+use crate::models::{
+    Adjunto, Asiento, EstadoSincronizacion, Moneda, Parte, RegistroTiempo, Tarea, TipoVinculo,
+    Vinculo,
+};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
 use uuid::Uuid;
 
+/// Situación procesal de la causa
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Estado {
+    #[default]
+    EnTramite,
+    Paralizada,
+    Finalizada,
+    Archivada,
+}
+
+impl Estado {
+    pub const TODOS: [Estado; 4] = [
+        Estado::EnTramite,
+        Estado::Paralizada,
+        Estado::Finalizada,
+        Estado::Archivada,
+    ];
+
+    /// Las causas cerradas no se controlan por falta de movimiento
+    pub fn esta_cerrada(&self) -> bool {
+        matches!(self, Estado::Finalizada | Estado::Archivada)
+    }
+}
+
+impl fmt::Display for Estado {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Estado::EnTramite => write!(f, "En trámite"),
+            Estado::Paralizada => write!(f, "Paralizada"),
+            Estado::Finalizada => write!(f, "Finalizada"),
+            Estado::Archivada => write!(f, "Archivada"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "HojaGuardada")]
 pub struct Hoja {
     pub contenido: String,
-    pub fecha: DateTime<Utc>,
+    /// Fecha procesal del movimiento, que puede ser anterior a la carga
+    pub fecha: NaiveDate,
+    /// Momento en que se cargó la hoja; no cambia una vez creada
+    pub registrada: DateTime<Utc>,
+    /// Id de la `CategoriaHoja` asignada, si la hay
+    #[serde(default)]
+    pub categoria: Option<String>,
+    #[serde(default)]
+    pub adjuntos: Vec<Adjunto>,
+    /// Id del `Usuario` que la escribió; vacío en hojas anteriores a los perfiles
+    #[serde(default)]
+    pub autor: Option<Uuid>,
+    /// Hoja de un archivo anterior cuya `fecha` sale del día UTC de
+    /// `registrada` hasta que `Ficha::migrar_fechas` la pasa a la zona
+    /// configurada
+    #[serde(skip)]
+    fecha_sin_zona: bool,
+}
+
+/// Fecha de una hoja tal como está en el archivo: hasta que se separó la
+/// fecha del movimiento de la de carga, era el momento de carga
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FechaGuardada {
+    Dia(NaiveDate),
+    Momento(DateTime<Utc>),
+}
+
+/// Hoja leída del archivo, antes de migrar el formato anterior
+#[derive(Deserialize)]
+struct HojaGuardada {
+    contenido: String,
+    fecha: FechaGuardada,
+    #[serde(default)]
+    registrada: Option<DateTime<Utc>>,
+    #[serde(default)]
+    categoria: Option<String>,
+    #[serde(default)]
+    adjuntos: Vec<Adjunto>,
+    #[serde(default)]
+    autor: Option<Uuid>,
+}
+
+impl From<HojaGuardada> for Hoja {
+    fn from(guardada: HojaGuardada) -> Self {
+        let (fecha, registrada, fecha_sin_zona) = match guardada.fecha {
+            FechaGuardada::Momento(momento) => (
+                momento.date_naive(),
+                guardada.registrada.unwrap_or(momento),
+                true,
+            ),
+            FechaGuardada::Dia(dia) => (
+                dia,
+                guardada
+                    .registrada
+                    .unwrap_or_else(|| dia.and_time(NaiveTime::MIN).and_utc()),
+                false,
+            ),
+        };
+        Self {
+            contenido: guardada.contenido,
+            fecha,
+            registrada,
+            categoria: guardada.categoria,
+            adjuntos: guardada.adjuntos,
+            autor: guardada.autor,
+            fecha_sin_zona,
+        }
+    }
+}
+
+impl Hoja {
+    /// Devuelve el contenido como texto plano de una línea, sin marcas
+    /// Markdown, recortado a `max_caracteres`.
+    pub fn resumen(&self, max_caracteres: usize) -> String {
+        let plano = self
+            .contenido
+            .lines()
+            .map(|linea| {
+                linea
+                    .trim()
+                    .trim_start_matches('#')
+                    .trim_start_matches("- ")
+                    .trim_start_matches("* ")
+                    .replace("**", "")
+                    .replace("__", "")
+                    .replace('`', "")
+                    .trim()
+                    .to_string()
+            })
+            .filter(|linea| !linea.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        if plano.chars().count() > max_caracteres {
+            let recortado: String = plano.chars().take(max_caracteres).collect();
+            format!("{}…", recortado.trim_end())
+        } else {
+            plano
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,41 +157,188 @@ pub struct Ficha {
     pub id: Uuid,
     pub titulo: String,
     pub descripcion: String,
+    /// Número de expediente judicial
+    #[serde(default)]
+    pub expediente: String,
+    #[serde(default)]
+    pub cliente: String,
+    #[serde(default)]
+    pub partes: Vec<Parte>,
+    #[serde(default)]
+    pub etiquetas: Vec<String>,
+    #[serde(default)]
+    pub estado: Estado,
+    /// Gastos, honorarios y pagos de la causa
+    #[serde(default)]
+    pub cuenta: Vec<Asiento>,
+    #[serde(default)]
+    pub tiempos: Vec<RegistroTiempo>,
+    #[serde(default)]
+    pub tareas: Vec<Tarea>,
+    /// Inicio del cronómetro en curso, si hay uno
+    #[serde(default)]
+    pub cronometro: Option<DateTime<Utc>>,
+    /// Ordenadas por fecha del movimiento, la más reciente primero
     pub hojas: Vec<Hoja>,
+    /// Fichas relacionadas; cada vínculo tiene su inverso en la otra ficha
+    #[serde(default)]
+    pub vinculos: Vec<Vinculo>,
+    /// Usuario a cargo de la ficha
+    #[serde(default)]
+    pub responsable: Option<Uuid>,
+    #[serde(default)]
+    pub creada_por: Option<Uuid>,
+    #[serde(default)]
+    pub modificada_por: Option<Uuid>,
+    /// Última consulta de los movimientos en el portal, si se hizo alguna
+    #[serde(default)]
+    pub sincronizacion: Option<EstadoSincronizacion>,
     pub fecha_creacion: DateTime<Utc>,
     pub fecha_modificacion: DateTime<Utc>,
 }
 
 impl Ficha {
-    pub fn new(titulo: String, descripcion: String, hoja_inicial: String) -> Self {
+    /// Crea la ficha con `autor` como creador y responsable. La hoja inicial
+    /// lleva la fecha `hoy`, el día en la zona configurada.
+    pub fn new(
+        titulo: String,
+        descripcion: String,
+        hoja_inicial: String,
+        hoy: NaiveDate,
+        autor: Option<Uuid>,
+    ) -> Self {
         let now = Utc::now();
         Self {
             id: Uuid::new_v4(),
             titulo,
             descripcion,
+            expediente: String::new(),
+            cliente: String::new(),
+            partes: Vec::new(),
+            etiquetas: Vec::new(),
+            estado: Estado::default(),
+            cuenta: Vec::new(),
+            tiempos: Vec::new(),
+            tareas: Vec::new(),
+            cronometro: None,
             hojas: vec![Hoja {
                 contenido: hoja_inicial,
-                fecha: now,
+                fecha: hoy,
+                registrada: now,
+                categoria: None,
+                adjuntos: Vec::new(),
+                autor,
+                fecha_sin_zona: false,
             }],
+            vinculos: Vec::new(),
+            responsable: autor,
+            creada_por: autor,
+            modificada_por: autor,
+            sincronizacion: None,
             fecha_creacion: now,
             fecha_modificacion: now,
         }
     }
 
-    pub fn actualizar(&mut self, titulo: String, descripcion: String) {
+    /// Ficha "Pérez c/ ACME" con el expediente indicado, para las pruebas
+    #[cfg(test)]
+    pub fn de_prueba(expediente: &str) -> Self {
+        let mut ficha = Self::new(
+            "Pérez c/ ACME".into(),
+            String::new(),
+            "Inicio".into(),
+            NaiveDate::default(),
+            None,
+        );
+        ficha.expediente = expediente.into();
+        ficha
+    }
+
+    pub fn actualizar(
+        &mut self,
+        titulo: String,
+        descripcion: String,
+        expediente: String,
+        cliente: String,
+        partes: Vec<Parte>,
+        autor: Option<Uuid>,
+    ) {
         self.titulo = titulo;
         self.descripcion = descripcion;
+        self.expediente = expediente;
+        self.cliente = cliente;
+        self.partes = partes;
+        self.marcar_modificada(autor);
+    }
+
+    /// Registra quién y cuándo modificó la ficha por última vez
+    pub fn marcar_modificada(&mut self, autor: Option<Uuid>) {
         self.fecha_modificacion = Utc::now();
+        if autor.is_some() {
+            self.modificada_por = autor;
+        }
     }
 
-    pub fn agregar_hoja(&mut self, contenido: String) {
+    /// Agrega una hoja sin adjuntos de un movimiento de `fecha`
+    pub fn agregar_hoja(
+        &mut self,
+        fecha: NaiveDate,
+        contenido: String,
+        categoria: Option<String>,
+        autor: Option<Uuid>,
+    ) {
+        self.agregar_hoja_fechada(fecha, contenido, categoria, Vec::new(), autor);
+    }
+
+    /// Agrega una hoja de un movimiento ocurrido en `fecha`, en su lugar
+    /// según el orden de las hojas
+    pub fn agregar_hoja_fechada(
+        &mut self,
+        fecha: NaiveDate,
+        contenido: String,
+        categoria: Option<String>,
+        adjuntos: Vec<Adjunto>,
+        autor: Option<Uuid>,
+    ) {
         let nueva_hoja = Hoja {
             contenido,
-            fecha: Utc::now(),
+            fecha,
+            registrada: Utc::now(),
+            categoria,
+            adjuntos,
+            autor,
+            fecha_sin_zona: false,
         };
-        // Insertar al inicio para que la última esté siempre primero
-        self.hojas.insert(0, nueva_hoja);
-        self.fecha_modificacion = Utc::now();
+        let posicion = self
+            .hojas
+            .partition_point(|h| Self::orden_hojas(h, &nueva_hoja).is_lt());
+        self.hojas.insert(posicion, nueva_hoja);
+        self.marcar_modificada(autor);
+    }
+
+    /// Más reciente primero por fecha del movimiento; a igual fecha, por
+    /// momento de carga
+    fn orden_hojas(a: &Hoja, b: &Hoja) -> std::cmp::Ordering {
+        (b.fecha, b.registrada).cmp(&(a.fecha, a.registrada))
+    }
+
+    /// Reordena las hojas, por ejemplo tras leer un archivo editado a mano
+    pub fn ordenar_hojas(&mut self) {
+        self.hojas.sort_by(Self::orden_hojas);
+    }
+
+    /// Completa la fecha de las hojas de archivos anteriores, que solo
+    /// guardaban el momento de carga, con su día según `dia`
+    pub fn migrar_fechas(&mut self, dia: impl Fn(DateTime<Utc>) -> NaiveDate) {
+        let mut migradas = false;
+        for hoja in self.hojas.iter_mut().filter(|h| h.fecha_sin_zona) {
+            hoja.fecha = dia(hoja.registrada);
+            hoja.fecha_sin_zona = false;
+            migradas = true;
+        }
+        if migradas {
+            self.ordenar_hojas();
+        }
     }
 
     pub fn hoja_actual(&self) -> Option<&Hoja> {
@@ -57,4 +348,245 @@ impl Ficha {
     pub fn obtener_hojas(&self) -> &Vec<Hoja> {
         &self.hojas
     }
+
+    /// Fecha del movimiento más reciente, o el día de creación según `dia`
+    /// si no tiene hojas
+    pub fn ultimo_movimiento(&self, dia: impl Fn(DateTime<Utc>) -> NaiveDate) -> NaiveDate {
+        self.hojas
+            .iter()
+            .map(|h| h.fecha)
+            .max()
+            .unwrap_or_else(|| dia(self.fecha_creacion))
+    }
+
+    pub fn tiene_etiqueta(&self, etiqueta: &str) -> bool {
+        self.etiquetas.iter().any(|e| e == etiqueta)
+    }
+
+    /// Reemplaza una etiqueta por otra; si la ficha ya tenía la nueva,
+    /// ambas quedan fusionadas. Devuelve si la ficha cambió.
+    pub fn renombrar_etiqueta(&mut self, anterior: &str, nueva: &str) -> bool {
+        if !self.tiene_etiqueta(anterior) || anterior == nueva {
+            return false;
+        }
+
+        self.etiquetas.retain(|e| e != anterior);
+        if !self.tiene_etiqueta(nueva) {
+            self.etiquetas.push(nueva.to_string());
+        }
+        self.fecha_modificacion = Utc::now();
+        true
+    }
+
+    /// Registra un asiento manteniendo la cuenta ordenada por fecha
+    pub fn agregar_asiento(&mut self, asiento: Asiento) {
+        let posicion = self
+            .cuenta
+            .iter()
+            .position(|a| a.fecha > asiento.fecha)
+            .unwrap_or(self.cuenta.len());
+        self.cuenta.insert(posicion, asiento);
+        self.fecha_modificacion = Utc::now();
+    }
+
+    pub fn eliminar_asiento(&mut self, asiento_id: Uuid) -> bool {
+        let cantidad = self.cuenta.len();
+        self.cuenta.retain(|a| a.id != asiento_id);
+        if self.cuenta.len() == cantidad {
+            return false;
+        }
+        self.fecha_modificacion = Utc::now();
+        true
+    }
+
+    /// Totales de la cuenta por moneda
+    pub fn saldo(&self) -> BTreeMap<Moneda, Totales> {
+        cuenta::totales(&self.cuenta)
+    }
+
+    /// Agrega una tarea manteniendo la lista ordenada por vencimiento
+    pub fn agregar_tarea(&mut self, tarea: Tarea) {
+        self.tareas.push(tarea);
+        self.tareas.sort_by_key(|t| t.orden());
+        self.fecha_modificacion = Utc::now();
+    }
+
+    /// Marca la tarea como cumplida y, si se pide, deja una hoja del día
+    /// `hoy` que lo registre
+    pub fn completar_tarea(
+        &mut self,
+        tarea_id: Uuid,
+        registrar_hoja: bool,
+        hoy: NaiveDate,
+        autor: Option<Uuid>,
+    ) -> bool {
+        let Some(tarea) = self
+            .tareas
+            .iter_mut()
+            .find(|t| t.id == tarea_id && !t.completada)
+        else {
+            return false;
+        };
+        tarea.completada = true;
+        let contenido = if tarea.responsable.is_empty() {
+            format!("Tarea cumplida: {}", tarea.descripcion)
+        } else {
+            format!(
+                "Tarea cumplida: {} (responsable: {})",
+                tarea.descripcion, tarea.responsable
+            )
+        };
+
+        if registrar_hoja {
+            self.agregar_hoja(hoy, contenido, None, autor);
+        } else {
+            self.marcar_modificada(autor);
+        }
+        true
+    }
+
+    pub fn eliminar_tarea(&mut self, tarea_id: Uuid) -> bool {
+        let cantidad = self.tareas.len();
+        self.tareas.retain(|t| t.id != tarea_id);
+        if self.tareas.len() == cantidad {
+            return false;
+        }
+        self.fecha_modificacion = Utc::now();
+        true
+    }
+
+    /// Vincula otra ficha; si ya estaba vinculada se reemplaza el tipo.
+    /// Devuelve `false` si se intenta vincular la ficha consigo misma o el
+    /// vínculo ya existía.
+    pub fn vincular(&mut self, ficha_id: Uuid, tipo: TipoVinculo) -> bool {
+        if ficha_id == self.id {
+            return false;
+        }
+        match self.vinculos.iter_mut().find(|v| v.ficha_id == ficha_id) {
+            Some(vinculo) if vinculo.tipo == tipo => return false,
+            Some(vinculo) => vinculo.tipo = tipo,
+            None => self.vinculos.push(Vinculo { ficha_id, tipo }),
+        }
+        self.fecha_modificacion = Utc::now();
+        true
+    }
+
+    pub fn desvincular(&mut self, ficha_id: Uuid) -> bool {
+        let cantidad = self.vinculos.len();
+        self.vinculos.retain(|v| v.ficha_id != ficha_id);
+        if self.vinculos.len() == cantidad {
+            return false;
+        }
+        self.fecha_modificacion = Utc::now();
+        true
+    }
+
+    pub fn iniciar_cronometro(&mut self) -> bool {
+        if self.cronometro.is_some() {
+            return false;
+        }
+        self.cronometro = Some(Utc::now());
+        true
+    }
+
+    /// Detiene el cronómetro y registra el tiempo transcurrido, redondeado
+    /// hacia arriba al minuto, en el día en que empezó según `dia`
+    pub fn detener_cronometro(
+        &mut self,
+        descripcion: String,
+        dia: impl Fn(DateTime<Utc>) -> NaiveDate,
+    ) -> Option<u32> {
+        let inicio = self.cronometro.take()?;
+        let segundos = (Utc::now() - inicio).num_seconds().max(1);
+        let minutos = ((segundos + 59) / 60) as u32;
+        let fecha = dia(inicio);
+
+        self.agregar_tiempo(RegistroTiempo::new(fecha, minutos, descripcion));
+        Some(minutos)
+    }
+
+    /// Segundos transcurridos del cronómetro en curso
+    pub fn segundos_cronometro(&self) -> Option<i64> {
+        self.cronometro
+            .map(|inicio| (Utc::now() - inicio).num_seconds())
+    }
+
+    /// Registra tiempo manteniendo la lista ordenada por fecha
+    pub fn agregar_tiempo(&mut self, registro: RegistroTiempo) {
+        let posicion = self
+            .tiempos
+            .iter()
+            .position(|t| t.fecha > registro.fecha)
+            .unwrap_or(self.tiempos.len());
+        self.tiempos.insert(posicion, registro);
+        self.fecha_modificacion = Utc::now();
+    }
+
+    pub fn eliminar_tiempo(&mut self, registro_id: Uuid) -> bool {
+        let cantidad = self.tiempos.len();
+        self.tiempos.retain(|t| t.id != registro_id);
+        if self.tiempos.len() == cantidad {
+            return false;
+        }
+        self.fecha_modificacion = Utc::now();
+        true
+    }
+
+    /// Minutos registrados entre las dos fechas, inclusive
+    pub fn minutos_en(&self, desde: NaiveDate, hasta: NaiveDate) -> u32 {
+        self.tiempos
+            .iter()
+            .filter(|t| t.en_rango(desde, hasta))
+            .fold(0u32, |total, t| total.saturating_add(t.minutos))
+    }
+
+    /// Devuelve la hoja más reciente de la categoría indicada
+    pub fn ultima_hoja_de(&self, categoria: &str) -> Option<&Hoja> {
+        self.hojas
+            .iter()
+            .find(|h| h.categoria.as_deref() == Some(categoria))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use chrono_tz::America::Argentina::Buenos_Aires;
+
+    #[test]
+    fn las_hojas_anteriores_toman_el_dia_de_la_zona_configurada() {
+        // Cargada en Buenos Aires el 12/03 a las 22:30, ya 13/03 en UTC
+        let cargada = Utc.with_ymd_and_hms(2024, 3, 13, 1, 30, 0).unwrap();
+        let mut ficha: Ficha = serde_json::from_value(serde_json::json!({
+            "id": Uuid::new_v4(),
+            "titulo": "Pérez c/ ACME",
+            "descripcion": "",
+            "hojas": [
+                { "contenido": "Téngase presente", "fecha": cargada },
+                { "contenido": "Inicio", "fecha": "2024-03-01" },
+            ],
+            "fecha_creacion": cargada,
+            "fecha_modificacion": cargada,
+        }))
+        .unwrap();
+
+        ficha.migrar_fechas(|momento| momento.with_timezone(&Buenos_Aires).date_naive());
+
+        let anterior = &ficha.hojas[0];
+        assert_eq!(anterior.fecha, NaiveDate::from_ymd_opt(2024, 3, 12).unwrap());
+        assert_eq!(anterior.registrada, cargada);
+        // Las hojas con día propio no se tocan
+        let con_dia = &ficha.hojas[1];
+        assert_eq!(con_dia.fecha, NaiveDate::from_ymd_opt(2024, 3, 1).unwrap());
+        assert_eq!(
+            con_dia.registrada,
+            Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap()
+        );
+
+        // Una vez migrada se guarda con el formato nuevo
+        let guardada = serde_json::to_value(anterior).unwrap();
+        assert_eq!(guardada["fecha"], "2024-03-12");
+        assert_eq!(guardada["registrada"], serde_json::json!(cargada));
+    }
 }
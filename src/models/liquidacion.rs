@@ -0,0 +1,129 @@
+use crate::models::cuenta::{self, Totales};
+use crate::models::{Asiento, Moneda};
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// Asiento incluido en una liquidación, con la ficha de la que proviene
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemLiquidacion {
+    pub ficha_id: Uuid,
+    pub ficha_titulo: String,
+    pub expediente: String,
+    pub asiento: Asiento,
+}
+
+/// Estado de cuenta numerado que se le entrega a un cliente
+#[derive(Debug, Clone, PartialEq)]
+pub struct Liquidacion {
+    pub numero: u32,
+    pub cliente: String,
+    pub fecha: NaiveDate,
+    pub items: Vec<ItemLiquidacion>,
+}
+
+impl Liquidacion {
+    /// Número con el formato que se imprime en los documentos
+    pub fn numero_formateado(&self) -> String {
+        format!("{:04}", self.numero)
+    }
+
+    pub fn totales(&self) -> BTreeMap<Moneda, Totales> {
+        cuenta::totales(self.items.iter().map(|item| &item.asiento))
+    }
+
+    /// Fichas afectadas, sin repetir y en el orden de los items
+    pub fn fichas(&self) -> Vec<Uuid> {
+        let mut fichas = Vec::new();
+        for item in &self.items {
+            if !fichas.contains(&item.ficha_id) {
+                fichas.push(item.ficha_id);
+            }
+        }
+        fichas
+    }
+
+    /// Contenido en CSV separado por `;`, como lo espera una planilla en español.
+    /// `emitida` es el momento de emisión ya formateado, con su zona horaria.
+    pub fn a_csv(&self, emitida: &str) -> String {
+        let mut csv = String::from(
+            "liquidacion;emitida;fecha;expediente;ficha;tipo;concepto;moneda;importe\n",
+        );
+        for item in &self.items {
+            let asiento = &item.asiento;
+            let signo = if asiento.tipo.es_cargo() { "" } else { "-" };
+            csv.push_str(&format!(
+                "{};{};{};{};{};{};{};{};{}{}\n",
+                self.numero_formateado(),
+                campo_csv(emitida),
+                asiento.fecha.format("%d/%m/%Y"),
+                campo_csv(&item.expediente),
+                campo_csv(&item.ficha_titulo),
+                asiento.tipo,
+                campo_csv(&asiento.concepto),
+                asiento.moneda,
+                signo,
+                asiento.importe
+            ));
+        }
+        csv
+    }
+
+    /// Líneas de texto del estado de cuenta, para imprimir
+    pub fn a_lineas(&self, emitida: &str) -> Vec<String> {
+        let mut lineas = vec![
+            format!("LIQUIDACIÓN N° {}", self.numero_formateado()),
+            format!("Cliente: {}", self.cliente),
+            format!("Fecha: {}", self.fecha.format("%d/%m/%Y")),
+            format!("Emitida: {}", emitida),
+            String::new(),
+        ];
+
+        for ficha_id in self.fichas() {
+            let items: Vec<_> = self
+                .items
+                .iter()
+                .filter(|i| i.ficha_id == ficha_id)
+                .collect();
+            if let Some(primero) = items.first() {
+                lineas.push(format!(
+                    "{} (Expte. {})",
+                    primero.ficha_titulo, primero.expediente
+                ));
+            }
+            for item in items {
+                let asiento = &item.asiento;
+                let signo = if asiento.tipo.es_cargo() { "" } else { "-" };
+                lineas.push(format!(
+                    "  {}  {:<10} {:<40} {}{} {}",
+                    asiento.fecha.format("%d/%m/%Y"),
+                    asiento.tipo.to_string(),
+                    asiento.concepto,
+                    signo,
+                    asiento.moneda,
+                    asiento.importe
+                ));
+            }
+            lineas.push(String::new());
+        }
+
+        for (moneda, totales) in self.totales() {
+            lineas.push(format!(
+                "Total {}: cargos {} - pagos {} = saldo {}",
+                moneda,
+                totales.cargos,
+                totales.pagos,
+                totales.saldo()
+            ));
+        }
+        lineas
+    }
+}
+
+fn campo_csv(valor: &str) -> String {
+    if valor.contains([';', '"', '\n']) {
+        format!("\"{}\"", valor.replace('"', "\"\""))
+    } else {
+        valor.to_string()
+    }
+}
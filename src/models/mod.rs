@@ -1,3 +1,29 @@
+pub mod adjunto;
+pub mod categoria;
+pub mod correo;
+pub mod cuenta;
 pub mod ficha;
+pub mod liquidacion;
+pub mod parte;
+pub mod plantilla;
+pub mod preferencias;
+pub mod sincronizacion;
+pub mod tarea;
+pub mod tiempo;
+pub mod usuario;
+pub mod vinculo;
 
-pub use ficha::Ficha;
+pub use adjunto::Adjunto;
+pub use categoria::CategoriaHoja;
+pub use correo::ReglaCorreo;
+pub use cuenta::{Asiento, Importe, Moneda, TipoAsiento};
+pub use ficha::{Estado, Ficha};
+pub use liquidacion::{ItemLiquidacion, Liquidacion};
+pub use parte::Parte;
+pub use plantilla::PlantillaHoja;
+pub use preferencias::{Idioma, Preferencias, Respaldo, Sincronizacion, Tema};
+pub use sincronizacion::{EstadoSincronizacion, ResultadoSincronizacion};
+pub use tarea::{Prioridad, Tarea};
+pub use tiempo::RegistroTiempo;
+pub use usuario::{Accion, Rol, Usuario};
+pub use vinculo::{TipoVinculo, Vinculo};
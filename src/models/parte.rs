@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+/// Parte interviniente en una causa (actor, demandado, tercero, etc.)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Parte {
+    pub rol: String,
+    pub nombre: String,
+}
+
+impl Parte {
+    /// Interpreta una lista con el formato `Rol: Nombre; Rol: Nombre`
+    pub fn parsear_lista(texto: &str) -> Vec<Parte> {
+        texto
+            .split(';')
+            .filter_map(|item| {
+                let (rol, nombre) = item.split_once(':')?;
+                let (rol, nombre) = (rol.trim(), nombre.trim());
+                if rol.is_empty() || nombre.is_empty() {
+                    return None;
+                }
+                Some(Parte {
+                    rol: rol.to_string(),
+                    nombre: nombre.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    /// Formatea la lista con el mismo formato que acepta `parsear_lista`
+    pub fn formatear_lista(partes: &[Parte]) -> String {
+        partes
+            .iter()
+            .map(|p| format!("{}: {}", p.rol, p.nombre))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
@@ -0,0 +1,83 @@
+use crate::models::Ficha;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+/// Texto reutilizable para nuevas hojas.
+///
+/// Admite los marcadores `{caratula}`, `{expediente}`, `{fecha}` y `{cliente}`,
+/// que se completan con los datos de la ficha al insertarla.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlantillaHoja {
+    pub id: Uuid,
+    pub nombre: String,
+    pub contenido: String,
+}
+
+impl PlantillaHoja {
+    pub fn new(nombre: String, contenido: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            nombre,
+            contenido,
+        }
+    }
+
+    /// Devuelve el contenido con los marcadores reemplazados por los datos de la ficha;
+    /// `{fecha}` se reemplaza por `fecha`, el día ya formateado en la zona configurada
+    ///
+    /// El reemplazo se hace en una sola pasada, así un dato que contiene un
+    /// marcador (una carátula con "{fecha}", por ejemplo) queda tal cual.
+    pub fn completar(&self, ficha: &Ficha, fecha: &str) -> String {
+        let marcadores = [
+            ("{caratula}", ficha.titulo.as_str()),
+            ("{expediente}", ficha.expediente.as_str()),
+            ("{cliente}", ficha.cliente.as_str()),
+            ("{fecha}", fecha),
+        ];
+        let mut resultado = String::with_capacity(self.contenido.len());
+        let mut resto = self.contenido.as_str();
+        while let Some(inicio) = resto.find('{') {
+            resultado.push_str(&resto[..inicio]);
+            resto = &resto[inicio..];
+            match marcadores.iter().find(|(m, _)| resto.starts_with(m)) {
+                Some((marcador, valor)) => {
+                    resultado.push_str(valor);
+                    resto = &resto[marcador.len()..];
+                }
+                None => {
+                    resultado.push('{');
+                    resto = &resto[1..];
+                }
+            }
+        }
+        resultado.push_str(resto);
+        resultado
+    }
+}
+
+impl fmt::Display for PlantillaHoja {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.nombre)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn completa_los_marcadores_en_una_pasada() {
+        let mut ficha = Ficha::de_prueba("CNT 12345/2023");
+        ficha.titulo = "Pérez c/ {cliente}".into();
+        ficha.cliente = "Juan {fecha}".into();
+        let plantilla = PlantillaHoja::new(
+            "Presentación".into(),
+            "Autos {caratula}, Expte. {expediente} ({cliente}), {fecha}. {otro} {".into(),
+        );
+        assert_eq!(
+            plantilla.completar(&ficha, "12/03/2024"),
+            "Autos Pérez c/ {cliente}, Expte. CNT 12345/2023 (Juan {fecha}), 12/03/2024. {otro} {"
+        );
+    }
+}
@@ -0,0 +1,355 @@
+use chrono::format::{Item, StrftimeItems};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
+
+/// Apariencia de la aplicación
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Tema {
+    Claro,
+    Oscuro,
+    /// Claro u oscuro según la configuración del sistema al iniciar
+    #[default]
+    Sistema,
+    AltoContraste,
+}
+
+impl Tema {
+    pub const TODOS: [Tema; 4] = [
+        Tema::Claro,
+        Tema::Oscuro,
+        Tema::Sistema,
+        Tema::AltoContraste,
+    ];
+}
+
+impl fmt::Display for Tema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Tema::Claro => write!(f, "Claro"),
+            Tema::Oscuro => write!(f, "Oscuro"),
+            Tema::Sistema => write!(f, "Según el sistema"),
+            Tema::AltoContraste => write!(f, "Alto contraste"),
+        }
+    }
+}
+
+/// Idioma de la interfaz
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Idioma {
+    #[default]
+    Es,
+    En,
+    Pt,
+}
+
+impl Idioma {
+    pub const TODOS: [Idioma; 3] = [Idioma::Es, Idioma::En, Idioma::Pt];
+}
+
+impl fmt::Display for Idioma {
+    /// Cada idioma con su propio nombre, para reconocerlo en cualquier otro
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Idioma::Es => write!(f, "Español"),
+            Idioma::En => write!(f, "English"),
+            Idioma::Pt => write!(f, "Português"),
+        }
+    }
+}
+
+/// Copias del archivo de datos que se hacen al abrir la aplicación
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Respaldo {
+    pub al_iniciar: bool,
+    pub directorio: PathBuf,
+    /// Copias que se conservan; al superarse se borran las más antiguas
+    pub conservar: usize,
+}
+
+impl Default for Respaldo {
+    fn default() -> Self {
+        Self {
+            al_iniciar: false,
+            directorio: PathBuf::from("respaldos"),
+            conservar: 10,
+        }
+    }
+}
+
+/// Consulta periódica de los movimientos de las fichas en un portal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Sincronizacion {
+    pub activa: bool,
+    /// Dirección de la consulta; `{expediente}`, `{numero}` y `{anio}` se
+    /// reemplazan por los datos de cada ficha
+    pub url: String,
+    pub intervalo_minutos: u32,
+}
+
+impl Default for Sincronizacion {
+    fn default() -> Self {
+        Self {
+            activa: false,
+            url: String::new(),
+            intervalo_minutos: 60,
+        }
+    }
+}
+
+/// Preferencias del usuario del equipo, guardadas aparte de los datos.
+/// Los campos que falten en el archivo toman el valor predeterminado, pero
+/// los desconocidos se rechazan para no ignorar errores de tipeo.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Preferencias {
+    /// Formato de `chrono` para las fechas con hora que se muestran
+    pub formato_fecha: String,
+    /// Nombre IANA de la zona horaria; vacía usa la del sistema
+    pub zona_horaria: String,
+    pub ruta_datos: PathBuf,
+    pub idioma: Idioma,
+    pub tema: Tema,
+    /// Factor de escala de toda la interfaz; 1 es el tamaño normal
+    pub escala: f64,
+    /// Tamaño de letra por debajo del cual no se muestra ningún texto
+    pub tamanio_minimo_texto: u16,
+    /// Tarjetas por fila en la lista; sin valor se calcula con el ancho
+    pub tarjetas_por_fila: Option<usize>,
+    /// Días sin hojas a partir de los cuales el inicio señala una causa
+    pub dias_sin_movimiento: u32,
+    /// Días hacia adelante en que el inicio muestra los vencimientos
+    pub dias_aviso_vencimientos: u32,
+    pub respaldo: Respaldo,
+    pub sincronizacion: Sincronizacion,
+}
+
+impl Default for Preferencias {
+    fn default() -> Self {
+        Self {
+            formato_fecha: String::from("%d/%m/%Y %H:%M"),
+            zona_horaria: String::new(),
+            ruta_datos: PathBuf::from("datos_procuracion.json"),
+            idioma: Idioma::default(),
+            tema: Tema::default(),
+            escala: 1.0,
+            tamanio_minimo_texto: 10,
+            tarjetas_por_fila: None,
+            dias_sin_movimiento: 30,
+            dias_aviso_vencimientos: 7,
+            respaldo: Respaldo::default(),
+            sincronizacion: Sincronizacion::default(),
+        }
+    }
+}
+
+impl Preferencias {
+    pub const MAXIMO_TARJETAS_POR_FILA: usize = 8;
+    pub const ESCALA_MINIMA: f64 = 0.5;
+    pub const ESCALA_MAXIMA: f64 = 3.0;
+    /// Los textos comunes miden 16; un mínimo mayor se logra con la escala
+    pub const TAMANIOS_MINIMOS: std::ops::RangeInclusive<u16> = 8..=16;
+    /// Para no sobrecargar los portales con consultas
+    pub const INTERVALO_MINIMO_SINCRONIZACION: u32 = 15;
+
+    /// Estas preferencias con las de visualización de `otras`: formato de
+    /// fecha, zona horaria, idioma, tema, escala, tamaño mínimo de letra y
+    /// tarjetas por fila. Las del espacio de trabajo (archivo de datos,
+    /// avisos, respaldos y sincronización) quedan como están.
+    pub fn con_visualizacion_de(&self, otras: &Preferencias) -> Preferencias {
+        Preferencias {
+            formato_fecha: otras.formato_fecha.clone(),
+            zona_horaria: otras.zona_horaria.clone(),
+            idioma: otras.idioma,
+            tema: otras.tema,
+            escala: otras.escala,
+            tamanio_minimo_texto: otras.tamanio_minimo_texto,
+            tarjetas_por_fila: otras.tarjetas_por_fila,
+            ..self.clone()
+        }
+    }
+
+    /// Problemas de las preferencias, uno por campo; vacío si son válidas
+    pub fn validar(&self) -> Vec<String> {
+        let mut errores = Vec::new();
+
+        if self.formato_fecha.trim().is_empty() {
+            errores.push("El formato de fecha no puede estar vacío".to_string());
+        } else if StrftimeItems::new(&self.formato_fecha).any(|item| item == Item::Error) {
+            errores.push(format!(
+                "El formato de fecha «{}» no es válido (ejemplo: %d/%m/%Y %H:%M)",
+                self.formato_fecha
+            ));
+        }
+
+        let zona = self.zona_horaria.trim();
+        if !zona.is_empty() && zona.parse::<chrono_tz::Tz>().is_err() {
+            errores.push(format!(
+                "Zona horaria desconocida «{}»; use un nombre como {}",
+                zona, "America/Argentina/Buenos_Aires"
+            ));
+        }
+
+        if self.ruta_datos.as_os_str().is_empty() {
+            errores.push("Indique el archivo de datos".to_string());
+        }
+
+        if !(Self::ESCALA_MINIMA..=Self::ESCALA_MAXIMA).contains(&self.escala) {
+            errores.push(format!(
+                "La escala debe ser entre {} y {}",
+                Self::ESCALA_MINIMA,
+                Self::ESCALA_MAXIMA
+            ));
+        }
+
+        if !Self::TAMANIOS_MINIMOS.contains(&self.tamanio_minimo_texto) {
+            errores.push(format!(
+                "El tamaño mínimo de letra debe ser entre {} y {}",
+                Self::TAMANIOS_MINIMOS.start(),
+                Self::TAMANIOS_MINIMOS.end()
+            ));
+        }
+
+        if let Some(cantidad) = self.tarjetas_por_fila {
+            if !(1..=Self::MAXIMO_TARJETAS_POR_FILA).contains(&cantidad) {
+                errores.push(format!(
+                    "Las tarjetas por fila deben ser entre 1 y {}",
+                    Self::MAXIMO_TARJETAS_POR_FILA
+                ));
+            }
+        }
+
+        if self.dias_sin_movimiento == 0 {
+            errores.push("Los días sin movimiento deben ser al menos 1".to_string());
+        }
+
+        if self.respaldo.al_iniciar {
+            if self.respaldo.directorio.as_os_str().is_empty() {
+                errores.push("Indique el directorio de respaldos".to_string());
+            }
+            if self.respaldo.conservar == 0 {
+                errores.push("Se debe conservar al menos un respaldo".to_string());
+            }
+        }
+
+        let sincronizacion = &self.sincronizacion;
+        if sincronizacion.activa {
+            let url = sincronizacion.url.trim();
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                errores.push(
+                    "La dirección del portal debe empezar con http:// o https://".to_string(),
+                );
+            } else if !["{expediente}", "{numero}"].iter().any(|m| url.contains(m)) {
+                errores.push(
+                    "La dirección del portal debe incluir {expediente} o {numero}".to_string(),
+                );
+            }
+            if sincronizacion.intervalo_minutos < Self::INTERVALO_MINIMO_SINCRONIZACION {
+                errores.push(format!(
+                    "El intervalo de sincronización debe ser de al menos {} minutos",
+                    Self::INTERVALO_MINIMO_SINCRONIZACION
+                ));
+            }
+        }
+
+        errores
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Valida las predeterminadas con un cambio y devuelve los errores
+    fn errores(cambio: impl FnOnce(&mut Preferencias)) -> Vec<String> {
+        let mut preferencias = Preferencias::default();
+        cambio(&mut preferencias);
+        preferencias.validar()
+    }
+
+    #[test]
+    fn las_predeterminadas_son_validas() {
+        assert!(Preferencias::default().validar().is_empty());
+    }
+
+    #[test]
+    fn rechaza_formatos_de_fecha_invalidos_y_zonas_desconocidas() {
+        let [error] = errores(|p| p.formato_fecha = "%d/%m/%Y %Q".into()).try_into().unwrap();
+        assert!(error.contains("%Q"), "{}", error);
+        assert_eq!(errores(|p| p.formato_fecha = " ".into()).len(), 1);
+
+        let [error] = errores(|p| p.zona_horaria = "America/Cordoba_Capital".into())
+            .try_into()
+            .unwrap();
+        assert!(error.contains("America/Cordoba_Capital"), "{}", error);
+        assert!(errores(|p| p.zona_horaria = "America/Argentina/Cordoba".into()).is_empty());
+    }
+
+    #[test]
+    fn rechaza_escala_y_tamanio_de_letra_fuera_de_rango() {
+        assert_eq!(errores(|p| p.escala = 0.4).len(), 1);
+        assert_eq!(errores(|p| p.escala = 3.5).len(), 1);
+        assert_eq!(errores(|p| p.escala = f64::NAN).len(), 1);
+        assert!(errores(|p| p.escala = 3.0).is_empty());
+
+        assert_eq!(errores(|p| p.tamanio_minimo_texto = 7).len(), 1);
+        assert_eq!(errores(|p| p.tamanio_minimo_texto = 17).len(), 1);
+        assert!(errores(|p| p.tamanio_minimo_texto = 16).is_empty());
+    }
+
+    #[test]
+    fn la_sincronizacion_activa_exige_direccion_con_marcador_e_intervalo_minimo() {
+        let activa = |url: &str, intervalo_minutos: u32| {
+            errores(|p| {
+                p.sincronizacion = Sincronizacion {
+                    activa: true,
+                    url: url.into(),
+                    intervalo_minutos,
+                }
+            })
+        };
+
+        assert!(activa("https://portal.example/causas?exp={expediente}", 15).is_empty());
+        let [error] = activa("https://portal.example/causas", 60).try_into().unwrap();
+        assert!(error.contains("{expediente}"), "{}", error);
+        let [error] = activa("portal.example/{numero}", 60).try_into().unwrap();
+        assert!(error.contains("http://"), "{}", error);
+        let [error] = activa("https://portal.example/{numero}/{anio}", 14)
+            .try_into()
+            .unwrap();
+        assert!(error.contains("15 minutos"), "{}", error);
+
+        // Desactivada, no importa lo que tenga
+        assert!(errores(|p| p.sincronizacion.intervalo_minutos = 1).is_empty());
+    }
+
+    #[test]
+    fn la_visualizacion_no_toca_el_espacio_de_trabajo() {
+        let vigentes = Preferencias::default();
+        let mut pedidas = Preferencias {
+            idioma: Idioma::En,
+            tema: Tema::AltoContraste,
+            escala: 1.5,
+            tamanio_minimo_texto: 14,
+            ruta_datos: PathBuf::from("otro.json"),
+            dias_sin_movimiento: 1,
+            ..Preferencias::default()
+        };
+        pedidas.respaldo.al_iniciar = true;
+        pedidas.sincronizacion.activa = true;
+
+        let resultado = vigentes.con_visualizacion_de(&pedidas);
+        assert_eq!(resultado.idioma, Idioma::En);
+        assert_eq!(resultado.tema, Tema::AltoContraste);
+        assert_eq!(resultado.escala, 1.5);
+        assert_eq!(resultado.tamanio_minimo_texto, 14);
+        assert_eq!(resultado.ruta_datos, vigentes.ruta_datos);
+        assert_eq!(resultado.dias_sin_movimiento, vigentes.dias_sin_movimiento);
+        assert_eq!(resultado.respaldo, vigentes.respaldo);
+        assert_eq!(resultado.sincronizacion, vigentes.sincronizacion);
+    }
+}
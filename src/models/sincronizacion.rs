@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Resultado de la última consulta de la ficha en el portal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EstadoSincronizacion {
+    pub momento: DateTime<Utc>,
+    pub resultado: ResultadoSincronizacion,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResultadoSincronizacion {
+    /// Consulta respondida; `nuevas` son las hojas que se agregaron
+    Correcta {
+        nuevas: usize,
+    },
+    Fallida {
+        error: String,
+    },
+}
+
+impl EstadoSincronizacion {
+    pub fn correcta(nuevas: usize) -> Self {
+        Self {
+            momento: Utc::now(),
+            resultado: ResultadoSincronizacion::Correcta { nuevas },
+        }
+    }
+
+    pub fn fallida(error: String) -> Self {
+        Self {
+            momento: Utc::now(),
+            resultado: ResultadoSincronizacion::Fallida { error },
+        }
+    }
+}
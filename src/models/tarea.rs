@@ -0,0 +1,99 @@
+use crate::models::Ficha;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::fmt;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Prioridad {
+    Baja,
+    #[default]
+    Media,
+    Alta,
+}
+
+impl Prioridad {
+    pub const TODAS: [Prioridad; 3] = [Prioridad::Alta, Prioridad::Media, Prioridad::Baja];
+}
+
+impl fmt::Display for Prioridad {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Prioridad::Baja => write!(f, "Baja"),
+            Prioridad::Media => write!(f, "Media"),
+            Prioridad::Alta => write!(f, "Alta"),
+        }
+    }
+}
+
+/// Algo que hay que hacer en la ficha: presentar un escrito, llamar al cliente...
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tarea {
+    pub id: Uuid,
+    pub descripcion: String,
+    pub vencimiento: Option<NaiveDate>,
+    pub prioridad: Prioridad,
+    pub completada: bool,
+    pub responsable: String,
+}
+
+impl Tarea {
+    pub fn new(
+        descripcion: String,
+        vencimiento: Option<NaiveDate>,
+        prioridad: Prioridad,
+        responsable: String,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            descripcion,
+            vencimiento,
+            prioridad,
+            completada: false,
+            responsable,
+        }
+    }
+
+    pub fn esta_vencida(&self, hoy: NaiveDate) -> bool {
+        !self.completada && self.vencimiento.is_some_and(|v| v < hoy)
+    }
+
+    /// Clave para ordenar por vencimiento (las que no tienen van al final) y
+    /// luego por prioridad, de mayor a menor
+    pub fn orden(&self) -> (bool, Option<NaiveDate>, Reverse<Prioridad>) {
+        (
+            self.vencimiento.is_none(),
+            self.vencimiento,
+            Reverse(self.prioridad),
+        )
+    }
+}
+
+/// Tarea pendiente con los datos de su ficha, para la lista global
+#[derive(Debug, Clone)]
+pub struct TareaPendiente {
+    pub ficha_id: Uuid,
+    pub ficha_titulo: String,
+    pub tarea: Tarea,
+}
+
+/// Tareas sin completar de todas las fichas, ordenadas por vencimiento
+pub fn pendientes(fichas: &[Ficha]) -> Vec<TareaPendiente> {
+    let mut pendientes: Vec<TareaPendiente> = fichas
+        .iter()
+        .flat_map(|ficha| {
+            ficha
+                .tareas
+                .iter()
+                .filter(|t| !t.completada)
+                .map(move |tarea| TareaPendiente {
+                    ficha_id: ficha.id,
+                    ficha_titulo: ficha.titulo.clone(),
+                    tarea: tarea.clone(),
+                })
+        })
+        .collect();
+    pendientes.sort_by_key(|p| p.tarea.orden());
+    pendientes
+}
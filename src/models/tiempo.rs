@@ -0,0 +1,159 @@
+use crate::models::Ficha;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// Tiempo dedicado a una ficha, cargado a mano o con el cronómetro
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegistroTiempo {
+    pub id: Uuid,
+    pub fecha: NaiveDate,
+    pub minutos: u32,
+    pub descripcion: String,
+}
+
+impl RegistroTiempo {
+    pub fn new(fecha: NaiveDate, minutos: u32, descripcion: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            fecha,
+            minutos,
+            descripcion,
+        }
+    }
+
+    pub fn en_rango(&self, desde: NaiveDate, hasta: NaiveDate) -> bool {
+        self.fecha >= desde && self.fecha <= hasta
+    }
+}
+
+/// Duración máxima de un registro: un día
+pub const MAXIMO_MINUTOS: u32 = 24 * 60;
+
+/// Formatea minutos como `2 h 05 min`
+pub fn formatear_minutos(minutos: u32) -> String {
+    if minutos < 60 {
+        format!("{} min", minutos)
+    } else {
+        format!("{} h {:02} min", minutos / 60, minutos % 60)
+    }
+}
+
+/// Formatea segundos como `hh:mm:ss`, para el cronómetro en curso
+pub fn formatear_segundos(segundos: i64) -> String {
+    let segundos = segundos.max(0);
+    format!(
+        "{:02}:{:02}:{:02}",
+        segundos / 3600,
+        (segundos % 3600) / 60,
+        segundos % 60
+    )
+}
+
+/// Interpreta duraciones como `90` (minutos), `1:30` o `1h30`. Con horas,
+/// los minutos van de 0 a 59; el total no puede pasar de `MAXIMO_MINUTOS`.
+pub fn parsear_duracion(texto: &str) -> Option<u32> {
+    let texto = texto.trim().to_lowercase().replace(' ', "");
+    let (horas, minutos, con_horas) = match texto.split_once([':', 'h']) {
+        Some((horas, minutos)) => (
+            horas,
+            minutos.trim_end_matches("min").trim_end_matches('m'),
+            true,
+        ),
+        None => ("0", texto.trim_end_matches("min").trim_end_matches('m'), false),
+    };
+
+    let horas: u32 = if horas.is_empty() {
+        0
+    } else {
+        horas.parse().ok()?
+    };
+    let minutos: u32 = if minutos.is_empty() {
+        0
+    } else {
+        minutos.parse().ok()?
+    };
+    if con_horas && minutos >= 60 {
+        return None;
+    }
+    let total = horas.checked_mul(60)?.checked_add(minutos)?;
+    (1..=MAXIMO_MINUTOS).contains(&total).then_some(total)
+}
+
+/// Minutos registrados en el rango, por ficha, ordenados de mayor a menor
+pub fn minutos_por_ficha(
+    fichas: &[Ficha],
+    desde: NaiveDate,
+    hasta: NaiveDate,
+) -> Vec<(String, u32)> {
+    let mut totales: Vec<(String, u32)> = fichas
+        .iter()
+        .map(|f| (f.titulo.clone(), f.minutos_en(desde, hasta)))
+        .filter(|(_, minutos)| *minutos > 0)
+        .collect();
+    totales.sort_by_key(|(_, minutos)| Reverse(*minutos));
+    totales
+}
+
+/// Minutos registrados en el rango, por cliente de la ficha
+pub fn minutos_por_cliente(
+    fichas: &[Ficha],
+    desde: NaiveDate,
+    hasta: NaiveDate,
+) -> BTreeMap<String, u32> {
+    let mut totales = BTreeMap::new();
+    for ficha in fichas {
+        let minutos = ficha.minutos_en(desde, hasta);
+        if minutos > 0 {
+            let total = totales.entry(ficha.cliente.clone()).or_insert(0u32);
+            *total = total.saturating_add(minutos);
+        }
+    }
+    totales
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsea_minutos_sueltos_y_horas_con_minutos() {
+        assert_eq!(parsear_duracion("90"), Some(90));
+        assert_eq!(parsear_duracion("45 min"), Some(45));
+        assert_eq!(parsear_duracion("1:30"), Some(90));
+        assert_eq!(parsear_duracion("2h05"), Some(125));
+        assert_eq!(parsear_duracion("3h"), Some(180));
+        assert_eq!(parsear_duracion("0"), None);
+    }
+
+    #[test]
+    fn rechaza_minutos_de_mas_despues_de_las_horas() {
+        assert_eq!(parsear_duracion("1:75"), None);
+        assert_eq!(parsear_duracion("1h60"), None);
+        assert_eq!(parsear_duracion("1:59"), Some(119));
+    }
+
+    #[test]
+    fn rechaza_duraciones_de_mas_de_un_dia() {
+        assert_eq!(parsear_duracion("1440"), Some(MAXIMO_MINUTOS));
+        assert_eq!(parsear_duracion("24:00"), Some(MAXIMO_MINUTOS));
+        assert_eq!(parsear_duracion("1441"), None);
+        assert_eq!(parsear_duracion("24h01"), None);
+        assert_eq!(parsear_duracion("4294967295"), None);
+        assert_eq!(parsear_duracion("99999999999h"), None);
+    }
+
+    #[test]
+    fn los_totales_no_desbordan() {
+        let hoy = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+        let mut ficha = Ficha::new("Pérez".into(), String::new(), String::new(), hoy, None);
+        ficha.cliente = "Pérez".into();
+        ficha.tiempos = vec![RegistroTiempo::new(hoy, u32::MAX, String::new()); 2];
+        let otra = ficha.clone();
+
+        assert_eq!(ficha.minutos_en(hoy, hoy), u32::MAX);
+        assert_eq!(minutos_por_cliente(&[ficha, otra], hoy, hoy)["Pérez"], u32::MAX);
+    }
+}
@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+/// Acciones sujetas a permisos
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Accion {
+    /// Crear y editar fichas, hojas, tareas y tiempos
+    EditarFichas,
+    EliminarFichas,
+    /// Registrar gastos, honorarios y pagos, y emitir liquidaciones
+    EditarCuenta,
+    /// Administrar plantillas y etiquetas compartidas
+    Configurar,
+    GestionarUsuarios,
+    /// Consultar y verificar el registro de auditoría
+    VerAuditoria,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rol {
+    Admin,
+    Abogado,
+    #[default]
+    Procurador,
+    SoloLectura,
+}
+
+impl Rol {
+    pub const TODOS: [Rol; 4] = [Rol::Admin, Rol::Abogado, Rol::Procurador, Rol::SoloLectura];
+
+    pub fn puede(&self, accion: Accion) -> bool {
+        match self {
+            Rol::Admin => true,
+            Rol::Abogado => accion != Accion::GestionarUsuarios,
+            Rol::Procurador => accion == Accion::EditarFichas,
+            Rol::SoloLectura => false,
+        }
+    }
+}
+
+impl fmt::Display for Rol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rol::Admin => write!(f, "Administrador"),
+            Rol::Abogado => write!(f, "Abogado"),
+            Rol::Procurador => write!(f, "Procurador"),
+            Rol::SoloLectura => write!(f, "Solo lectura"),
+        }
+    }
+}
+
+/// Persona que usa el sistema; se registra como autora de hojas y fichas
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Usuario {
+    pub id: Uuid,
+    pub nombre: String,
+    pub iniciales: String,
+    pub color: [u8; 3],
+    #[serde(default)]
+    pub rol: Rol,
+}
+
+impl Usuario {
+    /// Si no se indican iniciales se toman de las palabras del nombre
+    pub fn new(nombre: String, iniciales: String, color: [u8; 3], rol: Rol) -> Self {
+        let iniciales = match iniciales.trim() {
+            "" => iniciales_de(&nombre),
+            iniciales => iniciales.to_uppercase(),
+        };
+        Self {
+            id: Uuid::new_v4(),
+            nombre,
+            iniciales,
+            color,
+            rol,
+        }
+    }
+}
+
+impl fmt::Display for Usuario {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.nombre)
+    }
+}
+
+/// Primera letra de hasta tres palabras del nombre, en mayúsculas
+pub fn iniciales_de(nombre: &str) -> String {
+    nombre
+        .split_whitespace()
+        .filter_map(|palabra| palabra.chars().next())
+        .take(3)
+        .collect::<String>()
+        .to_uppercase()
+}
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+/// Relación de otra ficha con la que la contiene, vista desde esta última:
+/// un vínculo `Incidente` a B en la ficha A indica que B es incidente de A.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TipoVinculo {
+    Principal,
+    Incidente,
+    Apelacion,
+    /// Causa cuya resolución se apeló en la ficha vinculada
+    Apelada,
+    Conexo,
+    Acumulado,
+}
+
+impl TipoVinculo {
+    /// Tipos que se ofrecen al vincular; cada uno tiene su inverso
+    pub const TODOS: [TipoVinculo; 6] = [
+        TipoVinculo::Principal,
+        TipoVinculo::Incidente,
+        TipoVinculo::Apelacion,
+        TipoVinculo::Apelada,
+        TipoVinculo::Conexo,
+        TipoVinculo::Acumulado,
+    ];
+
+    /// Tipo con que se registra el vínculo en la otra ficha
+    pub fn inverso(&self) -> TipoVinculo {
+        match self {
+            TipoVinculo::Principal => TipoVinculo::Incidente,
+            TipoVinculo::Incidente => TipoVinculo::Principal,
+            TipoVinculo::Apelacion => TipoVinculo::Apelada,
+            TipoVinculo::Apelada => TipoVinculo::Apelacion,
+            TipoVinculo::Conexo => TipoVinculo::Conexo,
+            TipoVinculo::Acumulado => TipoVinculo::Acumulado,
+        }
+    }
+}
+
+impl fmt::Display for TipoVinculo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TipoVinculo::Principal => write!(f, "Principal"),
+            TipoVinculo::Incidente => write!(f, "Incidente"),
+            TipoVinculo::Apelacion => write!(f, "Apelación"),
+            TipoVinculo::Apelada => write!(f, "Causa apelada"),
+            TipoVinculo::Conexo => write!(f, "Conexo"),
+            TipoVinculo::Acumulado => write!(f, "Acumulado"),
+        }
+    }
+}
+
+/// Vínculo de una ficha con otra
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Vinculo {
+    pub ficha_id: Uuid,
+    pub tipo: TipoVinculo,
+}
@@ -0,0 +1,228 @@
+//! Fuente que consulta un portal por HTTP. La dirección se configura con
+//! una plantilla, de modo que sirve para cualquier portal que publique la
+//! causa en una dirección fija, y también para un servidor de prueba.
+
+use super::{ErrorSincronizacion, FuenteMovimientos};
+use crate::importacion::{self, ClaveExpediente, Formato, Movimiento};
+use std::io::Read;
+use std::time::Duration;
+
+/// Espera máxima por cada consulta
+const TIEMPO_MAXIMO: Duration = Duration::from_secs(30);
+/// Las páginas de movimientos más largas no llegan a unos cientos de KB
+const TAMANIO_MAXIMO: u64 = 10 * 1024 * 1024;
+
+pub struct FuenteHttp {
+    plantilla: String,
+    agente: ureq::Agent,
+}
+
+impl FuenteHttp {
+    /// `plantilla` es la dirección de la consulta, con `{expediente}`,
+    /// `{numero}` y `{anio}` en lugar de los datos de la causa
+    pub fn new(plantilla: &str) -> Self {
+        let agente = ureq::AgentBuilder::new()
+            .timeout(TIEMPO_MAXIMO)
+            .user_agent(concat!("fichas_procuracion/", env!("CARGO_PKG_VERSION")))
+            .build();
+        Self {
+            plantilla: plantilla.trim().to_string(),
+            agente,
+        }
+    }
+
+    /// Dirección de la consulta de un expediente
+    pub fn url(&self, expediente: &ClaveExpediente) -> String {
+        self.plantilla
+            .replace("{expediente}", &codificar(&expediente.to_string()))
+            .replace("{numero}", &expediente.numero.to_string())
+            .replace("{anio}", &expediente.anio.to_string())
+    }
+}
+
+impl FuenteMovimientos for FuenteHttp {
+    fn movimientos(
+        &self,
+        expediente: &ClaveExpediente,
+    ) -> Result<Vec<Movimiento>, ErrorSincronizacion> {
+        let respuesta = match self.agente.get(&self.url(expediente)).call() {
+            Ok(respuesta) => respuesta,
+            Err(ureq::Error::Status(codigo, _)) => {
+                return Err(ErrorSincronizacion::Respuesta(codigo))
+            }
+            Err(e) => return Err(ErrorSincronizacion::Conexion(e.to_string())),
+        };
+
+        let formato = Formato::de_tipo_mime(respuesta.content_type());
+        let mut bytes = Vec::new();
+        respuesta
+            .into_reader()
+            .take(TAMANIO_MAXIMO)
+            .read_to_end(&mut bytes)
+            .map_err(|e| ErrorSincronizacion::Conexion(e.to_string()))?;
+
+        importacion::parsear(&importacion::decodificar(bytes), formato, Some(expediente))
+            .map_err(ErrorSincronizacion::Formato)
+    }
+}
+
+/// Codifica el texto para usarlo dentro de una dirección (RFC 3986)
+fn codificar(texto: &str) -> String {
+    texto
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                char::from(b).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sincronizacion::{consultar, Consulta};
+    use chrono::NaiveDate;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::{SocketAddr, TcpListener};
+    use std::thread;
+    use uuid::Uuid;
+
+    /// Respuestas grabadas de los portales, con la línea de estado y los
+    /// encabezados tal como llegaron
+    const GRABACIONES: [(&str, &str); 3] = [
+        (
+            "/consulta?causa=CNT%2012345%2F2023",
+            include_str!("fixtures/pjn_cnt_12345_2023.http"),
+        ),
+        (
+            "/consulta?causa=LP%2098765%2F2021",
+            include_str!("fixtures/mev_lp_98765_2021.http"),
+        ),
+        (
+            "/consulta?causa=CIV%2045678%2F2022",
+            include_str!("fixtures/pjn_no_disponible.http"),
+        ),
+    ];
+
+    /// Servidor local que responde cada pedido con la grabación de su
+    /// dirección, o con 404 si no hay ninguna
+    fn servidor_simulado() -> SocketAddr {
+        let escucha = TcpListener::bind("127.0.0.1:0").unwrap();
+        let direccion = escucha.local_addr().unwrap();
+        thread::spawn(move || {
+            for conexion in escucha.incoming() {
+                let Ok(mut conexion) = conexion else {
+                    continue;
+                };
+                let mut lector = BufReader::new(&conexion);
+                let mut pedido = String::new();
+                lector.read_line(&mut pedido).unwrap();
+                // Se descartan los encabezados del pedido
+                let mut linea = String::new();
+                while lector.read_line(&mut linea).unwrap() > 2 {
+                    linea.clear();
+                }
+
+                let ruta = pedido.split_whitespace().nth(1).unwrap_or_default();
+                let grabacion = GRABACIONES
+                    .iter()
+                    .find(|(grabada, _)| *grabada == ruta)
+                    .map_or("HTTP/1.1 404 Not Found\n\n", |(_, respuesta)| respuesta);
+                conexion.write_all(&a_http(grabacion)).unwrap();
+            }
+        });
+        direccion
+    }
+
+    /// Las grabaciones se guardan con saltos de línea simples; el protocolo
+    /// pide CRLF en los encabezados
+    fn a_http(grabacion: &str) -> Vec<u8> {
+        let (encabezados, cuerpo) = grabacion.split_once("\n\n").unwrap_or((grabacion, ""));
+        let mut respuesta = encabezados.replace('\n', "\r\n").into_bytes();
+        respuesta.extend_from_slice(b"\r\nConnection: close\r\n\r\n");
+        respuesta.extend_from_slice(cuerpo.as_bytes());
+        respuesta
+    }
+
+    fn fuente() -> FuenteHttp {
+        let direccion = servidor_simulado();
+        FuenteHttp::new(&format!(
+            "http://{}/consulta?causa={{expediente}}",
+            direccion
+        ))
+    }
+
+    fn clave(texto: &str) -> ClaveExpediente {
+        ClaveExpediente::extraer(texto).unwrap()
+    }
+
+    #[test]
+    fn reemplaza_los_datos_del_expediente() {
+        let fuente = FuenteHttp::new("https://portal/causa/{anio}/{numero}?c={expediente}");
+        assert_eq!(
+            fuente.url(&clave("CNT 012345/2023")),
+            "https://portal/causa/2023/12345?c=CNT%2012345%2F2023"
+        );
+    }
+
+    #[test]
+    fn pagina_del_pjn() {
+        let movimientos = fuente().movimientos(&clave("CNT 12345/2023")).unwrap();
+        assert_eq!(movimientos.len(), 2);
+        assert_eq!(
+            movimientos[0].fecha,
+            NaiveDate::from_ymd_opt(2024, 3, 12).unwrap()
+        );
+        assert_eq!(
+            movimientos[0].texto,
+            "FIRMA DESPACHO: SE PROVEE - Téngase presente"
+        );
+    }
+
+    #[test]
+    fn listado_csv_de_la_mev_sin_expediente() {
+        // La respuesta no repite el expediente; se usa el consultado
+        let consultado = clave("LP 98765/2021");
+        let movimientos = fuente().movimientos(&consultado).unwrap();
+        assert_eq!(movimientos.len(), 2);
+        assert!(movimientos.iter().all(|m| m.expediente == consultado));
+        assert_eq!(movimientos[1].texto, "TRASLADO DE LA DEMANDA");
+    }
+
+    #[test]
+    fn errores_del_portal() {
+        let fuente = fuente();
+        assert!(matches!(
+            fuente.movimientos(&clave("CIV 45678/2022")),
+            Err(ErrorSincronizacion::Respuesta(503))
+        ));
+        assert!(matches!(
+            fuente.movimientos(&clave("CIV 1/2000")),
+            Err(ErrorSincronizacion::Respuesta(404))
+        ));
+
+        let sin_servidor = FuenteHttp::new("http://127.0.0.1:9/{expediente}");
+        assert!(matches!(
+            sin_servidor.movimientos(&clave("CIV 1/2000")),
+            Err(ErrorSincronizacion::Conexion(_))
+        ));
+    }
+
+    #[test]
+    fn consulta_cada_ficha_por_separado() {
+        let consultas: Vec<Consulta> = ["CNT 12345/2023", "CIV 45678/2022"]
+            .iter()
+            .map(|expediente| Consulta {
+                ficha_id: Uuid::new_v4(),
+                expediente: clave(expediente),
+            })
+            .collect();
+
+        let respuestas = consultar(&fuente(), &consultas, Duration::ZERO);
+        assert_eq!(respuestas.len(), 2);
+        assert_eq!(respuestas[0].movimientos.as_ref().map(Vec::len), Ok(2));
+        assert!(respuestas[1].movimientos.is_err());
+    }
+}
@@ -0,0 +1,299 @@
+//! Consulta periódica de los movimientos de cada ficha en un portal
+//! judicial. Las fuentes implementan `FuenteMovimientos`; el `Planificador`
+//! decide cuándo toca consultar y `registrar` vuelca lo obtenido en la ficha.
+
+pub mod http;
+
+pub use http::FuenteHttp;
+
+use crate::importacion::{self, ClaveExpediente, ErrorImportacion, Movimiento};
+use crate::models::{EstadoSincronizacion, Ficha};
+use std::fmt;
+use std::thread;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+#[derive(Debug)]
+pub enum ErrorSincronizacion {
+    /// No se pudo conectar o la conexión se cortó
+    Conexion(String),
+    /// El portal respondió con un código de error
+    Respuesta(u16),
+    /// La respuesta no tenía el formato esperado
+    Formato(ErrorImportacion),
+}
+
+impl fmt::Display for ErrorSincronizacion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorSincronizacion::Conexion(e) => write!(f, "sin conexión con el portal: {}", e),
+            ErrorSincronizacion::Respuesta(codigo) => {
+                write!(f, "el portal respondió con el código {}", codigo)
+            }
+            ErrorSincronizacion::Formato(e) => write!(f, "respuesta no reconocida: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ErrorSincronizacion {}
+
+/// Origen de los movimientos de un expediente, como un portal judicial
+pub trait FuenteMovimientos: Send {
+    /// Todos los movimientos publicados del expediente; los que ya están
+    /// cargados como hojas se descartan al registrarlos
+    fn movimientos(
+        &self,
+        expediente: &ClaveExpediente,
+    ) -> Result<Vec<Movimiento>, ErrorSincronizacion>;
+}
+
+/// Expediente de una ficha por consultar
+#[derive(Debug, Clone)]
+pub struct Consulta {
+    pub ficha_id: Uuid,
+    pub expediente: ClaveExpediente,
+}
+
+/// Respuesta de la fuente para una ficha; el error queda como texto para
+/// guardarlo en el estado de la ficha
+#[derive(Debug, Clone)]
+pub struct Respuesta {
+    pub ficha_id: Uuid,
+    pub movimientos: Result<Vec<Movimiento>, String>,
+}
+
+/// Fichas con un número de expediente reconocible
+pub fn consultas<'a>(fichas: impl IntoIterator<Item = &'a Ficha>) -> Vec<Consulta> {
+    fichas
+        .into_iter()
+        .filter_map(|ficha| {
+            ClaveExpediente::extraer(&ficha.expediente).map(|expediente| Consulta {
+                ficha_id: ficha.id,
+                expediente,
+            })
+        })
+        .collect()
+}
+
+/// Consulta los expedientes uno tras otro, con una pausa entre cada uno
+/// para no saturar el portal. Bloquea hasta terminar.
+pub fn consultar(
+    fuente: &dyn FuenteMovimientos,
+    consultas: &[Consulta],
+    pausa: Duration,
+) -> Vec<Respuesta> {
+    let mut respuestas = Vec::with_capacity(consultas.len());
+    for (i, consulta) in consultas.iter().enumerate() {
+        if i > 0 {
+            thread::sleep(pausa);
+        }
+        // Si la respuesta trae otras causas (incidentes, conexos), solo
+        // interesan los de la consultada
+        let movimientos = fuente
+            .movimientos(&consulta.expediente)
+            .map(|movimientos| {
+                movimientos
+                    .into_iter()
+                    .filter(|m| m.expediente.coincide(&consulta.expediente))
+                    .collect()
+            })
+            .map_err(|e| e.to_string());
+        respuestas.push(Respuesta {
+            ficha_id: consulta.ficha_id,
+            movimientos,
+        });
+    }
+    respuestas
+}
+
+/// Agrega a la ficha los movimientos nuevos de la respuesta y anota el
+/// resultado de la consulta. Devuelve cuántas hojas agregó.
+pub fn registrar(ficha: &mut Ficha, respuesta: &Respuesta, autor: Option<Uuid>) -> usize {
+    let (estado, nuevas) = match &respuesta.movimientos {
+        Ok(movimientos) => {
+            let movimientos: Vec<&Movimiento> = movimientos.iter().collect();
+            let nuevas = importacion::aplicar(ficha, &movimientos, autor);
+            (EstadoSincronizacion::correcta(nuevas), nuevas)
+        }
+        Err(error) => (EstadoSincronizacion::fallida(error.clone()), 0),
+    };
+    ficha.sincronizacion = Some(estado);
+    nuevas
+}
+
+/// Decide cuándo corresponde volver a consultar el portal. Nunca hay dos
+/// sincronizaciones en curso a la vez.
+#[derive(Debug)]
+pub struct Planificador {
+    intervalo: Duration,
+    ultima: Option<Instant>,
+    en_curso: bool,
+}
+
+impl Planificador {
+    pub fn new(intervalo: Duration) -> Self {
+        Self {
+            intervalo,
+            ultima: None,
+            en_curso: false,
+        }
+    }
+
+    pub fn cambiar_intervalo(&mut self, intervalo: Duration) {
+        self.intervalo = intervalo;
+    }
+
+    /// Si pasó el intervalo desde la última sincronización, o no hubo
+    /// ninguna todavía
+    pub fn toca(&self, ahora: Instant) -> bool {
+        !self.en_curso
+            && self
+                .ultima
+                .is_none_or(|ultima| ahora.duration_since(ultima) >= self.intervalo)
+    }
+
+    /// Marca el comienzo de una sincronización programada; el intervalo se
+    /// cuenta desde aquí. Devuelve `false` si ya había una en curso.
+    pub fn comenzar(&mut self, ahora: Instant) -> bool {
+        if !self.comenzar_manual() {
+            return false;
+        }
+        self.ultima = Some(ahora);
+        true
+    }
+
+    /// Como `comenzar`, para una consulta pedida por el usuario, que no
+    /// posterga la próxima programada
+    pub fn comenzar_manual(&mut self) -> bool {
+        if self.en_curso {
+            return false;
+        }
+        self.en_curso = true;
+        true
+    }
+
+    pub fn terminar(&mut self) {
+        self.en_curso = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ResultadoSincronizacion;
+    use chrono::NaiveDate;
+
+    /// Fuente que responde siempre lo mismo, sin red
+    struct FuenteFija(Result<Vec<Movimiento>, u16>);
+
+    impl FuenteMovimientos for FuenteFija {
+        fn movimientos(
+            &self,
+            _expediente: &ClaveExpediente,
+        ) -> Result<Vec<Movimiento>, ErrorSincronizacion> {
+            self.0.clone().map_err(ErrorSincronizacion::Respuesta)
+        }
+    }
+
+    fn movimiento(expediente: &str, texto: &str) -> Movimiento {
+        Movimiento {
+            expediente: ClaveExpediente::extraer(expediente).unwrap(),
+            fecha: NaiveDate::from_ymd_opt(2024, 3, 12).unwrap(),
+            texto: texto.into(),
+        }
+    }
+
+    #[test]
+    fn solo_se_consultan_las_fichas_con_expediente() {
+        let fichas = [
+            Ficha::de_prueba("  CNT 12345/2023  "),
+            Ficha::de_prueba(""),
+            Ficha::de_prueba("sin número"),
+            // Sin año, con un año imposible o con demasiadas cifras
+            Ficha::de_prueba("CNT 12345"),
+            Ficha::de_prueba("CNT 12345/1850"),
+            Ficha::de_prueba("CNT 1234567890/2023"),
+        ];
+        let consultas = consultas(&fichas);
+        assert_eq!(consultas.len(), 1);
+        assert_eq!(consultas[0].ficha_id, fichas[0].id);
+    }
+
+    #[test]
+    fn una_respuesta_vacia_o_de_otras_causas_no_agrega_hojas() {
+        let mut ficha = Ficha::de_prueba("CNT 12345/2023");
+        let consultas = consultas([&ficha]);
+        for movimientos in [
+            vec![],
+            vec![
+                movimiento("CNT 12345/2023/1", "Del incidente"),
+                movimiento("CIV 12345/2023", "Otro fuero"),
+            ],
+        ] {
+            let respuestas = consultar(&FuenteFija(Ok(movimientos)), &consultas, Duration::ZERO);
+            assert_eq!(registrar(&mut ficha, &respuestas[0], None), 0);
+            assert_eq!(
+                ficha.sincronizacion.as_ref().map(|e| &e.resultado),
+                Some(&ResultadoSincronizacion::Correcta { nuevas: 0 })
+            );
+        }
+        assert_eq!(ficha.obtener_hojas().len(), 1);
+    }
+
+    #[test]
+    fn registra_los_movimientos_nuevos_y_el_estado() {
+        let mut ficha = Ficha::de_prueba("CNT 12345/2023");
+        let fuente = FuenteFija(Ok(vec![
+            movimiento("CNT 12345/2023", "Se provee"),
+            movimiento("CNT 12345/2023/1", "Del incidente"),
+        ]));
+        let consultas = consultas([&ficha]);
+
+        let respuestas = consultar(&fuente, &consultas, Duration::ZERO);
+        assert_eq!(registrar(&mut ficha, &respuestas[0], None), 1);
+        assert_eq!(registrar(&mut ficha, &respuestas[0], None), 0);
+        assert_eq!(
+            ficha.sincronizacion.map(|e| e.resultado),
+            Some(ResultadoSincronizacion::Correcta { nuevas: 0 })
+        );
+    }
+
+    #[test]
+    fn el_error_queda_en_el_estado_sin_perder_lo_cargado() {
+        let mut ficha = Ficha::de_prueba("CNT 12345/2023");
+        let correcta = consultar(
+            &FuenteFija(Ok(vec![movimiento("CNT 12345/2023", "Se provee")])),
+            &consultas([&ficha]),
+            Duration::ZERO,
+        );
+        assert_eq!(registrar(&mut ficha, &correcta[0], None), 1);
+
+        let respuestas = consultar(&FuenteFija(Err(503)), &consultas([&ficha]), Duration::ZERO);
+        assert_eq!(registrar(&mut ficha, &respuestas[0], None), 0);
+        assert_eq!(ficha.obtener_hojas().len(), 2);
+        assert!(matches!(
+            ficha.sincronizacion.map(|e| e.resultado),
+            Some(ResultadoSincronizacion::Fallida { error }) if error.contains("503")
+        ));
+    }
+
+    #[test]
+    fn planificador_respeta_el_intervalo() {
+        let inicio = Instant::now();
+        let mut planificador = Planificador::new(Duration::from_secs(60));
+        assert!(planificador.toca(inicio));
+        assert!(planificador.comenzar(inicio));
+        assert!(!planificador.comenzar(inicio));
+        assert!(!planificador.toca(inicio + Duration::from_secs(120)));
+
+        planificador.terminar();
+        assert!(!planificador.toca(inicio + Duration::from_secs(59)));
+        assert!(planificador.toca(inicio + Duration::from_secs(60)));
+
+        // La consulta manual no corre el intervalo
+        assert!(planificador.comenzar_manual());
+        assert!(!planificador.toca(inicio + Duration::from_secs(60)));
+        planificador.terminar();
+        assert!(planificador.toca(inicio + Duration::from_secs(60)));
+    }
+}
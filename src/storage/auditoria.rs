@@ -0,0 +1,479 @@
+use crate::models::ficha::Hoja;
+use crate::models::{Ficha, Parte};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Operación registrada en la auditoría
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Operacion {
+    AgregarFicha,
+    ActualizarFicha,
+    EliminarFicha,
+    /// Hojas agregadas, modificadas o quitadas de una ficha existente
+    CambioHojas,
+}
+
+impl Operacion {
+    pub const TODAS: [Operacion; 4] = [
+        Operacion::AgregarFicha,
+        Operacion::ActualizarFicha,
+        Operacion::EliminarFicha,
+        Operacion::CambioHojas,
+    ];
+}
+
+impl fmt::Display for Operacion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operacion::AgregarFicha => write!(f, "Alta de ficha"),
+            Operacion::ActualizarFicha => write!(f, "Edición de ficha"),
+            Operacion::EliminarFicha => write!(f, "Baja de ficha"),
+            Operacion::CambioHojas => write!(f, "Cambio de hojas"),
+        }
+    }
+}
+
+/// Valor de un campo antes y después de la operación; `None` si no existía
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cambio {
+    pub campo: String,
+    pub antes: Option<String>,
+    pub despues: Option<String>,
+}
+
+/// Entrada del registro de auditoría.
+///
+/// Cada entrada incluye el hash de la anterior, de modo que modificar o
+/// quitar una entrada rompe la cadena a partir de ese punto.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistroAuditoria {
+    pub secuencia: u64,
+    pub fecha: DateTime<Utc>,
+    pub usuario: Option<Uuid>,
+    pub operacion: Operacion,
+    pub ficha_id: Uuid,
+    pub ficha_titulo: String,
+    pub cambios: Vec<Cambio>,
+    pub hash_anterior: String,
+    pub hash: String,
+}
+
+impl RegistroAuditoria {
+    /// SHA-256 del contenido de la entrada junto con el hash anterior
+    pub fn calcular_hash(&self) -> String {
+        let contenido = serde_json::to_vec(&(
+            self.secuencia,
+            &self.fecha,
+            &self.usuario,
+            &self.operacion,
+            &self.ficha_id,
+            &self.ficha_titulo,
+            &self.cambios,
+            &self.hash_anterior,
+        ))
+        .unwrap_or_default();
+        format!("{:x}", Sha256::digest(&contenido))
+    }
+}
+
+/// Resultado de verificar la cadena de hashes
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verificacion {
+    /// La cadena está completa; cantidad de entradas verificadas
+    Integra(usize),
+    /// Primera línea del archivo (desde 1) donde la cadena se rompe
+    Alterada { linea: usize, motivo: String },
+}
+
+/// Archivo de auditoría en formato JSON Lines, al que solo se agregan entradas
+#[derive(Debug, Default)]
+pub struct Auditoria {
+    ruta: PathBuf,
+    /// Secuencia y hash de la última entrada, leídos la primera vez que se escribe
+    ultima: Option<(u64, String)>,
+}
+
+/// Posición del registro de auditoría antes de un cambio
+#[derive(Debug)]
+pub struct Marca {
+    largo: u64,
+    ultima: Option<(u64, String)>,
+}
+
+impl Auditoria {
+    pub fn new(ruta: PathBuf) -> Self {
+        Self { ruta, ultima: None }
+    }
+
+    /// Agrega una entrada encadenada a la última y devuelve su hash
+    pub fn registrar(
+        &mut self,
+        usuario: Option<Uuid>,
+        operacion: Operacion,
+        ficha_id: Uuid,
+        ficha_titulo: &str,
+        cambios: Vec<Cambio>,
+    ) -> io::Result<String> {
+        let (secuencia, hash_anterior) = match self.ultima.take() {
+            Some(ultima) => ultima,
+            None => self
+                .leer()?
+                .last()
+                .map(|r| (r.secuencia, r.hash.clone()))
+                .unwrap_or_default(),
+        };
+
+        let mut registro = RegistroAuditoria {
+            secuencia: secuencia + 1,
+            fecha: Utc::now(),
+            usuario,
+            operacion,
+            ficha_id,
+            ficha_titulo: ficha_titulo.to_string(),
+            cambios,
+            hash_anterior,
+            hash: String::new(),
+        };
+        registro.hash = registro.calcular_hash();
+
+        let linea = serde_json::to_string(&registro)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if let Some(parent) = self.ruta.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut archivo = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.ruta)?;
+        writeln!(archivo, "{}", linea)?;
+
+        self.ultima = Some((registro.secuencia, registro.hash.clone()));
+        Ok(registro.hash)
+    }
+
+    /// Posición actual del registro, para volver a ella con `deshacer`
+    pub fn marca(&self) -> io::Result<Marca> {
+        let largo = match fs::metadata(&self.ruta) {
+            Ok(metadatos) => metadatos.len(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(e),
+        };
+        Ok(Marca {
+            largo,
+            ultima: self.ultima.clone(),
+        })
+    }
+
+    /// Quita las entradas agregadas después de `marca`
+    pub fn deshacer(&mut self, marca: Marca) -> io::Result<()> {
+        self.ultima = marca.ultima;
+        let largo = match fs::metadata(&self.ruta) {
+            Ok(metadatos) => metadatos.len(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        if largo > marca.largo {
+            OpenOptions::new()
+                .write(true)
+                .open(&self.ruta)?
+                .set_len(marca.largo)?;
+        }
+        Ok(())
+    }
+
+    /// Lee todas las entradas, en el orden en que se registraron
+    pub fn leer(&self) -> io::Result<Vec<RegistroAuditoria>> {
+        if !self.ruta.exists() {
+            return Ok(Vec::new());
+        }
+
+        fs::read_to_string(&self.ruta)?
+            .lines()
+            .filter(|linea| !linea.trim().is_empty())
+            .map(|linea| {
+                serde_json::from_str(linea)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            })
+            .collect()
+    }
+
+    /// Recorre el archivo comprobando secuencia, encadenamiento y hash de cada
+    /// entrada. `ultimo_hash` es el hash que el archivo de datos guardó como
+    /// último registrado; permite detectar que se quitaron entradas del final.
+    pub fn verificar(&self, ultimo_hash: &str) -> io::Result<Verificacion> {
+        let contenido = if self.ruta.exists() {
+            fs::read_to_string(&self.ruta)?
+        } else {
+            String::new()
+        };
+
+        let mut anterior = String::new();
+        let mut cantidad = 0;
+        for (indice, linea) in contenido.lines().enumerate() {
+            if linea.trim().is_empty() {
+                continue;
+            }
+            let alterada = |motivo: &str| Verificacion::Alterada {
+                linea: indice + 1,
+                motivo: motivo.to_string(),
+            };
+
+            let Ok(registro) = serde_json::from_str::<RegistroAuditoria>(linea) else {
+                return Ok(alterada("la entrada no se puede leer"));
+            };
+            if registro.secuencia != cantidad as u64 + 1 {
+                return Ok(alterada("falta una entrada o está fuera de orden"));
+            }
+            if registro.hash_anterior != anterior {
+                return Ok(alterada("no continúa la cadena de la entrada anterior"));
+            }
+            if registro.hash != registro.calcular_hash() {
+                return Ok(alterada("el contenido no coincide con su hash"));
+            }
+
+            anterior = registro.hash;
+            cantidad += 1;
+        }
+
+        if anterior != ultimo_hash {
+            return Ok(Verificacion::Alterada {
+                linea: cantidad + 1,
+                motivo: "faltan entradas al final del registro".to_string(),
+            });
+        }
+        Ok(Verificacion::Integra(cantidad))
+    }
+}
+
+/// Diferencias entre dos versiones de una ficha en los campos auditados.
+///
+/// `None` representa una ficha que no existía (alta) o que se eliminó (baja).
+/// Las hojas no tienen identificador: una hoja modificada figura como quitada
+/// en su versión anterior y agregada en la nueva.
+pub fn diferencias(antes: Option<&Ficha>, despues: Option<&Ficha>) -> Vec<Cambio> {
+    let campos_antes = antes.map(campos).unwrap_or_default();
+    let campos_despues = despues.map(campos).unwrap_or_default();
+
+    let mut cambios: Vec<Cambio> = CAMPOS
+        .iter()
+        .enumerate()
+        .filter_map(|(i, campo)| {
+            let anterior = campos_antes.get(i).filter(|v| !v.is_empty());
+            let nuevo = campos_despues.get(i).filter(|v| !v.is_empty());
+            (anterior != nuevo).then(|| Cambio {
+                campo: campo.to_string(),
+                antes: anterior.cloned(),
+                despues: nuevo.cloned(),
+            })
+        })
+        .collect();
+
+    let hojas_antes = antes.map(|f| f.hojas.as_slice()).unwrap_or_default();
+    let hojas_despues = despues.map(|f| f.hojas.as_slice()).unwrap_or_default();
+    let misma = |a: &Hoja, b: &Hoja| {
+        a.registrada == b.registrada
+            && a.fecha == b.fecha
+            && a.contenido == b.contenido
+            && a.categoria == b.categoria
+    };
+
+    for hoja in hojas_antes {
+        if !hojas_despues.iter().any(|h| misma(h, hoja)) {
+            cambios.push(Cambio {
+                campo: format!("hoja {}", hoja.registrada.format("%d/%m/%Y %H:%M:%S")),
+                antes: Some(hoja.contenido.clone()),
+                despues: None,
+            });
+        }
+    }
+    for hoja in hojas_despues {
+        if !hojas_antes.iter().any(|h| misma(h, hoja)) {
+            cambios.push(Cambio {
+                campo: format!("hoja {}", hoja.registrada.format("%d/%m/%Y %H:%M:%S")),
+                antes: None,
+                despues: Some(hoja.contenido.clone()),
+            });
+        }
+    }
+
+    cambios
+}
+
+/// Indica si alguna hoja difiere entre las dos versiones
+pub fn cambian_hojas(cambios: &[Cambio]) -> bool {
+    cambios.iter().any(|c| c.campo.starts_with("hoja "))
+}
+
+const CAMPOS: [&str; 8] = [
+    "título",
+    "descripción",
+    "expediente",
+    "cliente",
+    "partes",
+    "etiquetas",
+    "estado",
+    "responsable",
+];
+
+/// Valores de los campos auditados, en el orden de `CAMPOS`
+fn campos(ficha: &Ficha) -> Vec<String> {
+    vec![
+        ficha.titulo.clone(),
+        ficha.descripcion.clone(),
+        ficha.expediente.clone(),
+        ficha.cliente.clone(),
+        Parte::formatear_lista(&ficha.partes),
+        ficha.etiquetas.join(", "),
+        ficha.estado.to_string(),
+        ficha
+            .responsable
+            .map(|id| id.to_string())
+            .unwrap_or_default(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    /// Registro con tres entradas en un directorio temporal; devuelve también
+    /// el hash de la última
+    fn registro_de_prueba() -> (Auditoria, PathBuf, String) {
+        let directorio = std::env::temp_dir().join(format!("auditoria-{}", Uuid::new_v4()));
+        let mut auditoria = Auditoria::new(directorio.join("fichas.auditoria.jsonl"));
+        let ficha = Ficha::de_prueba("");
+        let mut ultimo = String::new();
+        for operacion in [
+            Operacion::AgregarFicha,
+            Operacion::ActualizarFicha,
+            Operacion::EliminarFicha,
+        ] {
+            ultimo = auditoria
+                .registrar(None, operacion, ficha.id, &ficha.titulo, Vec::new())
+                .unwrap();
+        }
+        (auditoria, directorio, ultimo)
+    }
+
+    fn reescribir(auditoria: &Auditoria, cambio: impl FnOnce(&mut Vec<String>)) {
+        let mut lineas: Vec<String> = fs::read_to_string(&auditoria.ruta)
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect();
+        cambio(&mut lineas);
+        fs::write(&auditoria.ruta, lineas.join("\n") + "\n").unwrap();
+    }
+
+    #[test]
+    fn un_registro_sin_tocar_es_integro_y_se_retoma_al_reabrirlo() {
+        let (auditoria, directorio, ultimo) = registro_de_prueba();
+        assert_eq!(auditoria.verificar(&ultimo).unwrap(), Verificacion::Integra(3));
+
+        let mut reabierta = Auditoria::new(auditoria.ruta.clone());
+        let nuevo = reabierta
+            .registrar(None, Operacion::CambioHojas, Uuid::new_v4(), "Otra", Vec::new())
+            .unwrap();
+        let registros = reabierta.leer().unwrap();
+        assert_eq!(registros[3].secuencia, 4);
+        assert_eq!(registros[3].hash_anterior, ultimo);
+        assert_eq!(reabierta.verificar(&nuevo).unwrap(), Verificacion::Integra(4));
+        fs::remove_dir_all(directorio).unwrap();
+    }
+
+    #[test]
+    fn detecta_una_entrada_modificada() {
+        let (auditoria, directorio, ultimo) = registro_de_prueba();
+        reescribir(&auditoria, |lineas| {
+            lineas[1] = lineas[1].replace("Pérez c/ ACME", "Gómez c/ ACME");
+        });
+
+        assert_eq!(
+            auditoria.verificar(&ultimo).unwrap(),
+            Verificacion::Alterada {
+                linea: 2,
+                motivo: "el contenido no coincide con su hash".into()
+            }
+        );
+        fs::remove_dir_all(directorio).unwrap();
+    }
+
+    #[test]
+    fn detecta_entradas_reordenadas() {
+        let (auditoria, directorio, ultimo) = registro_de_prueba();
+        reescribir(&auditoria, |lineas| lineas.swap(1, 2));
+
+        assert!(matches!(
+            auditoria.verificar(&ultimo).unwrap(),
+            Verificacion::Alterada { linea: 2, .. }
+        ));
+        fs::remove_dir_all(directorio).unwrap();
+    }
+
+    #[test]
+    fn detecta_una_entrada_quitada_del_medio_aunque_se_renumere() {
+        let (auditoria, directorio, ultimo) = registro_de_prueba();
+        reescribir(&auditoria, |lineas| {
+            lineas.remove(1);
+            lineas[1] = lineas[1].replace("\"secuencia\":3", "\"secuencia\":2");
+        });
+
+        assert!(matches!(
+            auditoria.verificar(&ultimo).unwrap(),
+            Verificacion::Alterada { linea: 2, .. }
+        ));
+        fs::remove_dir_all(directorio).unwrap();
+    }
+
+    #[test]
+    fn detecta_que_se_quito_la_ultima_entrada() {
+        let (auditoria, directorio, ultimo) = registro_de_prueba();
+        reescribir(&auditoria, |lineas| {
+            lineas.pop();
+        });
+
+        // Lo que queda es una cadena válida, pero no termina donde el archivo
+        // de datos dice que termina
+        assert_eq!(
+            auditoria.verificar(&ultimo).unwrap(),
+            Verificacion::Alterada {
+                linea: 3,
+                motivo: "faltan entradas al final del registro".into()
+            }
+        );
+        fs::remove_dir_all(directorio).unwrap();
+    }
+
+    #[test]
+    fn diferencias_distingue_campos_y_hojas() {
+        let antes = Ficha::de_prueba("");
+        let mut despues = antes.clone();
+        despues.etiquetas = vec!["laboral".into()];
+        let cambios = diferencias(Some(&antes), Some(&despues));
+        assert_eq!(
+            cambios,
+            [Cambio {
+                campo: "etiquetas".into(),
+                antes: None,
+                despues: Some("laboral".into()),
+            }]
+        );
+        assert!(!cambian_hojas(&cambios));
+
+        despues.agregar_hoja(NaiveDate::default(), "Se presentó escrito".into(), None, None);
+        let cambios = diferencias(Some(&antes), Some(&despues));
+        assert!(cambian_hojas(&cambios));
+        assert_eq!(
+            cambios.last().unwrap().despues.as_deref(),
+            Some("Se presentó escrito")
+        );
+    }
+}
@@ -1,43 +1,171 @@
-use crate::models::Ficha;
+use crate::models::{
+    Adjunto, CategoriaHoja, Ficha, ItemLiquidacion, Liquidacion, PlantillaHoja, ReglaCorreo, Rol,
+    TipoVinculo, Usuario,
+};
+use crate::storage::auditoria::{
+    self, Auditoria, Cambio, Operacion, RegistroAuditoria, Verificacion,
+};
+use chrono::{DateTime, Local, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct JsonStorage {
     fichas: Vec<Ficha>,
+    #[serde(default = "CategoriaHoja::predeterminadas")]
+    categorias: Vec<CategoriaHoja>,
+    #[serde(default)]
+    plantillas: Vec<PlantillaHoja>,
+    /// Color RGB de cada etiqueta que tenga uno asignado
+    #[serde(default)]
+    colores_etiquetas: BTreeMap<String, [u8; 3]>,
+    /// Último número de liquidación emitido; nunca se decrementa
+    #[serde(default)]
+    ultima_liquidacion: u32,
+    #[serde(default)]
+    usuarios: Vec<Usuario>,
+    #[serde(default)]
+    reglas_correo: Vec<ReglaCorreo>,
+    /// Hash de la última entrada de auditoría, para detectar que se quiten
+    /// entradas del final del registro
+    #[serde(default)]
+    hash_auditoria: String,
+    #[serde(skip)]
+    auditoria: Auditoria,
+    /// Motivo por el que no se pudo leer el archivo de datos. Mientras haya
+    /// uno no se guarda nada, para no reemplazar los datos reales por los
+    /// que quedaron en memoria.
+    #[serde(skip)]
+    error_carga: Option<String>,
     #[serde(skip)]
     file_path: PathBuf,
 }
 
+/// Formatos aceptados del archivo de datos
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Archivo {
+    Espacio(Box<JsonStorage>),
+    /// Formato original: solo la lista de fichas
+    SoloFichas(Vec<Ficha>),
+}
+
 impl JsonStorage {
     pub fn new(file_path: PathBuf) -> Self {
+        let ruta_auditoria = ruta_auditoria(&file_path);
         Self {
             fichas: Vec::new(),
+            categorias: CategoriaHoja::predeterminadas(),
+            plantillas: Vec::new(),
+            colores_etiquetas: BTreeMap::new(),
+            ultima_liquidacion: 0,
+            usuarios: Vec::new(),
+            reglas_correo: Vec::new(),
+            hash_auditoria: String::new(),
+            auditoria: Auditoria::new(ruta_auditoria),
+            error_carga: None,
             file_path,
         }
     }
 
-    /// Carga los datos desde el archivo JSON
+    /// Carga los datos desde el archivo JSON. Si el archivo existe pero no se
+    /// puede leer, no se vuelve a guardar hasta que se cargue bien.
     pub fn cargar(&mut self) -> io::Result<()> {
         if !self.file_path.exists() {
             // Si el archivo no existe, crear uno vacío
             return self.guardar();
         }
 
-        let contenido = fs::read_to_string(&self.file_path)?;
-        let fichas: Vec<Ficha> = serde_json::from_str(&contenido)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let archivo = fs::read_to_string(&self.file_path).and_then(|contenido| {
+            serde_json::from_str::<Archivo>(&contenido)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        });
+        let archivo = match archivo {
+            Ok(archivo) => archivo,
+            Err(e) => {
+                self.error_carga = Some(e.to_string());
+                return Err(e);
+            }
+        };
+        self.error_carga = None;
+
+        match archivo {
+            Archivo::Espacio(datos) => {
+                self.fichas = datos.fichas;
+                self.categorias = datos.categorias;
+                self.plantillas = datos.plantillas;
+                self.colores_etiquetas = datos.colores_etiquetas;
+                self.ultima_liquidacion = datos.ultima_liquidacion;
+                self.usuarios = datos.usuarios;
+                self.reglas_correo = datos.reglas_correo;
+                self.hash_auditoria = datos.hash_auditoria;
+                self.asegurar_administrador();
+            }
+            Archivo::SoloFichas(fichas) => {
+                self.fichas = fichas;
+            }
+        }
+
+        // Las hojas de archivos anteriores se migran al leerlas (ver `Hoja`)
+        // y se guardan con el formato nuevo en la próxima escritura; su día
+        // lo completa `migrar_fechas` con la zona configurada
+        for ficha in &mut self.fichas {
+            ficha.ordenar_hojas();
+        }
+        self.migrar_auditoria_compartida()
+    }
+
+    /// Fija la fecha de las hojas de archivos anteriores, que solo guardaban
+    /// el momento de carga, con su día según `dia`. Se llama después de
+    /// `cargar` con la zona de las preferencias.
+    pub fn migrar_fechas(&mut self, dia: impl Fn(DateTime<Utc>) -> NaiveDate) {
+        for ficha in &mut self.fichas {
+            ficha.migrar_fechas(&dia);
+        }
+    }
+
+    /// Antes, el registro de auditoría se llamaba `auditoria.jsonl` sin
+    /// importar el archivo de datos. Si este archivo aún no tiene registro
+    /// propio y la última entrada del anterior es la que tiene guardada, el
+    /// registro es suyo y pasa a llevar su nombre.
+    fn migrar_auditoria_compartida(&mut self) -> io::Result<()> {
+        let propia = ruta_auditoria(&self.file_path);
+        let compartida = self.directorio_base().join("auditoria.jsonl");
+        if self.hash_auditoria.is_empty() || propia.exists() || !compartida.exists() {
+            return Ok(());
+        }
 
-        self.fichas = fichas;
+        let ultima = Auditoria::new(compartida.clone())
+            .leer()
+            .ok()
+            .and_then(|registros| registros.last().map(|r| r.hash.clone()));
+        if ultima.as_ref() == Some(&self.hash_auditoria) {
+            fs::rename(&compartida, &propia)?;
+            self.auditoria = Auditoria::new(propia);
+        }
         Ok(())
     }
 
+    /// Motivo por el que no se pudo leer el archivo de datos, si no se pudo
+    pub fn error_carga(&self) -> Option<&str> {
+        self.error_carga.as_deref()
+    }
+
     /// Guarda los datos en el archivo JSON
     pub fn guardar(&self) -> io::Result<()> {
-        let json = serde_json::to_string_pretty(&self.fichas)
+        if let Some(error) = &self.error_carga {
+            return Err(io::Error::other(format!(
+                "no se guardan cambios porque {} no se pudo leer ({})",
+                self.file_path.display(),
+                error
+            )));
+        }
+
+        let json = serde_json::to_string_pretty(self)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
 
         // Crear el directorio si no existe
@@ -45,26 +173,113 @@ impl JsonStorage {
             fs::create_dir_all(parent)?;
         }
 
-        let mut file = fs::File::create(&self.file_path)?;
-        file.write_all(json.as_bytes())?;
+        // Se escribe en un archivo temporal que luego reemplaza al anterior,
+        // para que una escritura fallida no deje el archivo de datos a medias
+        let mut temporal = self.file_path.clone().into_os_string();
+        temporal.push(".tmp");
+        let temporal = PathBuf::from(temporal);
+        let escrito = fs::File::create(&temporal).and_then(|mut file| {
+            file.write_all(json.as_bytes())?;
+            file.sync_all()
+        });
+        if let Err(e) = escrito.and_then(|()| fs::rename(&temporal, &self.file_path)) {
+            let _ = fs::remove_file(&temporal);
+            return Err(e);
+        }
         Ok(())
     }
 
+    /// Copia el archivo de datos a `directorio` con la fecha en el nombre y
+    /// borra las copias más antiguas que excedan `conservar`
+    pub fn respaldar(&self, directorio: &Path, conservar: usize) -> io::Result<PathBuf> {
+        let nombre = self
+            .file_path
+            .file_stem()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| String::from("datos"));
+        fs::create_dir_all(directorio)?;
+
+        let destino = directorio.join(format!(
+            "{}_{}.json",
+            nombre,
+            Local::now().format("%Y%m%d_%H%M%S")
+        ));
+        fs::copy(&self.file_path, &destino)?;
+
+        // El nombre lleva la fecha, así que el orden alfabético es el cronológico
+        let prefijo = format!("{}_", nombre);
+        let mut copias: Vec<PathBuf> = fs::read_dir(directorio)?
+            .filter_map(|entrada| entrada.ok().map(|e| e.path()))
+            .filter(|ruta| {
+                ruta.extension().is_some_and(|e| e == "json")
+                    && ruta
+                        .file_name()
+                        .is_some_and(|n| n.to_string_lossy().starts_with(&prefijo))
+            })
+            .collect();
+        copias.sort();
+        let sobrantes = copias.len().saturating_sub(conservar);
+        for copia in &copias[..sobrantes] {
+            fs::remove_file(copia)?;
+        }
+
+        Ok(destino)
+    }
+
+    /// Directorio base de los archivos auxiliares, junto al archivo de datos
+    fn directorio_base(&self) -> &Path {
+        self.file_path.parent().unwrap_or(Path::new("."))
+    }
+
+    /// Directorio donde se buscan las plantillas de escritos
+    pub fn directorio_plantillas_escritos(&self) -> PathBuf {
+        self.directorio_base().join("plantillas_escritos")
+    }
+
+    /// Directorio donde se guardan los adjuntos de una ficha
+    pub fn directorio_adjuntos(&self, ficha_id: Uuid) -> PathBuf {
+        self.directorio_base()
+            .join("adjuntos")
+            .join(ficha_id.to_string())
+    }
+
+    /// Directorio donde se guardan los PDF y CSV de las liquidaciones
+    pub fn directorio_liquidaciones(&self) -> PathBuf {
+        self.directorio_base().join("liquidaciones")
+    }
+
     /// Obtiene todas las fichas
     pub fn obtener_fichas(&self) -> &Vec<Ficha> {
         &self.fichas
     }
 
-    /// Agrega una nueva ficha
-    pub fn agregar_ficha(&mut self, ficha: Ficha) -> io::Result<()> {
-        self.fichas.push(ficha);
+    /// Obtiene las categorías de hojas configuradas
+    pub fn obtener_categorias(&self) -> &Vec<CategoriaHoja> {
+        &self.categorias
+    }
+
+    /// Cantidad de hojas de cada categoría, en todas las fichas
+    pub fn usos_categorias(&self) -> BTreeMap<String, usize> {
+        let mut usos = BTreeMap::new();
+        for hoja in self.fichas.iter().flat_map(|f| f.obtener_hojas()) {
+            if let Some(categoria) = &hoja.categoria {
+                *usos.entry(categoria.clone()).or_insert(0) += 1;
+            }
+        }
+        usos
+    }
+
+    /// Agrega una categoría de hojas
+    pub fn agregar_categoria(&mut self, categoria: CategoriaHoja) -> io::Result<()> {
+        self.categorias.push(categoria);
         self.guardar()
     }
 
-    /// Elimina una ficha por ID
-    pub fn eliminar_ficha(&mut self, ficha_id: Uuid) -> io::Result<bool> {
-        if let Some(pos) = self.fichas.iter().position(|f| f.id == ficha_id) {
-            self.fichas.remove(pos);
+    /// Cambia el nombre, el ícono o el color de una categoría existente; las
+    /// hojas la siguen referenciando por su identificador
+    pub fn actualizar_categoria(&mut self, categoria: CategoriaHoja) -> io::Result<bool> {
+        if let Some(c) = self.categorias.iter_mut().find(|c| c.id == categoria.id) {
+            *c = categoria;
             self.guardar()?;
             Ok(true)
         } else {
@@ -72,15 +287,233 @@ impl JsonStorage {
         }
     }
 
-    /// Actualiza una ficha existente
+    /// Elimina una categoría que ninguna hoja usa; las reglas de correo que
+    /// la asignaban quedan sin categoría
+    pub fn eliminar_categoria(&mut self, categoria_id: &str) -> io::Result<bool> {
+        let en_uso = self.usos_categorias().contains_key(categoria_id);
+        let Some(pos) = self.categorias.iter().position(|c| c.id == categoria_id) else {
+            return Ok(false);
+        };
+        if en_uso {
+            return Ok(false);
+        }
+
+        self.categorias.remove(pos);
+        for regla in self.reglas_correo.iter_mut() {
+            if regla.categoria.as_deref() == Some(categoria_id) {
+                regla.categoria = None;
+            }
+        }
+        self.guardar()?;
+        Ok(true)
+    }
+
+    /// Agrega una nueva ficha; queda auditada a nombre de quien la creó
+    pub fn agregar_ficha(&mut self, ficha: Ficha) -> io::Result<()> {
+        self.auditado(|storage| {
+            let cambios = auditoria::diferencias(None, Some(&ficha));
+            storage.auditar(
+                ficha.creada_por,
+                Operacion::AgregarFicha,
+                ficha.id,
+                &ficha.titulo,
+                cambios,
+            )?;
+            storage.fichas.push(ficha);
+            Ok(())
+        })
+    }
+
+    /// Elimina una ficha por ID
+    pub fn eliminar_ficha(&mut self, ficha_id: Uuid, autor: Option<Uuid>) -> io::Result<bool> {
+        let Some(pos) = self.fichas.iter().position(|f| f.id == ficha_id) else {
+            return Ok(false);
+        };
+        self.auditado(|storage| {
+            let ficha = storage.fichas.remove(pos);
+            for otra in storage.fichas.iter_mut() {
+                otra.desvincular(ficha_id);
+            }
+            storage.reglas_correo.retain(|r| r.ficha_id != ficha_id);
+            let cambios = auditoria::diferencias(Some(&ficha), None);
+            storage.auditar(autor, Operacion::EliminarFicha, ficha.id, &ficha.titulo, cambios)
+        })?;
+        Ok(true)
+    }
+
+    /// Actualiza una ficha existente; queda auditada a nombre de quien la
+    /// modificó
     pub fn actualizar_ficha(&mut self, ficha: Ficha) -> io::Result<bool> {
-        if let Some(f) = self.fichas.iter_mut().find(|f| f.id == ficha.id) {
-            *f = ficha;
+        let Some(pos) = self.fichas.iter().position(|f| f.id == ficha.id) else {
+            return Ok(false);
+        };
+        self.auditado(|storage| {
+            let cambios = auditoria::diferencias(Some(&storage.fichas[pos]), Some(&ficha));
+            storage.auditar(
+                ficha.modificada_por,
+                Operacion::ActualizarFicha,
+                ficha.id,
+                &ficha.titulo,
+                cambios,
+            )?;
+            storage.fichas[pos] = ficha;
+            Ok(())
+        })?;
+        Ok(true)
+    }
+
+    /// Vincula dos fichas en ambos sentidos: `destino` queda en `origen` con
+    /// el tipo indicado y `origen` en `destino` con el tipo inverso
+    pub fn vincular_fichas(
+        &mut self,
+        origen: Uuid,
+        destino: Uuid,
+        tipo: TipoVinculo,
+    ) -> io::Result<bool> {
+        let existen = [origen, destino]
+            .iter()
+            .all(|id| self.fichas.iter().any(|f| f.id == *id));
+        if origen == destino || !existen {
+            return Ok(false);
+        }
+
+        for ficha in self.fichas.iter_mut() {
+            if ficha.id == origen {
+                ficha.vincular(destino, tipo);
+            } else if ficha.id == destino {
+                ficha.vincular(origen, tipo.inverso());
+            }
+        }
+        self.guardar()?;
+        Ok(true)
+    }
+
+    /// Quita el vínculo entre dos fichas, en ambos sentidos
+    pub fn desvincular_fichas(&mut self, ficha_a: Uuid, ficha_b: Uuid) -> io::Result<bool> {
+        let mut quitado = false;
+        for ficha in self.fichas.iter_mut() {
+            if ficha.id == ficha_a {
+                quitado |= ficha.desvincular(ficha_b);
+            } else if ficha.id == ficha_b {
+                quitado |= ficha.desvincular(ficha_a);
+            }
+        }
+        if quitado {
             self.guardar()?;
-            Ok(true)
-        } else {
-            Ok(false)
         }
+        Ok(quitado)
+    }
+
+    /// Aplica un cambio a una ficha y guarda. Si el cambio toca las hojas,
+    /// queda en la auditoría a nombre de `autor`.
+    ///
+    /// Devuelve lo que devuelva `cambio`, o `None` si la ficha no existe.
+    pub fn modificar_ficha<T>(
+        &mut self,
+        ficha_id: Uuid,
+        autor: Option<Uuid>,
+        cambio: impl FnOnce(&mut Ficha) -> T,
+    ) -> io::Result<Option<T>> {
+        if !self.fichas.iter().any(|f| f.id == ficha_id) {
+            return Ok(None);
+        }
+        self.auditado(|storage| storage.cambiar_ficha(ficha_id, autor, cambio))
+    }
+
+    /// Como `modificar_ficha` para varias fichas, guardando una sola vez al
+    /// final. Devuelve el resultado de cada ficha que existe.
+    pub fn modificar_fichas<T>(
+        &mut self,
+        fichas: &[Uuid],
+        autor: Option<Uuid>,
+        mut cambio: impl FnMut(&mut Ficha) -> T,
+    ) -> io::Result<Vec<(Uuid, T)>> {
+        if !self.fichas.iter().any(|f| fichas.contains(&f.id)) {
+            return Ok(Vec::new());
+        }
+        self.auditado(|storage| {
+            let mut resultados = Vec::with_capacity(fichas.len());
+            for &ficha_id in fichas {
+                if let Some(resultado) = storage.cambiar_ficha(ficha_id, autor, &mut cambio)? {
+                    resultados.push((ficha_id, resultado));
+                }
+            }
+            Ok(resultados)
+        })
+    }
+
+    /// Como `modificar_ficha`, pero sin guardar
+    fn cambiar_ficha<T>(
+        &mut self,
+        ficha_id: Uuid,
+        autor: Option<Uuid>,
+        cambio: impl FnOnce(&mut Ficha) -> T,
+    ) -> io::Result<Option<T>> {
+        let Some(ficha) = self.fichas.iter_mut().find(|f| f.id == ficha_id) else {
+            return Ok(None);
+        };
+
+        let antes = ficha.clone();
+        let resultado = cambio(ficha);
+        let cambios = auditoria::diferencias(Some(&antes), Some(ficha));
+        if auditoria::cambian_hojas(&cambios) {
+            let titulo = ficha.titulo.clone();
+            self.auditar(autor, Operacion::CambioHojas, ficha_id, &titulo, cambios)?;
+        }
+        Ok(Some(resultado))
+    }
+
+    /// Aplica `cambio`, que puede agregar entradas a la auditoría, y guarda.
+    /// Si algo falla, las fichas, las reglas de correo, los colores de las
+    /// etiquetas y el registro de auditoría vuelven a como estaban: el
+    /// registro nunca tiene entradas que el archivo de datos no guardó.
+    fn auditado<T>(
+        &mut self,
+        cambio: impl FnOnce(&mut Self) -> io::Result<T>,
+    ) -> io::Result<T> {
+        let marca = self.auditoria.marca()?;
+        let hash_auditoria = self.hash_auditoria.clone();
+        let fichas = self.fichas.clone();
+        let reglas_correo = self.reglas_correo.clone();
+        let colores_etiquetas = self.colores_etiquetas.clone();
+
+        let resultado = cambio(self).and_then(|r| self.guardar().map(|()| r));
+        if resultado.is_err() {
+            self.fichas = fichas;
+            self.reglas_correo = reglas_correo;
+            self.colores_etiquetas = colores_etiquetas;
+            self.hash_auditoria = hash_auditoria;
+            self.auditoria.deshacer(marca)?;
+        }
+        resultado
+    }
+
+    /// Agrega una entrada al registro de auditoría, si hubo cambios
+    fn auditar(
+        &mut self,
+        autor: Option<Uuid>,
+        operacion: Operacion,
+        ficha_id: Uuid,
+        ficha_titulo: &str,
+        cambios: Vec<Cambio>,
+    ) -> io::Result<()> {
+        if cambios.is_empty() && operacion != Operacion::EliminarFicha {
+            return Ok(());
+        }
+        self.hash_auditoria =
+            self.auditoria
+                .registrar(autor, operacion, ficha_id, ficha_titulo, cambios)?;
+        Ok(())
+    }
+
+    /// Entradas del registro de auditoría, de la más antigua a la más reciente
+    pub fn obtener_auditoria(&self) -> io::Result<Vec<RegistroAuditoria>> {
+        self.auditoria.leer()
+    }
+
+    /// Comprueba que el registro de auditoría no haya sido alterado
+    pub fn verificar_auditoria(&self) -> io::Result<Verificacion> {
+        self.auditoria.verificar(&self.hash_auditoria)
     }
 
     /// Obtiene una ficha por ID
@@ -92,4 +525,631 @@ impl JsonStorage {
     pub fn obtener_ficha_mut(&mut self, ficha_id: Uuid) -> Option<&mut Ficha> {
         self.fichas.iter_mut().find(|f| f.id == ficha_id)
     }
+
+    /// Obtiene las plantillas de hojas
+    pub fn obtener_plantillas(&self) -> &Vec<PlantillaHoja> {
+        &self.plantillas
+    }
+
+    /// Agrega una nueva plantilla
+    pub fn agregar_plantilla(&mut self, plantilla: PlantillaHoja) -> io::Result<()> {
+        self.plantillas.push(plantilla);
+        self.guardar()
+    }
+
+    /// Actualiza una plantilla existente
+    pub fn actualizar_plantilla(&mut self, plantilla: PlantillaHoja) -> io::Result<bool> {
+        if let Some(p) = self.plantillas.iter_mut().find(|p| p.id == plantilla.id) {
+            *p = plantilla;
+            self.guardar()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Elimina una plantilla por ID
+    pub fn eliminar_plantilla(&mut self, plantilla_id: Uuid) -> io::Result<bool> {
+        if let Some(pos) = self.plantillas.iter().position(|p| p.id == plantilla_id) {
+            self.plantillas.remove(pos);
+            self.guardar()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Reglas para asignar los correos importados a las fichas
+    pub fn obtener_reglas_correo(&self) -> &Vec<ReglaCorreo> {
+        &self.reglas_correo
+    }
+
+    pub fn agregar_regla_correo(&mut self, regla: ReglaCorreo) -> io::Result<()> {
+        self.reglas_correo.push(regla);
+        self.guardar()
+    }
+
+    pub fn eliminar_regla_correo(&mut self, regla_id: Uuid) -> io::Result<bool> {
+        if let Some(pos) = self.reglas_correo.iter().position(|r| r.id == regla_id) {
+            self.reglas_correo.remove(pos);
+            self.guardar()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Obtiene todas las etiquetas usadas en las fichas, ordenadas
+    pub fn obtener_etiquetas(&self) -> Vec<String> {
+        self.fichas
+            .iter()
+            .flat_map(|f| f.etiquetas.iter().cloned())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Obtiene los colores asignados a las etiquetas
+    pub fn obtener_colores_etiquetas(&self) -> &BTreeMap<String, [u8; 3]> {
+        &self.colores_etiquetas
+    }
+
+    /// Asigna o quita el color de una etiqueta
+    pub fn asignar_color_etiqueta(
+        &mut self,
+        etiqueta: &str,
+        color: Option<[u8; 3]>,
+    ) -> io::Result<()> {
+        match color {
+            Some(color) => self.colores_etiquetas.insert(etiqueta.to_string(), color),
+            None => self.colores_etiquetas.remove(etiqueta),
+        };
+        self.guardar()
+    }
+
+    /// Renombra una etiqueta en todas las fichas y guarda una única vez.
+    ///
+    /// Si la nueva etiqueta ya existe, ambas se fusionan. Cada ficha
+    /// modificada queda en la auditoría a nombre de `autor`. Devuelve la
+    /// cantidad de fichas modificadas. Si no se puede guardar, las fichas y
+    /// los colores quedan como estaban.
+    pub fn renombrar_etiqueta(
+        &mut self,
+        anterior: &str,
+        nueva: &str,
+        autor: Option<Uuid>,
+    ) -> io::Result<usize> {
+        let nueva = nueva.trim();
+        if nueva.is_empty() || anterior == nueva {
+            return Ok(0);
+        }
+
+        self.auditado(|storage| {
+            let mut cambios = Vec::new();
+            for ficha in storage.fichas.iter_mut() {
+                if ficha.tiene_etiqueta(anterior) {
+                    let original = ficha.clone();
+                    ficha.renombrar_etiqueta(anterior, nueva);
+                    let diferencias = auditoria::diferencias(Some(&original), Some(ficha));
+                    cambios.push((ficha.id, ficha.titulo.clone(), diferencias));
+                }
+            }
+            let modificadas = cambios.len();
+            for (ficha_id, titulo, diferencias) in cambios {
+                storage.auditar(autor, Operacion::ActualizarFicha, ficha_id, &titulo, diferencias)?;
+            }
+
+            if let Some(color) = storage.colores_etiquetas.remove(anterior) {
+                storage
+                    .colores_etiquetas
+                    .entry(nueva.to_string())
+                    .or_insert(color);
+            }
+            Ok(modificadas)
+        })
+    }
+
+    pub fn obtener_usuarios(&self) -> &Vec<Usuario> {
+        &self.usuarios
+    }
+
+    pub fn agregar_usuario(&mut self, usuario: Usuario) -> io::Result<()> {
+        self.usuarios.push(usuario);
+        self.asegurar_administrador();
+        self.guardar()
+    }
+
+    /// Cambia el rol de un usuario, salvo que deje al espacio sin administradores
+    pub fn cambiar_rol_usuario(&mut self, usuario_id: Uuid, rol: Rol) -> io::Result<bool> {
+        let otros_admin = self
+            .usuarios
+            .iter()
+            .any(|u| u.id != usuario_id && u.rol == Rol::Admin);
+        let Some(usuario) = self.usuarios.iter_mut().find(|u| u.id == usuario_id) else {
+            return Ok(false);
+        };
+        if usuario.rol == Rol::Admin && rol != Rol::Admin && !otros_admin {
+            return Ok(false);
+        }
+
+        usuario.rol = rol;
+        self.guardar()?;
+        Ok(true)
+    }
+
+    /// Elimina un perfil; lo que haya registrado queda sin autor visible.
+    /// No se puede eliminar al último administrador.
+    pub fn eliminar_usuario(&mut self, usuario_id: Uuid) -> io::Result<bool> {
+        let es_ultimo_admin = self
+            .usuarios
+            .iter()
+            .filter(|u| u.rol == Rol::Admin)
+            .all(|u| u.id == usuario_id);
+        let existe = self.usuarios.iter().any(|u| u.id == usuario_id);
+        if !existe || es_ultimo_admin {
+            return Ok(false);
+        }
+
+        self.usuarios.retain(|u| u.id != usuario_id);
+        self.guardar()?;
+        Ok(true)
+    }
+
+    /// Los perfiles creados antes de que existieran los roles no tienen
+    /// administrador: el primero pasa a serlo
+    fn asegurar_administrador(&mut self) {
+        if !self.usuarios.iter().any(|u| u.rol == Rol::Admin) {
+            if let Some(primero) = self.usuarios.first_mut() {
+                primero.rol = Rol::Admin;
+            }
+        }
+    }
+
+    /// Pone en marcha el cronómetro de la ficha y guarda en el acto, para que
+    /// siga corriendo si la aplicación se cierra
+    pub fn iniciar_cronometro(&mut self, ficha_id: Uuid) -> io::Result<bool> {
+        let iniciado = self
+            .obtener_ficha_mut(ficha_id)
+            .is_some_and(|f| f.iniciar_cronometro());
+        if iniciado {
+            self.guardar()?;
+        }
+        Ok(iniciado)
+    }
+
+    /// Detiene el cronómetro de la ficha, registra el tiempo y guarda.
+    ///
+    /// Devuelve los minutos registrados, o `None` si no estaba corriendo.
+    pub fn detener_cronometro(
+        &mut self,
+        ficha_id: Uuid,
+        descripcion: String,
+        dia: impl Fn(DateTime<Utc>) -> NaiveDate,
+    ) -> io::Result<Option<u32>> {
+        let minutos = self
+            .obtener_ficha_mut(ficha_id)
+            .and_then(|f| f.detener_cronometro(descripcion, dia));
+        if minutos.is_some() {
+            self.guardar()?;
+        }
+        Ok(minutos)
+    }
+
+    /// Indica si alguna ficha tiene el cronómetro en marcha
+    pub fn hay_cronometro_activo(&self) -> bool {
+        self.fichas.iter().any(|f| f.cronometro.is_some())
+    }
+
+    /// Prepara una liquidación con los asientos indicados (ficha, asiento).
+    ///
+    /// El número se reserva y se guarda en el acto, de modo que nunca se vuelve
+    /// a usar aunque la liquidación no llegue a registrarse. Los asientos ya
+    /// liquidados se ignoran; si alguno es de otro cliente no se reserva nada.
+    /// La liquidación lleva la fecha `hoy`, el día en la zona configurada.
+    pub fn reservar_liquidacion(
+        &mut self,
+        cliente: &str,
+        seleccion: &[(Uuid, Uuid)],
+        hoy: NaiveDate,
+    ) -> io::Result<Option<Liquidacion>> {
+        let items: Vec<ItemLiquidacion> = self
+            .fichas
+            .iter()
+            .flat_map(|ficha| {
+                ficha
+                    .cuenta
+                    .iter()
+                    .filter(|a| !a.esta_liquidado() && seleccion.contains(&(ficha.id, a.id)))
+                    .map(|asiento| ItemLiquidacion {
+                        ficha_id: ficha.id,
+                        ficha_titulo: ficha.titulo.clone(),
+                        expediente: ficha.expediente.clone(),
+                        asiento: asiento.clone(),
+                    })
+            })
+            .collect();
+
+        if let Some(ajeno) = items.iter().find(|i| i.asiento.cliente != cliente) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "el movimiento \"{}\" es de {}, no de {}",
+                    ajeno.asiento.concepto, ajeno.asiento.cliente, cliente
+                ),
+            ));
+        }
+        if items.is_empty() {
+            return Ok(None);
+        }
+
+        self.ultima_liquidacion += 1;
+        self.guardar()?;
+
+        Ok(Some(Liquidacion {
+            numero: self.ultima_liquidacion,
+            cliente: cliente.to_string(),
+            fecha: hoy,
+            items,
+        }))
+    }
+
+    /// Marca los asientos como liquidados y deja una hoja en cada ficha afectada
+    pub fn registrar_liquidacion(
+        &mut self,
+        liquidacion: &Liquidacion,
+        adjuntos: Vec<Adjunto>,
+        autor: Option<Uuid>,
+    ) -> io::Result<()> {
+        // La hoja va sin categoría si se eliminó la de pagos
+        let categoria = self
+            .categorias
+            .iter()
+            .any(|c| c.id == CategoriaHoja::PAGO)
+            .then(|| CategoriaHoja::PAGO.to_string());
+        self.auditado(|storage| {
+            for ficha_id in liquidacion.fichas() {
+                storage.cambiar_ficha(ficha_id, autor, |ficha| {
+                    for asiento in ficha.cuenta.iter_mut() {
+                        if liquidacion.items.iter().any(|i| i.asiento.id == asiento.id) {
+                            asiento.liquidacion = Some(liquidacion.numero);
+                        }
+                    }
+
+                    let cantidad = liquidacion
+                        .items
+                        .iter()
+                        .filter(|i| i.ficha_id == ficha_id)
+                        .count();
+                    ficha.agregar_hoja_fechada(
+                        liquidacion.fecha,
+                        format!(
+                            "Se emitió la liquidación N° {} a {} ({} movimientos)",
+                            liquidacion.numero_formateado(),
+                            liquidacion.cliente,
+                            cantidad
+                        ),
+                        categoria.clone(),
+                        adjuntos.clone(),
+                        autor,
+                    );
+                })?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Registro de auditoría propio de cada archivo de datos: `<nombre>.auditoria.jsonl`
+fn ruta_auditoria(file_path: &Path) -> PathBuf {
+    let nombre = file_path
+        .file_stem()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| String::from("datos"));
+    file_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join(format!("{}.auditoria.jsonl", nombre))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Asiento, Importe, Moneda, TipoAsiento};
+
+    fn storage_de_prueba() -> (JsonStorage, PathBuf) {
+        let directorio = std::env::temp_dir().join(format!("storage-{}", Uuid::new_v4()));
+        let mut storage = JsonStorage::new(directorio.join("fichas.json"));
+        storage.cargar().unwrap();
+        (storage, directorio)
+    }
+
+    fn ficha_con_etiquetas(titulo: &str, etiquetas: &[&str]) -> Ficha {
+        let hoy = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+        let mut ficha = Ficha::new(titulo.to_string(), String::new(), String::new(), hoy, None);
+        ficha.etiquetas = etiquetas.iter().map(|e| e.to_string()).collect();
+        ficha
+    }
+
+    #[test]
+    fn renombrar_etiqueta_fusiona_con_la_existente_y_conserva_el_color() {
+        let (mut storage, directorio) = storage_de_prueba();
+        storage
+            .agregar_ficha(ficha_con_etiquetas("Pérez", &["laboral", "urgente"]))
+            .unwrap();
+        storage
+            .agregar_ficha(ficha_con_etiquetas("Gómez", &["Laboral", "laboral"]))
+            .unwrap();
+        storage.agregar_ficha(ficha_con_etiquetas("Ruiz", &["familia"])).unwrap();
+        storage
+            .asignar_color_etiqueta("Laboral", Some([220, 38, 38]))
+            .unwrap();
+
+        assert_eq!(
+            storage
+                .renombrar_etiqueta("Laboral", " laboral ", None)
+                .unwrap(),
+            1
+        );
+
+        let mut releido = JsonStorage::new(directorio.join("fichas.json"));
+        releido.cargar().unwrap();
+        let etiquetas: Vec<_> = releido
+            .obtener_fichas()
+            .iter()
+            .map(|f| f.etiquetas.clone())
+            .collect();
+        assert_eq!(
+            etiquetas,
+            [vec!["laboral", "urgente"], vec!["laboral"], vec!["familia"]]
+        );
+        assert_eq!(
+            releido.obtener_colores_etiquetas().get("laboral"),
+            Some(&[220, 38, 38])
+        );
+        assert!(!releido.obtener_colores_etiquetas().contains_key("Laboral"));
+        fs::remove_dir_all(directorio).unwrap();
+    }
+
+    #[test]
+    fn renombrar_etiqueta_queda_en_la_auditoria() {
+        let (mut storage, directorio) = storage_de_prueba();
+        storage.agregar_ficha(ficha_con_etiquetas("Pérez", &["laboral"])).unwrap();
+        let autor = Some(Uuid::new_v4());
+
+        storage.renombrar_etiqueta("laboral", "trabajo", autor).unwrap();
+
+        let registros = storage.obtener_auditoria().unwrap();
+        let ultimo = registros.last().unwrap();
+        assert_eq!(ultimo.operacion, Operacion::ActualizarFicha);
+        assert_eq!(ultimo.usuario, autor);
+        assert_eq!(
+            ultimo.cambios,
+            [Cambio {
+                campo: "etiquetas".into(),
+                antes: Some("laboral".into()),
+                despues: Some("trabajo".into()),
+            }]
+        );
+        assert_eq!(storage.verificar_auditoria().unwrap(), Verificacion::Integra(2));
+        fs::remove_dir_all(directorio).unwrap();
+    }
+
+    #[test]
+    fn cada_archivo_de_datos_tiene_su_propio_registro() {
+        let (mut estudio, directorio) = storage_de_prueba();
+        let mut personal = JsonStorage::new(directorio.join("personal.json"));
+        personal.cargar().unwrap();
+
+        estudio.agregar_ficha(ficha_con_etiquetas("Pérez", &[])).unwrap();
+        personal.agregar_ficha(ficha_con_etiquetas("Gómez", &[])).unwrap();
+        estudio.agregar_ficha(ficha_con_etiquetas("Ruiz", &[])).unwrap();
+
+        assert!(directorio.join("fichas.auditoria.jsonl").exists());
+        assert!(directorio.join("personal.auditoria.jsonl").exists());
+        assert_eq!(estudio.verificar_auditoria().unwrap(), Verificacion::Integra(2));
+        assert_eq!(personal.verificar_auditoria().unwrap(), Verificacion::Integra(1));
+        fs::remove_dir_all(directorio).unwrap();
+    }
+
+    #[test]
+    fn el_registro_compartido_anterior_pasa_al_archivo_que_lo_escribio() {
+        let (mut storage, directorio) = storage_de_prueba();
+        storage.agregar_ficha(ficha_con_etiquetas("Pérez", &[])).unwrap();
+        let compartido = directorio.join("auditoria.jsonl");
+        fs::rename(directorio.join("fichas.auditoria.jsonl"), &compartido).unwrap();
+
+        // Otro archivo de datos del mismo directorio no se lo apropia
+        let mut otro = JsonStorage::new(directorio.join("personal.json"));
+        otro.cargar().unwrap();
+        assert!(compartido.exists());
+
+        let mut releido = JsonStorage::new(directorio.join("fichas.json"));
+        releido.cargar().unwrap();
+        assert!(!compartido.exists());
+        assert_eq!(releido.verificar_auditoria().unwrap(), Verificacion::Integra(1));
+        fs::remove_dir_all(directorio).unwrap();
+    }
+
+    #[test]
+    fn renombrar_etiqueta_sin_poder_guardar_deja_todo_como_estaba() {
+        let (mut storage, directorio) = storage_de_prueba();
+        storage.agregar_ficha(ficha_con_etiquetas("Pérez", &["laboral"])).unwrap();
+        storage
+            .asignar_color_etiqueta("laboral", Some([22, 163, 74]))
+            .unwrap();
+
+        // El directorio del archivo de datos pasa a ser un archivo común
+        let bloqueo = directorio.join("bloqueo");
+        fs::write(&bloqueo, "").unwrap();
+        storage.file_path = bloqueo.join("fichas.json");
+
+        assert!(storage.renombrar_etiqueta("laboral", "trabajo", None).is_err());
+        assert_eq!(storage.obtener_etiquetas(), ["laboral"]);
+        assert!(storage.obtener_colores_etiquetas().contains_key("laboral"));
+        assert_eq!(storage.verificar_auditoria().unwrap(), Verificacion::Integra(1));
+        fs::remove_dir_all(directorio).unwrap();
+    }
+
+    #[test]
+    fn sin_poder_guardar_la_auditoria_no_registra_el_cambio() {
+        let (mut storage, directorio) = storage_de_prueba();
+        let ficha = ficha_con_etiquetas("Pérez", &[]);
+        let id = ficha.id;
+        storage.agregar_ficha(ficha.clone()).unwrap();
+        storage.agregar_ficha(ficha_con_etiquetas("Gómez", &[])).unwrap();
+        let ruta = storage.file_path.clone();
+
+        let bloqueo = directorio.join("bloqueo");
+        fs::write(&bloqueo, "").unwrap();
+        storage.file_path = bloqueo.join("fichas.json");
+
+        let mut modificada = ficha.clone();
+        modificada.titulo = "Pérez c/ ACME".into();
+        assert!(storage.actualizar_ficha(modificada).is_err());
+        assert!(storage.eliminar_ficha(id, None).is_err());
+        let hoy = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+        assert!(storage
+            .modificar_ficha(id, None, |f| f.agregar_hoja(hoy, "Audiencia".into(), None, None))
+            .is_err());
+        assert!(storage.agregar_ficha(ficha_con_etiquetas("Ruiz", &[])).is_err());
+
+        assert_eq!(storage.obtener_fichas().len(), 2);
+        assert_eq!(storage.obtener_ficha(id).unwrap().titulo, "Pérez");
+        assert_eq!(storage.obtener_ficha(id).unwrap().hojas.len(), ficha.hojas.len());
+        assert_eq!(storage.verificar_auditoria().unwrap(), Verificacion::Integra(2));
+
+        // Al volver a poder guardar, la cadena sigue desde la última entrada
+        storage.file_path = ruta;
+        assert!(storage.eliminar_ficha(id, None).unwrap());
+        assert_eq!(storage.verificar_auditoria().unwrap(), Verificacion::Integra(3));
+        fs::remove_dir_all(directorio).unwrap();
+    }
+
+    fn ficha_con_asientos(clientes: &[&str]) -> Ficha {
+        let mut ficha = ficha_con_etiquetas("Pérez", &[]);
+        for cliente in clientes {
+            ficha.agregar_asiento(Asiento::new(
+                TipoAsiento::Honorario,
+                format!("Honorarios {}", cliente),
+                Importe(100_000),
+                Moneda::Pesos,
+                NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(),
+                cliente.to_string(),
+            ));
+        }
+        ficha
+    }
+
+    fn seleccion(ficha: &Ficha, cliente: &str) -> Vec<(Uuid, Uuid)> {
+        ficha
+            .cuenta
+            .iter()
+            .filter(|a| a.cliente == cliente)
+            .map(|a| (ficha.id, a.id))
+            .collect()
+    }
+
+    #[test]
+    fn las_liquidaciones_se_numeran_en_orden_y_no_repiten_asientos() {
+        let (mut storage, directorio) = storage_de_prueba();
+        let ficha = ficha_con_asientos(&["Juan Pérez", "Juan Pérez"]);
+        let asientos = seleccion(&ficha, "Juan Pérez");
+        let ficha_id = ficha.id;
+        storage.agregar_ficha(ficha).unwrap();
+        let hoy = NaiveDate::from_ymd_opt(2026, 10, 20).unwrap();
+
+        let primera = storage
+            .reservar_liquidacion("Juan Pérez", &asientos[..1], hoy)
+            .unwrap()
+            .unwrap();
+        storage
+            .registrar_liquidacion(&primera, Vec::new(), None)
+            .unwrap();
+        let segunda = storage
+            .reservar_liquidacion("Juan Pérez", &asientos, hoy)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!((primera.numero, segunda.numero), (1, 2));
+        // La hoja que registra la liquidación lleva el día de emisión
+        let hoja = storage.obtener_ficha(ficha_id).unwrap().hoja_actual().unwrap();
+        assert!(hoja.contenido.contains(&primera.numero_formateado()));
+        assert_eq!(hoja.fecha, hoy);
+        assert_eq!(hoja.categoria.as_deref(), Some(CategoriaHoja::PAGO));
+        assert_eq!(segunda.items.len(), 1);
+        assert_eq!(segunda.items[0].asiento.id, asientos[1].1);
+
+        // Un archivo de datos sin la categoría de pagos
+        storage.categorias.retain(|c| c.id != CategoriaHoja::PAGO);
+        storage
+            .registrar_liquidacion(&segunda, Vec::new(), None)
+            .unwrap();
+        let hoja = storage.obtener_ficha(ficha_id).unwrap().hoja_actual().unwrap();
+        assert!(hoja.contenido.contains(&segunda.numero_formateado()));
+        assert_eq!(hoja.categoria, None);
+        fs::remove_dir_all(directorio).unwrap();
+    }
+
+    #[test]
+    fn no_se_liquidan_asientos_de_otro_cliente() {
+        let (mut storage, directorio) = storage_de_prueba();
+        let ficha = ficha_con_asientos(&["Juan Pérez", "ACME S.A."]);
+        let todos: Vec<_> = ficha.cuenta.iter().map(|a| (ficha.id, a.id)).collect();
+        let propios = seleccion(&ficha, "Juan Pérez");
+        storage.agregar_ficha(ficha).unwrap();
+
+        let hoy = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+        let error = storage
+            .reservar_liquidacion("Juan Pérez", &todos, hoy)
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(error.to_string().contains("ACME S.A."));
+
+        // El número rechazado no se consumió
+        let liquidacion = storage
+            .reservar_liquidacion("Juan Pérez", &propios, hoy)
+            .unwrap()
+            .unwrap();
+        assert_eq!(liquidacion.numero, 1);
+        fs::remove_dir_all(directorio).unwrap();
+    }
+
+    #[test]
+    fn un_archivo_ilegible_no_se_sobrescribe() {
+        let directorio = std::env::temp_dir().join(format!("storage-{}", Uuid::new_v4()));
+        fs::create_dir_all(&directorio).unwrap();
+        let ruta = directorio.join("fichas.json");
+        let contenido = r#"{"fichas": [{"titulo": "Pérez", "#;
+        fs::write(&ruta, contenido).unwrap();
+
+        let mut storage = JsonStorage::new(ruta.clone());
+        assert!(storage.cargar().is_err());
+        assert!(storage.error_carga().is_some());
+        assert!(storage
+            .agregar_ficha(ficha_con_etiquetas("Gómez", &[]))
+            .is_err());
+        assert_eq!(fs::read_to_string(&ruta).unwrap(), contenido);
+
+        // Una vez corregido el archivo, se vuelve a guardar
+        fs::write(&ruta, "[]").unwrap();
+        storage.cargar().unwrap();
+        assert!(storage.error_carga().is_none());
+        storage.agregar_ficha(ficha_con_etiquetas("Gómez", &[])).unwrap();
+        fs::remove_dir_all(directorio).unwrap();
+    }
+
+    #[test]
+    fn guardar_no_deja_archivos_temporales() {
+        let (mut storage, directorio) = storage_de_prueba();
+        storage.agregar_ficha(ficha_con_etiquetas("Pérez", &[])).unwrap();
+
+        let archivos: Vec<_> = fs::read_dir(&directorio)
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .filter(|n| n.to_string_lossy().ends_with(".json"))
+            .collect();
+        assert_eq!(archivos, ["fichas.json"]);
+        assert!(!directorio.join("fichas.json.tmp").exists());
+        fs::remove_dir_all(directorio).unwrap();
+    }
 }
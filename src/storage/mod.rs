@@ -1,3 +1,7 @@
+pub mod auditoria;
 pub mod json_storage;
+pub mod preferencias;
 
+pub use auditoria::{Operacion, RegistroAuditoria, Verificacion};
 pub use json_storage::JsonStorage;
+pub use preferencias::{cargar_preferencias, guardar_preferencias};
@@ -0,0 +1,125 @@
+use crate::models::Preferencias;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub enum ErrorPreferencias {
+    Lectura(PathBuf, io::Error),
+    /// El archivo no es JSON o tiene campos desconocidos o de otro tipo
+    Formato(PathBuf, serde_json::Error),
+    Invalidas(PathBuf, Vec<String>),
+}
+
+impl fmt::Display for ErrorPreferencias {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorPreferencias::Lectura(ruta, e) => {
+                write!(f, "No se pudo leer {}: {}", ruta.display(), e)
+            }
+            ErrorPreferencias::Formato(ruta, e) => write!(
+                f,
+                "{} tiene un error en la línea {}, columna {}: {}",
+                ruta.display(),
+                e.line(),
+                e.column(),
+                e
+            ),
+            ErrorPreferencias::Invalidas(ruta, errores) => {
+                write!(
+                    f,
+                    "{} tiene valores inválidos: {}",
+                    ruta.display(),
+                    errores.join("; ")
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ErrorPreferencias {}
+
+/// Lee y valida las preferencias; si el archivo no existe se usan las
+/// predeterminadas
+pub fn cargar_preferencias(ruta: &Path) -> Result<Preferencias, ErrorPreferencias> {
+    if !ruta.exists() {
+        return Ok(Preferencias::default());
+    }
+
+    let contenido =
+        fs::read_to_string(ruta).map_err(|e| ErrorPreferencias::Lectura(ruta.into(), e))?;
+    let preferencias: Preferencias =
+        serde_json::from_str(&contenido).map_err(|e| ErrorPreferencias::Formato(ruta.into(), e))?;
+
+    let errores = preferencias.validar();
+    if !errores.is_empty() {
+        return Err(ErrorPreferencias::Invalidas(ruta.into(), errores));
+    }
+    Ok(preferencias)
+}
+
+pub fn guardar_preferencias(ruta: &Path, preferencias: &Preferencias) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(preferencias)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    if let Some(parent) = ruta.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(ruta, json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    /// Escribe `contenido` como archivo de preferencias y lo carga
+    fn cargar(contenido: &str) -> Result<Preferencias, ErrorPreferencias> {
+        let ruta = std::env::temp_dir().join(format!("preferencias-{}.json", Uuid::new_v4()));
+        fs::write(&ruta, contenido).unwrap();
+        let resultado = cargar_preferencias(&ruta);
+        fs::remove_file(ruta).unwrap();
+        resultado
+    }
+
+    #[test]
+    fn los_campos_que_faltan_toman_el_valor_predeterminado() {
+        let preferencias = cargar(r#"{"zona_horaria": "America/Argentina/Salta"}"#).unwrap();
+        assert_eq!(preferencias.zona_horaria, "America/Argentina/Salta");
+        assert_eq!(preferencias.formato_fecha, Preferencias::default().formato_fecha);
+
+        let inexistente = std::env::temp_dir().join(format!("{}.json", Uuid::new_v4()));
+        assert_eq!(
+            cargar_preferencias(&inexistente).unwrap(),
+            Preferencias::default()
+        );
+    }
+
+    #[test]
+    fn rechaza_campos_desconocidos_tambien_dentro_de_las_secciones() {
+        let error = cargar(r#"{"formato_fehca": "%d/%m/%Y"}"#).unwrap_err();
+        assert!(matches!(error, ErrorPreferencias::Formato(..)));
+        assert!(error.to_string().contains("formato_fehca"), "{}", error);
+
+        let error = cargar(r#"{"respaldo": {"conservar": 3, "carpeta": "copias"}}"#).unwrap_err();
+        assert!(error.to_string().contains("carpeta"), "{}", error);
+    }
+
+    #[test]
+    fn indica_la_linea_de_un_json_mal_formado() {
+        let error = cargar("{\n  \"escala\": 1.5,\n  \"tema\": oscuro\n}").unwrap_err();
+        assert!(matches!(error, ErrorPreferencias::Formato(..)));
+        assert!(error.to_string().contains("línea 3"), "{}", error);
+    }
+
+    #[test]
+    fn rechaza_valores_invalidos_con_todos_sus_motivos() {
+        let error = cargar(r#"{"escala": 5, "zona_horaria": "Marte/Olympus"}"#).unwrap_err();
+        let ErrorPreferencias::Invalidas(_, errores) = &error else {
+            panic!("se esperaban valores inválidos: {}", error);
+        };
+        assert_eq!(errores.len(), 2);
+        assert!(error.to_string().contains("Marte/Olympus"), "{}", error);
+    }
+}
@@ -0,0 +1,34 @@
+use iced::widget::{row, text};
+use iced::Element;
+
+/// Tamaños de letra de la interfaz. Cada vista guarda el que le pasa la
+/// aplicación al aplicar las preferencias y lo usa al dibujarse.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Letra {
+    /// Tamaño mínimo configurado en las preferencias
+    pub minimo: u16,
+}
+
+impl Letra {
+    pub fn new(minimo: u16) -> Self {
+        Self { minimo }
+    }
+
+    /// Tamaño de letra pedido por la vista, sin bajar del mínimo configurado
+    pub fn tamanio(self, pedido: u16) -> u16 {
+        pedido.max(self.minimo)
+    }
+}
+
+/// Contenido de un botón que antes mostraba solo un símbolo: el símbolo y
+/// su nombre a la vista. iced todavía no expone los controles a los
+/// lectores de pantalla, así que el nombre tiene que estar escrito.
+pub fn rotulo<'a, Message: 'a>(
+    simbolo: &'a str,
+    nombre: impl text::IntoFragment<'a>,
+    tamanio: u16,
+) -> Element<'a, Message> {
+    row![text(simbolo).size(tamanio), text(nombre).size(tamanio)]
+        .spacing(4)
+        .into()
+}
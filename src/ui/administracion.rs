@@ -0,0 +1,169 @@
+use crate::i18n;
+use crate::models::{Idioma, Rol, Usuario};
+use crate::ui::accesibilidad::{self, Letra};
+use crate::ui::etiquetas::PALETA;
+use crate::ui::styles;
+use crate::ui::usuarios::{self, avatar};
+use iced::widget::{
+    button, column, container, pick_list, row, scrollable, text, text_input, Column,
+};
+use iced::{Element, Length};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub enum AdministracionMessage {
+    Volver,
+    CambiarRol(Uuid, Rol),
+    EliminarUsuario(Uuid),
+    NombreChanged(String),
+    InicialesChanged(String),
+    ColorSeleccionado([u8; 3]),
+    RolSeleccionado(Rol),
+    CrearUsuario,
+}
+
+/// Pantalla de administración de usuarios y roles, solo para administradores
+#[derive(Debug)]
+pub struct AdministracionView {
+    pub usuarios: Vec<Usuario>,
+    pub usuario_actual: Option<Uuid>,
+    pub nombre_input: String,
+    pub iniciales_input: String,
+    pub color: [u8; 3],
+    pub rol: Rol,
+    /// Resultado de la última operación
+    pub mensaje: Option<String>,
+    pub idioma: Idioma,
+    pub letra: Letra,
+}
+
+impl AdministracionView {
+    pub fn new() -> Self {
+        Self {
+            usuarios: Vec::new(),
+            usuario_actual: None,
+            nombre_input: String::new(),
+            iniciales_input: String::new(),
+            color: PALETA[5],
+            rol: Rol::default(),
+            mensaje: None,
+            idioma: Idioma::default(),
+            letra: Letra::default(),
+        }
+    }
+
+    pub fn actualizar_usuarios(&mut self, usuarios: Vec<Usuario>) {
+        self.usuarios = usuarios;
+    }
+
+    pub fn limpiar_formulario(&mut self) {
+        self.nombre_input.clear();
+        self.iniciales_input.clear();
+        self.rol = Rol::default();
+    }
+
+    pub fn obtener_usuario(&self) -> Result<Usuario, String> {
+        usuarios::validar_usuario(
+            &self.usuarios,
+            &self.nombre_input,
+            &self.iniciales_input,
+            self.color,
+            self.rol,
+        )
+    }
+
+    pub fn view(&self) -> Element<'_, AdministracionMessage> {
+        let boton_volver = button(text(i18n::texto(self.idioma, "volver")))
+            .on_press(AdministracionMessage::Volver)
+            .padding(10)
+            .style(styles::secondary_button);
+
+        let titulo = i18n::texto(self.idioma, "administracion-titulo");
+        let header = row![boton_volver, text(titulo).size(self.letra.tamanio(24))]
+            .spacing(20)
+            .padding(10);
+
+        let lista =
+            self.usuarios
+                .iter()
+                .fold(Column::new().spacing(6), |lista, usuario| {
+                    let es_actual = Some(usuario.id) == self.usuario_actual;
+                    let usuario_id = usuario.id;
+                    lista.push(
+                        container(
+                            row![
+                                avatar(usuario, self.letra),
+                                text(&usuario.nombre)
+                                    .size(self.letra.tamanio(14))
+                                    .width(Length::Fill),
+                                pick_list(Rol::TODOS, Some(usuario.rol), move |rol| {
+                                    AdministracionMessage::CambiarRol(usuario_id, rol)
+                                })
+                                .padding(6),
+                                button(accesibilidad::rotulo(
+                                    "✕",
+                                    "Eliminar usuario",
+                                    self.letra.tamanio(12),
+                                ))
+                                .on_press_maybe((!es_actual).then_some(
+                                    AdministracionMessage::EliminarUsuario(usuario_id),
+                                ))
+                                .padding([2, 8])
+                                .style(styles::cancel_button),
+                            ]
+                            .spacing(10)
+                            .align_y(iced::Alignment::Center),
+                        )
+                        .padding(8)
+                        .style(styles::card_container),
+                    )
+                });
+
+        let formulario = container(
+            column![
+                text(i18n::texto(self.idioma, "administracion-nuevo")).size(self.letra.tamanio(18)),
+                text_input("Nombre y apellido", &self.nombre_input)
+                    .on_input(AdministracionMessage::NombreChanged)
+                    .on_submit(AdministracionMessage::CrearUsuario)
+                    .padding(8)
+                    .style(styles::campo_texto),
+                row![
+                    text_input("Iniciales (opcional)", &self.iniciales_input)
+                        .on_input(AdministracionMessage::InicialesChanged)
+                        .padding(8)
+                        .width(Length::Fixed(160.0))
+                        .style(styles::campo_texto),
+                    pick_list(
+                        Rol::TODOS,
+                        Some(self.rol),
+                        AdministracionMessage::RolSeleccionado
+                    )
+                    .padding(8),
+                ]
+                .spacing(10),
+                usuarios::selector_color(
+                    self.color,
+                    AdministracionMessage::ColorSeleccionado,
+                    self.letra,
+                ),
+                button(text(i18n::texto(self.idioma, "administracion-crear")))
+                    .on_press(AdministracionMessage::CrearUsuario)
+                    .padding(8)
+                    .style(styles::primary_button),
+            ]
+            .spacing(10),
+        )
+        .padding(15)
+        .width(Length::FillPortion(1))
+        .style(styles::card_container);
+
+        column![
+            header,
+            text(self.mensaje.as_deref().unwrap_or_default()).size(self.letra.tamanio(12)),
+            row![scrollable(lista).width(Length::FillPortion(1)), formulario].spacing(20),
+        ]
+        .spacing(10)
+        .padding(20)
+        .into()
+    }
+}
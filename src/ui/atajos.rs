@@ -0,0 +1,64 @@
+use iced::event::{self, Event};
+use iced::keyboard::{self, key::Named, Key};
+use iced::window;
+
+/// Acciones disponibles desde el teclado en toda la aplicación.
+///
+/// | Atajo                 | Acción                                   |
+/// |-----------------------|------------------------------------------|
+/// | Ctrl+K                | Paleta de comandos                       |
+/// | Ctrl+N                | Nueva ficha                              |
+/// | Ctrl+F o /            | Ir a la búsqueda                         |
+/// | ↓ → / ↑ ←             | Ficha siguiente / anterior de la lista   |
+/// | Enter                 | Abrir las hojas de la ficha seleccionada |
+/// | Ctrl+Enter            | Agregar la hoja escrita                  |
+/// | Ctrl+S                | Guardar el formulario                    |
+/// | Esc                   | Cancelar la edición o cerrar la paleta   |
+/// | Alt+←                 | Volver a la pantalla anterior            |
+/// | Tab / Shift+Tab       | Campo siguiente / anterior               |
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Atajo {
+    Paleta,
+    NuevaFicha,
+    Buscar,
+    Siguiente,
+    Anterior,
+    AbrirHistorial,
+    AgregarHoja,
+    Guardar,
+    Cancelar,
+    Volver,
+    CampoSiguiente,
+    CampoAnterior,
+}
+
+/// Traduce una tecla al atajo correspondiente.
+///
+/// Las combinaciones con Ctrl (Cmd en macOS), Esc, Tab y las flechas verticales se
+/// atienden aunque un campo de texto tenga el foco; el resto solo cuando
+/// ningún widget usó la tecla, para no interferir con la escritura.
+pub fn atajo(evento: Event, estado: event::Status, _ventana: window::Id) -> Option<Atajo> {
+    let Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) = evento else {
+        return None;
+    };
+    let libre = estado == event::Status::Ignored;
+
+    match key.as_ref() {
+        Key::Character("k") if modifiers.command() => Some(Atajo::Paleta),
+        Key::Character("n") if modifiers.command() => Some(Atajo::NuevaFicha),
+        Key::Character("f") if modifiers.command() => Some(Atajo::Buscar),
+        Key::Character("s") if modifiers.command() => Some(Atajo::Guardar),
+        Key::Named(Named::Enter) if modifiers.command() => Some(Atajo::AgregarHoja),
+        Key::Named(Named::Escape) => Some(Atajo::Cancelar),
+        Key::Named(Named::ArrowLeft) if modifiers.alt() => Some(Atajo::Volver),
+        Key::Named(Named::Tab) if modifiers.shift() => Some(Atajo::CampoAnterior),
+        Key::Named(Named::Tab) => Some(Atajo::CampoSiguiente),
+        Key::Named(Named::ArrowDown) => Some(Atajo::Siguiente),
+        Key::Named(Named::ArrowUp) => Some(Atajo::Anterior),
+        Key::Named(Named::ArrowRight) if libre => Some(Atajo::Siguiente),
+        Key::Named(Named::ArrowLeft) if libre => Some(Atajo::Anterior),
+        Key::Named(Named::Enter) if libre => Some(Atajo::AbrirHistorial),
+        Key::Character("/") if libre => Some(Atajo::Buscar),
+        _ => None,
+    }
+}
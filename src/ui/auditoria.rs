@@ -0,0 +1,242 @@
+use crate::i18n;
+use crate::models::{Idioma, Usuario};
+use crate::storage::auditoria::Cambio;
+use crate::storage::{Operacion, RegistroAuditoria};
+use crate::ui::accesibilidad::{self, Letra};
+use crate::ui::fechas::FormatoFecha;
+use crate::ui::styles;
+use iced::widget::{
+    button, column, container, pick_list, row, scrollable, text, text_input, Column, Space,
+};
+use iced::{Element, Length};
+use uuid::Uuid;
+
+/// Entradas que se muestran como máximo, las más recientes
+const MAXIMO_VISIBLES: usize = 200;
+
+#[derive(Debug, Clone)]
+pub enum AuditoriaMessage {
+    Volver,
+    TextoChanged(String),
+    FiltrarOperacion(Operacion),
+    FiltrarUsuario(Usuario),
+    QuitarFiltros,
+    Verificar,
+}
+
+/// Registro de auditoría de fichas y hojas, con filtros y verificación
+#[derive(Debug)]
+pub struct AuditoriaView {
+    pub registros: Vec<RegistroAuditoria>,
+    pub usuarios: Vec<Usuario>,
+    pub filtro_texto: String,
+    pub filtro_operacion: Option<Operacion>,
+    pub filtro_usuario: Option<Uuid>,
+    /// Resultado de la última verificación o error de lectura
+    pub mensaje: Option<String>,
+    pub fechas: FormatoFecha,
+    pub idioma: Idioma,
+    pub letra: Letra,
+}
+
+impl AuditoriaView {
+    pub fn new() -> Self {
+        Self {
+            registros: Vec::new(),
+            usuarios: Vec::new(),
+            filtro_texto: String::new(),
+            filtro_operacion: None,
+            filtro_usuario: None,
+            mensaje: None,
+            fechas: FormatoFecha::default(),
+            idioma: Idioma::default(),
+            letra: Letra::default(),
+        }
+    }
+
+    pub fn actualizar(&mut self, registros: Vec<RegistroAuditoria>, usuarios: Vec<Usuario>) {
+        self.registros = registros;
+        self.usuarios = usuarios;
+    }
+
+    pub fn quitar_filtros(&mut self) {
+        self.filtro_texto.clear();
+        self.filtro_operacion = None;
+        self.filtro_usuario = None;
+    }
+
+    fn nombre_usuario(&self, usuario_id: Option<Uuid>) -> &str {
+        self.usuarios
+            .iter()
+            .find(|u| Some(u.id) == usuario_id)
+            .map_or("—", |u| u.nombre.as_str())
+    }
+
+    fn coincide(&self, registro: &RegistroAuditoria) -> bool {
+        let texto = self.filtro_texto.trim().to_lowercase();
+        let coincide_texto = texto.is_empty()
+            || registro.ficha_titulo.to_lowercase().contains(&texto)
+            || registro.cambios.iter().any(|c| {
+                [Some(&c.campo), c.antes.as_ref(), c.despues.as_ref()]
+                    .into_iter()
+                    .flatten()
+                    .any(|valor| valor.to_lowercase().contains(&texto))
+            });
+
+        coincide_texto
+            && self
+                .filtro_operacion
+                .is_none_or(|operacion| registro.operacion == operacion)
+            && self
+                .filtro_usuario
+                .is_none_or(|usuario| registro.usuario == Some(usuario))
+    }
+
+    pub fn view(&self) -> Element<'_, AuditoriaMessage> {
+        let boton_volver = button(text(i18n::texto(self.idioma, "volver")))
+            .on_press(AuditoriaMessage::Volver)
+            .padding(10)
+            .style(styles::secondary_button);
+
+        let boton_verificar = button(text(i18n::texto(self.idioma, "auditoria-verificar")))
+            .on_press(AuditoriaMessage::Verificar)
+            .padding(10)
+            .style(styles::primary_button);
+
+        let header = row![
+            boton_volver,
+            text(i18n::texto(self.idioma, "auditoria-titulo")).size(self.letra.tamanio(24)),
+            Space::with_width(Length::Fill),
+            boton_verificar
+        ]
+        .spacing(20)
+        .padding(10);
+
+        let hay_filtros = !self.filtro_texto.is_empty()
+            || self.filtro_operacion.is_some()
+            || self.filtro_usuario.is_some();
+        let usuario_filtrado = self
+            .usuarios
+            .iter()
+            .find(|u| Some(u.id) == self.filtro_usuario)
+            .cloned();
+
+        let filtros = row![
+            text_input("Buscar en fichas y cambios", &self.filtro_texto)
+                .on_input(AuditoriaMessage::TextoChanged)
+                .padding(8)
+                .style(styles::campo_texto),
+            pick_list(
+                Operacion::TODAS,
+                self.filtro_operacion,
+                AuditoriaMessage::FiltrarOperacion
+            )
+            .placeholder("Operación")
+            .padding(8),
+            pick_list(
+                self.usuarios.as_slice(),
+                usuario_filtrado,
+                AuditoriaMessage::FiltrarUsuario
+            )
+            .placeholder("Usuario")
+            .padding(8),
+            button(accesibilidad::rotulo("✕", "Quitar filtros", self.letra.tamanio(16)))
+                .on_press_maybe(hay_filtros.then_some(AuditoriaMessage::QuitarFiltros))
+                .padding(8),
+        ]
+        .spacing(10);
+
+        let visibles: Vec<&RegistroAuditoria> = self
+            .registros
+            .iter()
+            .rev()
+            .filter(|r| self.coincide(r))
+            .collect();
+
+        let lista: Element<_> = if visibles.is_empty() {
+            text(i18n::texto(self.idioma, "auditoria-vacia")).into()
+        } else {
+            let total = visibles.len();
+            visibles
+                .into_iter()
+                .take(MAXIMO_VISIBLES)
+                .fold(Column::new().spacing(6), |lista, registro| {
+                    lista.push(self.vista_registro(registro))
+                })
+                .push_maybe((total > MAXIMO_VISIBLES).then(|| {
+                    text(format!(
+                        "Se muestran las {} más recientes de {}; use los filtros para ver otras",
+                        MAXIMO_VISIBLES, total
+                    ))
+                    .size(self.letra.tamanio(12))
+                }))
+                .into()
+        };
+
+        column![
+            header,
+            filtros,
+            text(self.mensaje.as_deref().unwrap_or_default()).size(self.letra.tamanio(13)),
+            scrollable(lista),
+        ]
+        .spacing(10)
+        .padding(20)
+        .into()
+    }
+
+    fn vista_registro<'a>(
+        &'a self,
+        registro: &'a RegistroAuditoria,
+    ) -> Element<'a, AuditoriaMessage> {
+        let encabezado = row![
+            text(format!("#{}", registro.secuencia))
+                .size(self.letra.tamanio(12))
+                .width(Length::Fixed(50.0)),
+            text(self.fechas.fecha_hora(registro.fecha))
+                .size(self.letra.tamanio(13))
+                .width(Length::Fixed(140.0)),
+            text(self.nombre_usuario(registro.usuario))
+                .size(self.letra.tamanio(13))
+                .width(Length::Fixed(150.0)),
+            text(registro.operacion.to_string())
+                .size(self.letra.tamanio(13))
+                .width(Length::Fixed(130.0)),
+            text(&registro.ficha_titulo).size(self.letra.tamanio(13)).width(Length::Fill),
+        ]
+        .spacing(10);
+
+        let cambios = registro
+            .cambios
+            .iter()
+            .fold(Column::new().spacing(2), |lista, cambio| {
+                lista.push(text(describir_cambio(cambio)).size(self.letra.tamanio(12)))
+            });
+
+        container(column![encabezado, cambios].spacing(6))
+            .padding(8)
+            .width(Length::Fill)
+            .style(styles::card_container)
+            .into()
+    }
+}
+
+/// Una línea con el campo y sus valores anterior y nuevo, recortados
+fn describir_cambio(cambio: &Cambio) -> String {
+    let recortar = |valor: &Option<String>| match valor {
+        Some(valor) => {
+            let plano = valor.split_whitespace().collect::<Vec<_>>().join(" ");
+            if plano.chars().count() > 80 {
+                format!("{}…", plano.chars().take(80).collect::<String>())
+            } else {
+                plano
+            }
+        }
+        None => String::from("∅"),
+    };
+    format!(
+        "{}: {} → {}",
+        cambio.campo,
+        recortar(&cambio.antes),
+        recortar(&cambio.despues)
+    )
+}
@@ -0,0 +1,151 @@
+use crate::i18n;
+use crate::models::cuenta::Totales;
+use crate::models::{Idioma, Moneda};
+use crate::ui::accesibilidad::Letra;
+use crate::ui::styles;
+use iced::widget::{button, column, container, row, scrollable, text, Column, Space};
+use iced::{Element, Length};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone)]
+pub enum BalanceMessage {
+    Volver,
+    NuevaLiquidacion,
+}
+
+/// Totales de gastos, honorarios y pagos de todas las fichas
+#[derive(Debug)]
+pub struct BalancesView {
+    pub por_cliente: BTreeMap<(String, Moneda), Totales>,
+    pub por_mes: BTreeMap<(String, Moneda), Totales>,
+    /// Si el rol del usuario actual permite emitir liquidaciones
+    pub puede_liquidar: bool,
+    pub idioma: Idioma,
+    pub letra: Letra,
+}
+
+impl BalancesView {
+    pub fn new() -> Self {
+        Self {
+            por_cliente: BTreeMap::new(),
+            por_mes: BTreeMap::new(),
+            puede_liquidar: false,
+            idioma: Idioma::default(),
+            letra: Letra::default(),
+        }
+    }
+
+    pub fn actualizar_totales(
+        &mut self,
+        por_cliente: BTreeMap<(String, Moneda), Totales>,
+        por_mes: BTreeMap<(String, Moneda), Totales>,
+    ) {
+        self.por_cliente = por_cliente;
+        self.por_mes = por_mes;
+    }
+
+    pub fn view(&self) -> Element<'_, BalanceMessage> {
+        let boton_volver = button(text(i18n::texto(self.idioma, "volver")))
+            .on_press(BalanceMessage::Volver)
+            .padding(10)
+            .style(styles::secondary_button);
+
+        let boton_liquidar = button(text(i18n::texto(self.idioma, "balances-liquidar")))
+            .on_press_maybe(
+                self.puede_liquidar
+                    .then_some(BalanceMessage::NuevaLiquidacion),
+            )
+            .padding(10)
+            .style(styles::primary_button);
+
+        let header = row![
+            boton_volver,
+            text(i18n::texto(self.idioma, "balances-titulo")).size(self.letra.tamanio(24)),
+            Space::with_width(Length::Fill),
+            boton_liquidar
+        ]
+        .spacing(20)
+        .padding(10);
+
+        let tablas = row![
+            tabla(
+                i18n::texto(self.idioma, "balances-por-cliente"),
+                i18n::texto(self.idioma, "balances-cliente"),
+                &self.por_cliente,
+                self.idioma,
+                self.letra
+            ),
+            tabla(
+                i18n::texto(self.idioma, "balances-por-mes"),
+                i18n::texto(self.idioma, "balances-mes"),
+                &self.por_mes,
+                self.idioma,
+                self.letra
+            ),
+        ]
+        .spacing(20);
+
+        column![header, scrollable(tablas)]
+            .spacing(10)
+            .padding(20)
+            .into()
+    }
+}
+
+fn tabla<'a>(
+    titulo: String,
+    columna: String,
+    totales: &'a BTreeMap<(String, Moneda), Totales>,
+    idioma: Idioma,
+    letra: Letra,
+) -> Element<'a, BalanceMessage> {
+    let encabezado = row![
+        text(columna).size(letra.tamanio(13)).width(Length::Fill),
+        text(i18n::texto(idioma, "balances-moneda"))
+            .size(letra.tamanio(13))
+            .width(Length::Fixed(60.0)),
+        text(i18n::texto(idioma, "balances-cargos"))
+            .size(letra.tamanio(13))
+            .width(Length::Fixed(110.0)),
+        text(i18n::texto(idioma, "balances-pagos"))
+            .size(letra.tamanio(13))
+            .width(Length::Fixed(110.0)),
+        text(i18n::texto(idioma, "balances-saldo"))
+            .size(letra.tamanio(13))
+            .width(Length::Fixed(110.0)),
+    ]
+    .spacing(10);
+
+    let filas = totales.iter().fold(
+        Column::new().spacing(4).push(encabezado),
+        |filas, ((clave, moneda), t)| {
+            let clave = if clave.is_empty() {
+                "Sin cliente"
+            } else {
+                clave.as_str()
+            };
+            filas.push(
+                row![
+                    text(clave).size(letra.tamanio(13)).width(Length::Fill),
+                    text(moneda.to_string()).size(letra.tamanio(13)).width(Length::Fixed(60.0)),
+                    text(t.cargos.to_string())
+                        .size(letra.tamanio(13))
+                        .width(Length::Fixed(110.0)),
+                    text(t.pagos.to_string())
+                        .size(letra.tamanio(13))
+                        .width(Length::Fixed(110.0)),
+                    text(t.saldo().to_string())
+                        .size(letra.tamanio(13))
+                        .width(Length::Fixed(110.0)),
+                ]
+                .spacing(10),
+            )
+        },
+    );
+
+    container(column![text(titulo).size(letra.tamanio(18)), filas].spacing(10))
+        .padding(15)
+        .width(Length::FillPortion(1))
+        .style(styles::card_container)
+        .into()
+}
@@ -0,0 +1,256 @@
+use crate::i18n;
+use crate::models::{CategoriaHoja, Idioma};
+use crate::ui::accesibilidad::Letra;
+use crate::ui::etiquetas::PALETA;
+use crate::ui::styles;
+use iced::widget::{
+    button, column, container, row, scrollable, text, text_input, Column, Row, Space,
+};
+use iced::{Element, Length};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub enum CategoriaMessage {
+    Volver,
+    Seleccionar(String),
+    NuevaCategoria,
+    NombreChanged(String),
+    IconoChanged(String),
+    ColorSeleccionado([u8; 3]),
+    Guardar,
+    Eliminar,
+}
+
+#[derive(Debug)]
+pub struct CategoriasView {
+    pub categorias: Vec<CategoriaHoja>,
+    /// Cantidad de hojas de cada categoría
+    pub usos: BTreeMap<String, usize>,
+    pub editando: bool,
+    /// Categoría que se edita; `None` si es una nueva
+    pub categoria_editando_id: Option<String>,
+    pub nombre_input: String,
+    pub icono_input: String,
+    pub color: [u8; 3],
+    /// Resultado de la última operación o error del formulario
+    pub mensaje: Option<String>,
+    pub idioma: Idioma,
+    pub letra: Letra,
+}
+
+impl CategoriasView {
+    pub fn new() -> Self {
+        Self {
+            categorias: Vec::new(),
+            usos: BTreeMap::new(),
+            editando: false,
+            categoria_editando_id: None,
+            nombre_input: String::new(),
+            icono_input: String::new(),
+            color: PALETA[5],
+            mensaje: None,
+            idioma: Idioma::default(),
+            letra: Letra::default(),
+        }
+    }
+
+    pub fn actualizar_categorias(
+        &mut self,
+        categorias: Vec<CategoriaHoja>,
+        usos: BTreeMap<String, usize>,
+    ) {
+        self.categorias = categorias;
+        self.usos = usos;
+    }
+
+    pub fn iniciar_nueva_categoria(&mut self) {
+        self.editando = true;
+        self.categoria_editando_id = None;
+        self.nombre_input.clear();
+        self.icono_input.clear();
+        self.color = PALETA[5];
+        self.mensaje = None;
+    }
+
+    pub fn iniciar_edicion(&mut self, categoria_id: &str) {
+        if let Some(categoria) = self.categorias.iter().find(|c| c.id == categoria_id) {
+            self.editando = true;
+            self.categoria_editando_id = Some(categoria.id.clone());
+            self.nombre_input = categoria.nombre.clone();
+            self.icono_input = categoria.icono.clone();
+            self.color = categoria.color;
+            self.mensaje = None;
+        }
+    }
+
+    pub fn cancelar_edicion(&mut self) {
+        self.editando = false;
+        self.categoria_editando_id = None;
+        self.nombre_input.clear();
+        self.icono_input.clear();
+    }
+
+    /// Construye la categoría a partir del formulario, nueva o editada
+    pub fn obtener_categoria(&self) -> Result<CategoriaHoja, String> {
+        let nombre = self.nombre_input.trim();
+        let icono = self.icono_input.trim();
+        if nombre.is_empty() {
+            return Err("La categoría necesita un nombre".to_string());
+        }
+        if icono.is_empty() {
+            return Err("La categoría necesita un ícono (un símbolo o una letra)".to_string());
+        }
+        let repetida = self.categorias.iter().any(|c| {
+            c.nombre.eq_ignore_ascii_case(nombre)
+                && Some(&c.id) != self.categoria_editando_id.as_ref()
+        });
+        if repetida {
+            return Err(format!("Ya existe una categoría \"{}\"", nombre));
+        }
+
+        let id = self
+            .categoria_editando_id
+            .clone()
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        Ok(CategoriaHoja::new(&id, nombre, icono, self.color))
+    }
+
+    pub fn view(&self) -> Element<'_, CategoriaMessage> {
+        let header = row![
+            button(text(i18n::texto(self.idioma, "volver")))
+                .on_press(CategoriaMessage::Volver)
+                .padding(10)
+                .style(styles::secondary_button),
+            text(i18n::texto(self.idioma, "categorias-titulo")).size(self.letra.tamanio(24)),
+            Space::with_width(Length::Fill),
+            button(text(i18n::texto(self.idioma, "categorias-nueva")))
+                .on_press(CategoriaMessage::NuevaCategoria)
+                .padding(10)
+                .style(styles::primary_button),
+        ]
+        .spacing(20)
+        .padding(10);
+
+        let lista = self
+            .categorias
+            .iter()
+            .fold(Column::new().spacing(6), |lista, categoria| {
+                let activa = self.categoria_editando_id.as_ref() == Some(&categoria.id);
+                let usos = self.usos.get(&categoria.id).copied().unwrap_or_default();
+
+                lista.push(
+                    button(
+                        row![
+                            container(text(categoria.to_string()).size(self.letra.tamanio(13)))
+                                .padding([2, 8])
+                                .style(styles::etiqueta(categoria.color)),
+                            Space::with_width(Length::Fill),
+                            text(format!("{} hojas", usos)).size(self.letra.tamanio(12)),
+                        ]
+                        .spacing(10),
+                    )
+                    .on_press(CategoriaMessage::Seleccionar(categoria.id.clone()))
+                    .width(Length::Fill)
+                    .padding(8)
+                    .style(if activa {
+                        styles::primary_button
+                    } else {
+                        button::text
+                    }),
+                )
+            });
+
+        column![
+            header,
+            row![
+                container(scrollable(lista)).width(Length::FillPortion(1)),
+                container(self.vista_edicion())
+                    .padding(15)
+                    .width(Length::FillPortion(1))
+                    .style(styles::card_container),
+            ]
+            .spacing(20),
+        ]
+        .spacing(10)
+        .padding(20)
+        .into()
+    }
+
+    fn vista_edicion(&self) -> Element<'_, CategoriaMessage> {
+        let mensaje =
+            text(self.mensaje.as_deref().unwrap_or_default()).size(self.letra.tamanio(12));
+        if !self.editando {
+            return column![text(i18n::texto(self.idioma, "categorias-ayuda")), mensaje,]
+                .spacing(10)
+                .into();
+        }
+
+        let titulo = if self.categoria_editando_id.is_some() {
+            "Editar categoría"
+        } else {
+            "Nueva categoría"
+        };
+
+        let colores = PALETA.iter().fold(Row::new().spacing(6), |fila, color| {
+            let marcado = *color == self.color;
+            fila.push(
+                button(container(Space::new(16, 16)).style(styles::etiqueta(*color)))
+                    .on_press(CategoriaMessage::ColorSeleccionado(*color))
+                    .padding(2)
+                    .style(if marcado {
+                        styles::primary_button
+                    } else {
+                        button::text
+                    }),
+            )
+        });
+
+        let muestra = container(
+            text(format!(
+                "{} {}",
+                self.icono_input.trim(),
+                self.nombre_input.trim()
+            ))
+            .size(self.letra.tamanio(13)),
+        )
+        .padding([2, 8])
+        .style(styles::etiqueta(self.color));
+
+        let eliminar = self.categoria_editando_id.as_ref().map(|_| {
+            button(text(i18n::texto(self.idioma, "eliminar")))
+                .on_press(CategoriaMessage::Eliminar)
+                .padding(8)
+                .style(styles::cancel_button)
+        });
+
+        column![
+            text(titulo).size(self.letra.tamanio(18)),
+            text(i18n::texto(self.idioma, "campo-nombre")),
+            text_input("Escrito presentado", &self.nombre_input)
+                .on_input(CategoriaMessage::NombreChanged)
+                .on_submit(CategoriaMessage::Guardar)
+                .padding(8)
+                .style(styles::campo_texto),
+            text(i18n::texto(self.idioma, "categorias-icono")),
+            text_input("✎", &self.icono_input)
+                .on_input(CategoriaMessage::IconoChanged)
+                .on_submit(CategoriaMessage::Guardar)
+                .padding(8)
+                .width(Length::Fixed(80.0))
+                .style(styles::campo_texto),
+            text(i18n::texto(self.idioma, "campo-color")),
+            colores,
+            muestra,
+            row![button(text(i18n::texto(self.idioma, "guardar")))
+                .on_press(CategoriaMessage::Guardar)
+                .padding(8)
+                .style(styles::primary_button)]
+            .push_maybe(eliminar)
+            .spacing(10),
+            mensaje,
+        ]
+        .spacing(10)
+        .into()
+    }
+}
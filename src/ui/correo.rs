@@ -0,0 +1,340 @@
+use crate::correo::Resumen;
+use crate::i18n;
+use crate::models::{CategoriaHoja, Ficha, Idioma, ReglaCorreo};
+use crate::ui::accesibilidad::{self, Letra};
+use crate::ui::styles;
+use iced::widget::{
+    button, column, container, pick_list, row, scrollable, text, text_input, Column, Space,
+};
+use iced::{Element, Length};
+use std::fmt;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub enum CorreoMessage {
+    Volver,
+    RutaChanged(String),
+    Importar,
+    ExpedienteReglaChanged(String),
+    FichaRegla(OpcionFicha),
+    CategoriaRegla(CategoriaHoja),
+    QuitarCategoriaRegla,
+    AgregarRegla,
+    EliminarRegla(Uuid),
+}
+
+/// Ficha elegible como destino de una regla
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpcionFicha {
+    pub id: Uuid,
+    pub titulo: String,
+    pub expediente: String,
+}
+
+impl fmt::Display for OpcionFicha {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.expediente.trim().is_empty() {
+            write!(f, "{}", self.titulo)
+        } else {
+            write!(f, "{} ({})", self.titulo, self.expediente)
+        }
+    }
+}
+
+/// Importación de correos guardados (.eml, mbox, Maildir) y reglas para
+/// asignarlos a las fichas
+#[derive(Debug)]
+pub struct CorreoView {
+    pub ruta_input: String,
+    /// Resumen de la última importación o error de lectura
+    pub mensaje: Option<String>,
+    /// Fichas que recibieron hojas en la última importación, con cuántas
+    pub fichas_actualizadas: Vec<(String, usize)>,
+    /// Asuntos de los correos de la última importación que no se asignaron
+    pub sin_ficha: Vec<String>,
+    pub reglas: Vec<ReglaCorreo>,
+    pub fichas: Vec<OpcionFicha>,
+    pub categorias: Vec<CategoriaHoja>,
+    pub expediente_input: String,
+    pub ficha_regla: Option<OpcionFicha>,
+    pub categoria_regla: Option<CategoriaHoja>,
+    pub error_regla: Option<String>,
+    /// Si el rol del usuario permite modificar las reglas
+    pub puede_configurar: bool,
+    pub letra: Letra,
+    pub idioma: Idioma,
+}
+
+impl CorreoView {
+    pub fn new() -> Self {
+        Self {
+            ruta_input: String::new(),
+            mensaje: None,
+            fichas_actualizadas: Vec::new(),
+            sin_ficha: Vec::new(),
+            reglas: Vec::new(),
+            fichas: Vec::new(),
+            categorias: Vec::new(),
+            expediente_input: String::new(),
+            ficha_regla: None,
+            categoria_regla: None,
+            error_regla: None,
+            puede_configurar: false,
+            letra: Letra::default(),
+            idioma: Idioma::default(),
+        }
+    }
+
+    pub fn actualizar(
+        &mut self,
+        reglas: Vec<ReglaCorreo>,
+        fichas: &[Ficha],
+        categorias: Vec<CategoriaHoja>,
+    ) {
+        self.reglas = reglas;
+        self.fichas = fichas
+            .iter()
+            .map(|f| OpcionFicha {
+                id: f.id,
+                titulo: f.titulo.clone(),
+                expediente: f.expediente.clone(),
+            })
+            .collect();
+        self.fichas.sort_by(|a, b| a.titulo.cmp(&b.titulo));
+        self.categorias = categorias;
+    }
+
+    pub fn limpiar_resultado(&mut self) {
+        self.mensaje = None;
+        self.fichas_actualizadas.clear();
+        self.sin_ficha.clear();
+    }
+
+    pub fn limpiar_regla(&mut self) {
+        self.expediente_input.clear();
+        self.ficha_regla = None;
+        self.categoria_regla = None;
+        self.error_regla = None;
+    }
+
+    pub fn view(&self) -> Element<'_, CorreoMessage> {
+        let header = row![
+            button(text(format!("← {}", i18n::texto(self.idioma, "volver"))))
+                .on_press(CorreoMessage::Volver)
+                .padding(10)
+                .style(styles::secondary_button),
+            text(i18n::texto(self.idioma, "correo-titulo")).size(self.letra.tamanio(24)),
+        ]
+        .spacing(20)
+        .padding(10);
+
+        let ayuda = column![
+            text(i18n::texto(self.idioma, "correo-ayuda")),
+            text(i18n::texto(self.idioma, "correo-ayuda-arrastrar")).size(self.letra.tamanio(13)),
+        ]
+        .spacing(5);
+
+        let ruta = column![
+            text(i18n::texto(self.idioma, "correo-ruta")),
+            row![
+                text_input("/home/usuario/Correo/notificaciones.mbox", &self.ruta_input)
+                    .on_input(CorreoMessage::RutaChanged)
+                    .on_submit(CorreoMessage::Importar)
+                    .padding(8)
+                    .style(styles::campo_texto),
+                button(text(i18n::texto(self.idioma, "correo-importar")))
+                    .on_press_maybe(
+                        (!self.ruta_input.trim().is_empty()).then_some(CorreoMessage::Importar),
+                    )
+                    .padding(10)
+                    .style(styles::primary_button),
+            ]
+            .spacing(10),
+        ]
+        .spacing(5);
+
+        let fichas = self.fichas_actualizadas.iter().fold(
+            Column::new().spacing(4),
+            |lista, (titulo, agregadas)| {
+                lista.push(
+                    text(i18n::texto_con(
+                        self.idioma,
+                        "hojas-nuevas-en-ficha",
+                        &[
+                            ("titulo", titulo.clone().into()),
+                            ("cantidad", (*agregadas).into()),
+                        ],
+                    ))
+                    .size(self.letra.tamanio(13)),
+                )
+            },
+        );
+        let sin_ficha = self
+            .sin_ficha
+            .iter()
+            .fold(Column::new().spacing(4), |lista, asunto| {
+                let asunto = [("asunto", asunto.clone().into())];
+                let texto = i18n::texto_con(self.idioma, "correo-sin-ficha", &asunto);
+                lista.push(text(texto).size(self.letra.tamanio(13)))
+            });
+
+        let resultado = column![
+            text(self.mensaje.as_deref().unwrap_or_default()).size(self.letra.tamanio(14)),
+            fichas,
+            sin_ficha,
+        ]
+        .spacing(10);
+
+        column![
+            header,
+            ayuda,
+            ruta,
+            row![
+                container(scrollable(resultado)).width(Length::FillPortion(1)),
+                container(self.vista_reglas())
+                    .padding(15)
+                    .width(Length::FillPortion(1))
+                    .style(styles::card_container),
+            ]
+            .spacing(20),
+        ]
+        .spacing(15)
+        .padding(20)
+        .into()
+    }
+
+    fn vista_reglas(&self) -> Element<'_, CorreoMessage> {
+        let titulo_ficha = |id: Uuid| {
+            self.fichas.iter().find(|f| f.id == id).map_or_else(
+                || i18n::texto(self.idioma, "correo-ficha-eliminada"),
+                |f| f.titulo.clone(),
+            )
+        };
+
+        let lista: Element<_> = if self.reglas.is_empty() {
+            text(i18n::texto(self.idioma, "correo-sin-reglas"))
+                .size(self.letra.tamanio(13))
+                .into()
+        } else {
+            self.reglas
+                .iter()
+                .fold(Column::new().spacing(6), |lista, regla| {
+                    let categoria = regla
+                        .categoria
+                        .as_ref()
+                        .and_then(|id| self.categorias.iter().find(|c| &c.id == id))
+                        .map(|c| format!(" · {}", c.nombre))
+                        .unwrap_or_default();
+                    let eliminar = self.puede_configurar.then(|| {
+                        button(
+                            text(i18n::texto(self.idioma, "eliminar")).size(self.letra.tamanio(12)),
+                        )
+                        .on_press(CorreoMessage::EliminarRegla(regla.id))
+                        .padding([4, 8])
+                        .style(styles::cancel_button)
+                    });
+                    lista.push(
+                        row![
+                            text(format!(
+                                "{} → {}{}",
+                                regla.expediente,
+                                titulo_ficha(regla.ficha_id),
+                                categoria
+                            ))
+                            .size(self.letra.tamanio(13)),
+                            Space::with_width(Length::Fill),
+                        ]
+                        .push_maybe(eliminar)
+                        .spacing(10)
+                        .align_y(iced::Alignment::Center),
+                    )
+                })
+                .into()
+        };
+
+        let formulario = self.puede_configurar.then(|| {
+            column![
+                text_input(
+                    &i18n::texto(self.idioma, "correo-regla-expediente"),
+                    &self.expediente_input
+                )
+                .on_input(CorreoMessage::ExpedienteReglaChanged)
+                .padding(8)
+                .style(styles::campo_texto),
+                pick_list(
+                    self.fichas.as_slice(),
+                    self.ficha_regla.clone(),
+                    CorreoMessage::FichaRegla,
+                )
+                .placeholder(i18n::texto(self.idioma, "correo-regla-ficha"))
+                .padding(8)
+                .width(Length::Fill),
+                row![
+                    pick_list(
+                        self.categorias.as_slice(),
+                        self.categoria_regla.clone(),
+                        CorreoMessage::CategoriaRegla,
+                    )
+                    .placeholder(i18n::texto(self.idioma, "correo-regla-categoria"))
+                    .padding(8),
+                    button(accesibilidad::rotulo(
+                        "✕",
+                        i18n::texto(self.idioma, "nueva-hoja-quitar-categoria"),
+                        self.letra.tamanio(14),
+                    ))
+                    .on_press_maybe(
+                        self.categoria_regla
+                            .as_ref()
+                            .map(|_| CorreoMessage::QuitarCategoriaRegla),
+                    )
+                    .padding(8),
+                    Space::with_width(Length::Fill),
+                    button(text(i18n::texto(self.idioma, "correo-regla-agregar")))
+                        .on_press(CorreoMessage::AgregarRegla)
+                        .padding(8)
+                        .style(styles::primary_button),
+                ]
+                .spacing(10),
+            ]
+            .push_maybe(
+                self.error_regla
+                    .as_ref()
+                    .map(|error| text(format!("⚠ {}", error)).size(self.letra.tamanio(13))),
+            )
+            .spacing(10)
+        });
+
+        column![
+            text(i18n::texto(self.idioma, "correo-reglas")).size(self.letra.tamanio(18)),
+            text(i18n::texto(self.idioma, "correo-reglas-ayuda")).size(self.letra.tamanio(13)),
+            scrollable(lista),
+        ]
+        .push_maybe(formulario)
+        .spacing(10)
+        .into()
+    }
+}
+
+/// Resumen de una importación de correos en el idioma de la interfaz
+pub fn describir(resumen: &Resumen, idioma: Idioma) -> String {
+    let leidos = i18n::texto_con(
+        idioma,
+        "correo-resumen",
+        &[
+            ("mensajes", resumen.mensajes.into()),
+            ("agregadas", resumen.agregadas.into()),
+            ("repetidos", resumen.repetidos.into()),
+        ],
+    );
+    if resumen.sin_ficha.is_empty() {
+        return leidos;
+    }
+    i18n::texto_con(
+        idioma,
+        "correo-resumen-sin-ficha",
+        &[
+            ("resumen", leidos.into()),
+            ("cantidad", resumen.sin_ficha.len().into()),
+        ],
+    )
+}
@@ -0,0 +1,263 @@
+use crate::i18n;
+use crate::models::cuenta::Totales;
+use crate::models::{Asiento, Ficha, Idioma, Importe, Moneda, TipoAsiento};
+use crate::ui::accesibilidad::{self, Letra};
+use crate::ui::fechas::FORMATO_DIA;
+use crate::ui::styles;
+use chrono::NaiveDate;
+use iced::widget::{
+    button, column, container, pick_list, row, text, text_input, Column, Space,
+};
+use iced::{Element, Length};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub enum CuentaMessage {
+    TipoSeleccionado(TipoAsiento),
+    ConceptoChanged(String),
+    ImporteChanged(String),
+    MonedaSeleccionada(Moneda),
+    FechaChanged(String),
+    ClienteChanged(String),
+    AgregarAsiento(Uuid),
+    EliminarAsiento(Uuid, Uuid),
+}
+
+/// Formulario de la pestaña de cuenta (gastos, honorarios y pagos) de una ficha
+#[derive(Debug)]
+pub struct CuentaForm {
+    pub tipo: TipoAsiento,
+    pub concepto_input: String,
+    pub importe_input: String,
+    pub moneda: Moneda,
+    pub fecha_input: String,
+    pub cliente_input: String,
+    /// Error de validación del último asiento ingresado
+    pub error: Option<String>,
+}
+
+impl CuentaForm {
+    /// Propone `hoy`, el día en la zona configurada, como fecha del movimiento
+    pub fn new(hoy: NaiveDate) -> Self {
+        Self {
+            tipo: TipoAsiento::Gasto,
+            concepto_input: String::new(),
+            importe_input: String::new(),
+            moneda: Moneda::Pesos,
+            fecha_input: hoy.format(FORMATO_DIA).to_string(),
+            cliente_input: String::new(),
+            error: None,
+        }
+    }
+
+    /// Deja el formulario vacío, con el cliente de la ficha por defecto
+    pub fn reiniciar(&mut self, cliente: &str, hoy: NaiveDate) {
+        *self = Self::new(hoy);
+        self.cliente_input = cliente.to_string();
+    }
+
+    /// Actualiza los campos del formulario; las acciones sobre la ficha
+    /// las resuelve la aplicación.
+    pub fn actualizar(&mut self, mensaje: CuentaMessage) {
+        match mensaje {
+            CuentaMessage::TipoSeleccionado(tipo) => self.tipo = tipo,
+            CuentaMessage::ConceptoChanged(valor) => self.concepto_input = valor,
+            CuentaMessage::ImporteChanged(valor) => self.importe_input = valor,
+            CuentaMessage::MonedaSeleccionada(moneda) => self.moneda = moneda,
+            CuentaMessage::FechaChanged(valor) => self.fecha_input = valor,
+            CuentaMessage::ClienteChanged(valor) => self.cliente_input = valor,
+            CuentaMessage::AgregarAsiento(_) | CuentaMessage::EliminarAsiento(_, _) => {}
+        }
+    }
+
+    /// Valida el formulario y construye el asiento
+    pub fn obtener_asiento(&self) -> Result<Asiento, String> {
+        if self.concepto_input.trim().is_empty() {
+            return Err("Ingrese un concepto".into());
+        }
+        let importe = Importe::parsear(&self.importe_input)
+            .filter(|i| !i.es_cero())
+            .ok_or("Importe inválido")?;
+        let fecha = NaiveDate::parse_from_str(self.fecha_input.trim(), FORMATO_DIA)
+            .map_err(|_| "Fecha inválida, use dd/mm/aaaa")?;
+
+        Ok(Asiento::new(
+            self.tipo,
+            self.concepto_input.trim().to_string(),
+            importe,
+            self.moneda,
+            fecha,
+            self.cliente_input.trim().to_string(),
+        ))
+    }
+
+    /// Sin `editable` solo se muestran los movimientos y el saldo
+    pub fn view<'a>(
+        &'a self,
+        ficha: &'a Ficha,
+        editable: bool,
+        idioma: Idioma,
+        letra: Letra,
+    ) -> Element<'a, CuentaMessage> {
+        let formulario = container(
+            column![
+                text(i18n::texto(idioma, "cuenta-registrar")).size(letra.tamanio(16)),
+                row![
+                    pick_list(
+                        TipoAsiento::TODOS,
+                        Some(self.tipo),
+                        CuentaMessage::TipoSeleccionado
+                    )
+                    .padding(8),
+                    text_input(
+                        "Concepto (tasa de justicia, cédula...)",
+                        &self.concepto_input
+                    )
+                    .on_input(CuentaMessage::ConceptoChanged)
+                    .padding(8)
+                    .style(styles::campo_texto),
+                    text_input("Importe", &self.importe_input)
+                        .on_input(CuentaMessage::ImporteChanged)
+                        .padding(8)
+                        .width(Length::Fixed(120.0))
+                        .style(styles::campo_texto),
+                    pick_list(
+                        Moneda::TODAS,
+                        Some(self.moneda),
+                        CuentaMessage::MonedaSeleccionada
+                    )
+                    .padding(8),
+                ]
+                .spacing(10),
+                row![
+                    text_input("dd/mm/aaaa", &self.fecha_input)
+                        .on_input(CuentaMessage::FechaChanged)
+                        .padding(8)
+                        .width(Length::Fixed(120.0))
+                        .style(styles::campo_texto),
+                    text_input("Cliente", &self.cliente_input)
+                        .on_input(CuentaMessage::ClienteChanged)
+                        .padding(8)
+                        .style(styles::campo_texto),
+                    button(text(i18n::texto(idioma, "registrar")))
+                        .on_press(CuentaMessage::AgregarAsiento(ficha.id))
+                        .padding(8)
+                        .style(styles::primary_button),
+                ]
+                .spacing(10),
+                text(self.error.as_deref().unwrap_or_default()).size(letra.tamanio(12)),
+            ]
+            .spacing(10),
+        )
+        .padding(15)
+        .width(Length::Fill)
+        .style(styles::card_container);
+
+        let asientos =
+            ficha
+                .cuenta
+                .iter()
+                .rev()
+                .fold(Column::new().spacing(6), |lista, asiento| {
+                    let signo = if asiento.tipo.es_cargo() { "" } else { "-" };
+                    lista.push(
+                        container(
+                            row![
+                                text(asiento.fecha.format("%d/%m/%Y").to_string())
+                                    .size(letra.tamanio(13))
+                                    .width(Length::Fixed(90.0)),
+                                text(asiento.tipo.to_string())
+                                    .size(letra.tamanio(13))
+                                    .width(Length::Fixed(90.0)),
+                                text(&asiento.concepto).size(letra.tamanio(13)).width(Length::Fill),
+                                text(&asiento.cliente)
+                                    .size(letra.tamanio(13))
+                                    .width(Length::Fixed(150.0)),
+                                text(format!("{}{} {}", signo, asiento.moneda, asiento.importe))
+                                    .size(letra.tamanio(13))
+                                    .width(Length::Fixed(140.0)),
+                                match asiento.liquidacion {
+                                    Some(numero) => Element::from(
+                                        text(format!("Liq. N° {:04}", numero))
+                                            .size(letra.tamanio(12)),
+                                    ),
+                                    None => button(accesibilidad::rotulo(
+                                        "✕",
+                                        "Eliminar asiento",
+                                        letra.tamanio(12),
+                                    ))
+                                    .on_press_maybe(editable.then_some(
+                                        CuentaMessage::EliminarAsiento(ficha.id, asiento.id),
+                                    ))
+                                    .padding([2, 8])
+                                    .style(styles::cancel_button)
+                                    .into(),
+                                },
+                            ]
+                            .spacing(10),
+                        )
+                        .padding(8)
+                        .style(styles::card_container),
+                    )
+                });
+
+        let formulario: Element<_> = if editable {
+            formulario.into()
+        } else {
+            Space::with_height(0).into()
+        };
+
+        column![
+            formulario,
+            text(i18n::texto(idioma, "cuenta-saldo")).size(letra.tamanio(16)),
+            vista_totales(&ficha.saldo(), idioma, letra),
+            text(i18n::texto(idioma, "cuenta-movimientos")).size(letra.tamanio(16)),
+            asientos,
+        ]
+        .spacing(10)
+        .into()
+    }
+}
+
+/// Una línea por moneda con cargos, pagos y saldo
+pub fn vista_totales<'a, Message: 'a>(
+    totales: &BTreeMap<Moneda, Totales>,
+    idioma: Idioma,
+    letra: Letra,
+) -> Element<'a, Message> {
+    if totales.is_empty() {
+        return text(i18n::texto(idioma, "cuenta-sin-movimientos")).size(letra.tamanio(13)).into();
+    }
+
+    totales
+        .iter()
+        .fold(Column::new().spacing(4), |lista, (moneda, t)| {
+            lista.push(
+                text(format!(
+                    "{}: cargos {} · pagos {} · saldo {}",
+                    moneda,
+                    t.cargos,
+                    t.pagos,
+                    t.saldo()
+                ))
+                .size(letra.tamanio(13)),
+            )
+        })
+        .into()
+}
+
+/// Resumen de una línea del saldo pendiente por moneda, para las cards
+pub fn resumen_saldo(totales: &BTreeMap<Moneda, Totales>) -> Option<String> {
+    if totales.is_empty() {
+        return None;
+    }
+
+    Some(
+        totales
+            .iter()
+            .map(|(moneda, t)| format!("{} {}", moneda, t.saldo()))
+            .collect::<Vec<_>>()
+            .join(" · "),
+    )
+}
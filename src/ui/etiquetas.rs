@@ -0,0 +1,209 @@
+use crate::i18n;
+use crate::models::Idioma;
+use crate::ui::accesibilidad::Letra;
+use crate::ui::styles;
+use iced::widget::{
+    button, column, container, row, scrollable, text, text_input, Column, Row, Space,
+};
+use iced::{Element, Length};
+use std::collections::BTreeMap;
+
+/// Colores que se pueden asignar a una etiqueta
+pub const PALETA: [[u8; 3]; 8] = [
+    [220, 38, 38],
+    [234, 88, 12],
+    [202, 138, 4],
+    [22, 163, 74],
+    [13, 148, 136],
+    [37, 99, 235],
+    [124, 58, 237],
+    [219, 39, 119],
+];
+
+/// Color de las etiquetas que no tienen uno asignado
+pub const COLOR_PREDETERMINADO: [u8; 3] = [100, 116, 139];
+
+#[derive(Debug, Clone)]
+pub enum EtiquetaMessage {
+    Volver,
+    Seleccionar(String),
+    NuevoNombreChanged(String),
+    Renombrar,
+    AsignarColor(Option<[u8; 3]>),
+}
+
+#[derive(Debug)]
+pub struct EtiquetasView {
+    /// Etiquetas con la cantidad de fichas que las usan
+    pub etiquetas: Vec<(String, usize)>,
+    pub colores: BTreeMap<String, [u8; 3]>,
+    pub seleccionada: Option<String>,
+    pub nuevo_nombre_input: String,
+    /// Resultado de la última operación
+    pub mensaje: Option<String>,
+    pub idioma: Idioma,
+    pub letra: Letra,
+}
+
+impl EtiquetasView {
+    pub fn new() -> Self {
+        Self {
+            etiquetas: Vec::new(),
+            colores: BTreeMap::new(),
+            seleccionada: None,
+            nuevo_nombre_input: String::new(),
+            mensaje: None,
+            idioma: Idioma::default(),
+            letra: Letra::default(),
+        }
+    }
+
+    pub fn actualizar_etiquetas(
+        &mut self,
+        etiquetas: Vec<(String, usize)>,
+        colores: BTreeMap<String, [u8; 3]>,
+    ) {
+        self.etiquetas = etiquetas;
+        self.colores = colores;
+    }
+
+    pub fn seleccionar(&mut self, etiqueta: Option<String>) {
+        self.nuevo_nombre_input = etiqueta.clone().unwrap_or_default();
+        self.seleccionada = etiqueta;
+    }
+
+    pub fn view(&self) -> Element<'_, EtiquetaMessage> {
+        let boton_volver = button(text(i18n::texto(self.idioma, "volver")))
+            .on_press(EtiquetaMessage::Volver)
+            .padding(10)
+            .style(styles::secondary_button);
+
+        let titulo = i18n::texto(self.idioma, "etiquetas-titulo");
+        let header = row![boton_volver, text(titulo).size(self.letra.tamanio(24))]
+            .spacing(20)
+            .padding(10);
+
+        let lista: Element<_> = if self.etiquetas.is_empty() {
+            text(i18n::texto(self.idioma, "etiquetas-vacia")).into()
+        } else {
+            let lista = self
+                .etiquetas
+                .iter()
+                .fold(Column::new().spacing(6), |lista, (etiqueta, cantidad)| {
+                    let color = self
+                        .colores
+                        .get(etiqueta)
+                        .copied()
+                        .unwrap_or(COLOR_PREDETERMINADO);
+                    let activa = self.seleccionada.as_ref() == Some(etiqueta);
+
+                    lista.push(
+                        button(
+                            row![
+                                container(text(etiqueta).size(self.letra.tamanio(13)))
+                                    .padding([2, 8])
+                                    .style(styles::etiqueta(color)),
+                                Space::with_width(Length::Fill),
+                                text(format!("{} fichas", cantidad)).size(self.letra.tamanio(12)),
+                            ]
+                            .spacing(10),
+                        )
+                        .on_press(EtiquetaMessage::Seleccionar(etiqueta.clone()))
+                        .width(Length::Fill)
+                        .padding(8)
+                        .style(if activa {
+                            styles::primary_button
+                        } else {
+                            button::text
+                        }),
+                    )
+                });
+            scrollable(lista).into()
+        };
+
+        column![
+            header,
+            row![
+                container(lista).width(Length::FillPortion(1)),
+                container(self.vista_edicion())
+                    .padding(15)
+                    .width(Length::FillPortion(1))
+                    .style(styles::card_container),
+            ]
+            .spacing(20),
+        ]
+        .spacing(10)
+        .padding(20)
+        .into()
+    }
+
+    fn vista_edicion(&self) -> Element<'_, EtiquetaMessage> {
+        let Some(seleccionada) = &self.seleccionada else {
+            return text(i18n::texto(self.idioma, "etiquetas-ayuda")).into();
+        };
+
+        let existe_destino = self.nuevo_nombre_input.trim() != seleccionada
+            && self
+                .etiquetas
+                .iter()
+                .any(|(e, _)| e == self.nuevo_nombre_input.trim());
+
+        let boton_renombrar = button(text(if existe_destino {
+            "Fusionar"
+        } else {
+            "Renombrar"
+        }))
+        .on_press(EtiquetaMessage::Renombrar)
+        .padding(8)
+        .style(styles::primary_button);
+
+        let colores = PALETA.iter().fold(
+            Row::new().spacing(6).push(
+                button(
+                    text(i18n::texto(self.idioma, "etiquetas-sin-color"))
+                        .size(self.letra.tamanio(12)),
+                )
+                .on_press(EtiquetaMessage::AsignarColor(None))
+                .padding([4, 8]),
+            ),
+            |fila, color| {
+                fila.push(
+                    button(container(Space::new(16, 16)).style(styles::etiqueta(*color)))
+                        .on_press(EtiquetaMessage::AsignarColor(Some(*color)))
+                        .padding(2)
+                        .style(button::text),
+                )
+            },
+        );
+
+        let aviso = if existe_destino {
+            text(format!(
+                "\"{}\" ya existe: las fichas quedarán con una sola etiqueta.",
+                self.nuevo_nombre_input.trim()
+            ))
+            .size(self.letra.tamanio(12))
+        } else {
+            text("")
+        };
+
+        column![
+            text(format!("Etiqueta: {}", seleccionada)).size(self.letra.tamanio(18)),
+            text(i18n::texto(self.idioma, "etiquetas-nuevo-nombre")),
+            row![
+                text_input("Nuevo nombre", &self.nuevo_nombre_input)
+                    .on_input(EtiquetaMessage::NuevoNombreChanged)
+                    .on_submit(EtiquetaMessage::Renombrar)
+                    .padding(8)
+                    .style(styles::campo_texto),
+                boton_renombrar,
+            ]
+            .spacing(10),
+            aviso,
+            text(i18n::texto(self.idioma, "campo-color")),
+            colores,
+            text(self.mensaje.as_deref().unwrap_or_default()).size(self.letra.tamanio(12)),
+        ]
+        .spacing(10)
+        .into()
+    }
+}
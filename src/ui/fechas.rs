@@ -0,0 +1,154 @@
+use crate::i18n;
+use crate::models::Idioma;
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use chrono_tz::Tz;
+
+/// Formato por omisión de las fechas con hora
+pub const FORMATO_PREDETERMINADO: &str = "%d/%m/%Y %H:%M";
+
+/// Formato de las fechas sin hora que se ingresan en los formularios
+pub const FORMATO_DIA: &str = "%d/%m/%Y";
+
+/// Formato y zona horaria con que se muestran las fechas guardadas en UTC.
+/// Todas las vistas y exportaciones lo usan para que coincidan entre sí.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatoFecha {
+    pub formato: String,
+    /// Sin zona se usa la del sistema
+    pub zona: Option<Tz>,
+    /// Idioma de los nombres de meses y días
+    pub idioma: Idioma,
+}
+
+impl Default for FormatoFecha {
+    fn default() -> Self {
+        Self {
+            formato: String::from(FORMATO_PREDETERMINADO),
+            zona: None,
+            idioma: Idioma::default(),
+        }
+    }
+}
+
+impl FormatoFecha {
+    /// `zona` es un nombre IANA; si está vacía o no se reconoce se usa la
+    /// del sistema
+    pub fn new(formato: &str, zona: &str) -> Self {
+        Self {
+            formato: formato.to_string(),
+            zona: zona.trim().parse().ok(),
+            idioma: Idioma::default(),
+        }
+    }
+
+    pub fn con_idioma(mut self, idioma: Idioma) -> Self {
+        self.idioma = idioma;
+        self
+    }
+
+    /// Fecha y hora en la zona configurada, con el formato elegido
+    pub fn fecha_hora(&self, fecha: DateTime<Utc>) -> String {
+        let locale = i18n::locale(self.idioma);
+        match self.zona {
+            Some(zona) => fecha
+                .with_timezone(&zona)
+                .format_localized(&self.formato, locale)
+                .to_string(),
+            None => fecha
+                .with_timezone(&Local)
+                .format_localized(&self.formato, locale)
+                .to_string(),
+        }
+    }
+
+    /// Como `fecha_hora`, pero aclarando la zona y su diferencia con UTC
+    pub fn fecha_hora_con_zona(&self, fecha: DateTime<Utc>) -> String {
+        format!("{} ({})", self.fecha_hora(fecha), self.nombre_zona(fecha))
+    }
+
+    /// Día calendario de `fecha` en la zona configurada
+    pub fn dia(&self, fecha: DateTime<Utc>) -> NaiveDate {
+        match self.zona {
+            Some(zona) => fecha.with_timezone(&zona).date_naive(),
+            None => fecha.with_timezone(&Local).date_naive(),
+        }
+    }
+
+    /// Día con el mes en palabras, según el idioma: "19 de octubre de 2026"
+    pub fn dia_largo(&self, dia: NaiveDate) -> String {
+        let formato = i18n::texto(self.idioma, "fecha-larga");
+        dia.format_localized(&formato, i18n::locale(self.idioma))
+            .to_string()
+    }
+
+    /// Día en números, como se ingresa en los formularios: "19/10/2026"
+    pub fn dia_corto(&self, dia: NaiveDate) -> String {
+        dia.format(FORMATO_DIA).to_string()
+    }
+
+    pub fn hoy(&self) -> NaiveDate {
+        self.dia(Utc::now())
+    }
+
+    /// Nombre de la zona y diferencia con UTC en `fecha`, por ejemplo
+    /// "America/Argentina/Buenos_Aires, UTC-03:00"
+    pub fn nombre_zona(&self, fecha: DateTime<Utc>) -> String {
+        let (nombre, desplazamiento) = match self.zona {
+            Some(zona) => (
+                zona.name().to_string(),
+                fecha.with_timezone(&zona).format("%:z").to_string(),
+            ),
+            None => (
+                iana_time_zone::get_timezone().unwrap_or_else(|_| String::from("hora local")),
+                fecha.with_timezone(&Local).format("%:z").to_string(),
+            ),
+        };
+        format!("{}, UTC{}", nombre, desplazamiento)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn buenos_aires() -> FormatoFecha {
+        FormatoFecha::new(FORMATO_PREDETERMINADO, "America/Argentina/Buenos_Aires")
+    }
+
+    #[test]
+    fn zona_desconocida_o_vacia_usa_la_del_sistema() {
+        assert_eq!(FormatoFecha::new(FORMATO_PREDETERMINADO, "Marte/Olympus").zona, None);
+        assert_eq!(FormatoFecha::new(FORMATO_PREDETERMINADO, "").zona, None);
+        assert_eq!(
+            FormatoFecha::new(FORMATO_PREDETERMINADO, "  Europe/Madrid ").zona,
+            Some(chrono_tz::Europe::Madrid)
+        );
+    }
+
+    #[test]
+    fn cerca_de_medianoche_el_dia_es_el_de_la_zona() {
+        let fechas = buenos_aires();
+        let momento = Utc.with_ymd_and_hms(2026, 10, 19, 2, 30, 0).unwrap();
+
+        assert_eq!(fechas.fecha_hora(momento), "18/10/2026 23:30");
+        assert_eq!(fechas.dia(momento), NaiveDate::from_ymd_opt(2026, 10, 18).unwrap());
+        assert_eq!(fechas.dia_corto(fechas.dia(momento)), "18/10/2026");
+        assert_eq!(
+            fechas.fecha_hora_con_zona(momento),
+            "18/10/2026 23:30 (America/Argentina/Buenos_Aires, UTC-03:00)"
+        );
+    }
+
+    #[test]
+    fn el_desplazamiento_sigue_el_horario_de_verano() {
+        let fechas = FormatoFecha::new("%Y-%m-%d %H:%M", "Europe/Madrid");
+        let invierno = Utc.with_ymd_and_hms(2026, 1, 15, 23, 30, 0).unwrap();
+        let verano = Utc.with_ymd_and_hms(2026, 7, 15, 23, 30, 0).unwrap();
+
+        assert_eq!(fechas.nombre_zona(invierno), "Europe/Madrid, UTC+01:00");
+        assert_eq!(fechas.nombre_zona(verano), "Europe/Madrid, UTC+02:00");
+        assert_eq!(fechas.fecha_hora(invierno), "2026-01-16 00:30");
+        assert_eq!(fechas.fecha_hora(verano), "2026-07-16 01:30");
+    }
+}
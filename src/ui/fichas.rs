@@ -1,9 +1,118 @@
-use crate::models::Ficha;
+use crate::documentos::PlantillaEscrito;
+use crate::models::{
+    tiempo, Accion, CategoriaHoja, Estado, Ficha, Idioma, Parte, PlantillaHoja,
+    ResultadoSincronizacion, Usuario,
+};
+use crate::i18n;
+use crate::importacion::ClaveExpediente;
+use crate::ui::accesibilidad::{self, Letra};
+use crate::ui::cuenta::{self, CuentaForm, CuentaMessage};
+use crate::ui::etiquetas::COLOR_PREDETERMINADO;
+use crate::ui::fechas::{FormatoFecha, FORMATO_DIA};
+use crate::ui::hoja_editor::{self, Formato};
+use crate::ui::selector_fecha::{SelectorFecha, SelectorFechaMessage};
 use crate::ui::styles;
-use iced::widget::{button, column, container, row, scrollable, text, text_input, Column, Space};
-use iced::{Border, Color, Element, Length};
+use crate::ui::tareas::{TareaForm, TareaMessage};
+use crate::ui::tiempo::{TiempoForm, TiempoMessage};
+use crate::ui::usuarios;
+use crate::ui::vinculos::{VinculoForm, VinculoMessage};
+use chrono::NaiveDate;
+use fluent_bundle::FluentValue;
+use iced::widget::text_editor::{self, Action, Edit};
+use iced::widget::{
+    button, column, container, markdown, pick_list, responsive, row, scrollable, text,
+    text_input, Column, Row, Space,
+};
+use iced::{Element, Length, Theme};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
 use uuid::Uuid;
 
+/// Pestañas del detalle de una ficha
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PestaniaHistorial {
+    Hojas,
+    Cuenta,
+    Tiempo,
+}
+
+/// Presentación de la lista de fichas
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModoLista {
+    Tarjetas,
+    Tabla,
+}
+
+/// Columnas de la vista de tabla por las que se puede ordenar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnaTabla {
+    Titulo,
+    Expediente,
+    UltimaHoja,
+    UltimoMovimiento,
+    Estado,
+}
+
+impl ColumnaTabla {
+    pub const TODAS: [ColumnaTabla; 5] = [
+        ColumnaTabla::Titulo,
+        ColumnaTabla::Expediente,
+        ColumnaTabla::UltimaHoja,
+        ColumnaTabla::UltimoMovimiento,
+        ColumnaTabla::Estado,
+    ];
+
+    /// Clave del catálogo con el nombre de la columna
+    fn clave(&self) -> &'static str {
+        match self {
+            ColumnaTabla::Titulo => "columna-titulo",
+            ColumnaTabla::Expediente => "columna-expediente",
+            ColumnaTabla::UltimaHoja => "columna-ultima-hoja",
+            ColumnaTabla::UltimoMovimiento => "columna-ultimo-movimiento",
+            ColumnaTabla::Estado => "columna-estado",
+        }
+    }
+
+    /// Proporción del ancho de la tabla que ocupa la columna
+    fn ancho(&self) -> u16 {
+        match self {
+            ColumnaTabla::Titulo => 4,
+            ColumnaTabla::Expediente => 2,
+            ColumnaTabla::UltimaHoja => 5,
+            ColumnaTabla::UltimoMovimiento => 2,
+            ColumnaTabla::Estado => 2,
+        }
+    }
+
+    fn comparar(&self, a: &Ficha, b: &Ficha, fechas: &FormatoFecha) -> std::cmp::Ordering {
+        match self {
+            ColumnaTabla::Titulo => a.titulo.to_lowercase().cmp(&b.titulo.to_lowercase()),
+            ColumnaTabla::Expediente => a.expediente.cmp(&b.expediente),
+            ColumnaTabla::UltimaHoja => {
+                let resumen = |f: &Ficha| f.hoja_actual().map(|h| h.resumen(80).to_lowercase());
+                resumen(a).cmp(&resumen(b))
+            }
+            ColumnaTabla::UltimoMovimiento => {
+                let dia = |momento| fechas.dia(momento);
+                a.ultimo_movimiento(dia).cmp(&b.ultimo_movimiento(dia))
+            }
+            ColumnaTabla::Estado => a.estado.cmp(&b.estado),
+        }
+    }
+}
+
+/// Ancho mínimo de una tarjeta; las columnas se calculan con el de la ventana
+const ANCHO_MINIMO_TARJETA: f32 = 300.0;
+const ESPACIO_TARJETAS: f32 = 15.0;
+
+/// Conjunto de fichas elegido desde el tablero, con la descripción que se
+/// muestra mientras el filtro está activo
+#[derive(Debug, Clone)]
+pub struct FiltroSeleccion {
+    pub descripcion: String,
+    pub fichas: Vec<Uuid>,
+}
+
 #[derive(Debug, Clone)]
 pub enum FichaMessage {
     NuevaFicha,
@@ -12,48 +121,516 @@ pub enum FichaMessage {
     VerHistorial(Uuid),
     TituloChanged(String),
     DescripcionChanged(String),
-    HojaChanged(String),
+    ExpedienteChanged(String),
+    ClienteChanged(String),
+    PartesChanged(String),
+    NuevaEtiquetaChanged(String),
+    AgregarEtiqueta(String),
+    QuitarEtiqueta(String),
+    FiltrarEtiqueta(String),
+    QuitarFiltroEtiqueta,
+    EstadoSeleccionado(Estado),
+    FiltrarEstado(Estado),
+    QuitarFiltroEstado,
+    QuitarFiltroSeleccion,
+    BusquedaChanged(String),
+    CambiarModoLista(ModoLista),
+    /// Ordena la lista por la columna; si ya lo estaba, invierte el sentido
+    OrdenarPor(ColumnaTabla),
+    VerTablero,
+    GestionarEtiquetas,
+    GestionarCategorias,
+    HojaEditada(text_editor::Action),
+    FormatoHoja(Formato),
+    NuevaHojaEditada(text_editor::Action),
+    FormatoNuevaHoja(Formato),
+    EnlaceHoja,
+    CategoriaNuevaHoja(CategoriaHoja),
+    QuitarCategoriaNuevaHoja,
+    FechaNuevaHojaChanged(String),
+    SelectorFechaNuevaHoja(SelectorFechaMessage),
+    FiltrarCategoria(Option<String>),
+    InsertarPlantilla(PlantillaHoja),
+    GestionarPlantillas,
+    GenerarEscrito(PlantillaEscrito),
+    CambiarPestania(PestaniaHistorial),
+    Cuenta(CuentaMessage),
+    VerBalances,
+    IniciarCronometro(Uuid),
+    DetenerCronometro(Uuid),
+    Tiempo(TiempoMessage),
+    VerReporteTiempo,
+    Tarea(TareaMessage),
+    VerMisTareas,
+    Vinculo(VinculoMessage),
+    ResponsableSeleccionado(Usuario),
+    FiltrarResponsable(Usuario),
+    QuitarFiltroResponsable,
+    CambiarUsuario,
+    GestionarUsuarios,
+    VerAuditoria,
+    VerPreferencias,
+    VerImportacion,
+    VerCorreo,
+    /// Consulta ahora en el portal los movimientos de la ficha
+    Sincronizar(Uuid),
     AgregarHoja(Uuid),
     GuardarFicha,
     CancelarEdicion,
     CerrarHistorial,
 }
 
-#[derive(Debug, Clone)]
+impl FichaMessage {
+    /// Permiso necesario para el mensaje; los que solo cambian campos de un
+    /// formulario o la navegación no requieren ninguno
+    pub fn accion_requerida(&self) -> Option<Accion> {
+        match self {
+            FichaMessage::NuevaFicha
+            | FichaMessage::EditarFicha(_)
+            | FichaMessage::GuardarFicha
+            | FichaMessage::AgregarHoja(_)
+            | FichaMessage::GenerarEscrito(_)
+            | FichaMessage::VerImportacion
+            | FichaMessage::VerCorreo
+            | FichaMessage::Sincronizar(_)
+            | FichaMessage::IniciarCronometro(_)
+            | FichaMessage::DetenerCronometro(_)
+            | FichaMessage::Tiempo(TiempoMessage::AgregarRegistro(_))
+            | FichaMessage::Tiempo(TiempoMessage::EliminarRegistro(_, _))
+            | FichaMessage::Tarea(TareaMessage::AgregarTarea(_))
+            | FichaMessage::Tarea(TareaMessage::CompletarTarea(_, _, _))
+            | FichaMessage::Tarea(TareaMessage::EliminarTarea(_, _))
+            | FichaMessage::Vinculo(VinculoMessage::Vincular(_))
+            | FichaMessage::Vinculo(VinculoMessage::Desvincular(_, _)) => {
+                Some(Accion::EditarFichas)
+            }
+            FichaMessage::EliminarFicha(_) => Some(Accion::EliminarFichas),
+            FichaMessage::Cuenta(CuentaMessage::AgregarAsiento(_))
+            | FichaMessage::Cuenta(CuentaMessage::EliminarAsiento(_, _)) => {
+                Some(Accion::EditarCuenta)
+            }
+            FichaMessage::GestionarEtiquetas
+            | FichaMessage::GestionarCategorias
+            | FichaMessage::GestionarPlantillas => {
+                Some(Accion::Configurar)
+            }
+            FichaMessage::GestionarUsuarios => Some(Accion::GestionarUsuarios),
+            FichaMessage::VerAuditoria => Some(Accion::VerAuditoria),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
 pub struct FichasView {
     pub fichas: Vec<Ficha>,
     pub editando: bool,
     pub titulo_input: String,
     pub descripcion_input: String,
-    pub hoja_input: String,
-    pub nueva_hoja_input: String,
+    pub expediente_input: String,
+    pub cliente_input: String,
+    pub partes_input: String,
+    pub etiquetas_input: Vec<String>,
+    pub nueva_etiqueta_input: String,
+    pub estado_input: Estado,
+    pub hoja_input: text_editor::Content,
+    pub nueva_hoja_input: text_editor::Content,
     pub ficha_editando_id: Option<Uuid>,
     pub ficha_historial_id: Option<Uuid>,
+    pub pestania: PestaniaHistorial,
+    pub cuenta: CuentaForm,
+    pub tiempo: TiempoForm,
+    pub tarea: TareaForm,
+    pub vinculo: VinculoForm,
+    pub filtro_etiqueta: Option<String>,
+    pub filtro_estado: Option<Estado>,
+    pub filtro_seleccion: Option<FiltroSeleccion>,
+    /// Texto buscado en título, expediente y cliente
+    pub busqueda_input: String,
+    /// Ficha resaltada al recorrer la lista con el teclado
+    pub seleccion: Option<Uuid>,
+    pub modo_lista: ModoLista,
+    /// Columna por la que se ordena la lista y si el orden es descendente;
+    /// sin orden se muestran como están guardadas
+    pub orden: Option<(ColumnaTabla, bool)>,
+    pub usuarios: Vec<Usuario>,
+    pub usuario_actual: Option<Uuid>,
+    pub responsable_input: Option<Uuid>,
+    pub filtro_responsable: Option<Uuid>,
+    pub colores_etiquetas: BTreeMap<String, [u8; 3]>,
+    pub categorias: Vec<CategoriaHoja>,
+    pub categoria_nueva_hoja: Option<CategoriaHoja>,
+    /// Fecha del movimiento de la nueva hoja, en formato dd/mm/aaaa
+    pub fecha_nueva_hoja_input: String,
+    pub selector_fecha_nueva_hoja: SelectorFecha,
+    pub error_nueva_hoja: Option<String>,
+    pub filtro_categoria: Option<String>,
+    pub plantillas: Vec<PlantillaHoja>,
+    pub plantillas_escritos: Vec<PlantillaEscrito>,
+    /// Resultado del último intento de generar un escrito
+    pub mensaje_escrito: Option<String>,
+    /// Resultado de los últimos correos soltados sobre la ficha
+    pub mensaje_correo: Option<String>,
+    /// Si está configurada la consulta de movimientos en un portal
+    pub sincronizacion_activa: bool,
+    pub sincronizando: bool,
+    /// Error al guardar los movimientos de la última consulta en el portal
+    pub error_sincronizacion: Option<String>,
+    /// Tema activo, para los colores del Markdown de las hojas
+    pub tema: Theme,
+    pub idioma: Idioma,
+    pub fechas: FormatoFecha,
+    pub letra: Letra,
+    /// Tarjetas por fila fijadas en las preferencias; sin valor dependen
+    /// del ancho de la ventana
+    pub tarjetas_por_fila: Option<usize>,
+    /// Hojas de la ficha en historial ya interpretadas como Markdown
+    hojas_markdown: Vec<Vec<markdown::Item>>,
 }
 
 impl FichasView {
     pub fn new() -> Self {
+        let fechas = FormatoFecha::default();
         Self {
             fichas: Vec::new(),
             editando: false,
             titulo_input: String::new(),
             descripcion_input: String::new(),
-            hoja_input: String::new(),
-            nueva_hoja_input: String::new(),
+            expediente_input: String::new(),
+            cliente_input: String::new(),
+            partes_input: String::new(),
+            etiquetas_input: Vec::new(),
+            nueva_etiqueta_input: String::new(),
+            estado_input: Estado::default(),
+            hoja_input: text_editor::Content::new(),
+            nueva_hoja_input: text_editor::Content::new(),
             ficha_editando_id: None,
             ficha_historial_id: None,
+            pestania: PestaniaHistorial::Hojas,
+            cuenta: CuentaForm::new(fechas.hoy()),
+            tiempo: TiempoForm::new(fechas.hoy()),
+            tarea: TareaForm::new(),
+            vinculo: VinculoForm::new(),
+            filtro_etiqueta: None,
+            filtro_estado: None,
+            filtro_seleccion: None,
+            busqueda_input: String::new(),
+            seleccion: None,
+            modo_lista: ModoLista::Tarjetas,
+            orden: None,
+            usuarios: Vec::new(),
+            usuario_actual: None,
+            responsable_input: None,
+            filtro_responsable: None,
+            colores_etiquetas: BTreeMap::new(),
+            categorias: Vec::new(),
+            categoria_nueva_hoja: None,
+            fecha_nueva_hoja_input: String::new(),
+            selector_fecha_nueva_hoja: SelectorFecha::new(fechas.hoy()),
+            error_nueva_hoja: None,
+            filtro_categoria: None,
+            plantillas: Vec::new(),
+            plantillas_escritos: Vec::new(),
+            mensaje_escrito: None,
+            mensaje_correo: None,
+            sincronizacion_activa: false,
+            sincronizando: false,
+            error_sincronizacion: None,
+            tema: Theme::Light,
+            idioma: Idioma::default(),
+            fechas,
+            letra: Letra::default(),
+            tarjetas_por_fila: None,
+            hojas_markdown: Vec::new(),
         }
     }
 
     pub fn actualizar_fichas(&mut self, fichas: Vec<Ficha>) {
         self.fichas = fichas;
+        self.refrescar_markdown();
+    }
+
+    /// Quita los filtros de la lista, para mostrar todas las fichas
+    pub fn quitar_filtros(&mut self) {
+        self.filtro_etiqueta = None;
+        self.filtro_estado = None;
+        self.filtro_responsable = None;
+        self.filtro_seleccion = None;
+        self.busqueda_input.clear();
+    }
+
+    pub fn id_busqueda() -> text_input::Id {
+        text_input::Id::new("busqueda")
+    }
+
+    pub fn ordenar_por(&mut self, columna: ColumnaTabla) {
+        self.orden = match self.orden {
+            Some((actual, descendente)) if actual == columna => Some((columna, !descendente)),
+            _ => Some((columna, false)),
+        };
+    }
+
+    /// Fichas que pasan los filtros y la búsqueda, en el orden de la lista
+    pub fn fichas_visibles(&self) -> Vec<&Ficha> {
+        let busqueda = self.busqueda_input.trim().to_lowercase();
+        let mut visibles: Vec<&Ficha> = self
+            .fichas
+            .iter()
+            .filter(|f| match &self.filtro_etiqueta {
+                Some(etiqueta) => f.tiene_etiqueta(etiqueta),
+                None => true,
+            })
+            .filter(|f| {
+                self.filtro_responsable
+                    .is_none_or(|responsable| f.responsable == Some(responsable))
+            })
+            .filter(|f| self.filtro_estado.is_none_or(|estado| f.estado == estado))
+            .filter(|f| {
+                self.filtro_seleccion
+                    .as_ref()
+                    .is_none_or(|filtro| filtro.fichas.contains(&f.id))
+            })
+            .filter(|f| {
+                busqueda.is_empty()
+                    || [&f.titulo, &f.expediente, &f.cliente]
+                        .iter()
+                        .any(|campo| campo.to_lowercase().contains(&busqueda))
+            })
+            .collect();
+
+        if let Some((columna, descendente)) = self.orden {
+            visibles.sort_by(|a, b| {
+                let orden = columna.comparar(a, b, &self.fechas);
+                if descendente {
+                    orden.reverse()
+                } else {
+                    orden
+                }
+            });
+        }
+        visibles
+    }
+
+    /// Resalta la ficha visible siguiente (o anterior, con `desplazamiento`
+    /// negativo); sin selección previa empieza por la primera
+    pub fn mover_seleccion(&mut self, desplazamiento: isize) {
+        let visibles: Vec<Uuid> = self.fichas_visibles().iter().map(|f| f.id).collect();
+        if visibles.is_empty() {
+            self.seleccion = None;
+            return;
+        }
+        let indice = match self
+            .seleccion
+            .and_then(|id| visibles.iter().position(|v| *v == id))
+        {
+            Some(actual) => actual
+                .saturating_add_signed(desplazamiento)
+                .min(visibles.len() - 1),
+            None => 0,
+        };
+        self.seleccion = Some(visibles[indice]);
+    }
+
+    pub fn actualizar_colores_etiquetas(&mut self, colores: BTreeMap<String, [u8; 3]>) {
+        self.colores_etiquetas = colores;
+    }
+
+    /// Todas las etiquetas usadas en alguna ficha, ordenadas
+    fn etiquetas_disponibles(&self) -> Vec<String> {
+        self.fichas
+            .iter()
+            .flat_map(|f| f.etiquetas.iter().cloned())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Agrega una etiqueta a la ficha del formulario, si no la tiene
+    pub fn agregar_etiqueta(&mut self, etiqueta: String) {
+        let etiqueta = etiqueta.trim().to_string();
+        if !etiqueta.is_empty() && !self.etiquetas_input.contains(&etiqueta) {
+            self.etiquetas_input.push(etiqueta);
+        }
+        self.nueva_etiqueta_input.clear();
+    }
+
+    /// Chip de color con el nombre de una etiqueta de ficha
+    fn chip_etiqueta(&self, etiqueta: &str) -> container::Container<'_, FichaMessage> {
+        let color = self
+            .colores_etiquetas
+            .get(etiqueta)
+            .copied()
+            .unwrap_or(COLOR_PREDETERMINADO);
+        container(text(etiqueta.to_string()).size(self.letra.tamanio(11)))
+            .padding([1, 6])
+            .style(styles::etiqueta(color))
+    }
+
+    pub fn actualizar_usuarios(&mut self, usuarios: Vec<Usuario>) {
+        self.usuarios = usuarios;
+    }
+
+    fn usuario(&self, id: Option<Uuid>) -> Option<&Usuario> {
+        id.and_then(|id| self.usuarios.iter().find(|u| u.id == id))
+    }
+
+    /// Indica si el rol del usuario actual permite la acción
+    pub fn puede(&self, accion: Accion) -> bool {
+        self.usuario(self.usuario_actual)
+            .is_some_and(|u| u.rol.puede(accion))
+    }
+
+    /// Nombre del usuario, o un guion para los registros sin autor
+    /// Texto de la interfaz en el idioma elegido
+    fn texto(&self, clave: &str) -> String {
+        i18n::texto(self.idioma, clave)
+    }
+
+    fn texto_con(&self, clave: &str, argumentos: &[(&str, FluentValue<'_>)]) -> String {
+        i18n::texto_con(self.idioma, clave, argumentos)
+    }
+
+    fn nombre_usuario(&self, id: Option<Uuid>) -> &str {
+        self.usuario(id).map(|u| u.nombre.as_str()).unwrap_or("—")
+    }
+
+    pub fn actualizar_categorias(&mut self, categorias: Vec<CategoriaHoja>) {
+        self.categorias = categorias;
+    }
+
+    pub fn actualizar_plantillas(&mut self, plantillas: Vec<PlantillaHoja>) {
+        self.plantillas = plantillas;
+    }
+
+    /// Inserta texto en la posición del cursor del editor de nueva hoja
+    pub fn insertar_en_nueva_hoja(&mut self, texto: String) {
+        self.nueva_hoja_input
+            .perform(Action::Edit(Edit::Paste(Arc::new(texto))));
+    }
+
+    pub fn abrir_historial(&mut self, ficha_id: Uuid) {
+        self.ficha_historial_id = Some(ficha_id);
+        self.limpiar_nueva_hoja();
+        self.filtro_categoria = None;
+        self.mensaje_escrito = None;
+        self.mensaje_correo = None;
+        self.pestania = PestaniaHistorial::Hojas;
+        let cliente = self
+            .fichas
+            .iter()
+            .find(|f| f.id == ficha_id)
+            .map(|f| f.cliente.as_str())
+            .unwrap_or_default();
+        self.cuenta.reiniciar(cliente, self.fechas.hoy());
+        self.tiempo.reiniciar(self.fechas.hoy());
+        self.tarea.reiniciar();
+        self.vinculo.reiniciar();
+        self.refrescar_markdown();
+    }
+
+    pub fn cerrar_historial(&mut self) {
+        self.ficha_historial_id = None;
+        self.limpiar_nueva_hoja();
+        self.filtro_categoria = None;
+        self.hojas_markdown.clear();
+    }
+
+    /// Botón para iniciar o detener el cronómetro, con el tiempo transcurrido
+    fn boton_cronometro(&self, ficha: &Ficha) -> Element<'_, FichaMessage> {
+        match ficha.segundos_cronometro() {
+            Some(segundos) => button(text(format!("■ {}", tiempo::formatear_segundos(segundos))))
+                .on_press_maybe(
+                    self.puede(Accion::EditarFichas)
+                        .then_some(FichaMessage::DetenerCronometro(ficha.id)),
+                )
+                .padding(8)
+                .style(styles::cancel_button)
+                .into(),
+            None => button(text(self.texto("cronometro-iniciar")))
+                .on_press_maybe(
+                    self.puede(Accion::EditarFichas)
+                        .then_some(FichaMessage::IniciarCronometro(ficha.id)),
+                )
+                .padding(8)
+                .style(styles::secondary_button)
+                .into(),
+        }
+    }
+
+    fn categoria(&self, id: &str) -> Option<&CategoriaHoja> {
+        self.categorias.iter().find(|c| c.id == id)
+    }
+
+    /// Chip de color con el icono y nombre de la categoría de una hoja
+    fn etiqueta_categoria(&self, id: &str) -> Element<'_, FichaMessage> {
+        match self.categoria(id) {
+            Some(categoria) => container(text(categoria.to_string()).size(self.letra.tamanio(12)))
+                .padding([2, 8])
+                .style(styles::etiqueta(categoria.color))
+                .into(),
+            None => Space::with_width(0).into(),
+        }
+    }
+
+    /// Texto de la nueva hoja en el historial, sin espacios finales
+    pub fn texto_nueva_hoja(&self) -> String {
+        hoja_editor::texto(&self.nueva_hoja_input)
+    }
+
+    pub fn limpiar_nueva_hoja(&mut self) {
+        self.nueva_hoja_input = text_editor::Content::new();
+        self.categoria_nueva_hoja = None;
+        let hoy = self.fechas.hoy();
+        self.fecha_nueva_hoja_input = hoy.format(FORMATO_DIA).to_string();
+        self.selector_fecha_nueva_hoja = SelectorFecha::new(hoy);
+        self.error_nueva_hoja = None;
+    }
+
+    /// Fecha del movimiento de la nueva hoja; no puede ser posterior a hoy
+    pub fn fecha_nueva_hoja(&self) -> Result<NaiveDate, String> {
+        let fecha = NaiveDate::parse_from_str(self.fecha_nueva_hoja_input.trim(), FORMATO_DIA)
+            .map_err(|_| self.texto("nueva-hoja-fecha-invalida"))?;
+        if fecha > self.fechas.hoy() {
+            return Err(self.texto("nueva-hoja-fecha-futura"));
+        }
+        Ok(fecha)
+    }
+
+    pub fn actualizar_selector_fecha(&mut self, mensaje: SelectorFechaMessage) {
+        let actual = self.fecha_nueva_hoja().ok();
+        if let Some(fecha) = self.selector_fecha_nueva_hoja.actualizar(mensaje, actual) {
+            self.fecha_nueva_hoja_input = fecha.format(FORMATO_DIA).to_string();
+            self.error_nueva_hoja = None;
+        }
+    }
+
+    /// Vuelve a interpretar las hojas de la ficha abierta en el historial
+    fn refrescar_markdown(&mut self) {
+        self.hojas_markdown = self
+            .ficha_historial_id
+            .and_then(|id| self.fichas.iter().find(|f| f.id == id))
+            .map(|ficha| {
+                ficha
+                    .obtener_hojas()
+                    .iter()
+                    .map(|hoja| markdown::parse(&hoja.contenido).collect())
+                    .collect()
+            })
+            .unwrap_or_default();
     }
 
     pub fn iniciar_nueva_ficha(&mut self) {
         self.editando = true;
         self.titulo_input.clear();
         self.descripcion_input.clear();
-        self.hoja_input.clear();
+        self.expediente_input.clear();
+        self.cliente_input.clear();
+        self.partes_input.clear();
+        self.etiquetas_input.clear();
+        self.nueva_etiqueta_input.clear();
+        self.estado_input = Estado::default();
+        self.hoja_input = text_editor::Content::new();
+        self.responsable_input = self.usuario_actual;
         self.ficha_editando_id = None;
     }
 
@@ -62,10 +639,19 @@ impl FichasView {
             self.editando = true;
             self.titulo_input = ficha.titulo.clone();
             self.descripcion_input = ficha.descripcion.clone();
-            self.hoja_input = ficha
-                .hoja_actual()
-                .map(|h| h.contenido.clone())
-                .unwrap_or_default();
+            self.expediente_input = ficha.expediente.clone();
+            self.cliente_input = ficha.cliente.clone();
+            self.partes_input = Parte::formatear_lista(&ficha.partes);
+            self.etiquetas_input = ficha.etiquetas.clone();
+            self.nueva_etiqueta_input.clear();
+            self.estado_input = ficha.estado;
+            self.responsable_input = ficha.responsable;
+            self.hoja_input = text_editor::Content::with_text(
+                ficha
+                    .hoja_actual()
+                    .map(|h| h.contenido.as_str())
+                    .unwrap_or_default(),
+            );
             self.ficha_editando_id = Some(ficha_id);
         }
     }
@@ -74,17 +660,33 @@ impl FichasView {
         self.editando = false;
         self.titulo_input.clear();
         self.descripcion_input.clear();
-        self.hoja_input.clear();
+        self.expediente_input.clear();
+        self.cliente_input.clear();
+        self.partes_input.clear();
+        self.etiquetas_input.clear();
+        self.nueva_etiqueta_input.clear();
+        self.hoja_input = text_editor::Content::new();
+        self.responsable_input = None;
         self.ficha_editando_id = None;
     }
 
     pub fn obtener_ficha_nueva(&self) -> Option<Ficha> {
-        if !self.titulo_input.is_empty() && !self.hoja_input.is_empty() {
-            Some(Ficha::new(
+        let hoja_inicial = hoja_editor::texto(&self.hoja_input);
+        if !self.titulo_input.is_empty() && !hoja_inicial.is_empty() {
+            let mut ficha = Ficha::new(
                 self.titulo_input.clone(),
                 self.descripcion_input.clone(),
-                self.hoja_input.clone(),
-            ))
+                hoja_inicial,
+                self.fechas.hoy(),
+                self.usuario_actual,
+            );
+            ficha.responsable = self.responsable_input;
+            ficha.expediente = self.expediente_input.clone();
+            ficha.cliente = self.cliente_input.clone();
+            ficha.partes = Parte::parsear_lista(&self.partes_input);
+            ficha.etiquetas = self.etiquetas_input.clone();
+            ficha.estado = self.estado_input;
+            Some(ficha)
         } else {
             None
         }
@@ -93,31 +695,233 @@ impl FichasView {
     pub fn obtener_ficha_editada(&self) -> Option<Ficha> {
         if let Some(id) = self.ficha_editando_id {
             if let Some(mut ficha) = self.fichas.iter().find(|f| f.id == id).cloned() {
-                ficha.actualizar(self.titulo_input.clone(), self.descripcion_input.clone());
+                ficha.actualizar(
+                    self.titulo_input.clone(),
+                    self.descripcion_input.clone(),
+                    self.expediente_input.clone(),
+                    self.cliente_input.clone(),
+                    Parte::parsear_lista(&self.partes_input),
+                    self.usuario_actual,
+                );
+                ficha.etiquetas = self.etiquetas_input.clone();
+                ficha.estado = self.estado_input;
+                ficha.responsable = self.responsable_input;
                 return Some(ficha);
             }
         }
         None
     }
 
-    pub fn view(&self) -> Element<FichaMessage> {
+    pub fn view(&self) -> Element<'_, FichaMessage> {
         // Si estamos viendo el historial de una ficha
         if let Some(ficha_id) = self.ficha_historial_id {
             return self.vista_historial(ficha_id);
         }
 
-        let titulo = text("Fichas").size(24);
+        let titulo = row![
+            text(self.texto("fichas-titulo")).size(self.letra.tamanio(24)),
+            text(self.texto_con(
+                "fichas-cantidad",
+                &[("cantidad", self.fichas_visibles().len().into())]
+            ))
+            .size(self.letra.tamanio(13)),
+        ]
+        .push_maybe(self.aviso_sincronizacion())
+        .spacing(10)
+        .align_y(iced::Alignment::Center);
+
+        let busqueda = text_input(&self.texto("fichas-buscar"), &self.busqueda_input)
+            .id(Self::id_busqueda())
+            .on_input(FichaMessage::BusquedaChanged)
+            .padding(8)
+            .width(Length::Fixed(200.0))
+            .style(styles::campo_texto);
 
-        let boton_nueva = button(text("+ Nueva Ficha"))
+        let boton_modo = match self.modo_lista {
+            ModoLista::Tarjetas => button(text(self.texto("fichas-ver-tabla")))
+                .on_press(FichaMessage::CambiarModoLista(ModoLista::Tabla)),
+            ModoLista::Tabla => button(text(self.texto("fichas-ver-tarjetas")))
+                .on_press(FichaMessage::CambiarModoLista(ModoLista::Tarjetas)),
+        }
+        .padding(8)
+        .style(styles::secondary_button);
+
+        let boton_nueva = button(text(self.texto("fichas-nueva")))
             .on_press(FichaMessage::NuevaFicha)
             .padding(10)
             .style(styles::primary_button);
 
+        let filtro_etiqueta = row![
+            pick_list(
+                self.etiquetas_disponibles(),
+                self.filtro_etiqueta.clone(),
+                FichaMessage::FiltrarEtiqueta,
+            )
+            .placeholder(self.texto("fichas-filtrar-etiqueta"))
+            .padding(8),
+            button(accesibilidad::rotulo(
+                "✕",
+                self.texto("quitar-filtro"),
+                self.letra.tamanio(16),
+            ))
+            .on_press_maybe(
+                self.filtro_etiqueta
+                    .as_ref()
+                    .map(|_| FichaMessage::QuitarFiltroEtiqueta),
+            )
+            .padding(8),
+        ]
+        .spacing(5);
+
+        let filtro_estado = row![
+            pick_list(
+                Estado::TODOS,
+                self.filtro_estado,
+                FichaMessage::FiltrarEstado
+            )
+            .placeholder(self.texto("fichas-filtrar-estado"))
+            .padding(8),
+            button(accesibilidad::rotulo(
+                "✕",
+                self.texto("quitar-filtro"),
+                self.letra.tamanio(16),
+            ))
+            .on_press_maybe(self.filtro_estado.map(|_| FichaMessage::QuitarFiltroEstado))
+            .padding(8),
+        ]
+        .spacing(5);
+
+        // Selección hecha desde el tablero; se quita con un clic
+        let filtro_seleccion: Element<_> = match &self.filtro_seleccion {
+            Some(filtro) => {
+                button(text(format!("{} ✕", filtro.descripcion)).size(self.letra.tamanio(13)))
+                    .on_press(FichaMessage::QuitarFiltroSeleccion)
+                    .padding([6, 10])
+                    .style(styles::primary_button)
+                    .into()
+            }
+            None => Space::with_width(0).into(),
+        };
+
+        let boton_tablero = button(text(self.texto("ir-inicio")))
+            .on_press(FichaMessage::VerTablero)
+            .padding(10)
+            .style(styles::secondary_button);
+
+        let boton_etiquetas = button(text(self.texto("ir-etiquetas")))
+            .on_press(FichaMessage::GestionarEtiquetas)
+            .padding(10)
+            .style(styles::secondary_button);
+
+        let boton_categorias = button(text(self.texto("ir-categorias")))
+            .on_press(FichaMessage::GestionarCategorias)
+            .padding(10)
+            .style(styles::secondary_button);
+
+        let boton_balances = button(text(self.texto("ir-honorarios")))
+            .on_press(FichaMessage::VerBalances)
+            .padding(10)
+            .style(styles::secondary_button);
+
+        let boton_plantillas = button(text(self.texto("ir-plantillas")))
+            .on_press(FichaMessage::GestionarPlantillas)
+            .padding(10)
+            .style(styles::secondary_button);
+
+        let boton_tareas = button(text(self.texto("ir-mis-tareas")))
+            .on_press(FichaMessage::VerMisTareas)
+            .padding(10)
+            .style(styles::secondary_button);
+
+        let boton_horas = button(text(self.texto("ir-horas")))
+            .on_press(FichaMessage::VerReporteTiempo)
+            .padding(10)
+            .style(styles::secondary_button);
+
+        let filtro_responsable = row![
+            pick_list(
+                self.usuarios.as_slice(),
+                self.usuario(self.filtro_responsable).cloned(),
+                FichaMessage::FiltrarResponsable,
+            )
+            .placeholder(self.texto("fichas-filtrar-responsable"))
+            .padding(8),
+            button(accesibilidad::rotulo(
+                "✕",
+                self.texto("quitar-filtro"),
+                self.letra.tamanio(16),
+            ))
+            .on_press_maybe(
+                self.filtro_responsable
+                    .map(|_| FichaMessage::QuitarFiltroResponsable),
+            )
+            .padding(8),
+        ]
+        .spacing(5);
+
+        let usuario_actual: Element<_> = match self.usuario(self.usuario_actual) {
+            Some(usuario) => button(usuarios::avatar(usuario, self.letra))
+                .on_press(FichaMessage::CambiarUsuario)
+                .padding(0)
+                .style(button::text)
+                .into(),
+            None => Space::with_width(0).into(),
+        };
+
+        let boton_usuarios = button(text(self.texto("ir-usuarios")))
+            .on_press(FichaMessage::GestionarUsuarios)
+            .padding(10)
+            .style(styles::secondary_button);
+
+        let boton_preferencias = button(text(self.texto("ir-preferencias")))
+            .on_press(FichaMessage::VerPreferencias)
+            .padding(10)
+            .style(styles::secondary_button);
+
+        let boton_importar = button(text(self.texto("ir-importar")))
+            .on_press(FichaMessage::VerImportacion)
+            .padding(10)
+            .style(styles::secondary_button);
+
+        let boton_correo = button(text(self.texto("ir-correo")))
+            .on_press(FichaMessage::VerCorreo)
+            .padding(10)
+            .style(styles::secondary_button);
+
+        let boton_auditoria = button(text(self.texto("ir-auditoria")))
+            .on_press(FichaMessage::VerAuditoria)
+            .padding(10)
+            .style(styles::secondary_button);
+
+        // Los botones de acciones no permitidas para el rol no se muestran
+        let configurar = self.puede(Accion::Configurar);
         let header = row![
+            usuario_actual,
+            boton_tablero,
             titulo,
             Space::with_width(Length::Fill),
-            boton_nueva
+            busqueda,
+            boton_modo,
+            filtro_seleccion,
+            filtro_estado,
+            filtro_responsable,
+            filtro_etiqueta,
         ]
+        .push_maybe(configurar.then_some(boton_etiquetas))
+        .push_maybe(configurar.then_some(boton_categorias))
+        .push(boton_tareas)
+        .push(boton_balances)
+        .push(boton_horas)
+        .push_maybe(configurar.then_some(boton_plantillas))
+        .push_maybe(
+            self.puede(Accion::GestionarUsuarios)
+                .then_some(boton_usuarios),
+        )
+        .push_maybe(self.puede(Accion::VerAuditoria).then_some(boton_auditoria))
+        .push(boton_preferencias)
+        .push_maybe(self.puede(Accion::EditarFichas).then_some(boton_importar))
+        .push_maybe(self.puede(Accion::EditarFichas).then_some(boton_correo))
+        .push_maybe(self.puede(Accion::EditarFichas).then_some(boton_nueva))
         .spacing(20)
         .padding(10);
 
@@ -130,42 +934,143 @@ impl FichasView {
         column![header, contenido].spacing(10).padding(20).into()
     }
 
-    fn vista_formulario(&self) -> Element<FichaMessage> {
+    fn vista_formulario(&self) -> Element<'_, FichaMessage> {
         let titulo = if self.ficha_editando_id.is_some() {
-            text("Editar Ficha").size(20)
+            text(self.texto("formulario-editar")).size(self.letra.tamanio(20))
         } else {
-            text("Nueva Ficha").size(20)
+            text(self.texto("formulario-nueva")).size(self.letra.tamanio(20))
         };
 
         let titulo_input = column![
-            text("Título:"),
-            text_input("Título de la ficha", &self.titulo_input)
+            text(self.texto("formulario-titulo")),
+            text_input(&self.texto("formulario-titulo-ayuda"), &self.titulo_input)
                 .on_input(FichaMessage::TituloChanged)
-                .padding(8),
+                .padding(8)
+                .style(styles::campo_texto),
         ]
         .spacing(5);
 
         let descripcion_input = column![
-            text("Descripción:"),
-            text_input("Descripción de la ficha", &self.descripcion_input)
+            text(self.texto("formulario-descripcion")),
+            text_input(&self.texto("formulario-descripcion-ayuda"), &self.descripcion_input)
                 .on_input(FichaMessage::DescripcionChanged)
-                .padding(8),
+                .padding(8)
+                .style(styles::campo_texto),
+        ]
+        .spacing(5);
+
+        let expediente_input = column![
+            text(self.texto("formulario-expediente")),
+            text_input(&self.texto("formulario-expediente-ayuda"), &self.expediente_input)
+                .on_input(FichaMessage::ExpedienteChanged)
+                .padding(8)
+                .style(styles::campo_texto),
+        ]
+        .spacing(5);
+
+        let cliente_input = column![
+            text(self.texto("formulario-cliente")),
+            text_input(&self.texto("formulario-cliente-ayuda"), &self.cliente_input)
+                .on_input(FichaMessage::ClienteChanged)
+                .padding(8)
+                .style(styles::campo_texto),
+        ]
+        .spacing(5);
+
+        let partes_input = column![
+            text(self.texto("formulario-partes")),
+            text_input(&self.texto("formulario-partes-ayuda"), &self.partes_input)
+                .on_input(FichaMessage::PartesChanged)
+                .padding(8)
+                .style(styles::campo_texto),
+        ]
+        .spacing(5);
+
+        // Etiquetas actuales y sugerencias que coinciden con lo escrito
+        let etiquetas_actuales = self.etiquetas_input.iter().fold(
+            Row::new().spacing(5),
+            |fila, etiqueta| {
+                fila.push(
+                    button(self.chip_etiqueta(etiqueta))
+                        .on_press(FichaMessage::QuitarEtiqueta(etiqueta.clone()))
+                        .padding(0)
+                        .style(button::text),
+                )
+            },
+        );
+
+        let prefijo = self.nueva_etiqueta_input.trim().to_lowercase();
+        let sugerencias = self
+            .etiquetas_disponibles()
+            .into_iter()
+            .filter(|e| {
+                !prefijo.is_empty()
+                    && e.to_lowercase().starts_with(&prefijo)
+                    && !self.etiquetas_input.contains(e)
+            })
+            .take(6)
+            .fold(Row::new().spacing(5), |fila, etiqueta| {
+                fila.push(
+                    button(text(etiqueta.clone()).size(self.letra.tamanio(12)))
+                        .on_press(FichaMessage::AgregarEtiqueta(etiqueta))
+                        .padding([2, 8]),
+                )
+            });
+
+        let etiquetas_input = column![
+            text(self.texto("formulario-etiquetas")),
+            etiquetas_actuales,
+            text_input(&self.texto("formulario-etiqueta-nueva"), &self.nueva_etiqueta_input)
+                .on_input(FichaMessage::NuevaEtiquetaChanged)
+                .on_submit(FichaMessage::AgregarEtiqueta(
+                    self.nueva_etiqueta_input.clone()
+                ))
+                .padding(8)
+                .style(styles::campo_texto),
+            sugerencias,
+        ]
+        .spacing(5);
+
+        let estado_input = column![
+            text(self.texto("formulario-estado")),
+            pick_list(
+                Estado::TODOS,
+                Some(self.estado_input),
+                FichaMessage::EstadoSeleccionado,
+            )
+            .padding(8),
+        ]
+        .spacing(5);
+
+        let responsable_input = column![
+            text(self.texto("formulario-responsable")),
+            pick_list(
+                self.usuarios.as_slice(),
+                self.usuario(self.responsable_input).cloned(),
+                FichaMessage::ResponsableSeleccionado,
+            )
+            .placeholder(self.texto("formulario-sin-responsable"))
+            .padding(8),
         ]
         .spacing(5);
 
         let hoja_picker = column![
-            text("Hoja inicial:"),
-            text_input("Contenido de la hoja inicial", &self.hoja_input)
-                .on_input(FichaMessage::HojaChanged)
-                .padding(8),
+            text(self.texto("formulario-hoja-inicial")),
+            hoja_editor::editor(
+                &self.hoja_input,
+                self.texto("formulario-hoja-inicial-ayuda"),
+                FichaMessage::HojaEditada,
+                FichaMessage::FormatoHoja,
+                self.idioma,
+            ),
         ]
         .spacing(5);
 
         let botones = row![
-            button(text("Guardar"))
+            button(text(self.texto("formulario-guardar")))
                 .on_press(FichaMessage::GuardarFicha)
                 .padding(10),
-            button(text("Cancelar"))
+            button(text(self.texto("formulario-cancelar")))
                 .on_press(FichaMessage::CancelarEdicion)
                 .padding(10),
         ]
@@ -175,6 +1080,9 @@ impl FichasView {
             titulo,
             titulo_input,
             descripcion_input,
+            row![expediente_input, cliente_input].spacing(15),
+            row![partes_input, estado_input, responsable_input].spacing(15),
+            etiquetas_input,
             hoja_picker,
             botones
         ]
@@ -183,152 +1091,490 @@ impl FichasView {
         .into()
     }
 
-    fn vista_lista(&self) -> Element<FichaMessage> {
+    fn vista_lista(&self) -> Element<'_, FichaMessage> {
         if self.fichas.is_empty() {
-            return container(text("No hay fichas. Crea una nueva."))
+            return container(text(self.texto("fichas-vacia")))
                 .padding(20)
                 .into();
         }
 
-        // Agrupar las cards en filas de 3
-        let cards_per_row = 3;
-        let mut rows_container = Column::new().spacing(15).padding(10);
-        
-        for chunk in self.fichas.chunks(cards_per_row) {
-            let mut row_elements = iced::widget::Row::new().spacing(15);
-            
-            for ficha in chunk {
-                let hoja_actual = ficha
-                    .hoja_actual()
-                    .map(|h| h.contenido.as_str())
-                    .unwrap_or("Sin hoja");
+        match self.modo_lista {
+            ModoLista::Tarjetas => {
+                responsive(move |tamanio| self.vista_tarjetas(tamanio.width)).into()
+            }
+            ModoLista::Tabla => self.vista_tabla(),
+        }
+    }
 
-                let fecha_hoja_actual = ficha
-                    .hoja_actual()
-                    .map(|h| h.fecha.format("%d/%m/%Y %H:%M").to_string())
-                    .unwrap_or("Sin fecha".into());
-
-                let card = container(
-                    column![
-                        text(&ficha.titulo).size(18),
-                        text(&ficha.descripcion).size(14),
-                        row![
-                            text("Ultima Hoja: ").size(12),
-                            text(hoja_actual).size(12).font(iced::Font {
-                                weight: iced::font::Weight::Bold,
-                                ..Default::default()
-                            }),
-                            text(", creada el: ").size(12),
-                            text(fecha_hoja_actual).size(10).font(iced::Font {
-                                weight: iced::font::Weight::Bold,
-                                ..Default::default()
-                            }),
-                        ],
-                        text(format!(
-                            "Creada: {}",
-                            ficha.fecha_creacion.format("%d/%m/%Y %H:%M")
-                        ))
-                        .size(11),
-                        Space::with_height(Length::Fill),
-                        row![
-                            button(text("Ver Hojas").center())
-                                .on_press(FichaMessage::VerHistorial(ficha.id))
-                                .padding(8)
-                                .width(Length::Fixed(110.0))
-                                .style(styles::primary_button),
-                            button(text("Editar").center())
-                                .on_press(FichaMessage::EditarFicha(ficha.id))
-                                .padding(8)
-                                .width(Length::Fixed(85.0))
-                                .style(styles::secondary_button),
-                            button(text("Eliminar").center())
-                                .on_press(FichaMessage::EliminarFicha(ficha.id))
-                                .width(Length::Fixed(90.0))
-                                .style(styles::cancel_button)
-                                .padding(8),
-                        ]
-                        .spacing(8),
-                    ]
-                    .spacing(8),
-                )
-                .padding(15)
-                .width(Length::Fixed(320.0))
-                .height(Length::Fixed(240.0))
-                .style(styles::card_container);
+    /// Grilla de tarjetas con tantas columnas como entren en `ancho`
+    fn vista_tarjetas(&self, ancho: f32) -> Element<'_, FichaMessage> {
+        let por_fila = self.tarjetas_por_fila.unwrap_or_else(|| {
+            (((ancho - 2.0 * 10.0 + ESPACIO_TARJETAS)
+            / (ANCHO_MINIMO_TARJETA + ESPACIO_TARJETAS))
+            .floor() as usize)
+            .max(1)
+        });
+        let mut rows_container = Column::new().spacing(ESPACIO_TARJETAS).padding(10);
+
+        for chunk in self.fichas_visibles().chunks(por_fila) {
+            // La última fila se completa para que sus tarjetas midan lo mismo
+            let row_elements = chunk
+                .iter()
+                .map(|ficha| self.tarjeta(ficha))
+                .chain((chunk.len()..por_fila).map(|_| Space::with_width(Length::Fill).into()))
+                .fold(Row::new().spacing(ESPACIO_TARJETAS), Row::push);
 
-                row_elements = row_elements.push(card);
-            }
-            
             rows_container = rows_container.push(row_elements);
         }
 
         scrollable(rows_container).into()
     }
 
-    fn vista_historial(&self, ficha_id: Uuid) -> Element<FichaMessage> {
+    fn tarjeta<'a>(&'a self, ficha: &'a Ficha) -> Element<'a, FichaMessage> {
+        let ultima_de = |etiqueta: String, categoria: &str| {
+            let (resumen, fecha) = ficha
+                .ultima_hoja_de(categoria)
+                .map(|h| (h.resumen(40), self.fechas.dia_corto(h.fecha)))
+                .unwrap_or((self.texto("tarjeta-sin-registrar"), String::new()));
+
+            row![
+                text(format!("{}: ", etiqueta)).size(self.letra.tamanio(12)),
+                text(resumen)
+                    .size(self.letra.tamanio(12))
+                    .width(Length::Fill)
+                    .font(iced::Font {
+                        weight: iced::font::Weight::Bold,
+                        ..Default::default()
+                    }),
+                text(format!(" {}", fecha)).size(self.letra.tamanio(10)),
+            ]
+        };
+
+        let etiquetas = ficha
+            .etiquetas
+            .iter()
+            .fold(Row::new().spacing(4), |fila, etiqueta| {
+                fila.push(self.chip_etiqueta(etiqueta))
+            });
+
+        let saldo: Element<_> = match cuenta::resumen_saldo(&ficha.saldo()) {
+            Some(resumen) => text(self.texto_con("tarjeta-saldo", &[("saldo", resumen.into())]))
+                .size(self.letra.tamanio(12))
+                .into(),
+            None => Space::with_height(0).into(),
+        };
+
+        container(
+            column![
+                row![
+                    text(&ficha.titulo).size(self.letra.tamanio(18)).width(Length::Fill),
+                    match self.usuario(ficha.responsable) {
+                        Some(usuario) => usuarios::avatar(usuario, self.letra),
+                        None => Space::with_width(0).into(),
+                    },
+                ],
+                etiquetas,
+                text(&ficha.descripcion).size(self.letra.tamanio(14)),
+                ultima_de(self.texto("tarjeta-ultimo-escrito"), CategoriaHoja::ESCRITO),
+                ultima_de(self.texto("tarjeta-ultima-notificacion"), CategoriaHoja::NOTIFICACION),
+                saldo,
+                row![
+                    text(self.texto_con(
+                        "tarjeta-creada",
+                        &[("fecha", self.fechas.fecha_hora(ficha.fecha_creacion).into())]
+                    ))
+                    .size(self.letra.tamanio(11)),
+                    text(format!(" · {}", ficha.estado)).size(self.letra.tamanio(11)),
+                    Space::with_width(Length::Fill),
+                    self.boton_cronometro(ficha),
+                ]
+                .align_y(iced::Alignment::Center),
+                Space::with_height(Length::Fill),
+                row![
+                    button(text(self.texto("tarjeta-ver-hojas")).center())
+                        .on_press(FichaMessage::VerHistorial(ficha.id))
+                        .padding(8)
+                        .width(Length::Fixed(110.0))
+                        .style(styles::primary_button),
+                    button(text(self.texto("tarjeta-editar")).center())
+                        .on_press_maybe(
+                            self.puede(Accion::EditarFichas)
+                                .then_some(FichaMessage::EditarFicha(ficha.id)),
+                        )
+                        .padding(8)
+                        .width(Length::Fixed(85.0))
+                        .style(styles::secondary_button),
+                    button(text(self.texto("tarjeta-eliminar")).center())
+                        .on_press_maybe(
+                            self.puede(Accion::EliminarFichas)
+                                .then_some(FichaMessage::EliminarFicha(ficha.id)),
+                        )
+                        .width(Length::Fixed(90.0))
+                        .style(styles::cancel_button)
+                        .padding(8),
+                ]
+                .spacing(8),
+            ]
+            .spacing(8),
+        )
+        .padding(15)
+        .width(Length::Fill)
+        .style(if self.seleccion == Some(ficha.id) {
+            styles::card_seleccionada
+        } else {
+            styles::card_container
+        })
+        .into()
+    }
+
+    /// Lista compacta, una ficha por renglón, ordenable por columna
+    fn vista_tabla(&self) -> Element<'_, FichaMessage> {
+        let encabezado = ColumnaTabla::TODAS
+            .iter()
+            .fold(Row::new().spacing(10), |fila, columna| {
+                let indicador = match self.orden {
+                    Some((actual, false)) if actual == *columna => " ▲",
+                    Some((actual, true)) if actual == *columna => " ▼",
+                    _ => "",
+                };
+                fila.push(
+                    button(
+                        text(format!("{}{}", self.texto(columna.clave()), indicador))
+                            .size(self.letra.tamanio(13)),
+                    )
+                        .on_press(FichaMessage::OrdenarPor(*columna))
+                        .padding([4, 0])
+                        .width(Length::FillPortion(columna.ancho()))
+                        .style(button::text),
+                )
+            });
+
+        let filas = self
+            .fichas_visibles()
+            .into_iter()
+            .fold(Column::new().spacing(2), |lista, ficha| {
+                let celdas = ColumnaTabla::TODAS.iter().map(|columna| {
+                    let valor = match columna {
+                        ColumnaTabla::Titulo => ficha.titulo.clone(),
+                        ColumnaTabla::Expediente => ficha.expediente.clone(),
+                        ColumnaTabla::UltimaHoja => ficha
+                            .hoja_actual()
+                            .map(|h| h.resumen(80))
+                            .unwrap_or_default(),
+                        ColumnaTabla::UltimoMovimiento => {
+                            let ultimo = ficha.ultimo_movimiento(|m| self.fechas.dia(m));
+                            self.fechas.dia_corto(ultimo)
+                        }
+                        ColumnaTabla::Estado => ficha.estado.to_string(),
+                    };
+                    text(valor)
+                        .size(self.letra.tamanio(13))
+                        .width(Length::FillPortion(columna.ancho()))
+                        .into()
+                });
+
+                lista.push(
+                    button(Row::with_children(celdas).spacing(10))
+                        .on_press(FichaMessage::VerHistorial(ficha.id))
+                        .padding([6, 10])
+                        .width(Length::Fill)
+                        .style(if self.seleccion == Some(ficha.id) {
+                            styles::secondary_button
+                        } else {
+                            button::text
+                        }),
+                )
+            });
+
+        column![
+            container(encabezado).padding([0, 10]),
+            scrollable(filas),
+        ]
+        .spacing(5)
+        .padding(10)
+        .into()
+    }
+
+    fn aviso_sincronizacion(&self) -> Option<Element<'_, FichaMessage>> {
+        self.error_sincronizacion
+            .as_ref()
+            .map(|error| text(format!("⚠ {}", error)).size(self.letra.tamanio(12)).into())
+    }
+
+    /// Resultado de la última consulta en el portal y botón para repetirla;
+    /// nada si la sincronización no está configurada ni se hizo nunca
+    fn estado_sincronizacion(&self, ficha: &Ficha) -> Option<Element<'_, FichaMessage>> {
+        if !self.sincronizacion_activa && ficha.sincronizacion.is_none() {
+            return None;
+        }
+
+        let estado = match &ficha.sincronizacion {
+            None => self.texto("sincronizacion-nunca"),
+            Some(estado) => {
+                let fecha = self.fechas.fecha_hora(estado.momento);
+                match &estado.resultado {
+                    ResultadoSincronizacion::Correcta { nuevas } => self.texto_con(
+                        "sincronizacion-correcta",
+                        &[("fecha", fecha.into()), ("nuevas", (*nuevas).into())],
+                    ),
+                    ResultadoSincronizacion::Fallida { error } => self.texto_con(
+                        "sincronizacion-fallida",
+                        &[("fecha", fecha.into()), ("error", error.as_str().into())],
+                    ),
+                }
+            }
+        };
+
+        // Solo se ofrece si el expediente se puede consultar
+        let consultable = self.sincronizacion_activa
+            && self.puede(Accion::EditarFichas)
+            && ClaveExpediente::extraer(&ficha.expediente).is_some();
+        let boton = consultable.then(|| {
+            let etiqueta = if self.sincronizando {
+                self.texto("sincronizacion-en-curso")
+            } else {
+                self.texto("sincronizacion-ahora")
+            };
+            button(text(etiqueta).size(self.letra.tamanio(12)))
+                .on_press_maybe(
+                    (!self.sincronizando).then_some(FichaMessage::Sincronizar(ficha.id)),
+                )
+                .padding([2, 8])
+                .style(styles::secondary_button)
+        });
+
+        Some(
+            row![text(estado).size(self.letra.tamanio(12))]
+                .push_maybe(boton)
+                .push_maybe(self.aviso_sincronizacion())
+                .spacing(8)
+                .align_y(iced::Alignment::Center)
+                .into(),
+        )
+    }
+
+    fn vista_historial(&self, ficha_id: Uuid) -> Element<'_, FichaMessage> {
         let ficha = self.fichas.iter().find(|f| f.id == ficha_id);
 
         if let Some(ficha) = ficha {
-            let titulo = text(format!("Hojas de la Ficha - {}", ficha.titulo)).size(24);
+            let titulo = text(self.texto_con(
+                "historial-titulo",
+                &[("titulo", ficha.titulo.as_str().into())],
+            ))
+            .size(self.letra.tamanio(24));
 
-            let boton_volver = button(text("← Volver"))
+            let boton_volver = button(text(self.texto("historial-volver")))
                 .on_press(FichaMessage::CerrarHistorial)
                 .padding(10)
                 .style(styles::secondary_button);
 
-            let header = row![boton_volver, Space::with_width(Length::Fill), titulo]
-                .spacing(20)
-                .padding(10);
+            let autoria = text(self.texto_con(
+                "historial-datos",
+                &[
+                    ("responsable", self.nombre_usuario(ficha.responsable).into()),
+                    ("creador", self.nombre_usuario(ficha.creada_por).into()),
+                    ("modificador", self.nombre_usuario(ficha.modificada_por).into()),
+                    ("fecha", self.fechas.fecha_hora(ficha.fecha_modificacion).into()),
+                ],
+            ))
+            .size(self.letra.tamanio(12));
+
+            let header = row![
+                boton_volver,
+                self.boton_cronometro(ficha),
+                Space::with_width(Length::Fill),
+                column![titulo, autoria]
+                    .push_maybe(self.estado_sincronizacion(ficha))
+                    .spacing(4)
+            ]
+            .spacing(20)
+            .padding(10);
 
             // Formulario para agregar nueva hoja
             let nueva_hoja_form = container(
                 column![
-                    text("Agregar nueva hoja:").size(16),
                     row![
-                        text_input("Ingrese el contenido de la nueva hoja...", &self.nueva_hoja_input)
-                            .on_input(FichaMessage::HojaChanged)
+                        text(self.texto("nueva-hoja-titulo")).size(self.letra.tamanio(16)),
+                        Space::with_width(Length::Fill),
+                        pick_list(
+                            self.plantillas.as_slice(),
+                            None::<PlantillaHoja>,
+                            FichaMessage::InsertarPlantilla,
+                        )
+                        .placeholder(self.texto("nueva-hoja-plantilla"))
+                        .padding(8),
+                    ]
+                    .spacing(10),
+                    hoja_editor::editor(
+                        &self.nueva_hoja_input,
+                        self.texto("nueva-hoja-contenido"),
+                        FichaMessage::NuevaHojaEditada,
+                        FichaMessage::FormatoNuevaHoja,
+                        self.idioma,
+                    ),
+                    row![
+                        text(self.texto("nueva-hoja-fecha")),
+                        text_input(&self.texto("fecha-ayuda"), &self.fecha_nueva_hoja_input)
+                            .on_input(FichaMessage::FechaNuevaHojaChanged)
                             .padding(8)
-                            .width(Length::Fill),
-                        button(text("Agregar"))
+                            .width(Length::Fixed(120.0))
+                            .style(styles::campo_texto),
+                        self.selector_fecha_nueva_hoja
+                            .view(self.fecha_nueva_hoja().ok(), self.idioma, self.letra)
+                            .map(FichaMessage::SelectorFechaNuevaHoja),
+                        pick_list(
+                            self.categorias.as_slice(),
+                            self.categoria_nueva_hoja.clone(),
+                            FichaMessage::CategoriaNuevaHoja,
+                        )
+                        .placeholder(self.texto("nueva-hoja-categoria"))
+                        .padding(8),
+                        button(accesibilidad::rotulo(
+                            "✕",
+                            self.texto("nueva-hoja-quitar-categoria"),
+                            self.letra.tamanio(16),
+                        ))
+                        .on_press_maybe(
+                            self.categoria_nueva_hoja
+                                .as_ref()
+                                .map(|_| FichaMessage::QuitarCategoriaNuevaHoja),
+                        )
+                        .padding(8),
+                        Space::with_width(Length::Fill),
+                        button(text(self.texto("nueva-hoja-agregar")))
                             .on_press(FichaMessage::AgregarHoja(ficha_id))
                             .padding(8)
                             .style(styles::primary_button),
                     ]
                     .spacing(10),
                 ]
+                .push_maybe(
+                    self.error_nueva_hoja
+                        .as_ref()
+                        .map(|error| text(format!("⚠ {}", error)).size(self.letra.tamanio(13))),
+                )
                 .spacing(10),
             )
             .padding(15)
             .width(Length::Fill)
             .style(styles::card_container);
 
+            // Generación de escritos desde plantillas de documentos
+            let escritos: Element<_> = if self.plantillas_escritos.is_empty() {
+                text(self.texto("escritos-sin-plantillas"))
+                    .size(self.letra.tamanio(12))
+                    .into()
+            } else {
+                pick_list(
+                    self.plantillas_escritos.as_slice(),
+                    None::<PlantillaEscrito>,
+                    FichaMessage::GenerarEscrito,
+                )
+                .placeholder(self.texto("escritos-generar"))
+                .padding(8)
+                .into()
+            };
+
+            let escritos_form = row![
+                text(self.texto("escritos-titulo")).size(self.letra.tamanio(16)),
+                escritos,
+                text(self.mensaje_escrito.as_deref().unwrap_or_default())
+                    .size(self.letra.tamanio(12)),
+            ]
+            .spacing(10)
+            .align_y(iced::Alignment::Center);
+
+            // Los correos se agregan soltándolos sobre la ventana
+            let correo_form = row![
+                text(self.texto("correo-soltar")).size(self.letra.tamanio(12)),
+                text(self.mensaje_correo.as_deref().unwrap_or_default())
+                    .size(self.letra.tamanio(12)),
+            ]
+            .spacing(10);
+
+            // Filtro por categoría
+            let filtro = self.categorias.iter().fold(
+                Row::new().spacing(5).push(
+                    button(text(self.texto("historial-todas")).size(self.letra.tamanio(12)))
+                        .on_press(FichaMessage::FiltrarCategoria(None))
+                        .padding([4, 8])
+                        .style(if self.filtro_categoria.is_none() {
+                            styles::primary_button
+                        } else {
+                            styles::secondary_button
+                        }),
+                ),
+                |fila, categoria| {
+                    let activa = self.filtro_categoria.as_ref() == Some(&categoria.id);
+                    fila.push(
+                        button(text(categoria.to_string()).size(self.letra.tamanio(12)))
+                            .on_press(FichaMessage::FiltrarCategoria(Some(categoria.id.clone())))
+                            .padding([4, 8])
+                            .style(if activa {
+                                styles::primary_button
+                            } else {
+                                styles::secondary_button
+                            }),
+                    )
+                },
+            );
+
             // Lista de hojas
-            let hojas_list: Element<_> = ficha
+            let visibles: Vec<_> = ficha
                 .obtener_hojas()
                 .iter()
-                .fold(Column::new().spacing(8), |column, hoja| {
+                .zip(&self.hojas_markdown)
+                .filter(|(hoja, _)| match &self.filtro_categoria {
+                    Some(filtro) => hoja.categoria.as_ref() == Some(filtro),
+                    None => true,
+                })
+                .collect();
+            let cantidad_hojas = visibles.len();
+            let hojas_list: Element<_> = visibles
+                .into_iter()
+                .fold(Column::new().spacing(8), |column, (hoja, items)| {
+                    let contenido = markdown::view(
+                        items,
+                        markdown::Settings::with_text_size(16),
+                        markdown::Style::from_palette(self.tema.palette()),
+                    )
+                    .map(|_url| FichaMessage::EnlaceHoja);
+
+                    let etiqueta = match &hoja.categoria {
+                        Some(id) => self.etiqueta_categoria(id),
+                        None => Space::with_width(0).into(),
+                    };
+
+                    let adjuntos = hoja.adjuntos.iter().fold(Column::new(), |lista, adjunto| {
+                        lista.push(
+                            text(format!("📎 {}", adjunto.ruta.display()))
+                                .size(self.letra.tamanio(12)),
+                        )
+                    });
+
                     let hoja_card = container(
                         column![
-                            text(&hoja.contenido).size(16),
-                            text(format!(
-                                "Fecha: {}",
-                                hoja.fecha.format("%d/%m/%Y %H:%M:%S")
+                            etiqueta,
+                            contenido,
+                            adjuntos,
+                            text(self.texto_con(
+                                "historial-hoja-datos",
+                                &[
+                                    ("fecha", self.fechas.dia_largo(hoja.fecha).into()),
+                                    (
+                                        "registrada",
+                                        self.fechas.fecha_hora(hoja.registrada).into()
+                                    ),
+                                    ("autor", self.nombre_usuario(hoja.autor).into()),
+                                ],
                             ))
-                            .size(12),
+                            .size(self.letra.tamanio(12)),
                         ]
                         .spacing(5),
                     )
                     .padding(12)
                     .width(Length::Fill)
-                    .style(|_theme| container::Style {
-                        background: Some(Color::from_rgb(0.95, 0.97, 0.99).into()),
-                        border: Border {
-                            color: Color::from_rgb(0.7, 0.8, 0.9),
-                            width: 1.0,
-                            radius: 5.0.into(),
-                        },
-                        ..Default::default()
-                    });
+                    .style(styles::hoja_card);
 
                     column.push(hoja_card)
                 })
@@ -336,7 +1582,12 @@ impl FichasView {
 
             let hojas_container = container(
                 column![
-                    text("Hojas (más reciente primero):").size(16),
+                    text(self.texto_con(
+                        "historial-hojas",
+                        &[("cantidad", cantidad_hojas.into())]
+                    ))
+                    .size(self.letra.tamanio(16)),
+                    filtro,
                     hojas_list,
                 ]
                 .spacing(10),
@@ -344,12 +1595,59 @@ impl FichasView {
             .padding(15)
             .width(Length::Fill);
 
-            column![header, nueva_hoja_form, hojas_container]
-                .spacing(15)
-                .padding(20)
-                .into()
+            let pestania = |titulo, pestania| {
+                button(text(titulo))
+                    .on_press(FichaMessage::CambiarPestania(pestania))
+                    .padding([6, 14])
+                    .style(if self.pestania == pestania {
+                        styles::primary_button
+                    } else {
+                        styles::secondary_button
+                    })
+            };
+
+            let pestanias = row![
+                pestania(self.texto("pestania-hojas"), PestaniaHistorial::Hojas),
+                pestania(self.texto("pestania-cuenta"), PestaniaHistorial::Cuenta),
+                pestania(self.texto("pestania-tiempo"), PestaniaHistorial::Tiempo),
+            ]
+            .spacing(5);
+
+            let contenido: Element<_> = match self.pestania {
+                PestaniaHistorial::Hojas => {
+                    let editable = self.puede(Accion::EditarFichas);
+                    let pendientes = self
+                        .tarea
+                        .view(ficha, editable, self.idioma, self.letra)
+                        .map(FichaMessage::Tarea);
+                    let vinculos = self
+                        .vinculo
+                        .view(ficha, &self.fichas, editable, self.idioma, self.letra)
+                        .map(FichaMessage::Vinculo);
+                    column![pendientes, vinculos]
+                        .push_maybe(editable.then_some(nueva_hoja_form))
+                        .push_maybe(editable.then_some(escritos_form))
+                        .push_maybe(editable.then_some(correo_form))
+                        .push(hojas_container)
+                        .spacing(15)
+                        .into()
+                }
+                PestaniaHistorial::Cuenta => self
+                    .cuenta
+                    .view(ficha, self.puede(Accion::EditarCuenta), self.idioma, self.letra)
+                    .map(FichaMessage::Cuenta),
+                PestaniaHistorial::Tiempo => self
+                    .tiempo
+                    .view(ficha, self.puede(Accion::EditarFichas), self.idioma, self.letra)
+                    .map(FichaMessage::Tiempo),
+            };
+
+            column![header, pestanias, scrollable(contenido)]
+            .spacing(15)
+            .padding(20)
+            .into()
         } else {
-            container(text("Ficha no encontrada")).padding(20).into()
+            container(text(self.texto("historial-no-encontrada"))).padding(20).into()
         }
     }
 }
@@ -0,0 +1,86 @@
+use crate::i18n;
+use crate::models::Idioma;
+use crate::ui::styles;
+use iced::widget::text_editor::{Action, Content, Edit};
+use iced::widget::{button, column, row, text, text_editor};
+use iced::{Element, Length};
+use std::sync::Arc;
+
+/// Formatos Markdown disponibles en la barra del editor de hojas
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Formato {
+    Negrita,
+    Lista,
+    Titulo,
+}
+
+/// Aplica un formato Markdown en la posición del cursor del editor.
+///
+/// Si hay texto seleccionado, se envuelve (negrita) o se antepone el marcador
+/// correspondiente (lista y título).
+pub fn aplicar_formato(contenido: &mut Content, formato: Formato) {
+    let seleccion = contenido.selection().unwrap_or_default();
+    let (_, columna) = contenido.cursor_position();
+    let salto = if columna > 0 && seleccion.is_empty() {
+        "\n"
+    } else {
+        ""
+    };
+
+    let insertado = match formato {
+        Formato::Negrita if seleccion.is_empty() => String::from("**texto**"),
+        Formato::Negrita => format!("**{}**", seleccion),
+        Formato::Lista => format!("{}- {}", salto, seleccion),
+        Formato::Titulo => format!("{}## {}", salto, seleccion),
+    };
+
+    contenido.perform(Action::Edit(Edit::Paste(Arc::new(insertado))));
+}
+
+/// Devuelve el texto del editor sin el salto de línea final que agrega iced
+pub fn texto(contenido: &Content) -> String {
+    contenido.text().trim_end().to_string()
+}
+
+/// Editor multilínea con barra de formato
+pub fn editor<'a, Message: Clone + 'a>(
+    contenido: &'a Content,
+    placeholder: impl text::IntoFragment<'a>,
+    on_action: impl Fn(Action) -> Message + 'a,
+    on_formato: impl Fn(Formato) -> Message,
+    idioma: Idioma,
+) -> Element<'a, Message> {
+    let barra = row![
+        button(
+            row![
+                text("B").font(iced::Font {
+                    weight: iced::font::Weight::Bold,
+                    ..Default::default()
+                }),
+                text(i18n::texto(idioma, "editor-negrita")),
+            ]
+            .spacing(4)
+        )
+        .on_press(on_formato(Formato::Negrita))
+        .padding([4, 10]),
+        button(text(i18n::texto(idioma, "editor-lista")))
+            .on_press(on_formato(Formato::Lista))
+            .padding([4, 10]),
+        button(text(i18n::texto(idioma, "editor-titulo")))
+            .on_press(on_formato(Formato::Titulo))
+            .padding([4, 10]),
+    ]
+    .spacing(5);
+
+    column![
+        barra,
+        text_editor(contenido)
+            .placeholder(placeholder)
+            .on_action(on_action)
+            .padding(8)
+            .height(Length::Fixed(140.0))
+            .style(styles::editor),
+    ]
+    .spacing(5)
+    .into()
+}
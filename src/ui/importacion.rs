@@ -0,0 +1,144 @@
+use crate::i18n;
+use crate::importacion::Resumen;
+use crate::models::Idioma;
+use crate::ui::accesibilidad::Letra;
+use crate::ui::styles;
+use iced::widget::{button, column, container, row, scrollable, text, text_input, Column};
+use iced::{Element, Length};
+
+#[derive(Debug, Clone)]
+pub enum ImportacionMessage {
+    Volver,
+    RutaChanged(String),
+    Importar,
+}
+
+/// Importación de los movimientos guardados desde los portales del PJN y
+/// de la MEV
+#[derive(Debug)]
+pub struct ImportacionView {
+    pub ruta_input: String,
+    /// Resumen de la última importación o error de lectura
+    pub mensaje: Option<String>,
+    /// Fichas que recibieron hojas en la última importación, con cuántas
+    pub fichas_actualizadas: Vec<(String, usize)>,
+    pub letra: Letra,
+    pub idioma: Idioma,
+}
+
+impl ImportacionView {
+    pub fn new() -> Self {
+        Self {
+            ruta_input: String::new(),
+            mensaje: None,
+            fichas_actualizadas: Vec::new(),
+            letra: Letra::default(),
+            idioma: Idioma::default(),
+        }
+    }
+
+    pub fn limpiar_resultado(&mut self) {
+        self.mensaje = None;
+        self.fichas_actualizadas.clear();
+    }
+
+    pub fn view(&self) -> Element<'_, ImportacionMessage> {
+        let header = row![
+            button(text(format!("← {}", i18n::texto(self.idioma, "volver"))))
+                .on_press(ImportacionMessage::Volver)
+                .padding(10)
+                .style(styles::secondary_button),
+            text(i18n::texto(self.idioma, "importacion-titulo")).size(self.letra.tamanio(24)),
+        ]
+        .spacing(20)
+        .padding(10);
+
+        let ayuda = column![
+            text(i18n::texto(self.idioma, "importacion-ayuda")),
+            text(i18n::texto(self.idioma, "importacion-ayuda-hojas")).size(self.letra.tamanio(13)),
+        ]
+        .spacing(5);
+
+        let ruta = column![
+            text(i18n::texto(self.idioma, "importacion-ruta")),
+            row![
+                text_input("/home/usuario/Descargas/expediente.html", &self.ruta_input)
+                    .on_input(ImportacionMessage::RutaChanged)
+                    .on_submit(ImportacionMessage::Importar)
+                    .padding(8)
+                    .style(styles::campo_texto),
+                button(text(i18n::texto(self.idioma, "importacion-importar")))
+                    .on_press_maybe(
+                        (!self.ruta_input.trim().is_empty())
+                            .then_some(ImportacionMessage::Importar),
+                    )
+                    .padding(10)
+                    .style(styles::primary_button),
+            ]
+            .spacing(10),
+        ]
+        .spacing(5);
+
+        let fichas = self.fichas_actualizadas.iter().fold(
+            Column::new().spacing(4),
+            |lista, (titulo, agregadas)| {
+                lista.push(
+                    text(i18n::texto_con(
+                        self.idioma,
+                        "hojas-nuevas-en-ficha",
+                        &[
+                            ("titulo", titulo.clone().into()),
+                            ("cantidad", (*agregadas).into()),
+                        ],
+                    ))
+                    .size(self.letra.tamanio(13)),
+                )
+            },
+        );
+
+        let resultado = container(
+            column![
+                text(self.mensaje.as_deref().unwrap_or_default()).size(self.letra.tamanio(14)),
+                scrollable(fichas),
+            ]
+            .spacing(10),
+        )
+        .width(Length::Fill);
+
+        column![header, ayuda, ruta, resultado]
+            .spacing(15)
+            .padding(20)
+            .into()
+    }
+}
+
+/// Resumen de una importación de movimientos en el idioma de la interfaz
+pub fn describir(resumen: &Resumen, idioma: Idioma) -> String {
+    let leidos = i18n::texto_con(
+        idioma,
+        "importacion-resumen",
+        &[
+            ("movimientos", resumen.movimientos.into()),
+            ("agregadas", resumen.agregadas.into()),
+            ("fichas", resumen.fichas.into()),
+            ("repetidas", resumen.repetidas.into()),
+        ],
+    );
+    [
+        ("importacion-resumen-sin-ficha", &resumen.sin_ficha),
+        ("importacion-resumen-ambiguos", &resumen.ambiguos),
+    ]
+    .into_iter()
+    .filter(|(_, expedientes)| !expedientes.is_empty())
+    .fold(leidos, |texto, (clave, expedientes)| {
+        let expedientes: Vec<_> = expedientes.iter().map(String::as_str).collect();
+        i18n::texto_con(
+            idioma,
+            clave,
+            &[
+                ("resumen", texto.into()),
+                ("expedientes", expedientes.join(", ").into()),
+            ],
+        )
+    })
+}
@@ -0,0 +1,190 @@
+use crate::i18n;
+use crate::models::{Asiento, Ficha, Idioma};
+use crate::ui::accesibilidad::Letra;
+use crate::ui::styles;
+use iced::widget::{button, checkbox, column, container, pick_list, row, scrollable, text, Column};
+use iced::{Element, Length};
+use std::collections::BTreeSet;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub enum LiquidacionMessage {
+    Volver,
+    ClienteSeleccionado(String),
+    AlternarAsiento(Uuid, Uuid, bool),
+    SeleccionarTodo,
+    Generar,
+}
+
+/// Asiento pendiente de liquidar con los datos de su ficha
+#[derive(Debug, Clone)]
+pub struct AsientoPendiente {
+    pub ficha_id: Uuid,
+    pub ficha_titulo: String,
+    pub asiento: Asiento,
+}
+
+#[derive(Debug)]
+pub struct LiquidacionesView {
+    pub clientes: Vec<String>,
+    pub cliente: Option<String>,
+    pub pendientes: Vec<AsientoPendiente>,
+    /// Pares (ficha, asiento) seleccionados para liquidar
+    pub seleccion: BTreeSet<(Uuid, Uuid)>,
+    /// Resultado de la última liquidación generada
+    pub mensaje: Option<String>,
+    pub idioma: Idioma,
+    pub letra: Letra,
+}
+
+impl LiquidacionesView {
+    pub fn new() -> Self {
+        Self {
+            clientes: Vec::new(),
+            cliente: None,
+            pendientes: Vec::new(),
+            seleccion: BTreeSet::new(),
+            mensaje: None,
+            idioma: Idioma::default(),
+            letra: Letra::default(),
+        }
+    }
+
+    /// Recalcula los clientes con asientos sin liquidar y los pendientes del
+    /// cliente elegido, descartando la selección que ya no corresponda
+    pub fn actualizar(&mut self, fichas: &[Ficha]) {
+        let pendientes = || {
+            fichas.iter().flat_map(|ficha| {
+                ficha
+                    .cuenta
+                    .iter()
+                    .filter(|a| !a.esta_liquidado())
+                    .map(move |a| (ficha, a))
+            })
+        };
+
+        self.clientes = pendientes()
+            .map(|(_, a)| a.cliente.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        if self
+            .cliente
+            .as_ref()
+            .is_some_and(|c| !self.clientes.contains(c))
+        {
+            self.cliente = None;
+        }
+
+        self.pendientes = pendientes()
+            .filter(|(_, a)| Some(&a.cliente) == self.cliente.as_ref())
+            .map(|(ficha, asiento)| AsientoPendiente {
+                ficha_id: ficha.id,
+                ficha_titulo: ficha.titulo.clone(),
+                asiento: asiento.clone(),
+            })
+            .collect();
+
+        let disponibles: BTreeSet<_> = self
+            .pendientes
+            .iter()
+            .map(|p| (p.ficha_id, p.asiento.id))
+            .collect();
+        self.seleccion.retain(|par| disponibles.contains(par));
+    }
+
+    pub fn seleccionar_todo(&mut self) {
+        self.seleccion = self
+            .pendientes
+            .iter()
+            .map(|p| (p.ficha_id, p.asiento.id))
+            .collect();
+    }
+
+    pub fn view(&self) -> Element<'_, LiquidacionMessage> {
+        let boton_volver = button(text(i18n::texto(self.idioma, "volver")))
+            .on_press(LiquidacionMessage::Volver)
+            .padding(10)
+            .style(styles::secondary_button);
+
+        let header = row![
+            boton_volver,
+            text(i18n::texto(self.idioma, "liquidacion-titulo")).size(self.letra.tamanio(24))
+        ]
+        .spacing(20)
+        .padding(10);
+
+        let selector = row![
+            text(i18n::texto(self.idioma, "liquidacion-cliente")),
+            pick_list(
+                self.clientes.as_slice(),
+                self.cliente.clone(),
+                LiquidacionMessage::ClienteSeleccionado,
+            )
+            .placeholder("Clientes con movimientos sin liquidar")
+            .padding(8),
+            button(text(i18n::texto(
+                self.idioma,
+                "liquidacion-seleccionar-todo"
+            )))
+            .on_press_maybe(
+                (!self.pendientes.is_empty()).then_some(LiquidacionMessage::SeleccionarTodo)
+            )
+            .padding(8),
+            button(text(i18n::texto(self.idioma, "liquidacion-generar")))
+                .on_press_maybe((!self.seleccion.is_empty()).then_some(LiquidacionMessage::Generar))
+                .padding(8)
+                .style(styles::primary_button),
+        ]
+        .spacing(10)
+        .align_y(iced::Alignment::Center);
+
+        let lista = self
+            .pendientes
+            .iter()
+            .fold(Column::new().spacing(6), |lista, pendiente| {
+                let asiento = &pendiente.asiento;
+                let par = (pendiente.ficha_id, asiento.id);
+                let signo = if asiento.tipo.es_cargo() { "" } else { "-" };
+
+                lista.push(
+                    container(
+                        row![
+                            checkbox("", self.seleccion.contains(&par)).on_toggle(move |marcado| {
+                                LiquidacionMessage::AlternarAsiento(par.0, par.1, marcado)
+                            }),
+                            text(asiento.fecha.format("%d/%m/%Y").to_string())
+                                .size(self.letra.tamanio(13))
+                                .width(Length::Fixed(90.0)),
+                            text(&pendiente.ficha_titulo)
+                                .size(self.letra.tamanio(13))
+                                .width(Length::FillPortion(1)),
+                            text(asiento.tipo.to_string())
+                                .size(self.letra.tamanio(13))
+                                .width(Length::Fixed(90.0)),
+                            text(&asiento.concepto)
+                                .size(self.letra.tamanio(13))
+                                .width(Length::FillPortion(1)),
+                            text(format!("{}{} {}", signo, asiento.moneda, asiento.importe))
+                                .size(self.letra.tamanio(13))
+                                .width(Length::Fixed(140.0)),
+                        ]
+                        .spacing(10),
+                    )
+                    .padding(8)
+                    .style(styles::card_container),
+                )
+            });
+
+        column![
+            header,
+            selector,
+            text(self.mensaje.as_deref().unwrap_or_default()).size(self.letra.tamanio(13)),
+            scrollable(lista),
+        ]
+        .spacing(10)
+        .padding(20)
+        .into()
+    }
+}
@@ -0,0 +1,150 @@
+use crate::i18n;
+use crate::models::tarea::{self, TareaPendiente};
+use crate::models::{Ficha, Idioma};
+use crate::ui::accesibilidad::{self, Letra};
+use crate::ui::styles;
+use crate::ui::tareas::{self as ui_tareas, TareaMessage};
+use chrono::Local;
+use iced::widget::{button, column, pick_list, row, scrollable, text, Column, Space};
+use iced::{Element, Length};
+use std::collections::BTreeSet;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub enum MisTareasMessage {
+    Volver,
+    FiltrarResponsable(String),
+    QuitarFiltro,
+    VerFicha(Uuid),
+    Tarea(TareaMessage),
+}
+
+/// Tareas pendientes de todas las fichas, por vencimiento
+#[derive(Debug)]
+pub struct MisTareasView {
+    pub pendientes: Vec<TareaPendiente>,
+    pub responsables: Vec<String>,
+    pub filtro_responsable: Option<String>,
+    /// Si el rol del usuario actual permite completar o eliminar tareas
+    pub editable: bool,
+    pub idioma: Idioma,
+    pub letra: Letra,
+}
+
+impl MisTareasView {
+    pub fn new() -> Self {
+        Self {
+            pendientes: Vec::new(),
+            responsables: Vec::new(),
+            filtro_responsable: None,
+            editable: false,
+            idioma: Idioma::default(),
+            letra: Letra::default(),
+        }
+    }
+
+    pub fn actualizar(&mut self, fichas: &[Ficha]) {
+        self.pendientes = tarea::pendientes(fichas);
+        self.responsables = self
+            .pendientes
+            .iter()
+            .map(|p| p.tarea.responsable.clone())
+            .filter(|r| !r.is_empty())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        if self
+            .filtro_responsable
+            .as_ref()
+            .is_some_and(|r| !self.responsables.contains(r))
+        {
+            self.filtro_responsable = None;
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, MisTareasMessage> {
+        let boton_volver = button(text(i18n::texto(self.idioma, "volver")))
+            .on_press(MisTareasMessage::Volver)
+            .padding(10)
+            .style(styles::secondary_button);
+
+        let filtro = row![
+            pick_list(
+                self.responsables.as_slice(),
+                self.filtro_responsable.clone(),
+                MisTareasMessage::FiltrarResponsable,
+            )
+            .placeholder("Filtrar por responsable")
+            .padding(8),
+            button(accesibilidad::rotulo(
+                "✕",
+                "Quitar filtro",
+                self.letra.tamanio(16)
+            ))
+            .on_press_maybe(
+                self.filtro_responsable
+                    .as_ref()
+                    .map(|_| MisTareasMessage::QuitarFiltro),
+            )
+            .padding(8),
+        ]
+        .spacing(5);
+
+        let header = row![
+            boton_volver,
+            text(i18n::texto(self.idioma, "mis-tareas-titulo")).size(self.letra.tamanio(24)),
+            Space::with_width(Length::Fill),
+            filtro
+        ]
+        .spacing(20)
+        .padding(10);
+
+        let hoy = Local::now().date_naive();
+        let visibles: Vec<&TareaPendiente> = self
+            .pendientes
+            .iter()
+            .filter(|p| match &self.filtro_responsable {
+                Some(responsable) => p.tarea.responsable == *responsable,
+                None => true,
+            })
+            .collect();
+
+        let lista: Element<_> = if visibles.is_empty() {
+            text(i18n::texto(self.idioma, "mis-tareas-vacia")).into()
+        } else {
+            visibles
+                .into_iter()
+                .fold(Column::new().spacing(6), |lista, pendiente| {
+                    lista.push(
+                        row![
+                            button(
+                                text(i18n::texto(self.idioma, "mis-tareas-abrir"))
+                                    .size(self.letra.tamanio(12))
+                            )
+                            .on_press(MisTareasMessage::VerFicha(pendiente.ficha_id))
+                            .padding([2, 8])
+                            .style(styles::secondary_button),
+                            ui_tareas::fila_tarea(
+                                pendiente.ficha_id,
+                                &pendiente.tarea,
+                                hoy,
+                                Some(&pendiente.ficha_titulo),
+                                self.editable,
+                                self.idioma,
+                                self.letra,
+                            )
+                            .map(MisTareasMessage::Tarea),
+                        ]
+                        .spacing(8)
+                        .align_y(iced::Alignment::Center),
+                    )
+                })
+                .into()
+        };
+
+        column![header, scrollable(lista)]
+            .spacing(10)
+            .padding(20)
+            .into()
+    }
+}
@@ -1,4 +1,43 @@
+pub mod accesibilidad;
+pub mod administracion;
+pub mod atajos;
+pub mod auditoria;
+pub mod balances;
+pub mod categorias;
+pub mod correo;
+pub mod cuenta;
+pub mod etiquetas;
+pub mod fechas;
 pub mod fichas;
+pub mod hoja_editor;
+pub mod importacion;
+pub mod liquidaciones;
+pub mod mis_tareas;
+pub mod paleta;
+pub mod plantillas;
+pub mod preferencias;
+pub mod reporte_tiempo;
+pub mod selector_fecha;
 pub mod styles;
+pub mod tablero;
+pub mod tareas;
+pub mod tiempo;
+pub mod usuarios;
+pub mod vinculos;
 
+pub use administracion::AdministracionView;
+pub use auditoria::AuditoriaView;
+pub use balances::BalancesView;
+pub use categorias::CategoriasView;
+pub use correo::CorreoView;
+pub use etiquetas::EtiquetasView;
 pub use fichas::FichasView;
+pub use importacion::ImportacionView;
+pub use liquidaciones::LiquidacionesView;
+pub use mis_tareas::MisTareasView;
+pub use paleta::PaletaView;
+pub use plantillas::PlantillasView;
+pub use preferencias::PreferenciasView;
+pub use reporte_tiempo::ReporteTiempoView;
+pub use tablero::TableroView;
+pub use usuarios::UsuariosView;
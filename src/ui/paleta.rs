@@ -0,0 +1,339 @@
+use crate::i18n;
+use crate::models::{Accion, Ficha, Idioma};
+use crate::ui::accesibilidad::{self, Letra};
+use crate::ui::styles;
+use iced::widget::{button, column, container, row, text, text_input, Column};
+use iced::{Element, Length};
+use std::fmt;
+use uuid::Uuid;
+
+/// Resultados que se muestran como máximo
+const MAXIMO_RESULTADOS: usize = 8;
+
+#[derive(Debug, Clone)]
+pub enum PaletaMessage {
+    ConsultaChanged(String),
+    /// Ejecuta el resultado resaltado
+    Confirmar,
+    Ejecutar(Destino),
+    Cerrar,
+}
+
+/// Acciones de navegación que se ofrecen en la paleta
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comando {
+    Inicio,
+    Fichas,
+    NuevaFicha,
+    MisTareas,
+    Horas,
+    Honorarios,
+    Etiquetas,
+    Categorias,
+    Plantillas,
+    Usuarios,
+    Auditoria,
+    Preferencias,
+    ImportarMovimientos,
+    ImportarCorreos,
+    CambiarUsuario,
+}
+
+impl Comando {
+    pub const TODOS: [Comando; 15] = [
+        Comando::Inicio,
+        Comando::Fichas,
+        Comando::NuevaFicha,
+        Comando::MisTareas,
+        Comando::Horas,
+        Comando::Honorarios,
+        Comando::Etiquetas,
+        Comando::Categorias,
+        Comando::Plantillas,
+        Comando::Usuarios,
+        Comando::Auditoria,
+        Comando::Preferencias,
+        Comando::ImportarMovimientos,
+        Comando::ImportarCorreos,
+        Comando::CambiarUsuario,
+    ];
+
+    /// Permiso necesario para ofrecer el comando
+    pub fn accion_requerida(&self) -> Option<Accion> {
+        match self {
+            Comando::NuevaFicha | Comando::ImportarMovimientos | Comando::ImportarCorreos => {
+                Some(Accion::EditarFichas)
+            }
+            Comando::Etiquetas | Comando::Categorias | Comando::Plantillas => {
+                Some(Accion::Configurar)
+            }
+            Comando::Usuarios => Some(Accion::GestionarUsuarios),
+            Comando::Auditoria => Some(Accion::VerAuditoria),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Comando {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Comando::Inicio => write!(f, "Ir al inicio"),
+            Comando::Fichas => write!(f, "Ver todas las fichas"),
+            Comando::NuevaFicha => write!(f, "Nueva ficha"),
+            Comando::MisTareas => write!(f, "Mis tareas"),
+            Comando::Horas => write!(f, "Reporte de horas"),
+            Comando::Honorarios => write!(f, "Honorarios y gastos"),
+            Comando::Etiquetas => write!(f, "Gestionar etiquetas"),
+            Comando::Categorias => write!(f, "Gestionar categorías de hojas"),
+            Comando::Plantillas => write!(f, "Gestionar plantillas"),
+            Comando::Usuarios => write!(f, "Usuarios y roles"),
+            Comando::Auditoria => write!(f, "Auditoría"),
+            Comando::Preferencias => write!(f, "Preferencias"),
+            Comando::ImportarMovimientos => write!(f, "Importar movimientos del PJN o la MEV"),
+            Comando::ImportarCorreos => write!(f, "Importar correos"),
+            Comando::CambiarUsuario => write!(f, "Cambiar de usuario"),
+        }
+    }
+}
+
+/// Lo que se abre al elegir un resultado
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Destino {
+    Comando(Comando),
+    Ficha(Uuid),
+}
+
+/// Resultado de la búsqueda, con el texto que se muestra
+#[derive(Debug, Clone)]
+pub struct Resultado {
+    pub titulo: String,
+    pub detalle: String,
+    pub destino: Destino,
+}
+
+/// Paleta de comandos (Ctrl+K) para ejecutar acciones o saltar a una ficha
+#[derive(Debug)]
+pub struct PaletaView {
+    pub abierta: bool,
+    pub consulta: String,
+    /// Índice del resultado resaltado
+    pub seleccion: usize,
+    pub comandos: Vec<Comando>,
+    pub resultados: Vec<Resultado>,
+    pub idioma: Idioma,
+    pub letra: Letra,
+}
+
+impl PaletaView {
+    pub fn new() -> Self {
+        Self {
+            abierta: false,
+            consulta: String::new(),
+            seleccion: 0,
+            comandos: Vec::new(),
+            resultados: Vec::new(),
+            idioma: Idioma::default(),
+            letra: Letra::default(),
+        }
+    }
+
+    pub fn id_consulta() -> text_input::Id {
+        text_input::Id::new("paleta")
+    }
+
+    /// Abre la paleta vacía con los comandos permitidos al usuario
+    pub fn abrir(&mut self, comandos: Vec<Comando>, fichas: &[Ficha]) {
+        self.abierta = true;
+        self.consulta.clear();
+        self.comandos = comandos;
+        self.buscar(fichas);
+    }
+
+    pub fn cerrar(&mut self) {
+        self.abierta = false;
+        self.consulta.clear();
+        self.resultados.clear();
+    }
+
+    /// Mueve el resaltado, sin salirse de la lista
+    pub fn mover(&mut self, desplazamiento: isize) {
+        if self.resultados.is_empty() {
+            return;
+        }
+        self.seleccion = self
+            .seleccion
+            .saturating_add_signed(desplazamiento)
+            .min(self.resultados.len() - 1);
+    }
+
+    pub fn seleccionado(&self) -> Option<Destino> {
+        self.resultados.get(self.seleccion).map(|r| r.destino)
+    }
+
+    /// Recalcula los resultados para la consulta actual, mejores primero
+    pub fn buscar(&mut self, fichas: &[Ficha]) {
+        let comandos = self.comandos.iter().map(|comando| {
+            (
+                Resultado {
+                    titulo: comando.to_string(),
+                    detalle: String::from("Comando"),
+                    destino: Destino::Comando(*comando),
+                },
+                comando.to_string(),
+            )
+        });
+        let fichas = fichas.iter().map(|ficha| {
+            (
+                Resultado {
+                    titulo: ficha.titulo.clone(),
+                    detalle: [ficha.expediente.as_str(), ficha.cliente.as_str()]
+                        .iter()
+                        .filter(|s| !s.is_empty())
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join(" · "),
+                    destino: Destino::Ficha(ficha.id),
+                },
+                format!("{} {} {}", ficha.titulo, ficha.expediente, ficha.cliente),
+            )
+        });
+
+        let mut puntuados: Vec<(i32, Resultado)> = comandos
+            .chain(fichas)
+            .filter_map(|(resultado, texto)| {
+                puntaje_difuso(&self.consulta, &texto).map(|puntaje| (puntaje, resultado))
+            })
+            .collect();
+        // El orden es estable: a igual puntaje, comandos antes que fichas
+        puntuados.sort_by_key(|(puntaje, _)| std::cmp::Reverse(*puntaje));
+
+        self.resultados = puntuados
+            .into_iter()
+            .take(MAXIMO_RESULTADOS)
+            .map(|(_, resultado)| resultado)
+            .collect();
+        self.seleccion = 0;
+    }
+
+    pub fn view(&self) -> Element<'_, PaletaMessage> {
+        let consulta = text_input("Buscar acciones o fichas…", &self.consulta)
+            .id(Self::id_consulta())
+            .on_input(PaletaMessage::ConsultaChanged)
+            .on_submit(PaletaMessage::Confirmar)
+            .padding(10)
+            .size(self.letra.tamanio(18))
+            .style(styles::campo_texto);
+
+        let resultados: Element<_> = if self.resultados.is_empty() {
+            text(i18n::texto(self.idioma, "paleta-sin-resultados"))
+                .size(self.letra.tamanio(13))
+                .into()
+        } else {
+            self.resultados
+                .iter()
+                .enumerate()
+                .fold(Column::new().spacing(2), |lista, (i, resultado)| {
+                    lista.push(
+                        button(
+                            column![
+                                text(&resultado.titulo).size(self.letra.tamanio(15)),
+                                text(&resultado.detalle).size(self.letra.tamanio(11)),
+                            ]
+                            .spacing(2),
+                        )
+                        .on_press(PaletaMessage::Ejecutar(resultado.destino))
+                        .padding([6, 10])
+                        .width(Length::Fill)
+                        .style(if i == self.seleccion {
+                            styles::primary_button
+                        } else {
+                            button::text
+                        }),
+                    )
+                })
+                .into()
+        };
+
+        let paleta = container(
+            column![
+                row![
+                    consulta,
+                    button(accesibilidad::rotulo("✕", "Cerrar", self.letra.tamanio(16)))
+                        .on_press(PaletaMessage::Cerrar)
+                        .padding(10)
+                        .style(styles::cancel_button),
+                ]
+                .spacing(10),
+                resultados,
+                text(i18n::texto(self.idioma, "paleta-ayuda"))
+                    .size(self.letra.tamanio(11)),
+            ]
+            .spacing(10),
+        )
+        .padding(15)
+        .width(Length::Fixed(560.0))
+        .style(styles::card_container);
+
+        container(paleta)
+            .padding(80)
+            .center_x(Length::Fill)
+            .height(Length::Fill)
+            .style(styles::fondo_modal)
+            .into()
+    }
+}
+
+/// Puntaje de `texto` para la `consulta` si contiene todas sus letras en
+/// orden, no necesariamente seguidas. Suman las letras consecutivas y las que
+/// empiezan una palabra. No distingue mayúsculas ni acentos.
+pub fn puntaje_difuso(consulta: &str, texto: &str) -> Option<i32> {
+    let consulta: Vec<char> = normalizar(consulta)
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    if consulta.is_empty() {
+        return Some(0);
+    }
+
+    let texto: Vec<char> = normalizar(texto).chars().collect();
+    let mut puntaje = 0;
+    let mut encontradas = 0;
+    let mut anterior: Option<usize> = None;
+    for (i, c) in texto.iter().enumerate() {
+        if encontradas == consulta.len() {
+            break;
+        }
+        if *c != consulta[encontradas] {
+            continue;
+        }
+
+        puntaje += 1;
+        if i > 0 && anterior == Some(i - 1) {
+            puntaje += 5;
+        }
+        if i == 0 || !texto[i - 1].is_alphanumeric() {
+            puntaje += 3;
+        }
+        anterior = Some(i);
+        encontradas += 1;
+    }
+
+    (encontradas == consulta.len()).then_some(puntaje)
+}
+
+/// Minúsculas y sin tildes, para comparar
+fn normalizar(texto: &str) -> String {
+    texto
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'á' | 'à' | 'ä' | 'â' | 'ã' => 'a',
+            'é' | 'è' | 'ë' | 'ê' => 'e',
+            'í' | 'ì' | 'ï' | 'î' => 'i',
+            'ó' | 'ò' | 'ö' | 'ô' | 'õ' => 'o',
+            'ú' | 'ù' | 'ü' | 'û' => 'u',
+            'ç' => 'c',
+            c => c,
+        })
+        .collect()
+}
@@ -0,0 +1,205 @@
+use crate::i18n;
+use crate::models::{Idioma, PlantillaHoja};
+use crate::ui::accesibilidad::Letra;
+use crate::ui::hoja_editor::{self, Formato};
+use crate::ui::styles;
+use iced::widget::text_editor;
+use iced::widget::{button, column, container, row, scrollable, text, text_input, Column, Space};
+use iced::{Element, Length};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub enum PlantillaMessage {
+    Volver,
+    NuevaPlantilla,
+    EditarPlantilla(Uuid),
+    EliminarPlantilla(Uuid),
+    NombreChanged(String),
+    ContenidoEditado(text_editor::Action),
+    FormatoContenido(Formato),
+    GuardarPlantilla,
+    CancelarEdicion,
+}
+
+#[derive(Debug)]
+pub struct PlantillasView {
+    pub plantillas: Vec<PlantillaHoja>,
+    pub editando: bool,
+    pub nombre_input: String,
+    pub contenido_input: text_editor::Content,
+    pub plantilla_editando_id: Option<Uuid>,
+    pub idioma: Idioma,
+    pub letra: Letra,
+}
+
+impl PlantillasView {
+    pub fn new() -> Self {
+        Self {
+            plantillas: Vec::new(),
+            editando: false,
+            nombre_input: String::new(),
+            contenido_input: text_editor::Content::new(),
+            plantilla_editando_id: None,
+            idioma: Idioma::default(),
+            letra: Letra::default(),
+        }
+    }
+
+    pub fn actualizar_plantillas(&mut self, plantillas: Vec<PlantillaHoja>) {
+        self.plantillas = plantillas;
+    }
+
+    pub fn iniciar_nueva_plantilla(&mut self) {
+        self.editando = true;
+        self.nombre_input.clear();
+        self.contenido_input = text_editor::Content::new();
+        self.plantilla_editando_id = None;
+    }
+
+    pub fn iniciar_edicion(&mut self, plantilla_id: Uuid) {
+        if let Some(plantilla) = self.plantillas.iter().find(|p| p.id == plantilla_id) {
+            self.editando = true;
+            self.nombre_input = plantilla.nombre.clone();
+            self.contenido_input = text_editor::Content::with_text(&plantilla.contenido);
+            self.plantilla_editando_id = Some(plantilla_id);
+        }
+    }
+
+    pub fn cancelar_edicion(&mut self) {
+        self.editando = false;
+        self.nombre_input.clear();
+        self.contenido_input = text_editor::Content::new();
+        self.plantilla_editando_id = None;
+    }
+
+    /// Construye la plantilla a partir del formulario, nueva o editada
+    pub fn obtener_plantilla(&self) -> Option<PlantillaHoja> {
+        let contenido = hoja_editor::texto(&self.contenido_input);
+        if self.nombre_input.is_empty() || contenido.is_empty() {
+            return None;
+        }
+
+        match self.plantilla_editando_id {
+            Some(id) => Some(PlantillaHoja {
+                id,
+                nombre: self.nombre_input.clone(),
+                contenido,
+            }),
+            None => Some(PlantillaHoja::new(self.nombre_input.clone(), contenido)),
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, PlantillaMessage> {
+        let boton_volver = button(text(i18n::texto(self.idioma, "volver")))
+            .on_press(PlantillaMessage::Volver)
+            .padding(10)
+            .style(styles::secondary_button);
+
+        let boton_nueva = button(text(i18n::texto(self.idioma, "plantillas-nueva")))
+            .on_press(PlantillaMessage::NuevaPlantilla)
+            .padding(10)
+            .style(styles::primary_button);
+
+        let header = row![
+            boton_volver,
+            text(i18n::texto(self.idioma, "plantillas-titulo")).size(self.letra.tamanio(24)),
+            Space::with_width(Length::Fill),
+            boton_nueva
+        ]
+        .spacing(20)
+        .padding(10);
+
+        let contenido = if self.editando {
+            self.vista_formulario()
+        } else {
+            self.vista_lista()
+        };
+
+        column![header, contenido].spacing(10).padding(20).into()
+    }
+
+    fn vista_formulario(&self) -> Element<'_, PlantillaMessage> {
+        let titulo = if self.plantilla_editando_id.is_some() {
+            text(i18n::texto(self.idioma, "plantillas-editar-titulo")).size(self.letra.tamanio(20))
+        } else {
+            text(i18n::texto(self.idioma, "plantillas-nueva-titulo")).size(self.letra.tamanio(20))
+        };
+
+        let nombre_input = column![
+            text(i18n::texto(self.idioma, "campo-nombre")),
+            text_input("Nombre de la plantilla", &self.nombre_input)
+                .on_input(PlantillaMessage::NombreChanged)
+                .padding(8)
+                .style(styles::campo_texto),
+        ]
+        .spacing(5);
+
+        let contenido_input = column![
+            text(i18n::texto(self.idioma, "plantillas-contenido")),
+            text(i18n::texto(self.idioma, "plantillas-marcadores")).size(self.letra.tamanio(12)),
+            hoja_editor::editor(
+                &self.contenido_input,
+                "Se presentó escrito solicitando ...",
+                PlantillaMessage::ContenidoEditado,
+                PlantillaMessage::FormatoContenido,
+                self.idioma,
+            ),
+        ]
+        .spacing(5);
+
+        let botones = row![
+            button(text(i18n::texto(self.idioma, "guardar")))
+                .on_press(PlantillaMessage::GuardarPlantilla)
+                .padding(10),
+            button(text(i18n::texto(self.idioma, "cancelar")))
+                .on_press(PlantillaMessage::CancelarEdicion)
+                .padding(10),
+        ]
+        .spacing(10);
+
+        column![titulo, nombre_input, contenido_input, botones]
+            .spacing(15)
+            .padding(20)
+            .into()
+    }
+
+    fn vista_lista(&self) -> Element<'_, PlantillaMessage> {
+        if self.plantillas.is_empty() {
+            return container(text(i18n::texto(self.idioma, "plantillas-vacia")))
+                .padding(20)
+                .into();
+        }
+
+        let lista = self
+            .plantillas
+            .iter()
+            .fold(Column::new().spacing(8), |lista, plantilla| {
+                let card = container(
+                    row![
+                        column![
+                            text(&plantilla.nombre).size(self.letra.tamanio(16)),
+                            text(&plantilla.contenido).size(self.letra.tamanio(12)),
+                        ]
+                        .spacing(5)
+                        .width(Length::Fill),
+                        button(text(i18n::texto(self.idioma, "editar")).center())
+                            .on_press(PlantillaMessage::EditarPlantilla(plantilla.id))
+                            .padding(8)
+                            .style(styles::secondary_button),
+                        button(text(i18n::texto(self.idioma, "eliminar")).center())
+                            .on_press(PlantillaMessage::EliminarPlantilla(plantilla.id))
+                            .padding(8)
+                            .style(styles::cancel_button),
+                    ]
+                    .spacing(8),
+                )
+                .padding(12)
+                .width(Length::Fill)
+                .style(styles::card_container);
+
+                lista.push(card)
+            });
+
+        scrollable(lista).into()
+    }
+}
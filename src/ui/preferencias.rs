@@ -0,0 +1,465 @@
+use crate::i18n;
+use crate::models::{Idioma, Preferencias, Respaldo, Sincronizacion, Tema};
+use crate::ui::accesibilidad::Letra;
+use crate::ui::styles;
+use chrono::format::{Item, StrftimeItems};
+use crate::ui::fechas::FormatoFecha;
+use chrono::Utc;
+use iced::widget::{
+    button, checkbox, column, container, pick_list, row, scrollable, text, text_input, Column,
+};
+use iced::{Element, Length};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub enum PreferenciasMessage {
+    Volver,
+    FormatoFechaChanged(String),
+    ZonaHorariaChanged(String),
+    RutaDatosChanged(String),
+    IdiomaSeleccionado(Idioma),
+    TemaSeleccionado(Tema),
+    EscalaChanged(String),
+    TamanioMinimoChanged(String),
+    TarjetasPorFilaChanged(String),
+    DiasSinMovimientoChanged(String),
+    DiasVencimientosChanged(String),
+    RespaldoAlIniciar(bool),
+    DirectorioRespaldoChanged(String),
+    ConservarRespaldosChanged(String),
+    SincronizacionActiva(bool),
+    UrlSincronizacionChanged(String),
+    IntervaloSincronizacionChanged(String),
+    Guardar,
+    RespaldarAhora,
+}
+
+/// Pantalla de preferencias; los cambios se aplican al guardar
+#[derive(Debug)]
+pub struct PreferenciasView {
+    pub formato_fecha_input: String,
+    pub zona_horaria_input: String,
+    pub ruta_datos_input: String,
+    pub idioma: Idioma,
+    pub tema: Tema,
+    pub escala_input: String,
+    pub tamanio_minimo_input: String,
+    pub tarjetas_por_fila_input: String,
+    pub dias_sin_movimiento_input: String,
+    pub dias_vencimientos_input: String,
+    pub respaldo_al_iniciar: bool,
+    pub directorio_respaldo_input: String,
+    pub conservar_respaldos_input: String,
+    pub sincronizacion_activa: bool,
+    pub url_sincronizacion_input: String,
+    pub intervalo_sincronizacion_input: String,
+    pub errores: Vec<String>,
+    pub mensaje: Option<String>,
+    /// Si el rol del usuario permite cambiar las preferencias del espacio de
+    /// trabajo; sin permiso solo se muestran las de visualización
+    pub puede_configurar: bool,
+    /// Idioma en que se muestra la pantalla; `idioma` es el elegido en el
+    /// formulario
+    pub idioma_interfaz: Idioma,
+    pub letra: Letra,
+}
+
+impl PreferenciasView {
+    pub fn new() -> Self {
+        let mut vista = Self {
+            formato_fecha_input: String::new(),
+            zona_horaria_input: String::new(),
+            ruta_datos_input: String::new(),
+            idioma: Idioma::default(),
+            tema: Tema::default(),
+            escala_input: String::new(),
+            tamanio_minimo_input: String::new(),
+            tarjetas_por_fila_input: String::new(),
+            dias_sin_movimiento_input: String::new(),
+            dias_vencimientos_input: String::new(),
+            respaldo_al_iniciar: false,
+            directorio_respaldo_input: String::new(),
+            conservar_respaldos_input: String::new(),
+            sincronizacion_activa: false,
+            url_sincronizacion_input: String::new(),
+            intervalo_sincronizacion_input: String::new(),
+            errores: Vec::new(),
+            mensaje: None,
+            puede_configurar: false,
+            idioma_interfaz: Idioma::default(),
+            letra: Letra::default(),
+        };
+        vista.cargar(&Preferencias::default());
+        vista
+    }
+
+    /// Completa el formulario con las preferencias vigentes
+    pub fn cargar(&mut self, preferencias: &Preferencias) {
+        self.formato_fecha_input = preferencias.formato_fecha.clone();
+        self.zona_horaria_input = preferencias.zona_horaria.clone();
+        self.ruta_datos_input = preferencias.ruta_datos.display().to_string();
+        self.idioma = preferencias.idioma;
+        self.tema = preferencias.tema;
+        self.escala_input = preferencias.escala.to_string();
+        self.tamanio_minimo_input = preferencias.tamanio_minimo_texto.to_string();
+        self.tarjetas_por_fila_input = preferencias
+            .tarjetas_por_fila
+            .map(|n| n.to_string())
+            .unwrap_or_default();
+        self.dias_sin_movimiento_input = preferencias.dias_sin_movimiento.to_string();
+        self.dias_vencimientos_input = preferencias.dias_aviso_vencimientos.to_string();
+        self.respaldo_al_iniciar = preferencias.respaldo.al_iniciar;
+        self.directorio_respaldo_input = preferencias.respaldo.directorio.display().to_string();
+        self.conservar_respaldos_input = preferencias.respaldo.conservar.to_string();
+        self.sincronizacion_activa = preferencias.sincronizacion.activa;
+        self.url_sincronizacion_input = preferencias.sincronizacion.url.clone();
+        self.intervalo_sincronizacion_input =
+            preferencias.sincronizacion.intervalo_minutos.to_string();
+        self.errores.clear();
+    }
+
+    /// Actualiza los campos del formulario; guardar y respaldar los resuelve
+    /// la aplicación.
+    pub fn actualizar(&mut self, mensaje: PreferenciasMessage) {
+        match mensaje {
+            PreferenciasMessage::FormatoFechaChanged(valor) => self.formato_fecha_input = valor,
+            PreferenciasMessage::ZonaHorariaChanged(valor) => self.zona_horaria_input = valor,
+            PreferenciasMessage::RutaDatosChanged(valor) => self.ruta_datos_input = valor,
+            PreferenciasMessage::IdiomaSeleccionado(idioma) => self.idioma = idioma,
+            PreferenciasMessage::TemaSeleccionado(tema) => self.tema = tema,
+            PreferenciasMessage::EscalaChanged(valor) => self.escala_input = valor,
+            PreferenciasMessage::TamanioMinimoChanged(valor) => self.tamanio_minimo_input = valor,
+            PreferenciasMessage::TarjetasPorFilaChanged(valor) => {
+                self.tarjetas_por_fila_input = valor
+            }
+            PreferenciasMessage::DiasSinMovimientoChanged(valor) => {
+                self.dias_sin_movimiento_input = valor
+            }
+            PreferenciasMessage::DiasVencimientosChanged(valor) => {
+                self.dias_vencimientos_input = valor
+            }
+            PreferenciasMessage::RespaldoAlIniciar(activo) => self.respaldo_al_iniciar = activo,
+            PreferenciasMessage::DirectorioRespaldoChanged(valor) => {
+                self.directorio_respaldo_input = valor
+            }
+            PreferenciasMessage::ConservarRespaldosChanged(valor) => {
+                self.conservar_respaldos_input = valor
+            }
+            PreferenciasMessage::SincronizacionActiva(activa) => {
+                self.sincronizacion_activa = activa
+            }
+            PreferenciasMessage::UrlSincronizacionChanged(valor) => {
+                self.url_sincronizacion_input = valor
+            }
+            PreferenciasMessage::IntervaloSincronizacionChanged(valor) => {
+                self.intervalo_sincronizacion_input = valor
+            }
+            PreferenciasMessage::Volver
+            | PreferenciasMessage::Guardar
+            | PreferenciasMessage::RespaldarAhora => {}
+        }
+    }
+
+    /// Preferencias del formulario, o la lista de campos con problemas
+    pub fn obtener_preferencias(&self) -> Result<Preferencias, Vec<String>> {
+        let mut errores = Vec::new();
+        // Se acepta la coma decimal, como se escribe en castellano
+        let escala = match self.escala_input.trim().replace(',', ".").parse::<f64>() {
+            Ok(escala) => escala,
+            Err(_) => {
+                errores.push(String::from("Escala: ingrese un número, por ejemplo 1,25"));
+                1.0
+            }
+        };
+        let mut numero = |valor: &str, campo: &str| match valor.trim().parse::<u32>() {
+            Ok(numero) => numero,
+            Err(_) => {
+                errores.push(format!("{}: ingrese un número entero", campo));
+                0
+            }
+        };
+
+        let tamanio_minimo_texto =
+            u16::try_from(numero(&self.tamanio_minimo_input, "Tamaño mínimo de letra"))
+                .unwrap_or(u16::MAX);
+
+        let tarjetas_por_fila = match self.tarjetas_por_fila_input.trim() {
+            "" => None,
+            valor => Some(numero(valor, "Tarjetas por fila") as usize),
+        };
+        let preferencias = Preferencias {
+            formato_fecha: self.formato_fecha_input.trim().to_string(),
+            zona_horaria: self.zona_horaria_input.trim().to_string(),
+            ruta_datos: PathBuf::from(self.ruta_datos_input.trim()),
+            idioma: self.idioma,
+            tema: self.tema,
+            escala,
+            tamanio_minimo_texto,
+            tarjetas_por_fila,
+            dias_sin_movimiento: numero(&self.dias_sin_movimiento_input, "Días sin movimiento"),
+            dias_aviso_vencimientos: numero(
+                &self.dias_vencimientos_input,
+                "Días de aviso de vencimientos",
+            ),
+            respaldo: Respaldo {
+                al_iniciar: self.respaldo_al_iniciar,
+                directorio: PathBuf::from(self.directorio_respaldo_input.trim()),
+                conservar: numero(&self.conservar_respaldos_input, "Respaldos a conservar")
+                    as usize,
+            },
+            sincronizacion: Sincronizacion {
+                activa: self.sincronizacion_activa,
+                url: self.url_sincronizacion_input.trim().to_string(),
+                intervalo_minutos: numero(
+                    &self.intervalo_sincronizacion_input,
+                    "Intervalo de sincronización",
+                ),
+            },
+        };
+
+        if errores.is_empty() {
+            errores = preferencias.validar();
+        }
+        if errores.is_empty() {
+            Ok(preferencias)
+        } else {
+            Err(errores)
+        }
+    }
+
+    fn texto(&self, clave: &str) -> String {
+        i18n::texto(self.idioma_interfaz, clave)
+    }
+
+    pub fn view(&self) -> Element<'_, PreferenciasMessage> {
+        let boton_volver = button(text(self.texto("volver")))
+            .on_press(PreferenciasMessage::Volver)
+            .padding(10)
+            .style(styles::secondary_button);
+
+        let titulo = self.texto("preferencias-titulo");
+        let header = row![boton_volver, text(titulo).size(self.letra.tamanio(24))]
+            .spacing(20)
+            .padding(10);
+
+        // Ejemplo con la fecha actual, solo si el formato es válido
+        let ejemplo = if StrftimeItems::new(&self.formato_fecha_input).any(|i| i == Item::Error) {
+            self.texto("preferencias-formato-invalido")
+        } else {
+            FormatoFecha::new(&self.formato_fecha_input, &self.zona_horaria_input)
+                .con_idioma(self.idioma)
+                .fecha_hora_con_zona(Utc::now())
+        };
+
+        let apariencia = column![
+            text(self.texto("preferencias-apariencia")).size(self.letra.tamanio(18)),
+            campo(
+                self.texto("preferencias-idioma"),
+                pick_list(
+                    Idioma::TODOS,
+                    Some(self.idioma),
+                    PreferenciasMessage::IdiomaSeleccionado
+                )
+                .padding(8)
+            ),
+            campo(
+                self.texto("preferencias-tema"),
+                pick_list(
+                    Tema::TODOS,
+                    Some(self.tema),
+                    PreferenciasMessage::TemaSeleccionado
+                )
+                .padding(8)
+            ),
+            campo(
+                self.texto("preferencias-formato-fecha"),
+                row![
+                    text_input("%d/%m/%Y %H:%M", &self.formato_fecha_input)
+                        .on_input(PreferenciasMessage::FormatoFechaChanged)
+                        .padding(8)
+                        .width(Length::Fixed(200.0))
+                        .style(styles::campo_texto),
+                    text(ejemplo).size(self.letra.tamanio(13)),
+                ]
+                .spacing(10)
+                .align_y(iced::Alignment::Center)
+            ),
+            campo(
+                self.texto("preferencias-zona-horaria"),
+                text_input(&self.texto("preferencias-zona-del-sistema"), &self.zona_horaria_input)
+                    .on_input(PreferenciasMessage::ZonaHorariaChanged)
+                    .padding(8)
+                    .width(Length::Fixed(300.0))
+                    .style(styles::campo_texto)
+            ),
+            campo(
+                self.texto("preferencias-tarjetas"),
+                text_input(
+                    &self.texto("preferencias-tarjetas-segun-ancho"),
+                    &self.tarjetas_por_fila_input
+                )
+                .on_input(PreferenciasMessage::TarjetasPorFilaChanged)
+                .padding(8)
+                .width(Length::Fixed(120.0))
+                .style(styles::campo_texto)
+            ),
+        ]
+        .spacing(10);
+
+        let accesibilidad = column![
+            text(self.texto("preferencias-accesibilidad")).size(self.letra.tamanio(18)),
+            campo(
+                self.texto("preferencias-escala"),
+                text_input("1", &self.escala_input)
+                    .on_input(PreferenciasMessage::EscalaChanged)
+                    .padding(8)
+                    .width(Length::Fixed(120.0))
+                    .style(styles::campo_texto)
+            ),
+            campo(
+                self.texto("preferencias-tamanio-minimo"),
+                text_input("10", &self.tamanio_minimo_input)
+                    .on_input(PreferenciasMessage::TamanioMinimoChanged)
+                    .padding(8)
+                    .width(Length::Fixed(120.0))
+                    .style(styles::campo_texto)
+            ),
+            text(self.texto("preferencias-teclado"))
+                .size(self.letra.tamanio(13)),
+        ]
+        .spacing(10);
+
+        let avisos = column![
+            text(self.texto("preferencias-avisos")).size(self.letra.tamanio(18)),
+            campo(
+                self.texto("preferencias-dias-sin-movimiento"),
+                text_input("30", &self.dias_sin_movimiento_input)
+                    .on_input(PreferenciasMessage::DiasSinMovimientoChanged)
+                    .padding(8)
+                    .width(Length::Fixed(120.0))
+                    .style(styles::campo_texto)
+            ),
+            campo(
+                self.texto("preferencias-dias-vencimientos"),
+                text_input("7", &self.dias_vencimientos_input)
+                    .on_input(PreferenciasMessage::DiasVencimientosChanged)
+                    .padding(8)
+                    .width(Length::Fixed(120.0))
+                    .style(styles::campo_texto)
+            ),
+        ]
+        .spacing(10);
+
+        let datos = column![
+            text(self.texto("preferencias-datos")).size(self.letra.tamanio(18)),
+            campo(
+                self.texto("preferencias-archivo-datos"),
+                text_input("datos_procuracion.json", &self.ruta_datos_input)
+                    .on_input(PreferenciasMessage::RutaDatosChanged)
+                    .padding(8)
+                    .width(Length::Fill)
+                    .style(styles::campo_texto)
+            ),
+            checkbox(
+                self.texto("preferencias-respaldo-al-iniciar"),
+                self.respaldo_al_iniciar
+            )
+                .on_toggle(PreferenciasMessage::RespaldoAlIniciar),
+            campo(
+                self.texto("preferencias-directorio-respaldos"),
+                text_input("respaldos", &self.directorio_respaldo_input)
+                    .on_input(PreferenciasMessage::DirectorioRespaldoChanged)
+                    .padding(8)
+                    .width(Length::Fill)
+                    .style(styles::campo_texto)
+            ),
+            campo(
+                self.texto("preferencias-respaldos-conservar"),
+                text_input("10", &self.conservar_respaldos_input)
+                    .on_input(PreferenciasMessage::ConservarRespaldosChanged)
+                    .padding(8)
+                    .width(Length::Fixed(120.0))
+                    .style(styles::campo_texto)
+            ),
+        ]
+        .spacing(10);
+
+        let sincronizacion = column![
+            text(self.texto("preferencias-sincronizacion")).size(self.letra.tamanio(18)),
+            checkbox(
+                self.texto("preferencias-sincronizacion-activa"),
+                self.sincronizacion_activa
+            )
+            .on_toggle(PreferenciasMessage::SincronizacionActiva),
+            campo(
+                self.texto("preferencias-sincronizacion-url"),
+                text_input(
+                    "https://portal.ejemplo/consulta?expediente={expediente}",
+                    &self.url_sincronizacion_input
+                )
+                .on_input(PreferenciasMessage::UrlSincronizacionChanged)
+                .padding(8)
+                .width(Length::Fill)
+                .style(styles::campo_texto)
+            ),
+            campo(
+                self.texto("preferencias-sincronizacion-intervalo"),
+                text_input("60", &self.intervalo_sincronizacion_input)
+                    .on_input(PreferenciasMessage::IntervaloSincronizacionChanged)
+                    .padding(8)
+                    .width(Length::Fixed(120.0))
+                    .style(styles::campo_texto)
+            ),
+            text(self.texto("preferencias-sincronizacion-ayuda")).size(self.letra.tamanio(13)),
+        ]
+        .spacing(10);
+
+        let errores = self
+            .errores
+            .iter()
+            .fold(Column::new().spacing(4), |lista, error| {
+                lista.push(text(format!("⚠ {}", error)).size(self.letra.tamanio(13)))
+            });
+
+        let respaldar = self.puede_configurar.then(|| {
+            button(text(self.texto("preferencias-respaldar")))
+                .on_press(PreferenciasMessage::RespaldarAhora)
+                .padding(10)
+                .style(styles::secondary_button)
+        });
+        let acciones = row![button(text(self.texto("guardar")))
+            .on_press(PreferenciasMessage::Guardar)
+            .padding(10)
+            .style(styles::primary_button)]
+        .push_maybe(respaldar)
+        .push(text(self.mensaje.as_deref().unwrap_or_default()).size(self.letra.tamanio(13)))
+        .spacing(10)
+        .align_y(iced::Alignment::Center);
+
+        let mut secciones = column![apariencia, accesibilidad];
+        if self.puede_configurar {
+            secciones = secciones.push(avisos).push(datos).push(sincronizacion);
+        }
+        let secciones = secciones.push(errores).push(acciones);
+        let formulario = container(secciones.spacing(25))
+            .padding(20)
+            .width(Length::Fill)
+            .style(styles::card_container);
+
+        column![header, scrollable(formulario)]
+            .spacing(10)
+            .padding(20)
+            .into()
+    }
+}
+
+/// Renglón del formulario con la etiqueta a la izquierda
+fn campo<'a>(
+    etiqueta: String,
+    control: impl Into<Element<'a, PreferenciasMessage>>,
+) -> Element<'a, PreferenciasMessage> {
+    row![text(etiqueta).width(Length::Fixed(230.0)), control.into()]
+        .spacing(10)
+        .align_y(iced::Alignment::Center)
+        .into()
+}
@@ -0,0 +1,178 @@
+use crate::i18n;
+use crate::models::tiempo;
+use crate::models::{Ficha, Idioma};
+use crate::ui::accesibilidad::Letra;
+use crate::ui::styles;
+use chrono::{Datelike, Local, NaiveDate};
+use iced::widget::{button, column, container, row, scrollable, text, text_input, Column};
+use iced::{Element, Length};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone)]
+pub enum ReporteTiempoMessage {
+    Volver,
+    DesdeChanged(String),
+    HastaChanged(String),
+}
+
+/// Horas registradas por ficha y por cliente en un rango de fechas
+#[derive(Debug)]
+pub struct ReporteTiempoView {
+    pub desde_input: String,
+    pub hasta_input: String,
+    pub por_ficha: Vec<(String, u32)>,
+    pub por_cliente: BTreeMap<String, u32>,
+    pub error: Option<String>,
+    pub idioma: Idioma,
+    pub letra: Letra,
+}
+
+impl ReporteTiempoView {
+    /// Por defecto muestra el mes en curso
+    pub fn new() -> Self {
+        let hoy = Local::now().date_naive();
+        let inicio_mes = hoy.with_day(1).unwrap_or(hoy);
+        Self {
+            desde_input: inicio_mes.format("%d/%m/%Y").to_string(),
+            hasta_input: hoy.format("%d/%m/%Y").to_string(),
+            por_ficha: Vec::new(),
+            por_cliente: BTreeMap::new(),
+            error: None,
+            idioma: Idioma::default(),
+            letra: Letra::default(),
+        }
+    }
+
+    /// Recalcula los totales con el rango ingresado; si es inválido deja los
+    /// totales anteriores y muestra el error
+    pub fn actualizar(&mut self, fichas: &[Ficha]) {
+        let fecha = |valor: &str| NaiveDate::parse_from_str(valor.trim(), "%d/%m/%Y").ok();
+        let (Some(desde), Some(hasta)) = (fecha(&self.desde_input), fecha(&self.hasta_input))
+        else {
+            self.error = Some("Fechas inválidas, use dd/mm/aaaa".into());
+            return;
+        };
+        if desde > hasta {
+            self.error = Some("La fecha inicial es posterior a la final".into());
+            return;
+        }
+
+        self.error = None;
+        self.por_ficha = tiempo::minutos_por_ficha(fichas, desde, hasta);
+        self.por_cliente = tiempo::minutos_por_cliente(fichas, desde, hasta);
+    }
+
+    pub fn view(&self) -> Element<'_, ReporteTiempoMessage> {
+        let boton_volver = button(text(i18n::texto(self.idioma, "volver")))
+            .on_press(ReporteTiempoMessage::Volver)
+            .padding(10)
+            .style(styles::secondary_button);
+
+        let header = row![
+            boton_volver,
+            text(i18n::texto(self.idioma, "reporte-tiempo-titulo")).size(self.letra.tamanio(24))
+        ]
+        .spacing(20)
+        .padding(10);
+
+        let rango = row![
+            text(i18n::texto(self.idioma, "reporte-tiempo-desde")),
+            text_input("dd/mm/aaaa", &self.desde_input)
+                .on_input(ReporteTiempoMessage::DesdeChanged)
+                .padding(8)
+                .width(Length::Fixed(120.0))
+                .style(styles::campo_texto),
+            text(i18n::texto(self.idioma, "reporte-tiempo-hasta")),
+            text_input("dd/mm/aaaa", &self.hasta_input)
+                .on_input(ReporteTiempoMessage::HastaChanged)
+                .padding(8)
+                .width(Length::Fixed(120.0))
+                .style(styles::campo_texto),
+            text(self.error.as_deref().unwrap_or_default()).size(self.letra.tamanio(12)),
+        ]
+        .spacing(10)
+        .align_y(iced::Alignment::Center);
+
+        let por_cliente: Vec<(String, u32)> = self
+            .por_cliente
+            .iter()
+            .map(|(cliente, minutos)| {
+                let cliente = if cliente.is_empty() {
+                    "Sin cliente".to_string()
+                } else {
+                    cliente.clone()
+                };
+                (cliente, *minutos)
+            })
+            .collect();
+
+        let tablas = row![
+            tabla(
+                i18n::texto(self.idioma, "reporte-tiempo-por-ficha"),
+                i18n::texto(self.idioma, "reporte-tiempo-ficha"),
+                self.por_ficha.clone(),
+                self.idioma,
+                self.letra
+            ),
+            tabla(
+                i18n::texto(self.idioma, "reporte-tiempo-por-cliente"),
+                i18n::texto(self.idioma, "reporte-tiempo-cliente"),
+                por_cliente,
+                self.idioma,
+                self.letra
+            ),
+        ]
+        .spacing(20);
+
+        column![header, rango, scrollable(tablas)]
+            .spacing(10)
+            .padding(20)
+            .into()
+    }
+}
+
+fn tabla<'a>(
+    titulo: String,
+    columna: String,
+    filas: Vec<(String, u32)>,
+    idioma: Idioma,
+    letra: Letra,
+) -> Element<'a, ReporteTiempoMessage> {
+    let encabezado = row![
+        text(columna).size(letra.tamanio(13)).width(Length::Fill),
+        text(i18n::texto(idioma, "reporte-tiempo-columna"))
+            .size(letra.tamanio(13))
+            .width(Length::Fixed(110.0)),
+    ]
+    .spacing(10);
+
+    let total: u32 = filas.iter().map(|(_, minutos)| minutos).sum();
+
+    let filas = filas.into_iter().fold(
+        Column::new().spacing(4).push(encabezado),
+        |lista, (clave, minutos)| {
+            lista.push(
+                row![
+                    text(clave).size(letra.tamanio(13)).width(Length::Fill),
+                    text(tiempo::formatear_minutos(minutos))
+                        .size(letra.tamanio(13))
+                        .width(Length::Fixed(110.0)),
+                ]
+                .spacing(10),
+            )
+        },
+    );
+
+    container(
+        column![
+            text(titulo).size(letra.tamanio(18)),
+            filas,
+            text(format!("Total: {}", tiempo::formatear_minutos(total))).size(letra.tamanio(13)),
+        ]
+        .spacing(10),
+    )
+    .padding(15)
+    .width(Length::FillPortion(1))
+    .style(styles::card_container)
+    .into()
+}
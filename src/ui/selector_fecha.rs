@@ -0,0 +1,167 @@
+use crate::i18n;
+use crate::models::Idioma;
+use crate::ui::accesibilidad::{self, Letra};
+use crate::ui::styles;
+use chrono::{Datelike, Days, Months, NaiveDate};
+use iced::widget::{button, column, container, row, text, Column, Row, Space};
+use iced::{Element, Length};
+
+const ANCHO_DIA: f32 = 36.0;
+
+#[derive(Debug, Clone)]
+pub enum SelectorFechaMessage {
+    Alternar,
+    MesAnterior,
+    MesSiguiente,
+    Elegir(NaiveDate),
+}
+
+/// Calendario desplegable para elegir un día; la fecha elegida la guarda
+/// quien lo usa
+#[derive(Debug, Clone)]
+pub struct SelectorFecha {
+    pub abierto: bool,
+    /// Primer día del mes que se muestra
+    mes: NaiveDate,
+}
+
+impl SelectorFecha {
+    pub fn new(fecha: NaiveDate) -> Self {
+        Self {
+            abierto: false,
+            mes: primero_del_mes(fecha),
+        }
+    }
+
+    /// Procesa el mensaje y devuelve el día elegido, si lo hubo. `actual` es
+    /// la fecha vigente, para abrir el calendario en su mes.
+    pub fn actualizar(
+        &mut self,
+        mensaje: SelectorFechaMessage,
+        actual: Option<NaiveDate>,
+    ) -> Option<NaiveDate> {
+        match mensaje {
+            SelectorFechaMessage::Alternar => {
+                self.abierto = !self.abierto;
+                if let Some(actual) = actual {
+                    self.mes = primero_del_mes(actual);
+                }
+                None
+            }
+            SelectorFechaMessage::MesAnterior => {
+                self.mes = self.mes - Months::new(1);
+                None
+            }
+            SelectorFechaMessage::MesSiguiente => {
+                self.mes = self.mes + Months::new(1);
+                None
+            }
+            SelectorFechaMessage::Elegir(fecha) => {
+                self.abierto = false;
+                Some(fecha)
+            }
+        }
+    }
+
+    /// Botón que abre el calendario y, si está abierto, el mes con sus días
+    /// nombrados en `idioma`
+    pub fn view(
+        &self,
+        seleccionada: Option<NaiveDate>,
+        idioma: Idioma,
+        letra: Letra,
+    ) -> Element<'_, SelectorFechaMessage> {
+        let boton = button(accesibilidad::rotulo(
+            "📅",
+            i18n::texto(idioma, "selector-fecha-abrir"),
+            letra.tamanio(16),
+        ))
+        .on_press(SelectorFechaMessage::Alternar)
+        .padding(8)
+        .style(styles::secondary_button);
+
+        if !self.abierto {
+            return boton.into();
+        }
+        let locale = i18n::locale(idioma);
+
+        let encabezado = row![
+            button(accesibilidad::rotulo(
+                "‹",
+                i18n::texto(idioma, "selector-fecha-mes-anterior"),
+                letra.tamanio(16),
+            ))
+            .on_press(SelectorFechaMessage::MesAnterior)
+            .padding([4, 10]),
+            text(self.mes.format_localized("%B %Y", locale).to_string())
+                .width(Length::Fill)
+                .center(),
+            button(accesibilidad::rotulo(
+                "›",
+                i18n::texto(idioma, "selector-fecha-mes-siguiente"),
+                letra.tamanio(16),
+            ))
+            .on_press(SelectorFechaMessage::MesSiguiente)
+            .padding([4, 10]),
+        ]
+        .spacing(5)
+        .align_y(iced::Alignment::Center);
+
+        // Abreviaturas de lunes a domingo a partir del lunes de esa semana
+        let lunes = self.mes - Days::new(self.mes.weekday().num_days_from_monday() as u64);
+        let nombres = lunes.iter_days().take(7).fold(Row::new(), |fila, dia| {
+            let nombre = dia.format_localized("%a", locale).to_string();
+            fila.push(
+                text(nombre)
+                    .size(letra.tamanio(12))
+                    .width(Length::Fixed(ANCHO_DIA))
+                    .center(),
+            )
+        });
+
+        // Semanas de lunes a domingo, con huecos antes del día 1
+        let vacios = self.mes.weekday().num_days_from_monday() as usize;
+        let dias = self
+            .mes
+            .iter_days()
+            .take_while(|d| d.month() == self.mes.month());
+        let celdas: Vec<Option<NaiveDate>> = std::iter::repeat_n(None, vacios)
+            .chain(dias.map(Some))
+            .collect();
+
+        let semanas = celdas
+            .chunks(7)
+            .fold(Column::new().spacing(2), |semanas, semana| {
+                let fila = semana.iter().fold(Row::new().spacing(0), |fila, celda| {
+                    fila.push(match celda {
+                        Some(dia) => {
+                            let elegido = Some(*dia) == seleccionada;
+                            button(text(dia.day().to_string()).size(letra.tamanio(13)).center())
+                                .on_press(SelectorFechaMessage::Elegir(*dia))
+                                .padding([4, 0])
+                                .width(Length::Fixed(ANCHO_DIA))
+                                .style(if elegido {
+                                    styles::primary_button
+                                } else {
+                                    button::text
+                                })
+                                .into()
+                        }
+                        None => Element::from(Space::with_width(ANCHO_DIA)),
+                    })
+                });
+                semanas.push(fila)
+            });
+
+        let calendario = container(column![encabezado, nombres, semanas].spacing(6))
+            .padding(10)
+            .width(Length::Fixed(ANCHO_DIA * 7.0 + 20.0))
+            .style(styles::card_container);
+
+        column![boton, calendario].spacing(5).into()
+    }
+}
+
+fn primero_del_mes(fecha: NaiveDate) -> NaiveDate {
+    fecha.with_day(1).unwrap_or(fecha)
+}
@@ -1,80 +1,296 @@
+use crate::models::Tema;
+use iced::theme::Palette;
 use iced::widget::button;
 use iced::widget::container;
+use iced::widget::{text_editor, text_input};
 use iced::{Border, Color, Shadow, Theme};
 
-pub fn primary_button(theme: &Theme, status: button::Status) -> button::Style {
-    let color = match status {
-        button::Status::Active => Color::from_rgb(0.0, 0.482, 1.0),
-        button::Status::Hovered => Color::from_rgb(0.0, 0.40, 0.85),
-        button::Status::Pressed => Color::from_rgb(0.0, 0.35, 0.70),
-        button::Status::Disabled => Color::from_rgb(0.5, 0.65, 0.8),
-    };
+/// Ancho del borde de los campos con el foco, para que se vea sin el mouse
+const ANCHO_FOCO: f32 = 3.0;
 
-    button::Style {
-        background: Some(color.into()),
-        border: Border {
-            color: Color::from_rgb(0.196, 0.804, 0.196),
-            width: 1.0,
-            radius: 5.0.into(),
+/// Tema de iced para la preferencia elegida. El del sistema se consulta en
+/// cada llamada, así que conviene guardar el resultado.
+pub fn tema(tema: Tema) -> Theme {
+    match tema {
+        Tema::Claro => Theme::Light,
+        Tema::Oscuro => Theme::Dark,
+        Tema::Sistema => match dark_light::detect() {
+            dark_light::Mode::Dark => Theme::Dark,
+            dark_light::Mode::Light | dark_light::Mode::Default => Theme::Light,
         },
-        text_color: Color::from_rgb(1.0, 1.0, 1.0),
-        ..Default::default()
+        Tema::AltoContraste => Theme::custom(
+            String::from("Alto contraste"),
+            Palette {
+                background: Color::BLACK,
+                text: Color::WHITE,
+                primary: Color::from_rgb(1.0, 0.84, 0.0),
+                success: Color::from_rgb(0.0, 1.0, 0.5),
+                danger: Color::from_rgb(1.0, 0.35, 0.35),
+            },
+        ),
     }
 }
 
-pub fn secondary_button(theme: &Theme, status: button::Status) -> button::Style {
-    let color = match status {
-        button::Status::Active => Color::from_rgb(0.17, 0.70, 0.17),
-        button::Status::Hovered => Color::from_rgb(0.19, 0.75, 0.19),
-        button::Status::Pressed => Color::from_rgb(0.14, 0.60, 0.14),
-        button::Status::Disabled => Color::from_rgb(0.3, 0.45, 0.3),
+/// Botón relleno con un par de la paleta. Deshabilitado toma los colores
+/// neutros del fondo en lugar de volverse transparente, para que el texto
+/// siga leyéndose.
+fn boton(
+    theme: &Theme,
+    par: iced::theme::palette::Pair,
+    fuerte: Color,
+    status: button::Status,
+) -> button::Style {
+    let neutro = theme.extended_palette().background.strong;
+    let (fondo, texto) = match status {
+        button::Status::Active => (par.color, par.text),
+        button::Status::Hovered | button::Status::Pressed => (fuerte, texto_sobre(fuerte)),
+        button::Status::Disabled => (neutro.color, neutro.text),
     };
 
     button::Style {
-        background: Some(color.into()),
+        background: Some(fondo.into()),
         border: Border {
-            color: Color::from_rgb(0.196, 0.804, 0.196),
+            color: fuerte,
             width: 1.0,
             radius: 5.0.into(),
         },
-        text_color: Color::from_rgb(1.0, 1.0, 1.0),
+        text_color: texto,
         ..Default::default()
     }
 }
 
+pub fn primary_button(theme: &Theme, status: button::Status) -> button::Style {
+    let paleta = theme.extended_palette();
+    boton(theme, paleta.primary.base, paleta.primary.strong.color, status)
+}
+
+pub fn secondary_button(theme: &Theme, status: button::Status) -> button::Style {
+    let paleta = theme.extended_palette();
+    boton(theme, paleta.success.base, paleta.success.strong.color, status)
+}
+
 pub fn cancel_button(theme: &Theme, status: button::Status) -> button::Style {
-    let color = match status {
-        button::Status::Active => Color::from_rgb(1.0, 0.0, 0.0),
-        button::Status::Hovered => Color::from_rgb(0.9, 0.1, 0.1),
-        button::Status::Pressed => Color::from_rgb(0.7, 0.0, 0.0),
-        button::Status::Disabled => Color::from_rgb(0.6, 0.3, 0.3),
+    let paleta = theme.extended_palette();
+    boton(theme, paleta.danger.base, paleta.danger.strong.color, status)
+}
+
+/// Campo de texto con un borde grueso mientras tiene el foco y un texto de
+/// ayuda más oscuro que el predeterminado de iced
+pub fn campo_texto(theme: &Theme, status: text_input::Status) -> text_input::Style {
+    let paleta = theme.extended_palette();
+    let estilo = text_input::Style {
+        placeholder: paleta.background.base.text.scale_alpha(0.65),
+        border: Border {
+            color: paleta.background.base.text.scale_alpha(0.5),
+            ..text_input::default(theme, status).border
+        },
+        ..text_input::default(theme, status)
     };
 
-    button::Style {
-        background: Some(color.into()),
+    match status {
+        text_input::Status::Focused => text_input::Style {
+            border: Border {
+                color: paleta.primary.strong.color,
+                width: ANCHO_FOCO,
+                ..estilo.border
+            },
+            ..estilo
+        },
+        text_input::Status::Hovered => text_input::Style {
+            border: Border {
+                color: paleta.background.base.text,
+                ..estilo.border
+            },
+            ..estilo
+        },
+        text_input::Status::Active | text_input::Status::Disabled => estilo,
+    }
+}
+
+/// Editor de hojas con el mismo foco visible que `campo_texto`
+pub fn editor(theme: &Theme, status: text_editor::Status) -> text_editor::Style {
+    let paleta = theme.extended_palette();
+    let estilo = text_editor::Style {
+        placeholder: paleta.background.base.text.scale_alpha(0.65),
         border: Border {
-            color: Color::from_rgb(1.0, 0.0, 0.0),
-            width: 1.0,
-            radius: 5.0.into(),
+            color: paleta.background.base.text.scale_alpha(0.5),
+            ..text_editor::default(theme, status).border
         },
-        text_color: Color::from_rgb(1.0, 1.0, 1.0),
-        ..Default::default()
+        ..text_editor::default(theme, status)
+    };
+
+    match status {
+        text_editor::Status::Focused => text_editor::Style {
+            border: Border {
+                color: paleta.primary.strong.color,
+                width: ANCHO_FOCO,
+                ..estilo.border
+            },
+            ..estilo
+        },
+        text_editor::Status::Hovered => text_editor::Style {
+            border: Border {
+                color: paleta.background.base.text,
+                ..estilo.border
+            },
+            ..estilo
+        },
+        text_editor::Status::Active | text_editor::Status::Disabled => estilo,
     }
 }
 
 pub fn card_container(theme: &Theme) -> container::Style {
+    let paleta = theme.extended_palette();
     container::Style {
-        background: Some(Color::from_rgb(0.98, 0.98, 0.99).into()),
+        background: Some(paleta.background.base.color.into()),
+        text_color: Some(paleta.background.base.text),
         border: Border {
-            color: Color::from_rgb(0.85, 0.85, 0.87),
+            color: paleta.background.strong.color,
             width: 1.0,
             radius: 8.0.into(),
         },
         shadow: Shadow {
             offset: iced::Vector::new(0.0, 2.0),
             blur_radius: 4.0,
-            color: Color::from_rgba(0.0, 0.0, 0.0, 0.1),
+            color: Color::BLACK.scale_alpha(0.1),
         },
+    }
+}
+
+/// Hoja dentro del historial de una ficha
+pub fn hoja_card(theme: &Theme) -> container::Style {
+    let paleta = theme.extended_palette();
+    container::Style {
+        background: Some(paleta.background.weak.color.into()),
+        text_color: Some(paleta.background.weak.text),
+        border: Border {
+            color: paleta.primary.weak.color,
+            width: 1.0,
+            radius: 5.0.into(),
+        },
+        ..Default::default()
+    }
+}
+
+pub fn etiqueta(color: [u8; 3]) -> impl Fn(&Theme) -> container::Style {
+    move |theme| {
+        let [r, g, b] = color;
+        let fondo = Color::from_rgb8(r, g, b);
+        container::Style {
+            background: Some(fondo.into()),
+            border: Border {
+                color: theme.extended_palette().background.base.text,
+                width: if theme.extended_palette().is_dark {
+                    1.0
+                } else {
+                    0.0
+                },
+                radius: 10.0.into(),
+            },
+            text_color: Some(texto_sobre(fondo)),
+            ..Default::default()
+        }
+    }
+}
+
+/// Card resaltada al recorrer la lista con el teclado
+pub fn card_seleccionada(theme: &Theme) -> container::Style {
+    container::Style {
+        border: Border {
+            color: theme.extended_palette().primary.strong.color,
+            width: 2.0,
+            radius: 8.0.into(),
+        },
+        ..card_container(theme)
+    }
+}
+
+/// Fondo semitransparente detrás de la paleta de comandos
+pub fn fondo_modal(theme: &Theme) -> container::Style {
+    container::Style {
+        background: Some(
+            theme
+                .extended_palette()
+                .background
+                .base
+                .color
+                .scale_alpha(0.7)
+                .into(),
+        ),
         ..Default::default()
     }
 }
+
+/// Blanco o negro, el de mayor contraste sobre `fondo`
+pub fn texto_sobre(fondo: Color) -> Color {
+    if contraste(Color::BLACK, fondo) >= contraste(Color::WHITE, fondo) {
+        Color::BLACK
+    } else {
+        Color::WHITE
+    }
+}
+
+/// Relación de contraste entre dos colores según WCAG 2, de 1 a 21. Para
+/// texto normal se pide al menos 4,5.
+pub fn contraste(a: Color, b: Color) -> f32 {
+    let (claro, oscuro) = {
+        let (la, lb) = (luminancia(a), luminancia(b));
+        if la > lb {
+            (la, lb)
+        } else {
+            (lb, la)
+        }
+    };
+    (claro + 0.05) / (oscuro + 0.05)
+}
+
+/// Luminancia relativa de WCAG, a partir de los canales sRGB
+fn luminancia(color: Color) -> f32 {
+    let lineal = |canal: f32| {
+        if canal <= 0.04045 {
+            canal / 12.92
+        } else {
+            ((canal + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * lineal(color.r) + 0.7152 * lineal(color.g) + 0.0722 * lineal(color.b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contraste_de_los_extremos() {
+        assert_eq!(luminancia(Color::BLACK), 0.0);
+        assert!((luminancia(Color::WHITE) - 1.0).abs() < 1e-6);
+        assert!((contraste(Color::WHITE, Color::BLACK) - 21.0).abs() < 1e-4);
+        // El orden de los colores no importa y un color contra sí mismo da 1
+        let gris = Color::from_rgb(0.5, 0.5, 0.5);
+        assert_eq!(contraste(gris, Color::BLACK), contraste(Color::BLACK, gris));
+        assert!((contraste(gris, gris) - 1.0).abs() < 1e-6);
+        // El gris medio de sRGB está más cerca del negro que del blanco
+        assert_eq!(texto_sobre(gris), Color::BLACK);
+        assert_eq!(texto_sobre(Color::from_rgb(0.2, 0.2, 0.6)), Color::WHITE);
+    }
+
+    #[test]
+    fn el_alto_contraste_llega_a_4_5() {
+        let paleta = *tema(Tema::AltoContraste).extended_palette();
+        let pares = [
+            ("texto", paleta.background.base.text, paleta.background.base.color),
+            ("texto débil", paleta.background.weak.text, paleta.background.weak.color),
+            ("texto fuerte", paleta.background.strong.text, paleta.background.strong.color),
+            ("principal", paleta.primary.base.color, paleta.background.base.color),
+            ("sobre principal", paleta.primary.base.text, paleta.primary.base.color),
+            ("éxito", paleta.success.base.color, paleta.background.base.color),
+            ("sobre éxito", paleta.success.base.text, paleta.success.base.color),
+            ("peligro", paleta.danger.base.color, paleta.background.base.color),
+            ("sobre peligro", paleta.danger.base.text, paleta.danger.base.color),
+        ];
+        for (nombre, texto, fondo) in pares {
+            let relacion = contraste(texto, fondo);
+            assert!(relacion >= 4.5, "{}: contraste {:.2}", nombre, relacion);
+        }
+    }
+}
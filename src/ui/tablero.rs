@@ -0,0 +1,504 @@
+use crate::i18n;
+use crate::models::tarea::{self, TareaPendiente};
+use crate::models::{Estado, Ficha, Idioma};
+use crate::ui::accesibilidad::Letra;
+use crate::ui::fechas::FormatoFecha;
+use crate::ui::fichas::FiltroSeleccion;
+use crate::ui::styles;
+use chrono::{Datelike, Duration, NaiveDate};
+use iced::widget::canvas::{self, event, Canvas, Event, Frame, Geometry, Path};
+use iced::widget::{
+    button, column, container, row, scrollable, text, text_input, Column, Row, Space,
+};
+use iced::{alignment, mouse, Element, Length, Point, Rectangle, Renderer, Size, Theme};
+use std::collections::{BTreeMap, BTreeSet};
+use uuid::Uuid;
+
+/// Semanas que abarca el gráfico de hojas
+const SEMANAS_GRAFICO: i64 = 12;
+/// Elementos que se listan en cada widget; el resto se ve en la lista filtrada
+const MAXIMO_ITEMS: usize = 6;
+
+#[derive(Debug, Clone)]
+pub enum TableroMessage {
+    VerTodas,
+    DiasChanged(String),
+    FiltrarEstado(Estado),
+    FiltrarEtiqueta(String),
+    VerSeleccion(FiltroSeleccion),
+    VerFicha(Uuid),
+}
+
+/// Hojas registradas en una semana, de lunes a domingo
+#[derive(Debug, Clone)]
+pub struct SemanaHojas {
+    pub inicio: NaiveDate,
+    pub hojas: usize,
+    pub fichas: Vec<Uuid>,
+}
+
+/// Pantalla de inicio con el resumen de las fichas
+#[derive(Debug)]
+pub struct TableroView {
+    pub por_estado: Vec<(Estado, usize)>,
+    pub por_etiqueta: Vec<(String, usize)>,
+    /// Días sin hojas a partir de los cuales una causa abierta se señala
+    pub dias_input: String,
+    /// Fichas abiertas sin movimiento, de la más antigua a la más reciente
+    pub sin_movimiento: Vec<(Uuid, String, i64)>,
+    pub vencimientos: Vec<TareaPendiente>,
+    pub semanas: Vec<SemanaHojas>,
+    /// Fichas modificadas más recientemente
+    pub recientes: Vec<(Uuid, String, String)>,
+    pub error: Option<String>,
+    /// Días hacia adelante en que se listan los vencimientos
+    pub dias_aviso: u32,
+    pub fechas: FormatoFecha,
+    pub idioma: Idioma,
+    pub letra: Letra,
+}
+
+impl TableroView {
+    pub fn new() -> Self {
+        Self {
+            por_estado: Vec::new(),
+            por_etiqueta: Vec::new(),
+            dias_input: String::from("30"),
+            sin_movimiento: Vec::new(),
+            vencimientos: Vec::new(),
+            semanas: Vec::new(),
+            recientes: Vec::new(),
+            error: None,
+            dias_aviso: 7,
+            fechas: FormatoFecha::default(),
+            idioma: Idioma::default(),
+            letra: Letra::default(),
+        }
+    }
+
+    pub fn actualizar(&mut self, fichas: &[Ficha]) {
+        let hoy = self.fechas.hoy();
+        let lunes = hoy - Duration::days(hoy.weekday().num_days_from_monday() as i64);
+
+        self.por_estado = Estado::TODOS
+            .iter()
+            .map(|estado| {
+                (
+                    *estado,
+                    fichas.iter().filter(|f| f.estado == *estado).count(),
+                )
+            })
+            .collect();
+
+        let mut por_etiqueta: BTreeMap<&str, usize> = BTreeMap::new();
+        for etiqueta in fichas.iter().flat_map(|f| f.etiquetas.iter()) {
+            *por_etiqueta.entry(etiqueta).or_default() += 1;
+        }
+        let mut por_etiqueta: Vec<(String, usize)> = por_etiqueta
+            .into_iter()
+            .map(|(etiqueta, cantidad)| (etiqueta.to_string(), cantidad))
+            .collect();
+        por_etiqueta.sort_by_key(|(_, cantidad)| std::cmp::Reverse(*cantidad));
+        self.por_etiqueta = por_etiqueta;
+
+        self.error = None;
+        let dias = match self.dias_input.trim().parse::<u32>() {
+            Ok(dias) => dias as i64,
+            Err(_) => {
+                self.error = Some("Ingrese una cantidad de días válida".into());
+                30
+            }
+        };
+        let mut sin_movimiento: Vec<(Uuid, String, i64)> = fichas
+            .iter()
+            .filter(|f| !f.estado.esta_cerrada())
+            .map(|f| {
+                let ultimo = f.ultimo_movimiento(|momento| self.fechas.dia(momento));
+                (f.id, f.titulo.clone(), (hoy - ultimo).num_days())
+            })
+            .filter(|(_, _, transcurridos)| *transcurridos >= dias)
+            .collect();
+        sin_movimiento.sort_by_key(|(_, _, transcurridos)| std::cmp::Reverse(*transcurridos));
+        self.sin_movimiento = sin_movimiento;
+
+        self.vencimientos = tarea::pendientes(fichas)
+            .into_iter()
+            .filter(|p| {
+                p.tarea
+                    .vencimiento
+                    .is_some_and(|v| v <= hoy + Duration::days(self.dias_aviso as i64))
+            })
+            .collect();
+
+        self.semanas = (0..SEMANAS_GRAFICO)
+            .rev()
+            .map(|atras| SemanaHojas {
+                inicio: lunes - Duration::weeks(atras),
+                hojas: 0,
+                fichas: Vec::new(),
+            })
+            .collect();
+        for ficha in fichas {
+            for hoja in &ficha.hojas {
+                let fecha = hoja.fecha;
+                let inicio = fecha - Duration::days(fecha.weekday().num_days_from_monday() as i64);
+                if let Some(semana) = self.semanas.iter_mut().find(|s| s.inicio == inicio) {
+                    semana.hojas += 1;
+                    if !semana.fichas.contains(&ficha.id) {
+                        semana.fichas.push(ficha.id);
+                    }
+                }
+            }
+        }
+
+        let mut recientes: Vec<&Ficha> = fichas.iter().collect();
+        recientes.sort_by_key(|f| std::cmp::Reverse(f.fecha_modificacion));
+        self.recientes = recientes
+            .into_iter()
+            .take(MAXIMO_ITEMS)
+            .map(|f| {
+                (
+                    f.id,
+                    f.titulo.clone(),
+                    self.fechas.fecha_hora(f.fecha_modificacion),
+                )
+            })
+            .collect();
+    }
+
+    pub fn view(&self) -> Element<'_, TableroMessage> {
+        let header = row![
+            text(i18n::texto(self.idioma, "tablero-titulo")).size(self.letra.tamanio(24)),
+            Space::with_width(Length::Fill),
+            button(text(i18n::texto(self.idioma, "tablero-ver-todas")))
+                .on_press(TableroMessage::VerTodas)
+                .padding(10)
+                .style(styles::primary_button),
+        ]
+        .spacing(20)
+        .padding(10);
+
+        let por_estado =
+            self.por_estado
+                .iter()
+                .fold(Column::new().spacing(6), |lista, (estado, cantidad)| {
+                    lista.push(fila_enlace(
+                        estado.to_string(),
+                        cantidad.to_string(),
+                        TableroMessage::FiltrarEstado(*estado),
+                        self.letra,
+                    ))
+                });
+
+        let por_etiqueta: Element<_> = if self.por_etiqueta.is_empty() {
+            text(i18n::texto(self.idioma, "tablero-sin-etiquetas"))
+                .size(self.letra.tamanio(13))
+                .into()
+        } else {
+            self.por_etiqueta
+                .iter()
+                .take(MAXIMO_ITEMS * 2)
+                .fold(Column::new().spacing(6), |lista, (etiqueta, cantidad)| {
+                    lista.push(fila_enlace(
+                        etiqueta.clone(),
+                        cantidad.to_string(),
+                        TableroMessage::FiltrarEtiqueta(etiqueta.clone()),
+                        self.letra,
+                    ))
+                })
+                .into()
+        };
+
+        let sin_movimiento = self.sin_movimiento.iter().take(MAXIMO_ITEMS).fold(
+            Column::new().spacing(6),
+            |lista, (id, titulo, dias)| {
+                lista.push(fila_enlace(
+                    titulo.clone(),
+                    format!("{} días", dias),
+                    TableroMessage::VerFicha(*id),
+                    self.letra,
+                ))
+            },
+        );
+        let dias = self.dias_input.trim();
+        let sin_movimiento = column![
+            row![
+                text(i18n::texto(self.idioma, "tablero-sin-movimiento"))
+                    .size(self.letra.tamanio(13)),
+                text_input("30", &self.dias_input)
+                    .on_input(TableroMessage::DiasChanged)
+                    .padding(4)
+                    .width(Length::Fixed(50.0))
+                    .style(styles::campo_texto),
+                text(i18n::texto(self.idioma, "tablero-dias-o-mas")).size(self.letra.tamanio(13)),
+            ]
+            .spacing(6)
+            .align_y(iced::Alignment::Center),
+            text(self.error.as_deref().unwrap_or_default()).size(self.letra.tamanio(12)),
+            sin_movimiento,
+            boton_ver_todas(
+                self.sin_movimiento.len(),
+                FiltroSeleccion {
+                    descripcion: format!("Sin movimiento hace {} días", dias),
+                    fichas: self.sin_movimiento.iter().map(|(id, _, _)| *id).collect(),
+                },
+                self.letra,
+            ),
+        ]
+        .spacing(8);
+
+        let vencimientos: Element<_> = if self.vencimientos.is_empty() {
+            text(i18n::texto(self.idioma, "tablero-sin-vencimientos"))
+                .size(self.letra.tamanio(13))
+                .into()
+        } else {
+            self.vencimientos
+                .iter()
+                .take(MAXIMO_ITEMS)
+                .fold(Column::new().spacing(6), |lista, pendiente| {
+                    let fecha = pendiente
+                        .tarea
+                        .vencimiento
+                        .map(|v| v.format("%a %d/%m").to_string())
+                        .unwrap_or_default();
+                    lista.push(fila_enlace(
+                        format!(
+                            "{} — {}",
+                            pendiente.ficha_titulo, pendiente.tarea.descripcion
+                        ),
+                        fecha,
+                        TableroMessage::VerFicha(pendiente.ficha_id),
+                        self.letra,
+                    ))
+                })
+                .into()
+        };
+        let fichas_con_vencimiento: Vec<Uuid> = self
+            .vencimientos
+            .iter()
+            .map(|p| p.ficha_id)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let vencimientos = column![
+            vencimientos,
+            boton_ver_todas(
+                fichas_con_vencimiento.len(),
+                FiltroSeleccion {
+                    descripcion: String::from("Próximos vencimientos"),
+                    fichas: fichas_con_vencimiento,
+                },
+                self.letra,
+            ),
+        ]
+        .spacing(8);
+
+        let recientes =
+            self.recientes
+                .iter()
+                .fold(Column::new().spacing(6), |lista, (id, titulo, fecha)| {
+                    lista.push(fila_enlace(
+                        titulo.clone(),
+                        fecha.clone(),
+                        TableroMessage::VerFicha(*id),
+                        self.letra,
+                    ))
+                });
+
+        let grafico = column![
+            Canvas::new(GraficoSemanas {
+                semanas: &self.semanas,
+                letra: self.letra,
+            })
+            .width(Length::Fill)
+            .height(Length::Fixed(180.0)),
+            text(i18n::texto(self.idioma, "tablero-grafico-ayuda"))
+                .size(self.letra.tamanio(11)),
+        ]
+        .spacing(6);
+
+        let contenido = column![
+            row![
+                widget("Fichas por estado", por_estado, self.letra),
+                widget("Fichas por etiqueta", por_etiqueta, self.letra),
+                widget("Tocadas recientemente", recientes, self.letra),
+            ]
+            .spacing(15),
+            row![
+                widget("Sin movimiento", sin_movimiento, self.letra),
+                widget("Próximos vencimientos", vencimientos, self.letra),
+            ]
+            .spacing(15),
+            widget("Hojas por semana", grafico, self.letra),
+        ]
+        .spacing(15);
+
+        column![header, scrollable(contenido)]
+            .spacing(10)
+            .padding(20)
+            .into()
+    }
+}
+
+/// Tarjeta con título de un widget del tablero
+fn widget<'a>(
+    titulo: &'a str,
+    contenido: impl Into<Element<'a, TableroMessage>>,
+    letra: Letra,
+) -> Element<'a, TableroMessage> {
+    container(column![text(titulo).size(letra.tamanio(18)), contenido.into()].spacing(10))
+        .padding(15)
+        .width(Length::FillPortion(1))
+        .style(styles::card_container)
+        .into()
+}
+
+/// Fila con un texto que lleva a la lista filtrada y un valor a la derecha
+fn fila_enlace<'a>(
+    etiqueta: String,
+    valor: String,
+    mensaje: TableroMessage,
+    letra: Letra,
+) -> Element<'a, TableroMessage> {
+    Row::new()
+        .push(
+            button(text(etiqueta).size(letra.tamanio(13)))
+                .on_press(mensaje)
+                .padding([2, 6])
+                .style(button::text)
+                .width(Length::Fill),
+        )
+        .push(text(valor).size(letra.tamanio(13)))
+        .spacing(10)
+        .align_y(iced::Alignment::Center)
+        .into()
+}
+
+/// Botón para ver en la lista todas las fichas de un widget
+fn boton_ver_todas<'a>(
+    cantidad: usize,
+    filtro: FiltroSeleccion,
+    letra: Letra,
+) -> Element<'a, TableroMessage> {
+    button(text(format!("Ver {} fichas en la lista", cantidad)).size(letra.tamanio(12)))
+        .on_press_maybe((cantidad > 0).then_some(TableroMessage::VerSeleccion(filtro)))
+        .padding([4, 8])
+        .style(styles::secondary_button)
+        .into()
+}
+
+/// Gráfico de barras con las hojas registradas en cada semana
+struct GraficoSemanas<'a> {
+    semanas: &'a [SemanaHojas],
+    letra: Letra,
+}
+
+impl GraficoSemanas<'_> {
+    /// Semana bajo el cursor, si hay alguna
+    fn semana_en(&self, bounds: Rectangle, cursor: mouse::Cursor) -> Option<&SemanaHojas> {
+        let posicion = cursor.position_in(bounds)?;
+        let ancho = bounds.width / self.semanas.len().max(1) as f32;
+        self.semanas.get((posicion.x / ancho) as usize)
+    }
+}
+
+impl canvas::Program<TableroMessage> for GraficoSemanas<'_> {
+    type State = ();
+
+    fn update(
+        &self,
+        _state: &mut Self::State,
+        event: Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> (event::Status, Option<TableroMessage>) {
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                match self.semana_en(bounds, cursor).filter(|s| s.hojas > 0) {
+                    Some(semana) => (
+                        event::Status::Captured,
+                        Some(TableroMessage::VerSeleccion(FiltroSeleccion {
+                            descripcion: format!(
+                                "Hojas de la semana del {}",
+                                semana.inicio.format("%d/%m/%Y")
+                            ),
+                            fichas: semana.fichas.clone(),
+                        })),
+                    ),
+                    None => (event::Status::Ignored, None),
+                }
+            }
+            _ => (event::Status::Ignored, None),
+        }
+    }
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let maximo = self
+            .semanas
+            .iter()
+            .map(|s| s.hojas)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let ancho = bounds.width / self.semanas.len().max(1) as f32;
+        let alto_barras = bounds.height - 36.0;
+        let resaltada = self.semana_en(bounds, cursor).map(|s| s.inicio);
+        let paleta = theme.extended_palette();
+
+        for (i, semana) in self.semanas.iter().enumerate() {
+            let alto = alto_barras * semana.hojas as f32 / maximo as f32;
+            let x = i as f32 * ancho;
+            let y = 18.0 + alto_barras - alto;
+            let color = if resaltada == Some(semana.inicio) {
+                paleta.primary.strong.color
+            } else {
+                paleta.primary.base.color
+            };
+
+            frame.fill(
+                &Path::rectangle(Point::new(x + 4.0, y), Size::new(ancho - 8.0, alto)),
+                color,
+            );
+            frame.fill_text(canvas::Text {
+                content: semana.hojas.to_string(),
+                position: Point::new(x + ancho / 2.0, y - 2.0),
+                size: f32::from(self.letra.tamanio(12)).into(),
+                horizontal_alignment: alignment::Horizontal::Center,
+                vertical_alignment: alignment::Vertical::Bottom,
+                color: paleta.background.base.text,
+                ..canvas::Text::default()
+            });
+            frame.fill_text(canvas::Text {
+                content: semana.inicio.format("%d/%m").to_string(),
+                position: Point::new(x + ancho / 2.0, bounds.height - 2.0),
+                size: f32::from(self.letra.tamanio(11)).into(),
+                horizontal_alignment: alignment::Horizontal::Center,
+                vertical_alignment: alignment::Vertical::Bottom,
+                color: paleta.background.base.text,
+                ..canvas::Text::default()
+            });
+        }
+
+        vec![frame.into_geometry()]
+    }
+
+    fn mouse_interaction(
+        &self,
+        _state: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        match self.semana_en(bounds, cursor) {
+            Some(semana) if semana.hojas > 0 => mouse::Interaction::Pointer,
+            _ => mouse::Interaction::default(),
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use crate::i18n;
+use crate::models::{Ficha, Idioma, Prioridad, Tarea};
+use crate::ui::accesibilidad::{self, Letra};
+use crate::ui::styles;
+use chrono::{Local, NaiveDate};
+use iced::widget::{
+    button, column, container, pick_list, row, text, text_input, Column, Space,
+};
+use iced::{Element, Length};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub enum TareaMessage {
+    DescripcionChanged(String),
+    VencimientoChanged(String),
+    PrioridadSeleccionada(Prioridad),
+    ResponsableChanged(String),
+    AgregarTarea(Uuid),
+    /// Ficha, tarea y si se registra una hoja al completarla
+    CompletarTarea(Uuid, Uuid, bool),
+    EliminarTarea(Uuid, Uuid),
+}
+
+/// Sección de pendientes en el historial de una ficha
+#[derive(Debug)]
+pub struct TareaForm {
+    pub descripcion_input: String,
+    pub vencimiento_input: String,
+    pub prioridad: Prioridad,
+    pub responsable_input: String,
+    /// Error de validación de la última tarea ingresada
+    pub error: Option<String>,
+}
+
+impl TareaForm {
+    pub fn new() -> Self {
+        Self {
+            descripcion_input: String::new(),
+            vencimiento_input: String::new(),
+            prioridad: Prioridad::default(),
+            responsable_input: String::new(),
+            error: None,
+        }
+    }
+
+    pub fn reiniciar(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Actualiza los campos del formulario; las acciones sobre la ficha
+    /// las resuelve la aplicación.
+    pub fn actualizar(&mut self, mensaje: TareaMessage) {
+        match mensaje {
+            TareaMessage::DescripcionChanged(valor) => self.descripcion_input = valor,
+            TareaMessage::VencimientoChanged(valor) => self.vencimiento_input = valor,
+            TareaMessage::PrioridadSeleccionada(prioridad) => self.prioridad = prioridad,
+            TareaMessage::ResponsableChanged(valor) => self.responsable_input = valor,
+            TareaMessage::AgregarTarea(_)
+            | TareaMessage::CompletarTarea(_, _, _)
+            | TareaMessage::EliminarTarea(_, _) => {}
+        }
+    }
+
+    /// Valida el formulario y construye la tarea; el vencimiento es opcional
+    pub fn obtener_tarea(&self) -> Result<Tarea, String> {
+        if self.descripcion_input.trim().is_empty() {
+            return Err("Ingrese una descripción".into());
+        }
+        let vencimiento = match self.vencimiento_input.trim() {
+            "" => None,
+            fecha => Some(
+                NaiveDate::parse_from_str(fecha, "%d/%m/%Y")
+                    .map_err(|_| "Vencimiento inválido, use dd/mm/aaaa")?,
+            ),
+        };
+
+        Ok(Tarea::new(
+            self.descripcion_input.trim().to_string(),
+            vencimiento,
+            self.prioridad,
+            self.responsable_input.trim().to_string(),
+        ))
+    }
+
+    /// Sin `editable` la lista de pendientes es de solo lectura
+    pub fn view<'a>(
+        &'a self,
+        ficha: &'a Ficha,
+        editable: bool,
+        idioma: Idioma,
+        letra: Letra,
+    ) -> Element<'a, TareaMessage> {
+        let formulario = row![
+            text_input("Nueva tarea", &self.descripcion_input)
+                .on_input(TareaMessage::DescripcionChanged)
+                .on_submit(TareaMessage::AgregarTarea(ficha.id))
+                .padding(8)
+                .style(styles::campo_texto),
+            text_input("Vence dd/mm/aaaa", &self.vencimiento_input)
+                .on_input(TareaMessage::VencimientoChanged)
+                .padding(8)
+                .width(Length::Fixed(140.0))
+                .style(styles::campo_texto),
+            pick_list(
+                Prioridad::TODAS,
+                Some(self.prioridad),
+                TareaMessage::PrioridadSeleccionada
+            )
+            .padding(8),
+            text_input("Responsable", &self.responsable_input)
+                .on_input(TareaMessage::ResponsableChanged)
+                .padding(8)
+                .width(Length::Fixed(140.0))
+                .style(styles::campo_texto),
+            button(text(i18n::texto(idioma, "tareas-agregar")))
+                .on_press(TareaMessage::AgregarTarea(ficha.id))
+                .padding(8)
+                .style(styles::primary_button),
+        ]
+        .spacing(10);
+
+        let hoy = Local::now().date_naive();
+        let pendientes = ficha
+            .tareas
+            .iter()
+            .filter(|t| !t.completada)
+            .fold(Column::new().spacing(6), |lista, tarea| {
+                lista.push(fila_tarea(ficha.id, tarea, hoy, None, editable, idioma, letra))
+            });
+
+        let formulario: Element<_> = if editable {
+            column![
+                formulario,
+                text(self.error.as_deref().unwrap_or_default()).size(letra.tamanio(12)),
+            ]
+            .spacing(10)
+            .into()
+        } else {
+            Space::with_height(0).into()
+        };
+
+        let completadas = ficha.tareas.iter().filter(|t| t.completada).count();
+
+        container(
+            column![
+                text(i18n::texto(idioma, "tareas-pendientes")).size(letra.tamanio(16)),
+                formulario,
+                pendientes,
+                text(format!("{} tareas completadas", completadas)).size(letra.tamanio(12)),
+            ]
+            .spacing(10),
+        )
+        .padding(15)
+        .width(Length::Fill)
+        .style(styles::card_container)
+        .into()
+    }
+}
+
+/// Fila de una tarea pendiente, con el título de la ficha si se indica.
+/// Sin `editable` los botones quedan deshabilitados.
+pub fn fila_tarea<'a>(
+    ficha_id: Uuid,
+    tarea: &'a Tarea,
+    hoy: NaiveDate,
+    ficha_titulo: Option<&'a str>,
+    editable: bool,
+    idioma: Idioma,
+    letra: Letra,
+) -> Element<'a, TareaMessage> {
+    let vencimiento = match tarea.vencimiento {
+        Some(fecha) if tarea.esta_vencida(hoy) => format!("⚠ {}", fecha.format("%d/%m/%Y")),
+        Some(fecha) => fecha.format("%d/%m/%Y").to_string(),
+        None => String::from("Sin fecha"),
+    };
+
+    let mut fila = row![
+        text(vencimiento).size(letra.tamanio(13)).width(Length::Fixed(100.0)),
+        text(tarea.prioridad.to_string())
+            .size(letra.tamanio(13))
+            .width(Length::Fixed(60.0)),
+    ]
+    .spacing(10)
+    .align_y(iced::Alignment::Center);
+
+    if let Some(titulo) = ficha_titulo {
+        fila = fila.push(text(titulo).size(letra.tamanio(13)).width(Length::FillPortion(1)));
+    }
+
+    fila = fila
+        .push(
+            text(&tarea.descripcion)
+                .size(letra.tamanio(13))
+                .width(Length::FillPortion(2)),
+        )
+        .push(
+            text(&tarea.responsable)
+                .size(letra.tamanio(13))
+                .width(Length::Fixed(120.0)),
+        )
+        .push(
+            button(text(i18n::texto(idioma, "tareas-hecha")).size(letra.tamanio(12)))
+                .on_press_maybe(
+                    editable.then_some(TareaMessage::CompletarTarea(ficha_id, tarea.id, false)),
+                )
+                .padding([2, 8])
+                .style(styles::secondary_button),
+        )
+        .push(
+            button(text(i18n::texto(idioma, "tareas-hecha-hoja")).size(letra.tamanio(12)))
+                .on_press_maybe(
+                    editable.then_some(TareaMessage::CompletarTarea(ficha_id, tarea.id, true)),
+                )
+                .padding([2, 8])
+                .style(styles::primary_button),
+        )
+        .push(
+            button(accesibilidad::rotulo("✕", "Eliminar tarea", letra.tamanio(12)))
+                .on_press_maybe(editable.then_some(TareaMessage::EliminarTarea(ficha_id, tarea.id)))
+                .padding([2, 8])
+                .style(styles::cancel_button),
+        );
+
+    container(fila)
+        .padding(8)
+        .style(styles::card_container)
+        .into()
+}
@@ -0,0 +1,170 @@
+use crate::i18n;
+use crate::models::tiempo::{self, RegistroTiempo};
+use crate::models::{Ficha, Idioma};
+use crate::ui::accesibilidad::{self, Letra};
+use crate::ui::fechas::FORMATO_DIA;
+use crate::ui::styles;
+use chrono::NaiveDate;
+use iced::widget::{button, column, container, row, text, text_input, Column, Space};
+use iced::{Element, Length};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub enum TiempoMessage {
+    FechaChanged(String),
+    DuracionChanged(String),
+    DescripcionChanged(String),
+    AgregarRegistro(Uuid),
+    EliminarRegistro(Uuid, Uuid),
+}
+
+/// Formulario de la pestaña de tiempo dedicado de una ficha
+#[derive(Debug)]
+pub struct TiempoForm {
+    pub fecha_input: String,
+    pub duracion_input: String,
+    pub descripcion_input: String,
+    /// Error de validación del último registro ingresado
+    pub error: Option<String>,
+}
+
+impl TiempoForm {
+    /// Propone `hoy` como fecha del registro
+    pub fn new(hoy: NaiveDate) -> Self {
+        Self {
+            fecha_input: hoy.format(FORMATO_DIA).to_string(),
+            duracion_input: String::new(),
+            descripcion_input: String::new(),
+            error: None,
+        }
+    }
+
+    pub fn reiniciar(&mut self, hoy: NaiveDate) {
+        *self = Self::new(hoy);
+    }
+
+    /// Actualiza los campos del formulario; las acciones sobre la ficha
+    /// las resuelve la aplicación.
+    pub fn actualizar(&mut self, mensaje: TiempoMessage) {
+        match mensaje {
+            TiempoMessage::FechaChanged(valor) => self.fecha_input = valor,
+            TiempoMessage::DuracionChanged(valor) => self.duracion_input = valor,
+            TiempoMessage::DescripcionChanged(valor) => self.descripcion_input = valor,
+            TiempoMessage::AgregarRegistro(_) | TiempoMessage::EliminarRegistro(_, _) => {}
+        }
+    }
+
+    /// Valida el formulario y construye el registro
+    pub fn obtener_registro(&self) -> Result<RegistroTiempo, String> {
+        let fecha = NaiveDate::parse_from_str(self.fecha_input.trim(), FORMATO_DIA)
+            .map_err(|_| "Fecha inválida, use dd/mm/aaaa")?;
+        let minutos = tiempo::parsear_duracion(&self.duracion_input)
+            .ok_or("Duración inválida, use minutos (90) u horas (1:30), hasta 24 h")?;
+
+        Ok(RegistroTiempo::new(
+            fecha,
+            minutos,
+            self.descripcion_input.trim().to_string(),
+        ))
+    }
+
+    /// Sin `editable` solo se muestran los registros y el total
+    pub fn view<'a>(
+        &'a self,
+        ficha: &'a Ficha,
+        editable: bool,
+        idioma: Idioma,
+        letra: Letra,
+    ) -> Element<'a, TiempoMessage> {
+        let formulario = container(
+            column![
+                text(i18n::texto(idioma, "tiempo-registrar")).size(letra.tamanio(16)),
+                row![
+                    text_input("dd/mm/aaaa", &self.fecha_input)
+                        .on_input(TiempoMessage::FechaChanged)
+                        .padding(8)
+                        .width(Length::Fixed(120.0))
+                        .style(styles::campo_texto),
+                    text_input("Duración (1:30)", &self.duracion_input)
+                        .on_input(TiempoMessage::DuracionChanged)
+                        .padding(8)
+                        .width(Length::Fixed(130.0))
+                        .style(styles::campo_texto),
+                    text_input(
+                        "Descripción (audiencia, lectura de expediente...)",
+                        &self.descripcion_input
+                    )
+                    .on_input(TiempoMessage::DescripcionChanged)
+                    .on_submit(TiempoMessage::AgregarRegistro(ficha.id))
+                    .padding(8)
+                    .style(styles::campo_texto),
+                    button(text(i18n::texto(idioma, "registrar")))
+                        .on_press(TiempoMessage::AgregarRegistro(ficha.id))
+                        .padding(8)
+                        .style(styles::primary_button),
+                ]
+                .spacing(10),
+                text(self.error.as_deref().unwrap_or_default()).size(letra.tamanio(12)),
+            ]
+            .spacing(10),
+        )
+        .padding(15)
+        .width(Length::Fill)
+        .style(styles::card_container);
+
+        let total = ficha
+            .tiempos
+            .iter()
+            .fold(0u32, |total, t| total.saturating_add(t.minutos));
+
+        let registros =
+            ficha
+                .tiempos
+                .iter()
+                .rev()
+                .fold(Column::new().spacing(6), |lista, registro| {
+                    lista.push(
+                        container(
+                            row![
+                                text(registro.fecha.format("%d/%m/%Y").to_string())
+                                    .size(letra.tamanio(13))
+                                    .width(Length::Fixed(90.0)),
+                                text(tiempo::formatear_minutos(registro.minutos))
+                                    .size(letra.tamanio(13))
+                                    .width(Length::Fixed(100.0)),
+                                text(&registro.descripcion)
+                                    .size(letra.tamanio(13))
+                                    .width(Length::Fill),
+                                button(accesibilidad::rotulo(
+                                    "✕",
+                                    "Eliminar registro",
+                                    letra.tamanio(12),
+                                ))
+                                .on_press_maybe(editable.then_some(
+                                    TiempoMessage::EliminarRegistro(ficha.id, registro.id)
+                                ))
+                                .padding([2, 8])
+                                .style(styles::cancel_button),
+                            ]
+                            .spacing(10),
+                        )
+                        .padding(8)
+                        .style(styles::card_container),
+                    )
+                });
+
+        let formulario: Element<_> = if editable {
+            formulario.into()
+        } else {
+            Space::with_height(0).into()
+        };
+
+        column![
+            formulario,
+            text(format!("Total: {}", tiempo::formatear_minutos(total))).size(letra.tamanio(16)),
+            registros,
+        ]
+        .spacing(10)
+        .into()
+    }
+}
@@ -0,0 +1,202 @@
+use crate::i18n;
+use crate::models::{Idioma, Rol, Usuario};
+use crate::ui::accesibilidad::Letra;
+use crate::ui::etiquetas::PALETA;
+use crate::ui::styles;
+use iced::widget::{button, column, container, row, text, text_input, Column, Row, Space};
+use iced::{Element, Length};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub enum UsuarioMessage {
+    Seleccionar(Uuid),
+    NombreChanged(String),
+    InicialesChanged(String),
+    ColorSeleccionado([u8; 3]),
+    Crear,
+}
+
+/// Pantalla de inicio para elegir quién usa el sistema o crear un perfil
+#[derive(Debug)]
+pub struct UsuariosView {
+    pub usuarios: Vec<Usuario>,
+    pub nombre_input: String,
+    pub iniciales_input: String,
+    pub color: [u8; 3],
+    pub error: Option<String>,
+    pub idioma: Idioma,
+    pub letra: Letra,
+}
+
+impl UsuariosView {
+    pub fn new() -> Self {
+        Self {
+            usuarios: Vec::new(),
+            nombre_input: String::new(),
+            iniciales_input: String::new(),
+            color: PALETA[5],
+            error: None,
+            idioma: Idioma::default(),
+            letra: Letra::default(),
+        }
+    }
+
+    pub fn actualizar_usuarios(&mut self, usuarios: Vec<Usuario>) {
+        self.usuarios = usuarios;
+    }
+
+    pub fn limpiar_formulario(&mut self) {
+        self.nombre_input.clear();
+        self.iniciales_input.clear();
+        self.error = None;
+    }
+
+    /// Valida el formulario y construye el primer perfil, que administra el
+    /// espacio; los siguientes los crea un administrador
+    pub fn obtener_usuario(&self) -> Result<Usuario, String> {
+        if !self.usuarios.is_empty() {
+            return Err("Pida a un administrador que cree su usuario".into());
+        }
+        validar_usuario(
+            &self.usuarios,
+            &self.nombre_input,
+            &self.iniciales_input,
+            self.color,
+            Rol::Admin,
+        )
+    }
+
+    pub fn view(&self) -> Element<'_, UsuarioMessage> {
+        let lista: Element<_> = if self.usuarios.is_empty() {
+            text(i18n::texto(self.idioma, "usuarios-vacia")).into()
+        } else {
+            self.usuarios
+                .iter()
+                .fold(Column::new().spacing(6), |lista, usuario| {
+                    lista.push(
+                        button(
+                            row![
+                                avatar(usuario, self.letra),
+                                text(&usuario.nombre).size(self.letra.tamanio(16))
+                            ]
+                            .spacing(10)
+                            .align_y(iced::Alignment::Center),
+                        )
+                        .on_press(UsuarioMessage::Seleccionar(usuario.id))
+                        .width(Length::Fill)
+                        .padding(8)
+                        .style(button::text),
+                    )
+                })
+                .into()
+        };
+
+        if !self.usuarios.is_empty() {
+            return column![
+                text(i18n::texto(self.idioma, "usuarios-titulo")).size(self.letra.tamanio(24)),
+                container(lista).width(Length::FillPortion(1)),
+                Space::with_height(Length::Fill),
+            ]
+            .spacing(20)
+            .padding(30)
+            .into();
+        }
+
+        let formulario = container(
+            column![
+                text(i18n::texto(self.idioma, "usuarios-primero")).size(self.letra.tamanio(18)),
+                text_input("Nombre y apellido", &self.nombre_input)
+                    .on_input(UsuarioMessage::NombreChanged)
+                    .on_submit(UsuarioMessage::Crear)
+                    .padding(8)
+                    .style(styles::campo_texto),
+                text_input("Iniciales (opcional)", &self.iniciales_input)
+                    .on_input(UsuarioMessage::InicialesChanged)
+                    .padding(8)
+                    .width(Length::Fixed(160.0))
+                    .style(styles::campo_texto),
+                selector_color(self.color, UsuarioMessage::ColorSeleccionado, self.letra),
+                button(text(i18n::texto(self.idioma, "usuarios-crear")))
+                    .on_press(UsuarioMessage::Crear)
+                    .padding(8)
+                    .style(styles::primary_button),
+                text(self.error.as_deref().unwrap_or_default()).size(self.letra.tamanio(12)),
+            ]
+            .spacing(10),
+        )
+        .padding(15)
+        .width(Length::FillPortion(1))
+        .style(styles::card_container);
+
+        column![
+            text(i18n::texto(self.idioma, "usuarios-titulo")).size(self.letra.tamanio(24)),
+            row![
+                container(lista).width(Length::FillPortion(1)),
+                formulario,
+            ]
+            .spacing(20),
+            Space::with_height(Length::Fill),
+        ]
+        .spacing(20)
+        .padding(30)
+        .into()
+    }
+}
+
+/// Valida los datos de un perfil nuevo contra los existentes
+pub fn validar_usuario(
+    usuarios: &[Usuario],
+    nombre: &str,
+    iniciales: &str,
+    color: [u8; 3],
+    rol: Rol,
+) -> Result<Usuario, String> {
+    let nombre = nombre.trim();
+    if nombre.is_empty() {
+        return Err("Ingrese un nombre".into());
+    }
+    if usuarios
+        .iter()
+        .any(|u| u.nombre.eq_ignore_ascii_case(nombre))
+    {
+        return Err(format!("Ya existe un usuario \"{}\"", nombre));
+    }
+    Ok(Usuario::new(
+        nombre.to_string(),
+        iniciales.to_string(),
+        color,
+        rol,
+    ))
+}
+
+/// Muestras de la paleta para elegir el color de un perfil
+pub fn selector_color<'a, Message: Clone + 'a>(
+    actual: [u8; 3],
+    on_select: impl Fn([u8; 3]) -> Message,
+    letra: Letra,
+) -> Element<'a, Message> {
+    PALETA
+        .iter()
+        .fold(Row::new().spacing(6), |fila, color| {
+            let marcado = if *color == actual { "✓" } else { "" };
+            fila.push(
+                button(
+                    container(text(marcado).size(letra.tamanio(12)))
+                        .center(20)
+                        .style(styles::etiqueta(*color)),
+                )
+                .on_press(on_select(*color))
+                .padding(2)
+                .style(button::text),
+            )
+        })
+        .into()
+}
+
+/// Círculo de color con las iniciales del usuario
+pub fn avatar<'a, Message: 'a>(usuario: &Usuario, letra: Letra) -> Element<'a, Message> {
+    container(text(usuario.iniciales.clone()).size(letra.tamanio(11)))
+        .center(26)
+        .style(styles::etiqueta(usuario.color))
+        .into()
+}
@@ -0,0 +1,175 @@
+use crate::i18n;
+use crate::models::{Ficha, Idioma, TipoVinculo};
+use crate::ui::accesibilidad::{self, Letra};
+use crate::ui::styles;
+use iced::widget::{button, column, container, pick_list, row, text, Column, Space};
+use iced::{Element, Length};
+use std::fmt;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub enum VinculoMessage {
+    FichaSeleccionada(OpcionFicha),
+    TipoSeleccionado(TipoVinculo),
+    /// Vincula la ficha indicada con la seleccionada en el formulario
+    Vincular(Uuid),
+    Desvincular(Uuid, Uuid),
+    /// Abre el historial de una ficha vinculada
+    Abrir(Uuid),
+}
+
+/// Ficha ofrecida en el selector de vínculos
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpcionFicha {
+    pub id: Uuid,
+    pub titulo: String,
+    pub expediente: String,
+}
+
+impl OpcionFicha {
+    fn de(ficha: &Ficha) -> Self {
+        Self {
+            id: ficha.id,
+            titulo: ficha.titulo.clone(),
+            expediente: ficha.expediente.clone(),
+        }
+    }
+}
+
+impl fmt::Display for OpcionFicha {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.expediente.is_empty() {
+            write!(f, "{}", self.titulo)
+        } else {
+            write!(f, "{} (Expte. {})", self.titulo, self.expediente)
+        }
+    }
+}
+
+/// Sección de fichas vinculadas en el historial de una ficha
+#[derive(Debug)]
+pub struct VinculoForm {
+    pub ficha: Option<OpcionFicha>,
+    pub tipo: TipoVinculo,
+}
+
+impl VinculoForm {
+    pub fn new() -> Self {
+        Self {
+            ficha: None,
+            tipo: TipoVinculo::Conexo,
+        }
+    }
+
+    pub fn reiniciar(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Actualiza los campos del formulario; las acciones sobre las fichas
+    /// las resuelve la aplicación.
+    pub fn actualizar(&mut self, mensaje: VinculoMessage) {
+        match mensaje {
+            VinculoMessage::FichaSeleccionada(opcion) => self.ficha = Some(opcion),
+            VinculoMessage::TipoSeleccionado(tipo) => self.tipo = tipo,
+            VinculoMessage::Vincular(_)
+            | VinculoMessage::Desvincular(_, _)
+            | VinculoMessage::Abrir(_) => {}
+        }
+    }
+
+    /// Sin `editable` solo se listan los vínculos, que siguen siendo navegables
+    pub fn view<'a>(
+        &'a self,
+        ficha: &'a Ficha,
+        fichas: &'a [Ficha],
+        editable: bool,
+        idioma: Idioma,
+        letra: Letra,
+    ) -> Element<'a, VinculoMessage> {
+        let vinculadas = ficha.vinculos.iter().filter_map(|vinculo| {
+            fichas
+                .iter()
+                .find(|f| f.id == vinculo.ficha_id)
+                .map(|otra| (vinculo.tipo, otra))
+        });
+
+        let lista = vinculadas.fold(Column::new().spacing(4), |lista, (tipo, otra)| {
+            lista.push(
+                row![
+                    text(tipo.to_string()).size(letra.tamanio(13)).width(Length::Fixed(110.0)),
+                    button(text(&otra.titulo).size(letra.tamanio(13)))
+                        .on_press(VinculoMessage::Abrir(otra.id))
+                        .padding([2, 8])
+                        .style(button::text),
+                    text(&otra.expediente).size(letra.tamanio(12)),
+                    Space::with_width(Length::Fill),
+                    button(accesibilidad::rotulo("✕", "Desvincular", letra.tamanio(12)))
+                        .on_press_maybe(
+                            editable.then_some(VinculoMessage::Desvincular(ficha.id, otra.id)),
+                        )
+                        .padding([2, 8])
+                        .style(styles::cancel_button),
+                ]
+                .spacing(10)
+                .align_y(iced::Alignment::Center),
+            )
+        });
+
+        let opciones: Vec<OpcionFicha> = fichas
+            .iter()
+            .filter(|f| f.id != ficha.id && !ficha.vinculos.iter().any(|v| v.ficha_id == f.id))
+            .map(OpcionFicha::de)
+            .collect();
+
+        let formulario: Element<_> = if editable {
+            row![
+                pick_list(
+                    TipoVinculo::TODOS,
+                    Some(self.tipo),
+                    VinculoMessage::TipoSeleccionado
+                )
+                .padding(8),
+                pick_list(
+                    opciones,
+                    self.ficha.clone(),
+                    VinculoMessage::FichaSeleccionada
+                )
+                .placeholder("Ficha a vincular...")
+                .padding(8)
+                .width(Length::Fill),
+                button(text(i18n::texto(idioma, "vinculos-vincular")))
+                    .on_press_maybe(
+                        self.ficha
+                            .as_ref()
+                            .map(|_| VinculoMessage::Vincular(ficha.id)),
+                    )
+                    .padding(8)
+                    .style(styles::primary_button),
+            ]
+            .spacing(10)
+            .into()
+        } else {
+            Space::with_height(0).into()
+        };
+
+        let vacia: Element<_> = if ficha.vinculos.is_empty() {
+            text(i18n::texto(idioma, "vinculos-vacia")).size(letra.tamanio(12)).into()
+        } else {
+            Space::with_height(0).into()
+        };
+
+        container(
+            column![
+                text(i18n::texto(idioma, "vinculos-titulo")).size(letra.tamanio(16)),
+                formulario,
+                vacia,
+                lista
+            ]
+            .spacing(10),
+        )
+        .padding(15)
+        .width(Length::Fill)
+        .style(styles::card_container)
+        .into()
+    }
+}